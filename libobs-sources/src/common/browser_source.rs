@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::sources::{ObsSourceBuilder, ObsSourceRef};
+use num_derive::{FromPrimitive, ToPrimitive};
+
+use crate::macro_helper::define_object_manager;
+
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how much the page of a browser source can access and control OBS.
+/// Used in `BrowserSourceBuilder`
+pub enum ObsBrowserControlLevel {
+    NoAccess = 0,
+    /// Read access to the status of OBS, like whether it is recording
+    ReadObs = 1,
+    /// Read access to user information, like the scene collection
+    ReadUser = 2,
+    /// Can save the replay buffer and similar basic actions
+    Basic = 3,
+    /// Can change scenes and start or stop the replay buffer
+    Advanced = 4,
+    /// Can start and stop streaming and recording
+    All = 5,
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the browser source, which renders a web page.
+    #[derive(Debug)]
+    struct BrowserSource("browser_source") for ObsSourceRef {
+        /// Whether `local_file` is shown instead of `url`
+        #[obs_property(type_t = "bool")]
+        is_local_file: bool,
+
+        /// Sets the file to show, use `set_local_file` instead.
+        #[obs_property(type_t = "string", settings_key = "local_file")]
+        local_file_raw: String,
+
+        /// The URL of the page to show
+        #[obs_property(type_t = "string")]
+        url: String,
+
+        /// The width of the page in pixels
+        #[obs_property(type_t = "int")]
+        width: i64,
+
+        /// The height of the page in pixels
+        #[obs_property(type_t = "int")]
+        height: i64,
+
+        /// Whether the page is rendered at `fps` instead of the frame rate of OBS
+        #[obs_property(type_t = "bool")]
+        fps_custom: bool,
+
+        #[obs_property(type_t = "int")]
+        fps: i64,
+
+        /// Whether the audio of the page is output by the source instead of the desktop
+        #[obs_property(type_t = "bool")]
+        reroute_audio: bool,
+
+        /// Custom CSS that is applied to the page
+        #[obs_property(type_t = "string")]
+        css: String,
+
+        /// Whether the page is closed while the source isn't shown
+        #[obs_property(type_t = "bool")]
+        shutdown: bool,
+
+        /// Whether the page is reloaded when the source becomes active
+        #[obs_property(type_t = "bool")]
+        restart_when_active: bool,
+
+        /// How much the page can access and control OBS
+        #[obs_property(type_t = "enum")]
+        webpage_control_level: ObsBrowserControlLevel,
+    }
+);
+
+#[obs_object_impl]
+impl BrowserSource {
+    /// Shows the given local HTML file.
+    pub fn set_local_file(self, path: &Path) -> Self {
+        self.set_is_local_file(true)
+            .set_local_file_raw(path.to_string_lossy().as_ref())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for BrowserSourceBuilder {}
@@ -0,0 +1,57 @@
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::sources::{ObsSourceBuilder, ObsSourceRef};
+
+use crate::macro_helper::define_object_manager;
+
+/// A color like sources store it in their settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObsColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl ObsColor {
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// The color as `0xAABBGGRR`, the format libobs uses for colors in settings.
+    pub fn to_abgr(&self) -> u32 {
+        u32::from_le_bytes([self.r, self.g, self.b, self.a])
+    }
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the color source, which fills an area with a
+    /// single color.
+    #[derive(Debug)]
+    struct ColorSource("color_source_v3") for ObsSourceRef {
+        /// Sets the color as `0xAABBGGRR`, use `set_color` instead.
+        #[obs_property(type_t = "int", settings_key = "color")]
+        color_raw: i64,
+
+        /// The width of the source, the canvas width by default
+        #[obs_property(type_t = "int")]
+        width: i64,
+
+        /// The height of the source, the canvas height by default
+        #[obs_property(type_t = "int")]
+        height: i64,
+    }
+);
+
+#[obs_object_impl]
+impl ColorSource {
+    pub fn set_color(self, color: ObsColor) -> Self {
+        self.set_color_raw(color.to_abgr() as i64)
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for ColorSourceBuilder {}
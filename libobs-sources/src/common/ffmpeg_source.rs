@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    sources::{ObsSourceBuilder, ObsSourceRef},
+    utils::ObsString,
+};
+use num_derive::{FromPrimitive, ToPrimitive};
+
+use crate::macro_helper::define_object_manager;
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes which range the colors of a video use.
+/// Used in `FfmpegSourceBuilder`
+pub enum ObsVideoRange {
+    /// Uses the range the file specifies
+    Default = libobs::video_range_type_VIDEO_RANGE_DEFAULT,
+    /// Limited range, usually used by YUV video
+    Partial = libobs::video_range_type_VIDEO_RANGE_PARTIAL,
+    Full = libobs::video_range_type_VIDEO_RANGE_FULL,
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the media source, which plays local files and
+    /// network streams with ffmpeg.
+    /// The playback can be controlled with `ObsSourceRef::media_controller`.
+    #[derive(Debug)]
+    struct FfmpegSource("ffmpeg_source") for ObsSourceRef {
+        /// Whether `local_file` is played. Network streams set in `input` are played otherwise.
+        #[obs_property(type_t = "bool")]
+        is_local_file: bool,
+
+        /// Sets the file to play, use `set_local_file` instead.
+        #[obs_property(type_t = "string", settings_key = "local_file")]
+        local_file_raw: String,
+
+        /// Sets the URL of the network stream to play, use `set_url` instead.
+        #[obs_property(type_t = "string", settings_key = "input")]
+        input_raw: String,
+
+        /// The container format of the network stream, detected automatically if empty
+        #[obs_property(type_t = "string")]
+        input_format: String,
+
+        /// The size of the network buffer in megabytes
+        #[obs_property(type_t = "int")]
+        buffering_mb: i64,
+
+        /// How long to wait before reconnecting to a network stream, in seconds
+        #[obs_property(type_t = "int")]
+        reconnect_delay_sec: i64,
+
+        /// Whether the network stream supports seeking
+        #[obs_property(type_t = "bool")]
+        seekable: bool,
+
+        /// Whether the media starts again once it has ended
+        #[obs_property(type_t = "bool")]
+        looping: bool,
+
+        /// Whether the media starts from the beginning when the source becomes active
+        #[obs_property(type_t = "bool")]
+        restart_on_activate: bool,
+
+        /// Whether the file is closed while the source isn't shown
+        #[obs_property(type_t = "bool")]
+        close_when_inactive: bool,
+
+        /// Whether the last frame is cleared once the media has ended
+        #[obs_property(type_t = "bool")]
+        clear_on_media_end: bool,
+
+        /// Whether the video is decoded on the GPU if possible
+        #[obs_property(type_t = "bool")]
+        hw_decode: bool,
+
+        /// The playback speed in percent, from 1 to 200 (100 by default)
+        #[obs_property(type_t = "int")]
+        speed_percent: i64,
+
+        /// The color range of the video
+        #[obs_property(type_t = "enum")]
+        color_range: ObsVideoRange,
+
+        /// Whether alpha is applied in linear space, for videos with transparency
+        #[obs_property(type_t = "bool")]
+        linear_alpha: bool,
+
+        /// Options passed to ffmpeg, like `rtsp_transport=tcp`
+        #[obs_property(type_t = "string")]
+        ffmpeg_options: String,
+    }
+);
+
+#[obs_object_impl]
+impl FfmpegSource {
+    /// Plays the given local file.
+    pub fn set_local_file(self, path: &Path) -> Self {
+        self.set_is_local_file(true)
+            .set_local_file_raw(path.to_string_lossy().as_ref())
+    }
+
+    /// Plays the network stream at the given URL, like `rtmp://` or `srt://` streams.
+    pub fn set_url<T: Into<ObsString> + Sync + Send>(self, url: T) -> Self {
+        self.set_is_local_file(false).set_input_raw(url)
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for FfmpegSourceBuilder {}
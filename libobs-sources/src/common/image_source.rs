@@ -0,0 +1,35 @@
+use std::path::Path;
+
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::sources::{ObsSourceBuilder, ObsSourceRef};
+
+use crate::macro_helper::define_object_manager;
+
+define_object_manager!(
+    /// Provides an easy to use builder for the image source.
+    #[derive(Debug)]
+    struct ImageSource("image_source") for ObsSourceRef {
+        /// Sets the image to show, use `set_file` instead.
+        #[obs_property(type_t = "string", settings_key = "file")]
+        file_raw: String,
+
+        /// Whether the image is unloaded from memory while the source isn't shown
+        #[obs_property(type_t = "bool")]
+        unload: bool,
+
+        /// Whether alpha is applied in linear space, for images with transparency
+        #[obs_property(type_t = "bool")]
+        linear_alpha: bool,
+    }
+);
+
+#[obs_object_impl]
+impl ImageSource {
+    /// Shows the given image file. Animated GIFs are played as well.
+    pub fn set_file(self, path: &Path) -> Self {
+        self.set_file_raw(path.to_string_lossy().as_ref())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for ImageSourceBuilder {}
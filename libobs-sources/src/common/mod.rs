@@ -0,0 +1,19 @@
+//! Builders for sources of plugins that are available on every platform.
+
+mod browser_source;
+pub use browser_source::*;
+
+mod color_source;
+pub use color_source::*;
+
+mod ffmpeg_source;
+pub use ffmpeg_source::*;
+
+mod image_source;
+pub use image_source::*;
+
+mod slideshow;
+pub use slideshow::*;
+
+mod text_source;
+pub use text_source::*;
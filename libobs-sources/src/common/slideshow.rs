@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    data::{ObsDataChange, ObsObjectBuilder, ObsObjectUpdater, StringEnum},
+    sources::{ObsSourceBuilder, ObsSourceRef},
+};
+
+use crate::macro_helper::define_object_manager;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Describes what the slideshow does when it is shown or hidden.
+/// Used in `SlideshowSourceBuilder`
+pub enum ObsSlideshowBehavior {
+    /// Keeps playing while the source isn't shown
+    AlwaysPlay,
+    /// Stops while hidden and restarts from the first slide once shown
+    StopRestart,
+    /// Pauses while hidden and continues once shown
+    PauseUnpause,
+}
+
+impl StringEnum for ObsSlideshowBehavior {
+    fn to_str(&self) -> &str {
+        match self {
+            ObsSlideshowBehavior::AlwaysPlay => "always_play",
+            ObsSlideshowBehavior::StopRestart => "stop_restart",
+            ObsSlideshowBehavior::PauseUnpause => "pause_unpause",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Describes how the slideshow advances to the next slide.
+pub enum ObsSlideshowMode {
+    /// Advances after the slide time has passed
+    Automatic,
+    /// Advances using the media controls or hotkeys
+    Manual,
+}
+
+impl StringEnum for ObsSlideshowMode {
+    fn to_str(&self) -> &str {
+        match self {
+            ObsSlideshowMode::Automatic => "mode_auto",
+            ObsSlideshowMode::Manual => "mode_manual",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Describes the transition between two slides.
+pub enum ObsSlideshowTransition {
+    Cut,
+    Fade,
+    Swipe,
+    Slide,
+}
+
+impl StringEnum for ObsSlideshowTransition {
+    fn to_str(&self) -> &str {
+        match self {
+            ObsSlideshowTransition::Cut => "cut",
+            ObsSlideshowTransition::Fade => "fade",
+            ObsSlideshowTransition::Swipe => "swipe",
+            ObsSlideshowTransition::Slide => "slide",
+        }
+    }
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the image slideshow source.
+    #[derive(Debug)]
+    struct SlideshowSource("slideshow_v2") for ObsSourceRef {
+        /// What the slideshow does when it is shown or hidden
+        #[obs_property(type_t = "enum_string", settings_key = "playback_behavior")]
+        behavior: ObsSlideshowBehavior,
+
+        /// How the slideshow advances to the next slide
+        #[obs_property(type_t = "enum_string", settings_key = "slide_mode")]
+        mode: ObsSlideshowMode,
+
+        /// The transition between two slides
+        #[obs_property(type_t = "enum_string")]
+        transition: ObsSlideshowTransition,
+
+        /// For how long each slide is shown in milliseconds
+        #[obs_property(type_t = "int")]
+        slide_time: i64,
+
+        /// The duration of the transition in milliseconds
+        #[obs_property(type_t = "int")]
+        transition_speed: i64,
+
+        /// Whether the slideshow starts again after the last slide
+        #[obs_property(type_t = "bool", settings_key = "loop")]
+        looping: bool,
+
+        /// Whether the source is hidden once the slideshow has ended
+        #[obs_property(type_t = "bool")]
+        hide: bool,
+
+        /// Whether the slides are shown in random order
+        #[obs_property(type_t = "bool")]
+        randomize: bool,
+
+        /// Sets the size of the slideshow as `WIDTHxHEIGHT`, use `set_custom_size` instead.
+        #[obs_property(type_t = "string", settings_key = "use_custom_size")]
+        custom_size_raw: String,
+    }
+);
+
+#[obs_object_impl]
+impl SlideshowSource {
+    /// Sets the images of the slideshow, in the order they are shown.
+    pub fn set_files<P: AsRef<Path>>(mut self, files: &[P]) -> Self {
+        let files = files
+            .iter()
+            .map(|f| {
+                let path = f.as_ref().to_string_lossy();
+                vec![ObsDataChange::String("value".into(), path.as_ref().into())]
+            })
+            .collect();
+
+        self.get_settings_updater().set_array_ref("files", files);
+        self
+    }
+
+    /// Scales every slide to the given size instead of the size of the first image.
+    pub fn set_custom_size(self, width: u32, height: u32) -> Self {
+        self.set_custom_size_raw(format!("{}x{}", width, height))
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for SlideshowSourceBuilder {}
@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    data::{ObsDataChange, ObsObjectBuilder, ObsObjectUpdater},
+    sources::{ObsSourceBuilder, ObsSourceRef},
+};
+
+use crate::macro_helper::define_object_manager;
+
+use super::ObsColor;
+
+/// The font of a text source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObsTextFont {
+    /// The name of the font family, like `Sans Serif`
+    pub face: String,
+    /// The style of the font family, like `Regular` or `Bold`
+    pub style: String,
+    pub size: u32,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikeout: bool,
+}
+
+impl ObsTextFont {
+    pub fn new(face: impl Into<String>, size: u32) -> Self {
+        Self {
+            face: face.into(),
+            style: "Regular".to_string(),
+            size,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikeout: false,
+        }
+    }
+
+    /// The `OBS_FONT_*` flags of this font.
+    pub fn flags(&self) -> i64 {
+        let flags = [
+            (self.bold, libobs::OBS_FONT_BOLD),
+            (self.italic, libobs::OBS_FONT_ITALIC),
+            (self.underline, libobs::OBS_FONT_UNDERLINE),
+            (self.strikeout, libobs::OBS_FONT_STRIKEOUT),
+        ];
+
+        flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .fold(0, |flags, (_, flag)| flags | *flag as i64)
+    }
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the FreeType 2 text source.
+    #[derive(Debug)]
+    struct TextSource("text_ft2_source_v2") for ObsSourceRef {
+        /// The text to show
+        #[obs_property(type_t = "string")]
+        text: String,
+
+        /// Whether the text is read from `text_file` instead
+        #[obs_property(type_t = "bool")]
+        from_file: bool,
+
+        /// Sets the file the text is read from, use `set_text_file` instead.
+        #[obs_property(type_t = "string", settings_key = "text_file")]
+        text_file_raw: String,
+
+        /// Whether only the last `log_lines` lines of the text file are shown
+        #[obs_property(type_t = "bool")]
+        log_mode: bool,
+
+        /// The number of lines that are shown in log mode
+        #[obs_property(type_t = "int")]
+        log_lines: i64,
+
+        /// Sets the color at the top as `0xAABBGGRR`, use `set_color` instead.
+        #[obs_property(type_t = "int", settings_key = "color1")]
+        color1_raw: i64,
+
+        /// Sets the color at the bottom as `0xAABBGGRR`, use `set_color` instead.
+        #[obs_property(type_t = "int", settings_key = "color2")]
+        color2_raw: i64,
+
+        /// Whether the text has a black outline
+        #[obs_property(type_t = "bool")]
+        outline: bool,
+
+        /// Whether the text has a black drop shadow
+        #[obs_property(type_t = "bool")]
+        drop_shadow: bool,
+
+        /// Whether the text is wrapped at `custom_width`
+        #[obs_property(type_t = "bool")]
+        word_wrap: bool,
+
+        /// The width at which the text is wrapped, in pixels
+        #[obs_property(type_t = "int")]
+        custom_width: i64,
+
+        #[obs_property(type_t = "bool")]
+        antialiasing: bool,
+    }
+);
+
+#[obs_object_impl]
+impl TextSource {
+    pub fn set_font(mut self, font: &ObsTextFont) -> Self {
+        let font = vec![
+            ObsDataChange::String("face".into(), font.face.as_str().into()),
+            ObsDataChange::String("style".into(), font.style.as_str().into()),
+            ObsDataChange::Int("size".into(), font.size as i64),
+            ObsDataChange::Int("flags".into(), font.flags()),
+        ];
+
+        self.get_settings_updater().set_obj_ref("font", font);
+        self
+    }
+
+    /// Sets a single color for the whole text.
+    pub fn set_color(self, color: ObsColor) -> Self {
+        self.set_gradient(color, color)
+    }
+
+    /// Sets a vertical gradient from the top to the bottom of the text.
+    pub fn set_gradient(self, top: ObsColor, bottom: ObsColor) -> Self {
+        self.set_color1_raw(top.to_abgr() as i64)
+            .set_color2_raw(bottom.to_abgr() as i64)
+    }
+
+    /// Reads the text from the given file, which is reloaded when it changes.
+    pub fn set_text_file(self, path: &Path) -> Self {
+        self.set_from_file(true)
+            .set_text_file_raw(path.to_string_lossy().as_ref())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for TextSourceBuilder {}
@@ -28,11 +28,14 @@
 //! .unwrap();
 //! `````
 
-#[cfg(not(windows))]
-compiler_error!("libobs-sources can only be used in windows");
-
+#[cfg(windows)]
 pub mod windows;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+pub mod common;
+
 pub mod encoders;
 pub mod output;
 mod macro_helper;
@@ -0,0 +1,3 @@
+mod sources;
+
+pub use sources::*;
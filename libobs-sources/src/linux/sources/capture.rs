@@ -0,0 +1,66 @@
+use libobs_wrapper::{
+    data::properties::{types::ObsListItemValue, ObsProperty, ObsPropertyObject},
+    runtime::ObsRuntime,
+    sources::ObsSourceRef,
+    utils::ObsError,
+};
+
+/// A screen of the X server, listed by `XshmInputSourceBuilder::get_screens`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObsXScreen {
+    /// The description shown in OBS, including resolution and position of the screen
+    pub name: String,
+    /// The index of the screen
+    pub index: i64,
+}
+
+/// A window, listed by `XCompositeInputSourceBuilder::get_windows`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObsXWindow {
+    /// The title of the window
+    pub name: String,
+    /// The id OBS uses to find the window again, contains the window id, title and class
+    pub id: String,
+}
+
+/// A video or audio device, listed by `V4l2InputSourceBuilder::get_devices` and the
+/// builders of the PulseAudio sources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObsLinuxDevice {
+    /// The name of the device
+    pub name: String,
+    /// The id of the device, like `/dev/video0` or the name of the PulseAudio sink or source
+    pub id: String,
+}
+
+/// Returns the enabled items of the list property `property` of the source type `source_id`.
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+pub(crate) async fn get_list_items(
+    source_id: &str,
+    property: &str,
+    runtime: &ObsRuntime,
+) -> Result<Vec<(String, ObsListItemValue)>, ObsError> {
+    let properties = ObsSourceRef::get_properties_by_id(source_id, runtime).await?;
+    let items = match properties.get(property) {
+        Some(ObsProperty::List(list)) => list
+            .items()
+            .iter()
+            .filter(|item| !*item.disabled())
+            .map(|item| (item.name().clone(), item.value().clone()))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(items)
+}
+
+/// Collects the list items with a string value, e.g. device ids.
+pub(crate) fn string_items(items: Vec<(String, ObsListItemValue)>) -> Vec<(String, String)> {
+    items
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            ObsListItemValue::String(value) => Some((name, value)),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,20 @@
+//! Builders for the capture sources of the `linux-capture`, `linux-pipewire`, `linux-v4l2`
+//! and `linux-pulseaudio` plugins.
+
+mod capture;
+pub use capture::*;
+
+mod xshm_input;
+pub use xshm_input::*;
+
+mod xcomposite_input;
+pub use xcomposite_input::*;
+
+mod pipewire_capture;
+pub use pipewire_capture::*;
+
+mod v4l2_input;
+pub use v4l2_input::*;
+
+mod pulse_capture;
+pub use pulse_capture::*;
@@ -0,0 +1,24 @@
+use libobs_wrapper::sources::{ObsSourceBuilder, ObsSourceRef};
+
+use crate::macro_helper::define_object_manager;
+
+define_object_manager!(
+    /// Provides an easy to use builder for the screen capture source on Wayland (PipeWire).
+    ///
+    /// The screen or window is picked by the user through the dialog of the desktop portal,
+    /// so there is nothing to list here. Store the `RestoreToken` from the settings of the
+    /// created source and pass it to `set_restore_token` to skip the dialog next time.
+    #[derive(Debug)]
+    struct PipeWireScreenCaptureSource("pipewire-screen-capture-source") for ObsSourceRef {
+        /// Sets whether the cursor should be captured.
+        #[obs_property(type_t = "bool", settings_key = "ShowCursor")]
+        show_cursor: bool,
+
+        /// The token the desktop portal returned for a previous capture session
+        #[obs_property(type_t = "string", settings_key = "RestoreToken")]
+        restore_token: String,
+    }
+);
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for PipeWireScreenCaptureSourceBuilder {}
@@ -0,0 +1,83 @@
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    sources::{ObsSourceBuilder, ObsSourceRef},
+    utils::ObsError,
+};
+
+use crate::macro_helper::define_object_manager;
+
+use super::{get_list_items, string_items, ObsLinuxDevice};
+
+define_object_manager!(
+    /// Provides an easy to use builder for the PulseAudio input capture source, which
+    /// captures microphones and other recording devices.
+    #[derive(Debug)]
+    struct PulseInputCaptureSource("pulse_input_capture") for ObsSourceRef {
+        /// The name of the PulseAudio source to capture, use `set_device` instead.
+        /// `default` captures the default device.
+        #[obs_property(type_t = "string", settings_key = "device_id")]
+        device_id_raw: String,
+    }
+);
+
+#[obs_object_impl]
+impl PulseInputCaptureSource {
+    /// Sets the device to capture.
+    /// A list of available devices can be retrieved using `PulseInputCaptureSourceBuilder::get_devices`
+    pub fn set_device(self, device: &ObsLinuxDevice) -> Self {
+        self.set_device_id_raw(device.id.as_str())
+    }
+}
+
+impl PulseInputCaptureSourceBuilder {
+    /// Gets the input devices that can be captured, as listed in the properties of the source.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_devices(&self) -> Result<Vec<ObsLinuxDevice>, ObsError> {
+        let items = get_list_items("pulse_input_capture", "device_id", &self.runtime).await?;
+
+        Ok(string_items(items)
+            .into_iter()
+            .map(|(name, id)| ObsLinuxDevice { name, id })
+            .collect())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for PulseInputCaptureSourceBuilder {}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the PulseAudio output capture source, which
+    /// captures the audio played on an output device (desktop audio).
+    #[derive(Debug)]
+    struct PulseOutputCaptureSource("pulse_output_capture") for ObsSourceRef {
+        /// The name of the PulseAudio sink to capture, use `set_device` instead.
+        /// `default` captures the default device.
+        #[obs_property(type_t = "string", settings_key = "device_id")]
+        device_id_raw: String,
+    }
+);
+
+#[obs_object_impl]
+impl PulseOutputCaptureSource {
+    /// Sets the device to capture.
+    /// A list of available devices can be retrieved using `PulseOutputCaptureSourceBuilder::get_devices`
+    pub fn set_device(self, device: &ObsLinuxDevice) -> Self {
+        self.set_device_id_raw(device.id.as_str())
+    }
+}
+
+impl PulseOutputCaptureSourceBuilder {
+    /// Gets the output devices that can be captured, as listed in the properties of the source.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_devices(&self) -> Result<Vec<ObsLinuxDevice>, ObsError> {
+        let items = get_list_items("pulse_output_capture", "device_id", &self.runtime).await?;
+
+        Ok(string_items(items)
+            .into_iter()
+            .map(|(name, id)| ObsLinuxDevice { name, id })
+            .collect())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for PulseOutputCaptureSourceBuilder {}
@@ -0,0 +1,101 @@
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    sources::{ObsSourceBuilder, ObsSourceRef},
+    utils::ObsError,
+};
+
+use crate::{common::ObsVideoRange, macro_helper::define_object_manager};
+
+use super::{get_list_items, string_items, ObsLinuxDevice};
+
+/// Packs two values the way the v4l2 plugin stores resolutions and frame intervals.
+fn pack_tuple(a: u32, b: u32) -> i64 {
+    (((a & 0xffff) << 16) | (b & 0xffff)) as i64
+}
+
+define_object_manager!(
+    /// Provides an easy to use builder for the Video4Linux2 capture source, which captures
+    /// webcams and capture cards.
+    #[derive(Debug)]
+    struct V4l2InputSource("v4l2_input") for ObsSourceRef {
+        /// The path of the device to capture, use `set_device` instead.
+        #[obs_property(type_t = "string", settings_key = "device_id")]
+        device_id_raw: String,
+
+        /// The input of the device to capture, for devices with multiple inputs
+        #[obs_property(type_t = "int")]
+        input: i64,
+
+        /// The pixel format as V4L2 fourcc code, -1 uses the format of the device
+        #[obs_property(type_t = "int")]
+        pixelformat: i64,
+
+        /// The video standard of analog inputs
+        #[obs_property(type_t = "int")]
+        standard: i64,
+
+        /// The DV timing of digital inputs
+        #[obs_property(type_t = "int")]
+        dv_timing: i64,
+
+        /// The packed resolution, use `set_resolution` instead.
+        #[obs_property(type_t = "int", settings_key = "resolution")]
+        resolution_raw: i64,
+
+        /// The packed frame interval, use `set_framerate` instead.
+        #[obs_property(type_t = "int", settings_key = "framerate")]
+        framerate_raw: i64,
+
+        /// The color range of the video
+        #[obs_property(type_t = "enum")]
+        color_range: ObsVideoRange,
+
+        /// Whether the device is reset if it doesn't deliver frames for `timeout_frames` frames
+        #[obs_property(type_t = "bool")]
+        auto_reset: bool,
+
+        /// The number of missing frames after which the device is reset
+        #[obs_property(type_t = "int")]
+        timeout_frames: i64,
+
+        /// Whether frames are buffered, which smooths the video but adds latency
+        #[obs_property(type_t = "bool")]
+        buffering: bool,
+    }
+);
+
+#[obs_object_impl]
+impl V4l2InputSource {
+    /// Sets the device to capture.
+    /// A list of available devices can be retrieved using `V4l2InputSourceBuilder::get_devices`
+    pub fn set_device(self, device: &ObsLinuxDevice) -> Self {
+        self.set_device_id_raw(device.id.as_str())
+    }
+
+    /// Sets the resolution the device captures with.
+    pub fn set_resolution(self, width: u32, height: u32) -> Self {
+        self.set_resolution_raw(pack_tuple(width, height))
+    }
+
+    /// Sets the frame rate the device captures with, as fraction like `30000 / 1001`.
+    pub fn set_framerate(self, numerator: u32, denominator: u32) -> Self {
+        // The plugin stores the frame interval, which is the inverse of the frame rate
+        self.set_framerate_raw(pack_tuple(denominator, numerator))
+    }
+}
+
+impl V4l2InputSourceBuilder {
+    /// Gets the video devices that can be captured, as listed in the properties of the source.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_devices(&self) -> Result<Vec<ObsLinuxDevice>, ObsError> {
+        let items = get_list_items("v4l2_input", "device_id", &self.runtime).await?;
+
+        Ok(string_items(items)
+            .into_iter()
+            .map(|(name, id)| ObsLinuxDevice { name, id })
+            .collect())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for V4l2InputSourceBuilder {}
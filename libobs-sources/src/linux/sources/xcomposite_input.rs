@@ -0,0 +1,76 @@
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    sources::{ObsSourceBuilder, ObsSourceRef},
+    utils::ObsError,
+};
+
+use crate::macro_helper::define_object_manager;
+
+use super::{get_list_items, string_items, ObsXWindow};
+
+define_object_manager!(
+    /// Provides an easy to use builder for the window capture source on X11 (XComposite).
+    #[derive(Debug)]
+    struct XCompositeInputSource("xcomposite_input") for ObsSourceRef {
+        /// The id of the window to capture, use `set_window` instead.
+        #[obs_property(type_t = "string", settings_key = "capture_window")]
+        capture_window_raw: String,
+
+        /// Sets whether the cursor should be captured.
+        #[obs_property(type_t = "bool")]
+        show_cursor: bool,
+
+        /// Whether the window decorations drawn by the window manager are captured as well
+        #[obs_property(type_t = "bool")]
+        include_border: bool,
+
+        /// Whether the alpha channel of the window is ignored
+        #[obs_property(type_t = "bool")]
+        exclude_alpha: bool,
+
+        /// Swaps the red and blue channels, for drivers that report them the other way around
+        #[obs_property(type_t = "bool")]
+        swap_redblue: bool,
+
+        /// Pixels to crop from the top of the window
+        #[obs_property(type_t = "int")]
+        cut_top: i64,
+
+        /// Pixels to crop from the left of the window
+        #[obs_property(type_t = "int")]
+        cut_left: i64,
+
+        /// Pixels to crop from the right of the window
+        #[obs_property(type_t = "int")]
+        cut_right: i64,
+
+        /// Pixels to crop from the bottom of the window
+        #[obs_property(type_t = "int", settings_key = "cut_bot")]
+        cut_bottom: i64,
+    }
+);
+
+#[obs_object_impl]
+impl XCompositeInputSource {
+    /// Sets the window to capture.
+    /// A list of available windows can be retrieved using `XCompositeInputSourceBuilder::get_windows`
+    pub fn set_window(self, window: &ObsXWindow) -> Self {
+        self.set_capture_window_raw(window.id.as_str())
+    }
+}
+
+impl XCompositeInputSourceBuilder {
+    /// Gets the windows that can be captured, as listed in the properties of the source.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_windows(&self) -> Result<Vec<ObsXWindow>, ObsError> {
+        let items = get_list_items("xcomposite_input", "capture_window", &self.runtime).await?;
+
+        Ok(string_items(items)
+            .into_iter()
+            .map(|(name, id)| ObsXWindow { name, id })
+            .collect())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for XCompositeInputSourceBuilder {}
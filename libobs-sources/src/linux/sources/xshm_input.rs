@@ -0,0 +1,76 @@
+use libobs_source_macro::obs_object_impl;
+use libobs_wrapper::{
+    data::properties::types::ObsListItemValue,
+    sources::{ObsSourceBuilder, ObsSourceRef},
+    utils::ObsError,
+};
+
+use crate::macro_helper::define_object_manager;
+
+use super::{get_list_items, ObsXScreen};
+
+define_object_manager!(
+    /// Provides an easy to use builder for the screen capture source on X11 (XSHM).
+    #[derive(Debug)]
+    struct XshmInputSource("xshm_input") for ObsSourceRef {
+        /// The index of the screen to capture, use `set_screen` instead.
+        #[obs_property(type_t = "int", settings_key = "screen")]
+        screen_raw: i64,
+
+        /// Sets whether the cursor should be captured.
+        #[obs_property(type_t = "bool")]
+        show_cursor: bool,
+
+        /// Whether `server` is used instead of the default X server.
+        #[obs_property(type_t = "bool")]
+        advanced: bool,
+
+        /// The X server to connect to, like `:1`. Only used if `advanced` is set.
+        #[obs_property(type_t = "string")]
+        server: String,
+
+        /// Pixels to crop from the top of the screen
+        #[obs_property(type_t = "int")]
+        cut_top: i64,
+
+        /// Pixels to crop from the left of the screen
+        #[obs_property(type_t = "int")]
+        cut_left: i64,
+
+        /// Pixels to crop from the right of the screen
+        #[obs_property(type_t = "int")]
+        cut_right: i64,
+
+        /// Pixels to crop from the bottom of the screen
+        #[obs_property(type_t = "int", settings_key = "cut_bot")]
+        cut_bottom: i64,
+    }
+);
+
+#[obs_object_impl]
+impl XshmInputSource {
+    /// Sets the screen to capture.
+    /// A list of available screens can be retrieved using `XshmInputSourceBuilder::get_screens`
+    pub fn set_screen(self, screen: &ObsXScreen) -> Self {
+        self.set_screen_raw(screen.index)
+    }
+}
+
+impl XshmInputSourceBuilder {
+    /// Gets the screens of the default X server, as listed in the properties of the source.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_screens(&self) -> Result<Vec<ObsXScreen>, ObsError> {
+        let items = get_list_items("xshm_input", "screen", &self.runtime).await?;
+
+        Ok(items
+            .into_iter()
+            .filter_map(|(name, value)| match value {
+                ObsListItemValue::Int(index) => Some(ObsXScreen { name, index }),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+#[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
+impl ObsSourceBuilder for XshmInputSourceBuilder {}
@@ -0,0 +1,198 @@
+//! Checks the settings the builders of the common sources create, using the fake backend.
+#![cfg(not(feature = "blocking"))]
+
+use std::path::Path;
+
+use libobs_sources::common::{
+    BrowserSourceBuilder, ColorSourceBuilder, FfmpegSourceBuilder, ImageSourceBuilder,
+    ObsBrowserControlLevel, ObsColor, ObsSlideshowBehavior, ObsSlideshowMode,
+    ObsSlideshowTransition, ObsTextFont, ObsVideoRange, SlideshowSourceBuilder, TextSourceBuilder,
+};
+use libobs_wrapper::backend::fake::{FakeObsBackend, FakeSettings};
+use libobs_wrapper::context::ObsContext;
+use libobs_wrapper::sources::ObsSourceBuilder;
+use libobs_wrapper::utils::StartupInfo;
+
+async fn fake_context() -> (FakeObsBackend, ObsContext) {
+    let backend = FakeObsBackend::new();
+    let startup_info = StartupInfo::default().set_backend(backend.clone());
+
+    let context = ObsContext::new(startup_info).await.unwrap();
+    #[cfg(feature = "bootstrapper")]
+    let context = match context {
+        libobs_wrapper::context::ObsContextReturn::Done(c) => c,
+        libobs_wrapper::context::ObsContextReturn::Restart => panic!("Restart not supported"),
+    };
+
+    (backend, context)
+}
+
+/// Builds the source into a new scene and returns the settings it was created with.
+async fn build<T: ObsSourceBuilder + Send>(
+    backend: &FakeObsBackend,
+    context: &mut ObsContext,
+    builder: impl FnOnce(T) -> T,
+) -> (String, FakeSettings) {
+    let mut scene = context.scene("main").await.unwrap();
+    let source = builder(context.source_builder::<T, _>("source").await.unwrap())
+        .add_to_scene(&mut scene)
+        .await
+        .unwrap();
+
+    let fake_source = backend.source("source").unwrap();
+    assert_eq!(fake_source.id, source.id());
+    (fake_source.id, fake_source.settings)
+}
+
+#[tokio::test]
+async fn image_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let (id, settings) = build::<ImageSourceBuilder>(&backend, &mut context, |b| {
+        b.set_file(Path::new("logo.png"))
+            .set_unload(true)
+            .set_linear_alpha(false)
+    })
+    .await;
+    assert_eq!(id, "image_source");
+    assert_eq!(settings["file"], "logo.png");
+    assert_eq!(settings["unload"], true);
+    assert_eq!(settings["linear_alpha"], false);
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn color_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let (id, settings) = build::<ColorSourceBuilder>(&backend, &mut context, |b| {
+        b.set_color(ObsColor::rgba(0x11, 0x22, 0x33, 0x80))
+            .set_width(320)
+            .set_height(240)
+    })
+    .await;
+    assert_eq!(id, "color_source_v3");
+    assert_eq!(settings["color"], 0x8033_2211_i64);
+    assert_eq!(settings["width"], 320);
+    assert_eq!(settings["height"], 240);
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn slideshow_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let (id, settings) = build::<SlideshowSourceBuilder>(&backend, &mut context, |b| {
+        b.set_files(&["a.png", "b.png"])
+            .set_behavior(ObsSlideshowBehavior::PauseUnpause)
+            .set_mode(ObsSlideshowMode::Manual)
+            .set_transition(ObsSlideshowTransition::Swipe)
+            .set_slide_time(5000)
+            .set_looping(true)
+            .set_custom_size(1920, 1080)
+    })
+    .await;
+    assert_eq!(id, "slideshow_v2");
+    assert_eq!(settings["files"][0]["value"], "a.png");
+    assert_eq!(settings["files"][1]["value"], "b.png");
+    assert_eq!(settings["playback_behavior"], "pause_unpause");
+    assert_eq!(settings["slide_mode"], "mode_manual");
+    assert_eq!(settings["transition"], "swipe");
+    assert_eq!(settings["slide_time"], 5000);
+    assert_eq!(settings["loop"], true);
+    assert_eq!(settings["use_custom_size"], "1920x1080");
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn text_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let mut font = ObsTextFont::new("Sans Serif", 48);
+    font.bold = true;
+    font.underline = true;
+
+    let (id, settings) = build::<TextSourceBuilder>(&backend, &mut context, |b| {
+        b.set_text("Hello")
+            .set_font(&font)
+            .set_gradient(ObsColor::rgb(255, 0, 0), ObsColor::rgb(0, 0, 255))
+            .set_outline(true)
+    })
+    .await;
+    assert_eq!(id, "text_ft2_source_v2");
+    assert_eq!(settings["text"], "Hello");
+    assert_eq!(settings["font"]["face"], "Sans Serif");
+    assert_eq!(settings["font"]["style"], "Regular");
+    assert_eq!(settings["font"]["size"], 48);
+    assert_eq!(
+        settings["font"]["flags"],
+        (libobs::OBS_FONT_BOLD | libobs::OBS_FONT_UNDERLINE) as i64
+    );
+    assert_eq!(settings["color1"], 0xFF00_00FF_i64);
+    assert_eq!(settings["color2"], 0xFFFF_0000_i64);
+    assert_eq!(settings["outline"], true);
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn ffmpeg_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let (id, settings) = build::<FfmpegSourceBuilder>(&backend, &mut context, |b| {
+        b.set_local_file(Path::new("intro.mp4"))
+            .set_looping(true)
+            .set_speed_percent(150)
+            .set_color_range(ObsVideoRange::Full)
+    })
+    .await;
+    assert_eq!(id, "ffmpeg_source");
+    assert_eq!(settings["is_local_file"], true);
+    assert_eq!(settings["local_file"], "intro.mp4");
+    assert_eq!(settings["looping"], true);
+    assert_eq!(settings["speed_percent"], 150);
+    assert_eq!(
+        settings["color_range"],
+        libobs::video_range_type_VIDEO_RANGE_FULL as i64
+    );
+
+    assert!(context.shutdown().await.is_clean());
+
+    // Network streams replace the local file
+    let (backend, mut context) = fake_context().await;
+    let (_, settings) = build::<FfmpegSourceBuilder>(&backend, &mut context, |b| {
+        b.set_url("srt://127.0.0.1:9000")
+    })
+    .await;
+    assert_eq!(settings["is_local_file"], false);
+    assert_eq!(settings["input"], "srt://127.0.0.1:9000");
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn browser_source_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let (id, settings) = build::<BrowserSourceBuilder>(&backend, &mut context, |b| {
+        b.set_url("https://example.com")
+            .set_width(1280)
+            .set_height(720)
+            .set_reroute_audio(true)
+            .set_css("body { background: transparent; }")
+            .set_webpage_control_level(ObsBrowserControlLevel::Advanced)
+    })
+    .await;
+    assert_eq!(id, "browser_source");
+    assert_eq!(settings["url"], "https://example.com");
+    assert_eq!(settings["width"], 1280);
+    assert_eq!(settings["height"], 720);
+    assert_eq!(settings["reroute_audio"], true);
+    assert_eq!(settings["css"], "body { background: transparent; }");
+    assert_eq!(settings["webpage_control_level"], 4);
+
+    assert!(context.shutdown().await.is_clean());
+}
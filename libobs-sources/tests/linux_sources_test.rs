@@ -0,0 +1,217 @@
+//! Checks the Linux source builders against the fake backend, which provides the list
+//! properties the plugins would fill with devices, screens and windows.
+#![cfg(all(target_os = "linux", not(feature = "blocking")))]
+
+use libobs_sources::linux::{
+    ObsLinuxDevice, ObsXScreen, ObsXWindow, PulseInputCaptureSourceBuilder,
+    PulseOutputCaptureSourceBuilder, V4l2InputSourceBuilder, XCompositeInputSourceBuilder,
+    XshmInputSourceBuilder,
+};
+use libobs_wrapper::backend::fake::{FakeListItem, FakeObsBackend};
+use libobs_wrapper::context::ObsContext;
+use libobs_wrapper::sources::ObsSourceBuilder;
+use libobs_wrapper::utils::StartupInfo;
+
+async fn fake_context() -> (FakeObsBackend, ObsContext) {
+    let backend = FakeObsBackend::new();
+    let startup_info = StartupInfo::default().set_backend(backend.clone());
+
+    let context = ObsContext::new(startup_info).await.unwrap();
+    #[cfg(feature = "bootstrapper")]
+    let context = match context {
+        libobs_wrapper::context::ObsContextReturn::Done(c) => c,
+        libobs_wrapper::context::ObsContextReturn::Restart => panic!("Restart not supported"),
+    };
+
+    (backend, context)
+}
+
+fn device(name: &str, id: &str) -> ObsLinuxDevice {
+    ObsLinuxDevice {
+        name: name.to_string(),
+        id: id.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn v4l2_devices_test() {
+    let (backend, context) = fake_context().await;
+    backend.register_list_property(
+        "v4l2_input",
+        "device_id",
+        "Device",
+        vec![
+            FakeListItem::new("Integrated Camera", "/dev/video0"),
+            FakeListItem::new("Busy Camera", "/dev/video1").disabled(),
+            FakeListItem::new("Capture Card", "/dev/video2"),
+        ],
+    );
+
+    let devices = context
+        .source_builder::<V4l2InputSourceBuilder, _>("camera")
+        .await
+        .unwrap()
+        .get_devices()
+        .await
+        .unwrap();
+    assert_eq!(
+        devices,
+        vec![
+            device("Integrated Camera", "/dev/video0"),
+            device("Capture Card", "/dev/video2"),
+        ]
+    );
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn v4l2_settings_test() {
+    let (backend, mut context) = fake_context().await;
+
+    let mut scene = context.scene("main").await.unwrap();
+    context
+        .source_builder::<V4l2InputSourceBuilder, _>("camera")
+        .await
+        .unwrap()
+        .set_device(&device("Integrated Camera", "/dev/video0"))
+        .set_resolution(1280, 720)
+        .set_framerate(30000, 1001)
+        .add_to_scene(&mut scene)
+        .await
+        .unwrap();
+
+    let settings = backend.source("camera").unwrap().settings;
+    assert_eq!(settings["device_id"], "/dev/video0");
+    assert_eq!(settings["resolution"], (1280_i64 << 16) | 720);
+    // The plugin stores the frame interval, so the denominator comes first
+    assert_eq!(settings["framerate"], (1001_i64 << 16) | 30000);
+
+    drop(scene);
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn xshm_screens_test() {
+    let (backend, context) = fake_context().await;
+    backend.register_list_property(
+        "xshm_input",
+        "screen",
+        "Screen",
+        vec![
+            FakeListItem::new("Screen 0: 1920x1080 @ 0,0", 0),
+            FakeListItem::new("Screen 1: 2560x1440 @ 1920,0", 1).disabled(),
+            FakeListItem::new("Screen 2: 1280x1024 @ 4480,0", 2),
+        ],
+    );
+
+    let screens = context
+        .source_builder::<XshmInputSourceBuilder, _>("screen")
+        .await
+        .unwrap()
+        .get_screens()
+        .await
+        .unwrap();
+    assert_eq!(
+        screens,
+        vec![
+            ObsXScreen {
+                name: "Screen 0: 1920x1080 @ 0,0".to_string(),
+                index: 0,
+            },
+            ObsXScreen {
+                name: "Screen 2: 1280x1024 @ 4480,0".to_string(),
+                index: 2,
+            },
+        ]
+    );
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn xcomposite_windows_test() {
+    let (backend, context) = fake_context().await;
+    backend.register_list_property(
+        "xcomposite_input",
+        "capture_window",
+        "Window",
+        vec![
+            FakeListItem::new("Terminal", "73400323\r\nTerminal\r\nxterm"),
+            FakeListItem::new("Closed Window", "0\r\nClosed Window\r\nxterm").disabled(),
+        ],
+    );
+
+    let windows = context
+        .source_builder::<XCompositeInputSourceBuilder, _>("window")
+        .await
+        .unwrap()
+        .get_windows()
+        .await
+        .unwrap();
+    assert_eq!(
+        windows,
+        vec![ObsXWindow {
+            name: "Terminal".to_string(),
+            id: "73400323\r\nTerminal\r\nxterm".to_string(),
+        }]
+    );
+
+    assert!(context.shutdown().await.is_clean());
+}
+
+#[tokio::test]
+async fn pulse_devices_test() {
+    let (backend, context) = fake_context().await;
+    backend.register_list_property(
+        "pulse_input_capture",
+        "device_id",
+        "Device",
+        vec![
+            FakeListItem::new("Default", "default"),
+            FakeListItem::new("Built-in Microphone", "alsa_input.pci.analog-stereo"),
+            FakeListItem::new("Unplugged Headset", "alsa_input.usb.mono").disabled(),
+        ],
+    );
+    backend.register_list_property(
+        "pulse_output_capture",
+        "device_id",
+        "Device",
+        vec![
+            FakeListItem::new("Default", "default"),
+            FakeListItem::new("Speakers", "alsa_output.pci.analog-stereo"),
+        ],
+    );
+
+    let inputs = context
+        .source_builder::<PulseInputCaptureSourceBuilder, _>("mic")
+        .await
+        .unwrap()
+        .get_devices()
+        .await
+        .unwrap();
+    assert_eq!(
+        inputs,
+        vec![
+            device("Default", "default"),
+            device("Built-in Microphone", "alsa_input.pci.analog-stereo"),
+        ]
+    );
+
+    let outputs = context
+        .source_builder::<PulseOutputCaptureSourceBuilder, _>("desktop")
+        .await
+        .unwrap()
+        .get_devices()
+        .await
+        .unwrap();
+    assert_eq!(
+        outputs,
+        vec![
+            device("Default", "default"),
+            device("Speakers", "alsa_output.pci.analog-stereo"),
+        ]
+    );
+
+    assert!(context.shutdown().await.is_clean());
+}
@@ -2,9 +2,9 @@
 //! This crate provides necessary information about windows that could be used
 //! so they can be captured with the `window_capture` or `game_capture` source in OBS.
 //! <br> The function you probably want to use is `get_all_windows` which returns a list of `WindowInfo` structs.
-
-#[cfg(not(windows))]
-compile_error!("This library only supports windows!");
+//!
+//! This library only supports windows, on every other platform the crate is empty.
+#![cfg(windows)]
 
 #[cfg(not(target_pointer_width = "64"))]
 compile_error!("compilation is only allowed for 64-bit targets");
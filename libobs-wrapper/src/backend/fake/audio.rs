@@ -0,0 +1,942 @@
+//! Audio controls, raw audio, volume meters, faders and media playback of the fake backend.
+
+use std::{
+    ffi::{c_int, c_void},
+    ptr,
+};
+
+use libobs::{
+    audio_convert_info, audio_data, audio_output_callback_t, obs_fader_t, obs_fader_type,
+    obs_media_state, obs_monitoring_type, obs_peak_meter_type, obs_source_audio_capture_t,
+    obs_source_t, obs_volmeter_t, obs_volmeter_updated_t,
+};
+use num_traits::FromPrimitive;
+
+use crate::{
+    enums::{ObsFaderType, ObsMediaState, ObsMonitoringType, ObsPeakMeterType, ObsSpeakerLayout},
+    sources::{db_to_mul, mul_to_db},
+};
+
+use super::{
+    handle, ptr_of, FakeAudioCaptureConnection, FakeCalldata, FakeFader, FakeObsBackend,
+    FakeRawAudioConnection, FakeVolmeter,
+};
+
+/// The audio controls of a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeSourceAudio {
+    pub volume: f32,
+    pub muted: bool,
+    pub balance: f32,
+    pub sync_offset: i64,
+    pub mixers: u32,
+    pub push_to_talk: bool,
+    pub push_to_talk_delay: u64,
+    pub push_to_mute: bool,
+    pub push_to_mute_delay: u64,
+    pub monitoring_type: ObsMonitoringType,
+}
+
+impl Default for FakeSourceAudio {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            balance: 0.5,
+            sync_offset: 0,
+            mixers: 0xFF,
+            push_to_talk: false,
+            push_to_talk_delay: 0,
+            push_to_mute: false,
+            push_to_mute_delay: 0,
+            monitoring_type: ObsMonitoringType::None,
+        }
+    }
+}
+
+/// The media playback of a source, in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeSourceMedia {
+    pub state: ObsMediaState,
+    pub time: i64,
+    pub duration: i64,
+}
+
+impl Default for FakeSourceMedia {
+    fn default() -> Self {
+        Self {
+            state: ObsMediaState::None,
+            time: 0,
+            duration: 0,
+        }
+    }
+}
+
+const LOG_OFFSET_DB: f32 = 6.0;
+const LOG_RANGE_DB: f32 = 96.0;
+/// `-log10(LOG_OFFSET_DB)`
+const LOG_OFFSET_VAL: f32 = -0.778_151_3;
+/// `-log10(LOG_RANGE_DB + LOG_OFFSET_DB)`
+const LOG_RANGE_VAL: f32 = -2.008_600_2;
+
+/// The lowest volume a fader can be set to before it is muted, like in libobs.
+fn fader_min_db(fader_type: ObsFaderType) -> f32 {
+    match fader_type {
+        ObsFaderType::Cubic | ObsFaderType::Iec => f32::NEG_INFINITY,
+        ObsFaderType::Log => -LOG_RANGE_DB,
+    }
+}
+
+/// Maps the position of a fader to a volume in dB, using the curves of libobs.
+fn fader_def_to_db(fader_type: ObsFaderType, def: f32) -> f32 {
+    if def.is_nan() || def <= 0.0 {
+        return f32::NEG_INFINITY;
+    }
+
+    match fader_type {
+        ObsFaderType::Cubic if def == 1.0 => 0.0,
+        ObsFaderType::Cubic => mul_to_db(def * def * def),
+        ObsFaderType::Iec if def == 1.0 => 0.0,
+        ObsFaderType::Iec if def >= 0.75 => (def - 1.0) / 0.25 * 9.0,
+        ObsFaderType::Iec if def >= 0.5 => (def - 0.75) / 0.25 * 11.0 - 9.0,
+        ObsFaderType::Iec if def >= 0.3 => (def - 0.5) / 0.2 * 10.0 - 20.0,
+        ObsFaderType::Iec if def >= 0.15 => (def - 0.3) / 0.15 * 10.0 - 30.0,
+        ObsFaderType::Iec if def >= 0.075 => (def - 0.15) / 0.075 * 10.0 - 40.0,
+        ObsFaderType::Iec if def >= 0.025 => (def - 0.075) / 0.05 * 10.0 - 50.0,
+        ObsFaderType::Iec if def >= 0.001 => (def - 0.025) / 0.025 * 90.0 - 60.0,
+        ObsFaderType::Iec => f32::NEG_INFINITY,
+        ObsFaderType::Log if def >= 1.0 => 0.0,
+        ObsFaderType::Log => {
+            -(LOG_RANGE_DB + LOG_OFFSET_DB)
+                * ((LOG_RANGE_DB + LOG_OFFSET_DB) / LOG_OFFSET_DB).powf(-def)
+                + LOG_OFFSET_DB
+        }
+    }
+}
+
+/// Maps a volume in dB to the position of a fader, using the curves of libobs.
+fn fader_db_to_def(fader_type: ObsFaderType, db: f32) -> f32 {
+    if db >= 0.0 {
+        return 1.0;
+    } else if db == f32::NEG_INFINITY {
+        return 0.0;
+    }
+
+    match fader_type {
+        ObsFaderType::Cubic => db_to_mul(db).cbrt(),
+        ObsFaderType::Iec if db >= -9.0 => (db + 9.0) / 9.0 * 0.25 + 0.75,
+        ObsFaderType::Iec if db >= -20.0 => (db + 20.0) / 11.0 * 0.25 + 0.5,
+        ObsFaderType::Iec if db >= -30.0 => (db + 30.0) / 10.0 * 0.2 + 0.3,
+        ObsFaderType::Iec if db >= -40.0 => (db + 40.0) / 10.0 * 0.15 + 0.15,
+        ObsFaderType::Iec if db >= -50.0 => (db + 50.0) / 10.0 * 0.075 + 0.075,
+        ObsFaderType::Iec if db >= -60.0 => (db + 60.0) / 10.0 * 0.05 + 0.025,
+        ObsFaderType::Iec if db >= -114.0 => (db + 150.0) / 90.0 * 0.025,
+        ObsFaderType::Iec => 0.0,
+        ObsFaderType::Log if db <= -LOG_RANGE_DB => 0.0,
+        ObsFaderType::Log => {
+            (-(-db + LOG_OFFSET_DB).log10() - LOG_RANGE_VAL) / (LOG_OFFSET_VAL - LOG_RANGE_VAL)
+        }
+    }
+}
+
+impl FakeObsBackend {
+    /// Returns the audio controls of the source with the given name.
+    pub fn source_audio(&self, name: &str) -> Option<FakeSourceAudio> {
+        let state = self.state();
+        let source = state.source_by_name(name)?;
+        Some(state.source_audio.get(&source).cloned().unwrap_or_default())
+    }
+
+    /// Returns the media playback of the source with the given name.
+    pub fn source_media(&self, name: &str) -> Option<FakeSourceMedia> {
+        let state = self.state();
+        let source = state.source_by_name(name)?;
+        Some(state.source_media.get(&source).cloned().unwrap_or_default())
+    }
+
+    /// Replaces the media playback of the source with the given name, e.g. to pretend the
+    /// source has opened a file. Returns `false` if there is no source with that name.
+    pub fn set_source_media(&self, name: &str, media: FakeSourceMedia) -> bool {
+        let mut state = self.state();
+        let Some(source) = state.source_by_name(name) else {
+            return false;
+        };
+
+        state.source_media.insert(source, media);
+        true
+    }
+
+    /// Plays the media of the source with the given name until its end and emits
+    /// `media_ended`, like libobs does once the media has ended.
+    /// Returns `false` if there is no source with that name.
+    pub fn end_media(&self, name: &str) -> bool {
+        let source = {
+            let mut state = self.state();
+            let Some(source) = state.source_by_name(name) else {
+                return false;
+            };
+
+            let media = state.source_media.entry(source).or_default();
+            media.state = ObsMediaState::Ended;
+            media.time = media.duration;
+            source
+        };
+
+        self.emit(source, "media_ended", FakeCalldata::new());
+        true
+    }
+
+    /// The number of raw audio callbacks that are currently registered.
+    pub fn raw_audio_callbacks(&self) -> usize {
+        self.state().raw_audio_connections.len()
+    }
+
+    /// Calls every raw audio callback of the given mix with audio made of the given planes,
+    /// the same way the audio thread of libobs would do it.
+    pub fn emit_audio(&self, mix_idx: usize, planes: &[&[u8]], frames: u32, timestamp: u64) {
+        let callbacks = self
+            .state()
+            .raw_audio_connections
+            .iter()
+            .filter(|c| c.mix_idx == mix_idx)
+            .map(|c| (c.callback, c.param))
+            .collect::<Vec<_>>();
+
+        let mut data = audio_data {
+            data: [ptr::null_mut(); libobs::MAX_AV_PLANES as usize],
+            frames,
+            timestamp,
+        };
+        for (i, plane) in planes
+            .iter()
+            .take(libobs::MAX_AV_PLANES as usize)
+            .enumerate()
+        {
+            data.data[i] = plane.as_ptr() as *mut u8;
+        }
+
+        for (callback, param) in callbacks {
+            if let Some(callback) = callback {
+                unsafe { callback(param as *mut c_void, mix_idx, &mut data) };
+            }
+        }
+    }
+
+    /// The number of audio capture callbacks that are registered on the source with the given name.
+    pub fn audio_capture_callbacks(&self, source_name: &str) -> usize {
+        let state = self.state();
+        state
+            .audio_capture_connections
+            .iter()
+            .filter(|c| state.source_name(c.source) == source_name)
+            .count()
+    }
+
+    /// Calls every audio capture callback of the source with the given name, the same way
+    /// libobs does it once the source has output audio.
+    /// Returns `false` if there is no source with that name.
+    pub fn emit_source_audio(
+        &self,
+        source_name: &str,
+        planes: &[&[u8]],
+        frames: u32,
+        timestamp: u64,
+        muted: bool,
+    ) -> bool {
+        let (source, callbacks) = {
+            let state = self.state();
+            let Some(source) = state
+                .sources
+                .iter()
+                .find(|(_, s)| s.name == source_name)
+                .map(|(h, _)| *h)
+            else {
+                return false;
+            };
+
+            let callbacks = state
+                .audio_capture_connections
+                .iter()
+                .filter(|c| c.source == source)
+                .map(|c| (c.callback, c.param))
+                .collect::<Vec<_>>();
+            (source, callbacks)
+        };
+
+        let mut data = audio_data {
+            data: [ptr::null_mut(); libobs::MAX_AV_PLANES as usize],
+            frames,
+            timestamp,
+        };
+        for (i, plane) in planes
+            .iter()
+            .take(libobs::MAX_AV_PLANES as usize)
+            .enumerate()
+        {
+            data.data[i] = plane.as_ptr() as *mut u8;
+        }
+
+        for (callback, param) in callbacks {
+            if let Some(callback) = callback {
+                unsafe { callback(param as *mut c_void, ptr_of(source), &data, muted) };
+            }
+        }
+
+        true
+    }
+
+    /// The number of volume meters that are attached to the source with the given name.
+    pub fn volume_meters(&self, source_name: &str) -> usize {
+        let state = self.state();
+        state
+            .volmeters
+            .values()
+            .filter(|v| {
+                v.source
+                    .is_some_and(|s| state.source_name(s) == source_name)
+            })
+            .count()
+    }
+
+    /// The peak meter types of the volume meters that are attached to the source with the
+    /// given name.
+    pub fn volume_meter_peak_types(&self, source_name: &str) -> Vec<ObsPeakMeterType> {
+        let state = self.state();
+        state
+            .volmeters
+            .values()
+            .filter(|v| {
+                v.source
+                    .is_some_and(|s| state.source_name(s) == source_name)
+            })
+            .map(|v| v.peak_meter_type)
+            .collect()
+    }
+
+    /// The number of faders that are attached to the source with the given name.
+    pub fn faders(&self, source_name: &str) -> usize {
+        let state = self.state();
+        state
+            .faders
+            .values()
+            .filter(|f| {
+                f.source
+                    .is_some_and(|s| state.source_name(s) == source_name)
+            })
+            .count()
+    }
+
+    /// Calls the callbacks of every volume meter attached to the source with the given name,
+    /// the same way libobs does it once it has measured the levels of the source. Levels are
+    /// in dB with one value per channel, missing channels are silent.
+    /// Returns `false` if there is no source with that name.
+    pub fn emit_volume_levels(
+        &self,
+        source_name: &str,
+        magnitude: &[f32],
+        peak: &[f32],
+        input_peak: &[f32],
+    ) -> bool {
+        let callbacks = {
+            let state = self.state();
+            let Some(source) = state.source_by_name(source_name) else {
+                return false;
+            };
+
+            state
+                .volmeters
+                .values()
+                .filter(|v| v.source == Some(source))
+                .flat_map(|v| v.callbacks.iter().copied())
+                .collect::<Vec<_>>()
+        };
+
+        let channels = |levels: &[f32]| {
+            let mut channels = [f32::NEG_INFINITY; libobs::MAX_AUDIO_CHANNELS as usize];
+            for (channel, level) in channels.iter_mut().zip(levels) {
+                *channel = *level;
+            }
+            channels
+        };
+        let magnitude = channels(magnitude);
+        let peak = channels(peak);
+        let input_peak = channels(input_peak);
+
+        for (callback, param) in callbacks {
+            if let Some(callback) = callback {
+                unsafe {
+                    callback(
+                        param as *mut c_void,
+                        magnitude.as_ptr(),
+                        peak.as_ptr(),
+                        input_peak.as_ptr(),
+                    )
+                };
+            }
+        }
+
+        true
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_add_raw_audio_callback(
+        &self,
+        mix_idx: usize,
+        _conversion: *const audio_convert_info,
+        callback: audio_output_callback_t,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .raw_audio_connections
+            .push(FakeRawAudioConnection {
+                mix_idx,
+                callback,
+                param: param as usize,
+            });
+    }
+
+    pub(crate) unsafe fn obs_remove_raw_audio_callback(
+        &self,
+        mix_idx: usize,
+        _callback: audio_output_callback_t,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .raw_audio_connections
+            .retain(|c| !(c.mix_idx == mix_idx && c.param == param as usize));
+    }
+
+    pub(crate) unsafe fn obs_source_set_volume(&self, source: *mut obs_source_t, volume: f32) {
+        let calldata = FakeCalldata::new().set_float("volume", volume as f64);
+        self.set_audio_value(source, "volume", calldata, |a| a.volume = volume);
+    }
+
+    pub(crate) unsafe fn obs_source_get_volume(&self, source: *mut obs_source_t) -> f32 {
+        self.audio_value(source, |a| a.volume)
+    }
+
+    pub(crate) unsafe fn obs_source_set_muted(&self, source: *mut obs_source_t, muted: bool) {
+        let calldata = FakeCalldata::new().set_bool("muted", muted);
+        self.set_audio_value(source, "mute", calldata, |a| a.muted = muted);
+    }
+
+    pub(crate) unsafe fn obs_source_muted(&self, source: *mut obs_source_t) -> bool {
+        self.audio_value(source, |a| a.muted)
+    }
+
+    pub(crate) unsafe fn obs_source_set_balance_value(
+        &self,
+        source: *mut obs_source_t,
+        balance: f32,
+    ) {
+        let calldata = FakeCalldata::new().set_float("balance", balance as f64);
+        self.set_audio_value(source, "audio_balance", calldata, |a| a.balance = balance);
+    }
+
+    pub(crate) unsafe fn obs_source_get_balance_value(&self, source: *mut obs_source_t) -> f32 {
+        self.audio_value(source, |a| a.balance)
+    }
+
+    pub(crate) unsafe fn obs_source_set_sync_offset(&self, source: *mut obs_source_t, offset: i64) {
+        let calldata = FakeCalldata::new().set_int("offset", offset);
+        self.set_audio_value(source, "audio_sync", calldata, |a| a.sync_offset = offset);
+    }
+
+    pub(crate) unsafe fn obs_source_get_sync_offset(&self, source: *mut obs_source_t) -> i64 {
+        self.audio_value(source, |a| a.sync_offset)
+    }
+
+    pub(crate) unsafe fn obs_source_set_audio_mixers(
+        &self,
+        source: *mut obs_source_t,
+        mixers: u32,
+    ) {
+        let calldata = FakeCalldata::new().set_int("mixers", mixers as i64);
+        self.set_audio_value(source, "audio_mixers", calldata, |a| a.mixers = mixers);
+    }
+
+    pub(crate) unsafe fn obs_source_get_audio_mixers(&self, source: *mut obs_source_t) -> u32 {
+        self.audio_value(source, |a| a.mixers)
+    }
+
+    pub(crate) unsafe fn obs_source_enable_push_to_talk(
+        &self,
+        source: *mut obs_source_t,
+        enabled: bool,
+    ) {
+        let calldata = FakeCalldata::new().set_bool("enabled", enabled);
+        self.set_audio_value(source, "push_to_talk_changed", calldata, |a| {
+            a.push_to_talk = enabled
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_push_to_talk_enabled(&self, source: *mut obs_source_t) -> bool {
+        self.audio_value(source, |a| a.push_to_talk)
+    }
+
+    pub(crate) unsafe fn obs_source_set_push_to_talk_delay(
+        &self,
+        source: *mut obs_source_t,
+        delay: u64,
+    ) {
+        let calldata = FakeCalldata::new().set_int("delay", delay as i64);
+        self.set_audio_value(source, "push_to_talk_delay", calldata, |a| {
+            a.push_to_talk_delay = delay
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_get_push_to_talk_delay(
+        &self,
+        source: *mut obs_source_t,
+    ) -> u64 {
+        self.audio_value(source, |a| a.push_to_talk_delay)
+    }
+
+    pub(crate) unsafe fn obs_source_enable_push_to_mute(
+        &self,
+        source: *mut obs_source_t,
+        enabled: bool,
+    ) {
+        let calldata = FakeCalldata::new().set_bool("enabled", enabled);
+        self.set_audio_value(source, "push_to_mute_changed", calldata, |a| {
+            a.push_to_mute = enabled
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_push_to_mute_enabled(&self, source: *mut obs_source_t) -> bool {
+        self.audio_value(source, |a| a.push_to_mute)
+    }
+
+    pub(crate) unsafe fn obs_source_set_push_to_mute_delay(
+        &self,
+        source: *mut obs_source_t,
+        delay: u64,
+    ) {
+        let calldata = FakeCalldata::new().set_int("delay", delay as i64);
+        self.set_audio_value(source, "push_to_mute_delay", calldata, |a| {
+            a.push_to_mute_delay = delay
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_get_push_to_mute_delay(
+        &self,
+        source: *mut obs_source_t,
+    ) -> u64 {
+        self.audio_value(source, |a| a.push_to_mute_delay)
+    }
+
+    pub(crate) unsafe fn obs_source_set_monitoring_type(
+        &self,
+        source: *mut obs_source_t,
+        type_: obs_monitoring_type,
+    ) {
+        let monitoring_type =
+            ObsMonitoringType::from_i64(type_ as i64).unwrap_or(ObsMonitoringType::None);
+        let calldata = FakeCalldata::new().set_int("type", type_ as i64);
+        self.set_audio_value(source, "audio_monitoring", calldata, |a| {
+            a.monitoring_type = monitoring_type
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_get_monitoring_type(
+        &self,
+        source: *mut obs_source_t,
+    ) -> obs_monitoring_type {
+        self.audio_value(source, |a| a.monitoring_type as obs_monitoring_type)
+    }
+
+    pub(crate) unsafe fn obs_source_add_audio_capture_callback(
+        &self,
+        source: *mut obs_source_t,
+        callback: obs_source_audio_capture_t,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .audio_capture_connections
+            .push(FakeAudioCaptureConnection {
+                source: handle(source),
+                callback,
+                param: param as usize,
+            });
+    }
+
+    pub(crate) unsafe fn obs_source_remove_audio_capture_callback(
+        &self,
+        source: *mut obs_source_t,
+        _callback: obs_source_audio_capture_t,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .audio_capture_connections
+            .retain(|c| !(c.source == handle(source) && c.param == param as usize));
+    }
+
+    pub(crate) unsafe fn obs_source_media_play_pause(
+        &self,
+        source: *mut obs_source_t,
+        pause: bool,
+    ) {
+        let (state, signal) = if pause {
+            (ObsMediaState::Paused, "media_pause")
+        } else {
+            (ObsMediaState::Playing, "media_play")
+        };
+        self.set_media_value(source, signal, |m| m.state = state);
+    }
+
+    pub(crate) unsafe fn obs_source_media_restart(&self, source: *mut obs_source_t) {
+        self.set_media_value(source, "media_restart", |m| {
+            m.state = ObsMediaState::Playing;
+            m.time = 0;
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_media_stop(&self, source: *mut obs_source_t) {
+        self.set_media_value(source, "media_stopped", |m| {
+            m.state = ObsMediaState::Stopped;
+            m.time = 0;
+        });
+    }
+
+    pub(crate) unsafe fn obs_source_media_next(&self, source: *mut obs_source_t) {
+        self.set_media_value(source, "media_next", |m| m.time = 0);
+    }
+
+    pub(crate) unsafe fn obs_source_media_previous(&self, source: *mut obs_source_t) {
+        self.set_media_value(source, "media_previous", |m| m.time = 0);
+    }
+
+    pub(crate) unsafe fn obs_source_media_get_duration(&self, source: *mut obs_source_t) -> i64 {
+        self.media_value(source, |m| m.duration)
+    }
+
+    pub(crate) unsafe fn obs_source_media_get_time(&self, source: *mut obs_source_t) -> i64 {
+        self.media_value(source, |m| m.time)
+    }
+
+    pub(crate) unsafe fn obs_source_media_set_time(&self, source: *mut obs_source_t, ms: i64) {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return;
+        }
+
+        let media = state.source_media.entry(handle(source)).or_default();
+        media.time = ms.clamp(0, media.duration.max(0));
+    }
+
+    pub(crate) unsafe fn obs_source_media_get_state(
+        &self,
+        source: *mut obs_source_t,
+    ) -> obs_media_state {
+        self.media_value(source, |m| m.state as obs_media_state)
+    }
+
+    // The fader type only changes how libobs scales the levels for drawing, so it's not kept
+    pub(crate) unsafe fn obs_volmeter_create(&self, _type_: obs_fader_type) -> *mut obs_volmeter_t {
+        let mut state = self.state();
+        let volmeter = state.next_handle();
+        state.volmeters.insert(
+            volmeter,
+            FakeVolmeter {
+                source: None,
+                peak_meter_type: ObsPeakMeterType::SamplePeak,
+                callbacks: Vec::new(),
+            },
+        );
+        ptr_of(volmeter)
+    }
+
+    pub(crate) unsafe fn obs_volmeter_destroy(&self, volmeter: *mut obs_volmeter_t) {
+        self.state().volmeters.remove(&handle(volmeter));
+    }
+
+    pub(crate) unsafe fn obs_volmeter_attach_source(
+        &self,
+        volmeter: *mut obs_volmeter_t,
+        source: *mut obs_source_t,
+    ) -> bool {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return false;
+        }
+
+        match state.volmeters.get_mut(&handle(volmeter)) {
+            Some(volmeter) => {
+                volmeter.source = Some(handle(source));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) unsafe fn obs_volmeter_detach_source(&self, volmeter: *mut obs_volmeter_t) {
+        if let Some(volmeter) = self.state().volmeters.get_mut(&handle(volmeter)) {
+            volmeter.source = None;
+        }
+    }
+
+    pub(crate) unsafe fn obs_volmeter_set_peak_meter_type(
+        &self,
+        volmeter: *mut obs_volmeter_t,
+        peak_meter_type: obs_peak_meter_type,
+    ) {
+        if let Some(volmeter) = self.state().volmeters.get_mut(&handle(volmeter)) {
+            volmeter.peak_meter_type = ObsPeakMeterType::from_i64(peak_meter_type as i64)
+                .unwrap_or(ObsPeakMeterType::SamplePeak);
+        }
+    }
+
+    pub(crate) unsafe fn obs_volmeter_get_nr_channels(
+        &self,
+        volmeter: *mut obs_volmeter_t,
+    ) -> c_int {
+        let state = self.state();
+        let obs_channels = state
+            .audio
+            .and_then(|a| ObsSpeakerLayout::from_i64(a.speakers as i64))
+            .map(|s| s.channels())
+            .unwrap_or(2);
+
+        // Fake sources output audio with the speaker layout of the audio context
+        match state.volmeters.get(&handle(volmeter)) {
+            Some(FakeVolmeter {
+                source: Some(_), ..
+            }) => obs_channels.max(1) as c_int,
+            _ => 1,
+        }
+    }
+
+    pub(crate) unsafe fn obs_volmeter_add_callback(
+        &self,
+        volmeter: *mut obs_volmeter_t,
+        callback: obs_volmeter_updated_t,
+        param: *mut c_void,
+    ) {
+        if let Some(volmeter) = self.state().volmeters.get_mut(&handle(volmeter)) {
+            volmeter.callbacks.push((callback, param as usize));
+        }
+    }
+
+    pub(crate) unsafe fn obs_volmeter_remove_callback(
+        &self,
+        volmeter: *mut obs_volmeter_t,
+        _callback: obs_volmeter_updated_t,
+        param: *mut c_void,
+    ) {
+        if let Some(volmeter) = self.state().volmeters.get_mut(&handle(volmeter)) {
+            volmeter.callbacks.retain(|(_, p)| *p != param as usize);
+        }
+    }
+
+    pub(crate) unsafe fn obs_fader_create(&self, type_: obs_fader_type) -> *mut obs_fader_t {
+        let Some(fader_type) = ObsFaderType::from_i64(type_ as i64) else {
+            return ptr::null_mut();
+        };
+
+        let mut state = self.state();
+        let fader = state.next_handle();
+        state.faders.insert(
+            fader,
+            FakeFader {
+                fader_type,
+                source: None,
+                db: 0.0,
+            },
+        );
+        ptr_of(fader)
+    }
+
+    pub(crate) unsafe fn obs_fader_destroy(&self, fader: *mut obs_fader_t) {
+        self.state().faders.remove(&handle(fader));
+    }
+
+    pub(crate) unsafe fn obs_fader_attach_source(
+        &self,
+        fader: *mut obs_fader_t,
+        source: *mut obs_source_t,
+    ) -> bool {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return false;
+        }
+
+        match state.faders.get_mut(&handle(fader)) {
+            Some(fader) => {
+                fader.source = Some(handle(source));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) unsafe fn obs_fader_detach_source(&self, fader: *mut obs_fader_t) {
+        let mut state = self.state();
+        let Some(source) = state.faders.get(&handle(fader)).and_then(|f| f.source) else {
+            return;
+        };
+
+        // Keeps the volume the fader had while it was attached
+        let volume = state
+            .source_audio
+            .get(&source)
+            .map(|a| a.volume)
+            .unwrap_or(1.0);
+        if let Some(fader) = state.faders.get_mut(&handle(fader)) {
+            fader.source = None;
+            fader.db = mul_to_db(volume);
+        }
+    }
+
+    pub(crate) unsafe fn obs_fader_set_db(&self, fader: *mut obs_fader_t, db: f32) -> bool {
+        self.set_fader_db(fader, db)
+    }
+
+    pub(crate) unsafe fn obs_fader_get_db(&self, fader: *mut obs_fader_t) -> f32 {
+        self.fader_db(fader).map(|(_, db)| db).unwrap_or(0.0)
+    }
+
+    pub(crate) unsafe fn obs_fader_set_deflection(
+        &self,
+        fader: *mut obs_fader_t,
+        def: f32,
+    ) -> bool {
+        let Some((fader_type, _)) = self.fader_db(fader) else {
+            return false;
+        };
+
+        self.set_fader_db(fader, fader_def_to_db(fader_type, def))
+    }
+
+    pub(crate) unsafe fn obs_fader_get_deflection(&self, fader: *mut obs_fader_t) -> f32 {
+        self.fader_db(fader)
+            .map(|(fader_type, db)| fader_db_to_def(fader_type, db))
+            .unwrap_or(0.0)
+    }
+
+    pub(crate) unsafe fn obs_fader_set_mul(&self, fader: *mut obs_fader_t, mul: f32) -> bool {
+        self.set_fader_db(fader, mul_to_db(mul))
+    }
+
+    pub(crate) unsafe fn obs_fader_get_mul(&self, fader: *mut obs_fader_t) -> f32 {
+        self.fader_db(fader)
+            .map(|(_, db)| db_to_mul(db))
+            .unwrap_or(0.0)
+    }
+
+    fn audio_value<T: Default>(
+        &self,
+        source: *mut obs_source_t,
+        f: impl FnOnce(&FakeSourceAudio) -> T,
+    ) -> T {
+        let state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return T::default();
+        }
+
+        match state.source_audio.get(&handle(source)) {
+            Some(audio) => f(audio),
+            None => f(&FakeSourceAudio::default()),
+        }
+    }
+
+    /// Changes the audio controls of the source and emits the signal libobs emits for it.
+    fn set_audio_value(
+        &self,
+        source: *mut obs_source_t,
+        signal: &str,
+        calldata: FakeCalldata,
+        f: impl FnOnce(&mut FakeSourceAudio),
+    ) {
+        {
+            let mut state = self.state();
+            if !state.sources.contains_key(&handle(source)) {
+                return;
+            }
+
+            f(state.source_audio.entry(handle(source)).or_default());
+        }
+
+        self.emit(handle(source), signal, calldata);
+    }
+
+    fn media_value<T: Default>(
+        &self,
+        source: *mut obs_source_t,
+        f: impl FnOnce(&FakeSourceMedia) -> T,
+    ) -> T {
+        let state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return T::default();
+        }
+
+        match state.source_media.get(&handle(source)) {
+            Some(media) => f(media),
+            None => f(&FakeSourceMedia::default()),
+        }
+    }
+
+    /// Changes the media playback of the source and emits the signal libobs emits for it.
+    fn set_media_value(
+        &self,
+        source: *mut obs_source_t,
+        signal: &str,
+        f: impl FnOnce(&mut FakeSourceMedia),
+    ) {
+        {
+            let mut state = self.state();
+            if !state.sources.contains_key(&handle(source)) {
+                return;
+            }
+
+            f(state.source_media.entry(handle(source)).or_default());
+        }
+
+        self.emit(handle(source), signal, FakeCalldata::new());
+    }
+
+    /// The type and current volume of the fader, attached faders follow their source.
+    fn fader_db(&self, fader: *mut obs_fader_t) -> Option<(ObsFaderType, f32)> {
+        let state = self.state();
+        let fader = state.faders.get(&handle(fader))?;
+        let db = match fader.source {
+            Some(source) => mul_to_db(
+                state
+                    .source_audio
+                    .get(&source)
+                    .map(|a| a.volume)
+                    .unwrap_or(1.0),
+            ),
+            None => fader.db,
+        };
+
+        Some((fader.fader_type, db))
+    }
+
+    /// Sets the volume of the fader like libobs, clamped to its range. The volume of an
+    /// attached source is changed as well. Returns `false` if the volume was clamped.
+    fn set_fader_db(&self, fader: *mut obs_fader_t, db: f32) -> bool {
+        let (source, db, clamped) = {
+            let mut state = self.state();
+            let Some(fader) = state.faders.get_mut(&handle(fader)) else {
+                return false;
+            };
+
+            let (db, clamped) = if db > 0.0 {
+                (0.0, true)
+            } else if db.is_nan() || db < fader_min_db(fader.fader_type) {
+                (f32::NEG_INFINITY, true)
+            } else {
+                (db, false)
+            };
+            fader.db = db;
+            (fader.source, db, clamped)
+        };
+
+        if let Some(source) = source {
+            unsafe { self.obs_source_set_volume(ptr_of(source), db_to_mul(db)) };
+        }
+
+        !clamped
+    }
+}
@@ -0,0 +1,147 @@
+//! Startup, the video and audio contexts and the output channels of the fake backend.
+
+use std::{
+    ffi::{c_char, c_int, c_long, c_void},
+    ptr,
+};
+
+use libobs::{
+    audio_output, log_handler_t, obs_audio_info, obs_audio_info2, obs_module_failure_info,
+    obs_source_t, obs_video_info, profiler_name_store_t, video_output,
+};
+
+use crate::utils::VaList;
+
+use super::{
+    handle, ptr_of, FakeAudio, FakeObsBackend, FakeVideo, FAKE_AUDIO_HANDLE, FAKE_VIDEO_HANDLE,
+};
+
+impl FakeObsBackend {
+    #[cfg(windows)]
+    pub(crate) unsafe fn obs_init_win32_crash_handler(&self) {}
+
+    pub(crate) unsafe fn base_set_crash_handler(
+        &self,
+        _handler: Option<unsafe extern "C" fn(*const c_char, VaList, *mut c_void)>,
+        _param: *mut c_void,
+    ) {
+    }
+
+    pub(crate) unsafe fn base_set_log_handler(&self, _handler: log_handler_t, _param: *mut c_void) {
+    }
+
+    pub(crate) unsafe fn bnum_allocs(&self) -> c_long {
+        0
+    }
+
+    pub(crate) unsafe fn obs_startup(
+        &self,
+        _locale: *const c_char,
+        _module_config_path: *const c_char,
+        _store: *mut profiler_name_store_t,
+    ) -> bool {
+        self.state().started = true;
+        true
+    }
+
+    pub(crate) unsafe fn obs_shutdown(&self) {
+        let mut state = self.state();
+        state.started = false;
+        state.video = None;
+        state.raw_video_connections.clear();
+        state.audio = None;
+        state.raw_audio_connections.clear();
+    }
+
+    pub(crate) unsafe fn obs_get_version_string(&self) -> *const c_char {
+        c"0.0.0-fake".as_ptr()
+    }
+
+    pub(crate) unsafe fn obs_add_data_path(&self, _path: *const c_char) {}
+
+    pub(crate) unsafe fn obs_remove_data_path(&self, _path: *const c_char) -> bool {
+        true
+    }
+
+    pub(crate) unsafe fn obs_add_module_path(&self, _bin: *const c_char, _data: *const c_char) {}
+
+    pub(crate) unsafe fn obs_load_all_modules2(&self, _mfi: *mut obs_module_failure_info) {}
+
+    pub(crate) unsafe fn obs_log_loaded_modules(&self) {}
+
+    pub(crate) unsafe fn obs_post_load_modules(&self) {}
+
+    pub(crate) unsafe fn obs_reset_audio2(&self, oai: *const obs_audio_info2) -> bool {
+        let oai = &*oai;
+        self.state().audio = Some(FakeAudio {
+            samples_per_sec: oai.samples_per_sec,
+            speakers: oai.speakers,
+        });
+
+        true
+    }
+
+    pub(crate) unsafe fn obs_reset_video(&self, ovi: *mut obs_video_info) -> c_int {
+        let ovi = &*ovi;
+        self.state().video = Some(FakeVideo {
+            format: ovi.output_format,
+            width: ovi.output_width,
+            height: ovi.output_height,
+            fps_num: ovi.fps_num,
+            fps_den: ovi.fps_den,
+        });
+
+        libobs::OBS_VIDEO_SUCCESS as c_int
+    }
+
+    pub(crate) unsafe fn obs_get_video(&self) -> *mut video_output {
+        ptr_of(FAKE_VIDEO_HANDLE)
+    }
+
+    pub(crate) unsafe fn obs_get_video_info(&self, ovi: *mut obs_video_info) -> bool {
+        let Some(video) = self.state().video else {
+            return false;
+        };
+
+        let ovi = &mut *ovi;
+        ovi.fps_num = video.fps_num;
+        ovi.fps_den = video.fps_den;
+        ovi.base_width = video.width;
+        ovi.base_height = video.height;
+        ovi.output_width = video.width;
+        ovi.output_height = video.height;
+        ovi.output_format = video.format;
+        true
+    }
+
+    pub(crate) unsafe fn obs_get_audio(&self) -> *mut audio_output {
+        ptr_of(FAKE_AUDIO_HANDLE)
+    }
+
+    pub(crate) unsafe fn obs_get_audio_info(&self, oai: *mut obs_audio_info) -> bool {
+        let Some(audio) = self.state().audio else {
+            return false;
+        };
+
+        (*oai).samples_per_sec = audio.samples_per_sec;
+        (*oai).speakers = audio.speakers;
+        true
+    }
+
+    pub(crate) unsafe fn obs_set_output_source(&self, channel: u32, source: *mut obs_source_t) {
+        let mut state = self.state();
+        if source.is_null() {
+            state.channels.remove(&channel);
+        } else {
+            state.channels.insert(channel, handle(source));
+        }
+    }
+
+    pub(crate) unsafe fn obs_get_output_source(&self, channel: u32) -> *mut obs_source_t {
+        let source = self.state().channels.get(&channel).copied();
+        match source {
+            Some(source) => self.obs_source_get_ref(ptr_of(source)),
+            None => ptr::null_mut(),
+        }
+    }
+}
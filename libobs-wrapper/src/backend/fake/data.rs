@@ -0,0 +1,227 @@
+//! `obs_data` objects and arrays of the fake backend.
+
+use std::{
+    ffi::{c_char, CString},
+    ptr,
+};
+
+use libobs::{obs_data_array_t, obs_data_t};
+use serde_json::Value;
+
+use super::{handle, ptr_of, to_string, FakeObsBackend, FakeSettings};
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_data_create(&self) -> *mut obs_data_t {
+        ptr_of(self.state().create_data(FakeSettings::new()))
+    }
+
+    pub(crate) unsafe fn obs_data_create_from_json(
+        &self,
+        json_string: *const c_char,
+    ) -> *mut obs_data_t {
+        let json = to_string(json_string);
+        match serde_json::from_str::<Value>(&json) {
+            Ok(Value::Object(values)) => ptr_of(self.state().create_data(values)),
+            _ => ptr::null_mut(),
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_get_json(&self, data: *mut obs_data_t) -> *const c_char {
+        let mut state = self.state();
+        let Some(data) = state.data.get_mut(&handle(data)) else {
+            return ptr::null();
+        };
+
+        let json = Value::Object(data.values.clone()).to_string();
+        data.json = CString::new(json).unwrap_or_default();
+        data.json.as_ptr()
+    }
+
+    pub(crate) unsafe fn obs_data_release(&self, data: *mut obs_data_t) {
+        let mut state = self.state();
+        let data_handle = handle(data);
+        if let Some(entry) = state.data.get_mut(&data_handle) {
+            entry.refs = entry.refs.saturating_sub(1);
+            if entry.refs == 0 {
+                state.data.remove(&data_handle);
+            }
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_string(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        val: *const c_char,
+    ) {
+        if let Some(data) = self.state().data.get_mut(&handle(data)) {
+            data.values
+                .insert(to_string(name), Value::from(to_string(val)));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_int(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        val: i64,
+    ) {
+        if let Some(data) = self.state().data.get_mut(&handle(data)) {
+            data.values.insert(to_string(name), Value::from(val));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_bool(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        val: bool,
+    ) {
+        if let Some(data) = self.state().data.get_mut(&handle(data)) {
+            data.values.insert(to_string(name), Value::from(val));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_double(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        val: f64,
+    ) {
+        if let Some(data) = self.state().data.get_mut(&handle(data)) {
+            data.values.insert(to_string(name), Value::from(val));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_obj(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        obj: *mut obs_data_t,
+    ) {
+        let mut state = self.state();
+        let values = state.data_values(obj);
+        if let Some(data) = state.data.get_mut(&handle(data)) {
+            data.values.insert(to_string(name), Value::Object(values));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_set_array(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+        array: *mut obs_data_array_t,
+    ) {
+        let mut state = self.state();
+        let Some(items) = state
+            .data_arrays
+            .get(&handle(array))
+            .map(|a| a.items.clone())
+        else {
+            return;
+        };
+
+        if let Some(data) = state.data.get_mut(&handle(data)) {
+            let items = items.into_iter().map(Value::Object).collect();
+            data.values.insert(to_string(name), Value::Array(items));
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_get_string(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+    ) -> *const c_char {
+        let mut state = self.state();
+        let value = state
+            .data
+            .get(&handle(data))
+            .and_then(|d| d.values.get(&to_string(name)))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        state.store_string(handle(data), "string", &value)
+    }
+
+    pub(crate) unsafe fn obs_data_get_array(
+        &self,
+        data: *mut obs_data_t,
+        name: *const c_char,
+    ) -> *mut obs_data_array_t {
+        let mut state = self.state();
+        let Some(items) = state
+            .data
+            .get(&handle(data))
+            .and_then(|d| d.values.get(&to_string(name)))
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_object)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+        else {
+            return ptr::null_mut();
+        };
+
+        ptr_of(state.create_data_array(items))
+    }
+
+    pub(crate) unsafe fn obs_data_array_create(&self) -> *mut obs_data_array_t {
+        ptr_of(self.state().create_data_array(Vec::new()))
+    }
+
+    pub(crate) unsafe fn obs_data_array_release(&self, array: *mut obs_data_array_t) {
+        let mut state = self.state();
+        let array_handle = handle(array);
+        if let Some(entry) = state.data_arrays.get_mut(&array_handle) {
+            entry.refs = entry.refs.saturating_sub(1);
+            if entry.refs == 0 {
+                state.data_arrays.remove(&array_handle);
+            }
+        }
+    }
+
+    pub(crate) unsafe fn obs_data_array_count(&self, array: *mut obs_data_array_t) -> usize {
+        self.state()
+            .data_arrays
+            .get(&handle(array))
+            .map(|a| a.items.len())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_data_array_item(
+        &self,
+        array: *mut obs_data_array_t,
+        idx: usize,
+    ) -> *mut obs_data_t {
+        let mut state = self.state();
+        let Some(values) = state
+            .data_arrays
+            .get(&handle(array))
+            .and_then(|a| a.items.get(idx))
+            .cloned()
+        else {
+            return ptr::null_mut();
+        };
+
+        ptr_of(state.create_data(values))
+    }
+
+    pub(crate) unsafe fn obs_data_array_push_back(
+        &self,
+        array: *mut obs_data_array_t,
+        obj: *mut obs_data_t,
+    ) -> usize {
+        let mut state = self.state();
+        let values = state.data_values(obj);
+        let Some(array) = state.data_arrays.get_mut(&handle(array)) else {
+            return 0;
+        };
+
+        array.items.push(values);
+        array.items.len() - 1
+    }
+}
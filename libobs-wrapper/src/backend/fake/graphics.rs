@@ -0,0 +1,327 @@
+//! Rendering, textures, stage surfaces and raw video of the fake backend.
+
+use std::{ffi::c_void, ptr};
+
+use libobs::{
+    gs_blend_type, gs_color_format, gs_stagesurf_t, gs_texrender_t, gs_texture_t,
+    gs_zstencil_format, obs_source_t, vec4, video_data, video_scale_info,
+};
+
+use super::{
+    handle, ptr_of, FakeObsBackend, FakeRawVideoConnection, FakeSourceVideo, FakeState,
+    FakeTexture, RawVideoCallback,
+};
+
+impl FakeState {
+    /// The color a source renders. A scene renders the color of its topmost visible item
+    /// that has video.
+    fn render_color(&self, source: usize) -> Option<[u8; 4]> {
+        if let Some(video) = self.source_videos.get(&source) {
+            return Some(video.color);
+        }
+
+        if let Some(transition) = self.transitions.get(&source) {
+            return self.render_color(transition.active?);
+        }
+
+        let scene = self.scenes.values().find(|s| s.source == source)?;
+        scene
+            .items
+            .iter()
+            .rev()
+            .filter_map(|item| self.scene_items.get(item))
+            .filter(|item| item.visible)
+            .find_map(|item| self.render_color(item.source))
+    }
+}
+
+impl FakeObsBackend {
+    /// Makes the source with the given name render a single color at the given size,
+    /// which is what screenshots of it will show.
+    /// Returns `false` if there is no source with that name.
+    pub fn set_source_video(
+        &self,
+        source_name: &str,
+        width: u32,
+        height: u32,
+        color: [u8; 4],
+    ) -> bool {
+        let mut state = self.state();
+        let Some(source) = state
+            .sources
+            .iter()
+            .find(|(_, s)| s.name == source_name)
+            .map(|(h, _)| *h)
+        else {
+            return false;
+        };
+
+        state.source_videos.insert(
+            source,
+            FakeSourceVideo {
+                width,
+                height,
+                color,
+            },
+        );
+        true
+    }
+
+    /// The number of texrenders and stage surfaces that are currently alive.
+    pub fn graphics_objects(&self) -> usize {
+        let state = self.state();
+        state.texrenders.len() + state.stagesurfaces.len()
+    }
+
+    /// Fills the current texrender with the color the given source renders.
+    fn render_source(&self, source: usize) {
+        let mut state = self.state();
+        let Some(color) = state.render_color(source) else {
+            return;
+        };
+
+        let Some(target) = state.render_target else {
+            return;
+        };
+        if let Some(texture) = state.texrenders.get_mut(&target) {
+            for pixel in texture.pixels.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// The number of raw video callbacks that are currently registered.
+    pub fn raw_video_callbacks(&self) -> usize {
+        self.state().raw_video_connections.len()
+    }
+
+    /// Calls every registered raw video callback with a frame made of the given planes,
+    /// the same way the video thread of libobs would do it.
+    pub fn emit_video_frame(&self, planes: &[&[u8]], linesize: &[u32], timestamp: u64) {
+        let callbacks = self
+            .state()
+            .raw_video_connections
+            .iter()
+            .map(|c| (c.callback, c.param))
+            .collect::<Vec<_>>();
+
+        let mut frame = video_data {
+            data: [ptr::null_mut(); libobs::MAX_AV_PLANES as usize],
+            linesize: [0; libobs::MAX_AV_PLANES as usize],
+            timestamp,
+        };
+        let planes = planes
+            .iter()
+            .zip(linesize)
+            .take(libobs::MAX_AV_PLANES as usize);
+        for (i, (plane, size)) in planes.enumerate() {
+            frame.data[i] = plane.as_ptr() as *mut u8;
+            frame.linesize[i] = *size;
+        }
+
+        for (callback, param) in callbacks {
+            if let Some(callback) = callback {
+                unsafe { callback(param as *mut c_void, &mut frame) };
+            }
+        }
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_enter_graphics(&self) {}
+
+    pub(crate) unsafe fn obs_leave_graphics(&self) {}
+
+    pub(crate) unsafe fn obs_render_main_texture(&self) {
+        let source = self.state().channels.get(&0).copied();
+        if let Some(source) = source {
+            self.render_source(source);
+        }
+    }
+
+    pub(crate) unsafe fn gs_texrender_create(
+        &self,
+        _format: gs_color_format,
+        _zsformat: gs_zstencil_format,
+    ) -> *mut gs_texrender_t {
+        let mut state = self.state();
+        let texrender = state.next_handle();
+        state.texrenders.insert(texrender, FakeTexture::default());
+        ptr_of(texrender)
+    }
+
+    pub(crate) unsafe fn gs_texrender_destroy(&self, texrender: *mut gs_texrender_t) {
+        self.state().texrenders.remove(&handle(texrender));
+    }
+
+    pub(crate) unsafe fn gs_texrender_begin(
+        &self,
+        texrender: *mut gs_texrender_t,
+        cx: u32,
+        cy: u32,
+    ) -> bool {
+        let mut state = self.state();
+        if cx == 0 || cy == 0 || state.render_target.is_some() {
+            return false;
+        }
+
+        let Some(texture) = state.texrenders.get_mut(&handle(texrender)) else {
+            return false;
+        };
+
+        *texture = FakeTexture::new(cx, cy);
+        state.render_target = Some(handle(texrender));
+        true
+    }
+
+    pub(crate) unsafe fn gs_texrender_end(&self, texrender: *mut gs_texrender_t) {
+        let mut state = self.state();
+        if state.render_target == Some(handle(texrender)) {
+            state.render_target = None;
+        }
+    }
+
+    pub(crate) unsafe fn gs_texrender_get_texture(
+        &self,
+        texrender: *const gs_texrender_t,
+    ) -> *mut gs_texture_t {
+        ptr_of(handle(texrender as *mut gs_texrender_t))
+    }
+
+    pub(crate) unsafe fn gs_clear(
+        &self,
+        _clear_flags: u32,
+        _color: *const vec4,
+        _depth: f32,
+        _stencil: u8,
+    ) {
+        let mut state = self.state();
+        let Some(target) = state.render_target else {
+            return;
+        };
+
+        if let Some(texture) = state.texrenders.get_mut(&target) {
+            texture.pixels.fill(0);
+        }
+    }
+
+    pub(crate) unsafe fn gs_ortho(
+        &self,
+        _left: f32,
+        _right: f32,
+        _top: f32,
+        _bottom: f32,
+        _znear: f32,
+        _zfar: f32,
+    ) {
+    }
+
+    pub(crate) unsafe fn gs_blend_state_push(&self) {}
+
+    pub(crate) unsafe fn gs_blend_state_pop(&self) {}
+
+    pub(crate) unsafe fn gs_blend_function(&self, _src: gs_blend_type, _dest: gs_blend_type) {}
+
+    pub(crate) unsafe fn gs_stagesurface_create(
+        &self,
+        width: u32,
+        height: u32,
+        _color_format: gs_color_format,
+    ) -> *mut gs_stagesurf_t {
+        let mut state = self.state();
+        let stagesurf = state.next_handle();
+        state
+            .stagesurfaces
+            .insert(stagesurf, FakeTexture::new(width, height));
+        ptr_of(stagesurf)
+    }
+
+    pub(crate) unsafe fn gs_stagesurface_destroy(&self, stagesurf: *mut gs_stagesurf_t) {
+        self.state().stagesurfaces.remove(&handle(stagesurf));
+    }
+
+    pub(crate) unsafe fn gs_stage_texture(&self, dst: *mut gs_stagesurf_t, src: *mut gs_texture_t) {
+        let mut state = self.state();
+        let Some(pixels) = state.texrenders.get(&handle(src)).map(|t| t.pixels.clone()) else {
+            return;
+        };
+
+        if let Some(surface) = state.stagesurfaces.get_mut(&handle(dst)) {
+            let len = surface.pixels.len().min(pixels.len());
+            surface.pixels[..len].copy_from_slice(&pixels[..len]);
+        }
+    }
+
+    pub(crate) unsafe fn gs_stagesurface_map(
+        &self,
+        stagesurf: *mut gs_stagesurf_t,
+        data: *mut *mut u8,
+        linesize: *mut u32,
+    ) -> bool {
+        let mut state = self.state();
+        let Some(surface) = state.stagesurfaces.get_mut(&handle(stagesurf)) else {
+            return false;
+        };
+
+        // The pixels are not touched until the surface is unmapped, so the pointer stays valid
+        *data = surface.pixels.as_mut_ptr();
+        *linesize = surface.width * 4;
+        true
+    }
+
+    pub(crate) unsafe fn gs_stagesurface_unmap(&self, _stagesurf: *mut gs_stagesurf_t) {}
+
+    pub(crate) unsafe fn obs_add_raw_video_callback(
+        &self,
+        _conversion: *const video_scale_info,
+        callback: RawVideoCallback,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .raw_video_connections
+            .push(FakeRawVideoConnection {
+                callback,
+                param: param as usize,
+            });
+    }
+
+    pub(crate) unsafe fn obs_remove_raw_video_callback(
+        &self,
+        _callback: RawVideoCallback,
+        param: *mut c_void,
+    ) {
+        self.state()
+            .raw_video_connections
+            .retain(|c| c.param != param as usize);
+    }
+
+    pub(crate) unsafe fn obs_source_get_width(&self, source: *mut obs_source_t) -> u32 {
+        let state = self.state();
+        if state.scenes.values().any(|s| s.source == handle(source)) {
+            return state.video.map(|v| v.width).unwrap_or_default();
+        }
+
+        state
+            .source_videos
+            .get(&handle(source))
+            .map(|v| v.width)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_source_get_height(&self, source: *mut obs_source_t) -> u32 {
+        let state = self.state();
+        if state.scenes.values().any(|s| s.source == handle(source)) {
+            return state.video.map(|v| v.height).unwrap_or_default();
+        }
+
+        state
+            .source_videos
+            .get(&handle(source))
+            .map(|v| v.height)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_source_video_render(&self, source: *mut obs_source_t) {
+        self.render_source(handle(source));
+    }
+}
@@ -0,0 +1,498 @@
+//! An in-memory backend which records every call instead of calling into libobs.
+//!
+//! ```no_run
+//! use libobs_wrapper::{backend::fake::FakeObsBackend, context::ObsContext, utils::StartupInfo};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let backend = FakeObsBackend::new();
+//! let context = ObsContext::builder()
+//!     .set_backend(backend.clone())
+//!     .start()
+//!     .await?;
+//!
+//! // ... run your application logic on `context` ...
+//!
+//! assert!(backend.is_started());
+//! println!("Created sources: {:?}", backend.sources());
+//! # Ok(())
+//! # }
+//! ```
+
+// The libobs functions are implemented by the module of their domain,
+// `ObsBackend` forwards every call to them.
+mod audio;
+mod context;
+mod data;
+mod graphics;
+mod outputs;
+mod properties;
+mod scenes;
+mod signals;
+mod sources;
+
+pub use audio::{FakeSourceAudio, FakeSourceMedia};
+pub use outputs::{FakeEncoder, FakeOutput, FakeOutputStats, FakeRenderStats};
+pub use properties::FakeListItem;
+pub use scenes::{FakeScene, FakeSceneItem};
+pub use signals::{FakeCalldata, FakeSignal};
+pub use sources::FakeSource;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::{c_char, c_void, CStr, CString},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use libobs::{
+    audio_output_callback_t, obs_combo_format, obs_data_t, obs_source_audio_capture_t,
+    obs_source_t, obs_volmeter_updated_t, signal_callback_t, video_data, video_format,
+};
+use serde_json::{Map, Value};
+
+use crate::{
+    enums::{
+        ObsAlignment, ObsBlendingMethod, ObsBlendingType, ObsBoundsType, ObsEncoderType,
+        ObsFaderType, ObsPeakMeterType, ObsScaleType,
+    },
+    scenes::ObsSceneItemCrop,
+    Vec2,
+};
+
+/// Settings of a fake object in the same shape libobs would serialize them to json.
+pub type FakeSettings = Map<String, Value>;
+
+const FAKE_VIDEO_HANDLE: usize = 1;
+const FAKE_AUDIO_HANDLE: usize = 2;
+const FIRST_OBJECT_HANDLE: usize = 16;
+
+/// The id libobs uses for the source backing a scene.
+const SCENE_SOURCE_ID: &str = "scene";
+/// The id libobs uses for the source backing a group.
+const GROUP_SOURCE_ID: &str = "group";
+/// Transitions with this id switch the scene right away, ignoring the duration.
+const CUT_TRANSITION_ID: &str = "cut_transition";
+
+type RawVideoCallback = Option<unsafe extern "C" fn(param: *mut c_void, frame: *mut video_data)>;
+
+#[derive(Debug)]
+struct FakeData {
+    values: FakeSettings,
+    refs: usize,
+    json: CString,
+}
+
+#[derive(Debug)]
+struct FakeDataArray {
+    items: Vec<FakeSettings>,
+    refs: usize,
+}
+
+#[derive(Debug)]
+struct FakeSceneEntry {
+    name: String,
+    source: usize,
+    /// Handles of the scene items, from the bottom to the top.
+    items: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct FakeSceneItemEntry {
+    scene: usize,
+    source: usize,
+    position: Vec2,
+    scale: Vec2,
+    rotation: f32,
+    alignment: ObsAlignment,
+    bounds_type: ObsBoundsType,
+    bounds_alignment: ObsAlignment,
+    bounds: Vec2,
+    crop_to_bounds: bool,
+    crop: ObsSceneItemCrop,
+    scale_filter: ObsScaleType,
+    blending_method: ObsBlendingMethod,
+    blending_mode: ObsBlendingType,
+    visible: bool,
+    locked: bool,
+}
+
+impl FakeSceneItemEntry {
+    fn new(scene: usize, source: usize) -> Self {
+        Self {
+            scene,
+            source,
+            position: Vec2::new(0.0, 0.0),
+            scale: Vec2::new(1.0, 1.0),
+            rotation: 0.0,
+            alignment: ObsAlignment::TOP_LEFT,
+            bounds_type: ObsBoundsType::None,
+            bounds_alignment: ObsAlignment::CENTER,
+            bounds: Vec2::new(0.0, 0.0),
+            crop_to_bounds: false,
+            crop: ObsSceneItemCrop::default(),
+            scale_filter: ObsScaleType::Disable,
+            blending_method: ObsBlendingMethod::Default,
+            blending_mode: ObsBlendingType::Normal,
+            visible: true,
+            locked: false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FakeTransition {
+    /// The source that is shown once no transition is running.
+    active: Option<usize>,
+    /// The source the running transition goes to.
+    target: Option<usize>,
+    /// Increased on every start, so an outdated timer doesn't end a newer transition.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct FakeConnection {
+    handler: usize,
+    signal: String,
+    callback: signal_callback_t,
+    data: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FakeVideo {
+    format: video_format,
+    width: u32,
+    height: u32,
+    fps_num: u32,
+    fps_den: u32,
+}
+
+#[derive(Debug)]
+struct FakeRawVideoConnection {
+    callback: RawVideoCallback,
+    param: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FakeAudio {
+    samples_per_sec: u32,
+    speakers: libobs::speaker_layout,
+}
+
+#[derive(Debug)]
+struct FakeRawAudioConnection {
+    mix_idx: usize,
+    callback: audio_output_callback_t,
+    param: usize,
+}
+
+#[derive(Debug)]
+struct FakeAudioCaptureConnection {
+    source: usize,
+    callback: obs_source_audio_capture_t,
+    param: usize,
+}
+
+#[derive(Debug)]
+struct FakeVolmeter {
+    source: Option<usize>,
+    peak_meter_type: ObsPeakMeterType,
+    callbacks: Vec<(obs_volmeter_updated_t, usize)>,
+}
+
+#[derive(Debug)]
+struct FakeFader {
+    fader_type: ObsFaderType,
+    source: Option<usize>,
+    /// The volume of the fader while it isn't attached, attached faders follow their source.
+    db: f32,
+}
+
+/// What a source renders in the fake backend: a single color of the given size.
+#[derive(Debug, Clone, Copy)]
+struct FakeSourceVideo {
+    width: u32,
+    height: u32,
+    color: [u8; 4],
+}
+
+#[derive(Debug, Clone)]
+struct FakeListProperty {
+    name: String,
+    description: String,
+    items: Vec<FakeListItem>,
+}
+
+impl FakeListProperty {
+    fn format(&self) -> obs_combo_format {
+        match self.items.first().map(|item| &item.value) {
+            Some(Value::String(_)) => libobs::obs_combo_format_OBS_COMBO_FORMAT_STRING,
+            Some(Value::Number(n)) if n.is_f64() => libobs::obs_combo_format_OBS_COMBO_FORMAT_FLOAT,
+            Some(Value::Number(_)) => libobs::obs_combo_format_OBS_COMBO_FORMAT_INT,
+            Some(Value::Bool(_)) => libobs::obs_combo_format_OBS_COMBO_FORMAT_BOOL,
+            _ => libobs::obs_combo_format_OBS_COMBO_FORMAT_INVALID,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FakePropertyEntry {
+    properties: usize,
+    property: FakeListProperty,
+}
+
+/// A texrender or stage surface, holding RGBA pixels.
+#[derive(Debug, Default)]
+struct FakeTexture {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl FakeTexture {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FakeState {
+    next_handle: usize,
+    started: bool,
+    data: HashMap<usize, FakeData>,
+    data_arrays: HashMap<usize, FakeDataArray>,
+    sources: BTreeMap<usize, FakeSource>,
+    scenes: BTreeMap<usize, FakeSceneEntry>,
+    scene_items: BTreeMap<usize, FakeSceneItemEntry>,
+    outputs: BTreeMap<usize, FakeOutput>,
+    encoders: BTreeMap<usize, FakeEncoder>,
+    encoder_types: Vec<(CString, ObsEncoderType)>,
+    channels: BTreeMap<u32, usize>,
+    connections: Vec<FakeConnection>,
+    signals: Vec<FakeSignal>,
+    output_start_error: Option<CString>,
+    last_error: Option<CString>,
+    video: Option<FakeVideo>,
+    raw_video_connections: Vec<FakeRawVideoConnection>,
+    audio: Option<FakeAudio>,
+    raw_audio_connections: Vec<FakeRawAudioConnection>,
+    audio_capture_connections: Vec<FakeAudioCaptureConnection>,
+    source_videos: HashMap<usize, FakeSourceVideo>,
+    source_audio: HashMap<usize, FakeSourceAudio>,
+    source_media: HashMap<usize, FakeSourceMedia>,
+    volmeters: BTreeMap<usize, FakeVolmeter>,
+    faders: BTreeMap<usize, FakeFader>,
+    texrenders: HashMap<usize, FakeTexture>,
+    stagesurfaces: HashMap<usize, FakeTexture>,
+    render_target: Option<usize>,
+    render_stats: FakeRenderStats,
+    output_stats: HashMap<usize, FakeOutputStats>,
+    transitions: HashMap<usize, FakeTransition>,
+    /// References to sources that were taken with `obs_source_get_ref`.
+    source_refs: HashMap<usize, usize>,
+    /// How often sources were marked as showing with `obs_source_inc_showing`.
+    showing: HashMap<usize, usize>,
+    /// List properties registered per source or output type id.
+    list_properties: HashMap<String, Vec<FakeListProperty>>,
+    /// Property lists returned by `obs_get_source_properties` and friends, with their properties.
+    properties: HashMap<usize, Vec<usize>>,
+    property_entries: HashMap<usize, FakePropertyEntry>,
+    /// Strings returned by getters like `obs_source_get_name`, kept alive until the next call.
+    strings: HashMap<(usize, &'static str), CString>,
+}
+
+impl Default for FakeState {
+    fn default() -> Self {
+        Self {
+            next_handle: FIRST_OBJECT_HANDLE,
+            started: false,
+            data: HashMap::new(),
+            data_arrays: HashMap::new(),
+            sources: BTreeMap::new(),
+            scenes: BTreeMap::new(),
+            scene_items: BTreeMap::new(),
+            outputs: BTreeMap::new(),
+            encoders: BTreeMap::new(),
+            encoder_types: vec![
+                (CString::new("obs_x264").unwrap(), ObsEncoderType::Video),
+                (CString::new("ffmpeg_aac").unwrap(), ObsEncoderType::Audio),
+            ],
+            channels: BTreeMap::new(),
+            connections: Vec::new(),
+            signals: Vec::new(),
+            output_start_error: None,
+            last_error: None,
+            video: None,
+            raw_video_connections: Vec::new(),
+            audio: None,
+            raw_audio_connections: Vec::new(),
+            audio_capture_connections: Vec::new(),
+            source_videos: HashMap::new(),
+            source_audio: HashMap::new(),
+            source_media: HashMap::new(),
+            volmeters: BTreeMap::new(),
+            faders: BTreeMap::new(),
+            texrenders: HashMap::new(),
+            stagesurfaces: HashMap::new(),
+            render_target: None,
+            render_stats: FakeRenderStats::default(),
+            output_stats: HashMap::new(),
+            transitions: HashMap::new(),
+            source_refs: HashMap::new(),
+            showing: HashMap::new(),
+            list_properties: HashMap::new(),
+            properties: HashMap::new(),
+            property_entries: HashMap::new(),
+            strings: HashMap::new(),
+        }
+    }
+}
+
+impl FakeState {
+    fn next_handle(&mut self) -> usize {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        handle
+    }
+
+    fn create_data(&mut self, values: FakeSettings) -> usize {
+        let handle = self.next_handle();
+        self.data.insert(
+            handle,
+            FakeData {
+                values,
+                refs: 1,
+                json: CString::default(),
+            },
+        );
+
+        handle
+    }
+
+    fn data_values(&self, data: *mut obs_data_t) -> FakeSettings {
+        self.data
+            .get(&handle(data))
+            .map(|d| d.values.clone())
+            .unwrap_or_default()
+    }
+
+    fn create_data_array(&mut self, items: Vec<FakeSettings>) -> usize {
+        let handle = self.next_handle();
+        self.data_arrays
+            .insert(handle, FakeDataArray { items, refs: 1 });
+
+        handle
+    }
+
+    fn store_string(&mut self, object: usize, key: &'static str, value: &str) -> *const c_char {
+        let value = CString::new(value).unwrap_or_default();
+        let ptr = value.as_ptr();
+        self.strings.insert((object, key), value);
+        ptr
+    }
+
+    fn source_name(&self, source: usize) -> String {
+        self.sources
+            .get(&source)
+            .map(|s| s.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn object_name(&self, handler: usize) -> String {
+        if let Some(source) = self.sources.get(&handler) {
+            return source.name.clone();
+        }
+
+        self.outputs
+            .get(&handler)
+            .map(|o| o.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn handler_by_name(&self, name: &str) -> Option<usize> {
+        self.sources
+            .iter()
+            .find(|(_, s)| s.name == name)
+            .map(|(h, _)| *h)
+            .or_else(|| {
+                self.outputs
+                    .iter()
+                    .find(|(_, o)| o.name == name)
+                    .map(|(h, _)| *h)
+            })
+    }
+}
+
+/// A backend which keeps all objects in memory and never calls into libobs.
+///
+/// The backend can be cloned and all clones share the same state, so keep a clone
+/// around to inspect what your code did after passing it to `StartupInfo::set_backend`.
+#[derive(Debug, Clone, Default)]
+pub struct FakeObsBackend {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl FakeObsBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    /// Whether `obs_startup` has been called and OBS has not been shut down since.
+    pub fn is_started(&self) -> bool {
+        self.state().started
+    }
+}
+
+fn handle<T>(ptr: *mut T) -> usize {
+    ptr as usize
+}
+
+fn ptr_of<T>(handle: usize) -> *mut T {
+    handle as *mut T
+}
+
+unsafe fn to_string(s: *const c_char) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
+
+/// Fake sources get a uuid derived from their handle.
+fn fake_uuid(handle: usize) -> String {
+    format!("00000000-0000-4000-8000-{:012x}", handle)
+}
+
+/// Calls the enum callback for each source until it returns false, like `obs_enum_sources`.
+/// The lock must not be held, as the callback usually takes a reference.
+unsafe fn enum_handles(
+    sources: Vec<usize>,
+    enum_proc: Option<unsafe extern "C" fn(*mut c_void, *mut obs_source_t) -> bool>,
+    param: *mut c_void,
+) {
+    let Some(enum_proc) = enum_proc else {
+        return;
+    };
+
+    for source in sources {
+        if !enum_proc(param, ptr_of(source)) {
+            break;
+        }
+    }
+}
+
+fn merge(target: &mut FakeSettings, values: FakeSettings) {
+    for (key, value) in values {
+        target.insert(key, value);
+    }
+}
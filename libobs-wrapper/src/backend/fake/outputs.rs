@@ -0,0 +1,399 @@
+//! Outputs, encoders and render statistics of the fake backend.
+
+use std::{
+    collections::BTreeMap,
+    ffi::{c_char, c_int, CString},
+    ptr,
+};
+
+use libobs::{
+    audio_output, obs_data_t, obs_encoder_t, obs_encoder_type, obs_output_t, signal_handler_t,
+    video_output,
+};
+
+use crate::enums::{ObsEncoderType, OsEnumType};
+
+use super::{handle, merge, ptr_of, to_string, FakeCalldata, FakeObsBackend, FakeSettings};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeOutput {
+    pub id: String,
+    pub name: String,
+    pub settings: FakeSettings,
+    pub active: bool,
+    pub paused: bool,
+    /// Name of the video encoder attached to this output.
+    pub video_encoder: Option<String>,
+    /// Names of the audio encoders attached to this output by their mixer index.
+    pub audio_encoders: BTreeMap<usize, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeEncoder {
+    pub id: String,
+    pub name: String,
+    pub encoder_type: ObsEncoderType,
+    pub settings: FakeSettings,
+}
+
+/// Render statistics which are reported by the fake backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FakeRenderStats {
+    pub active_fps: f64,
+    pub average_frame_time_ns: u64,
+    pub total_frames: u32,
+    pub lagged_frames: u32,
+}
+
+/// Statistics of an output which are reported by the fake backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FakeOutputStats {
+    pub total_bytes: u64,
+    pub dropped_frames: i32,
+    pub total_frames: i32,
+    pub congestion: f32,
+}
+
+impl FakeObsBackend {
+    pub fn outputs(&self) -> Vec<FakeOutput> {
+        self.state().outputs.values().cloned().collect()
+    }
+
+    pub fn output(&self, name: &str) -> Option<FakeOutput> {
+        self.outputs().into_iter().find(|o| o.name == name)
+    }
+
+    pub fn encoders(&self) -> Vec<FakeEncoder> {
+        self.state().encoders.values().cloned().collect()
+    }
+
+    /// Registers an encoder type which is then listed by `obs_enum_encoder_types`.
+    /// By default `obs_x264` and `ffmpeg_aac` are registered.
+    pub fn register_encoder_type(&self, id: &str, encoder_type: ObsEncoderType) {
+        let id = CString::new(id).unwrap_or_default();
+        self.state().encoder_types.push((id, encoder_type));
+    }
+
+    /// Makes the next call to `obs_output_start` fail with the given error.
+    pub fn fail_next_output_start(&self, error: &str) {
+        self.state().output_start_error = Some(CString::new(error).unwrap_or_default());
+    }
+
+    /// Sets the render statistics the fake backend reports from now on.
+    pub fn set_render_stats(&self, stats: FakeRenderStats) {
+        self.state().render_stats = stats;
+    }
+
+    /// Sets the statistics the output with the given name reports from now on.
+    /// Returns `false` if there is no output with that name.
+    pub fn set_output_stats(&self, output_name: &str, stats: FakeOutputStats) -> bool {
+        let mut state = self.state();
+        let Some(output) = state
+            .outputs
+            .iter()
+            .find(|(_, o)| o.name == output_name)
+            .map(|(h, _)| *h)
+        else {
+            return false;
+        };
+
+        state.output_stats.insert(output, stats);
+        true
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_get_active_fps(&self) -> f64 {
+        self.state().render_stats.active_fps
+    }
+
+    pub(crate) unsafe fn obs_get_average_frame_time_ns(&self) -> u64 {
+        self.state().render_stats.average_frame_time_ns
+    }
+
+    pub(crate) unsafe fn obs_get_total_frames(&self) -> u32 {
+        self.state().render_stats.total_frames
+    }
+
+    pub(crate) unsafe fn obs_get_lagged_frames(&self) -> u32 {
+        self.state().render_stats.lagged_frames
+    }
+
+    pub(crate) unsafe fn obs_output_create(
+        &self,
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        _hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_output_t {
+        let mut state = self.state();
+        let output = FakeOutput {
+            id: to_string(id),
+            name: to_string(name),
+            settings: state.data_values(settings),
+            active: false,
+            paused: false,
+            video_encoder: None,
+            audio_encoders: BTreeMap::new(),
+        };
+
+        let output_handle = state.next_handle();
+        state.outputs.insert(output_handle, output);
+        ptr_of(output_handle)
+    }
+
+    pub(crate) unsafe fn obs_output_release(&self, output: *mut obs_output_t) {
+        let mut state = self.state();
+        state.outputs.remove(&handle(output));
+        state.connections.retain(|c| c.handler != handle(output));
+        state.output_stats.remove(&handle(output));
+    }
+
+    pub(crate) unsafe fn obs_output_get_total_bytes(&self, output: *const obs_output_t) -> u64 {
+        self.state()
+            .output_stats
+            .get(&handle(output as *mut obs_output_t))
+            .map(|s| s.total_bytes)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_output_get_frames_dropped(
+        &self,
+        output: *const obs_output_t,
+    ) -> c_int {
+        self.state()
+            .output_stats
+            .get(&handle(output as *mut obs_output_t))
+            .map(|s| s.dropped_frames)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_output_get_total_frames(&self, output: *const obs_output_t) -> c_int {
+        self.state()
+            .output_stats
+            .get(&handle(output as *mut obs_output_t))
+            .map(|s| s.total_frames)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_output_get_congestion(&self, output: *mut obs_output_t) -> f32 {
+        self.state()
+            .output_stats
+            .get(&handle(output))
+            .map(|s| s.congestion)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_output_active(&self, output: *mut obs_output_t) -> bool {
+        self.state()
+            .outputs
+            .get(&handle(output))
+            .is_some_and(|o| o.active)
+    }
+
+    pub(crate) unsafe fn obs_output_start(&self, output: *mut obs_output_t) -> bool {
+        {
+            let mut state = self.state();
+            if let Some(error) = state.output_start_error.take() {
+                state.last_error = Some(error);
+                return false;
+            }
+
+            let Some(output) = state.outputs.get_mut(&handle(output)) else {
+                return false;
+            };
+
+            output.active = true;
+            output.paused = false;
+        }
+
+        let output = handle(output);
+        self.emit(output, "starting", FakeCalldata::new());
+        self.emit(output, "activate", FakeCalldata::new());
+        self.emit(output, "start", FakeCalldata::new());
+        true
+    }
+
+    pub(crate) unsafe fn obs_output_stop(&self, output: *mut obs_output_t) {
+        {
+            let mut state = self.state();
+            let Some(output) = state.outputs.get_mut(&handle(output)) else {
+                return;
+            };
+
+            output.active = false;
+            output.paused = false;
+        }
+
+        let output = handle(output);
+        self.emit(output, "stopping", FakeCalldata::new());
+        self.emit(output, "deactivate", FakeCalldata::new());
+        self.emit(
+            output,
+            "stop",
+            FakeCalldata::new().set_int("code", libobs::OBS_OUTPUT_SUCCESS as i64),
+        );
+    }
+
+    pub(crate) unsafe fn obs_output_pause(&self, output: *mut obs_output_t, pause: bool) -> bool {
+        {
+            let mut state = self.state();
+            let Some(output) = state.outputs.get_mut(&handle(output)) else {
+                return false;
+            };
+
+            if !output.active {
+                return false;
+            }
+
+            output.paused = pause;
+        }
+
+        let signal = if pause { "pause" } else { "unpause" };
+        self.emit(handle(output), signal, FakeCalldata::new());
+        true
+    }
+
+    pub(crate) unsafe fn obs_output_update(
+        &self,
+        output: *mut obs_output_t,
+        settings: *mut obs_data_t,
+    ) {
+        let mut state = self.state();
+        let values = state.data_values(settings);
+        if let Some(output) = state.outputs.get_mut(&handle(output)) {
+            merge(&mut output.settings, values);
+        }
+    }
+
+    pub(crate) unsafe fn obs_output_get_last_error(
+        &self,
+        _output: *mut obs_output_t,
+    ) -> *const c_char {
+        self.state()
+            .last_error
+            .as_ref()
+            .map(|e| e.as_ptr())
+            .unwrap_or(ptr::null())
+    }
+
+    pub(crate) unsafe fn obs_output_set_video_encoder(
+        &self,
+        output: *mut obs_output_t,
+        encoder: *mut obs_encoder_t,
+    ) {
+        let mut state = self.state();
+        let encoder_name = state.encoders.get(&handle(encoder)).map(|e| e.name.clone());
+        if let Some(output) = state.outputs.get_mut(&handle(output)) {
+            output.video_encoder = encoder_name;
+        }
+    }
+
+    pub(crate) unsafe fn obs_output_set_audio_encoder(
+        &self,
+        output: *mut obs_output_t,
+        encoder: *mut obs_encoder_t,
+        idx: usize,
+    ) {
+        let mut state = self.state();
+        let encoder_name = state.encoders.get(&handle(encoder)).map(|e| e.name.clone());
+        if let (Some(output), Some(encoder_name)) =
+            (state.outputs.get_mut(&handle(output)), encoder_name)
+        {
+            output.audio_encoders.insert(idx, encoder_name);
+        }
+    }
+
+    pub(crate) unsafe fn obs_output_get_signal_handler(
+        &self,
+        output: *mut obs_output_t,
+    ) -> *mut signal_handler_t {
+        ptr_of(handle(output))
+    }
+
+    pub(crate) unsafe fn obs_video_encoder_create(
+        &self,
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        _hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_encoder_t {
+        self.create_encoder(id, name, settings, ObsEncoderType::Video)
+    }
+
+    pub(crate) unsafe fn obs_audio_encoder_create(
+        &self,
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        _mixer_idx: usize,
+        _hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_encoder_t {
+        self.create_encoder(id, name, settings, ObsEncoderType::Audio)
+    }
+
+    pub(crate) unsafe fn obs_encoder_release(&self, encoder: *mut obs_encoder_t) {
+        self.state().encoders.remove(&handle(encoder));
+    }
+
+    pub(crate) unsafe fn obs_encoder_set_video(
+        &self,
+        _encoder: *mut obs_encoder_t,
+        _video: *mut video_output,
+    ) {
+    }
+
+    pub(crate) unsafe fn obs_encoder_set_audio(
+        &self,
+        _encoder: *mut obs_encoder_t,
+        _audio: *mut audio_output,
+    ) {
+    }
+
+    pub(crate) unsafe fn obs_enum_encoder_types(&self, idx: usize, id: *mut *const c_char) -> bool {
+        match self.state().encoder_types.get(idx) {
+            Some((encoder_id, _)) => {
+                *id = encoder_id.as_ptr();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) unsafe fn obs_get_encoder_caps(&self, _encoder_id: *const c_char) -> u32 {
+        0
+    }
+
+    pub(crate) unsafe fn obs_get_encoder_type(&self, id: *const c_char) -> obs_encoder_type {
+        let id = to_string(id);
+        let encoder_type = self
+            .state()
+            .encoder_types
+            .iter()
+            .find(|(encoder_id, _)| encoder_id.to_string_lossy() == id)
+            .map(|(_, t)| *t)
+            .unwrap_or(ObsEncoderType::Video);
+
+        encoder_type as OsEnumType
+    }
+
+    unsafe fn create_encoder(
+        &self,
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        encoder_type: ObsEncoderType,
+    ) -> *mut obs_encoder_t {
+        let mut state = self.state();
+        let encoder = FakeEncoder {
+            id: to_string(id),
+            name: to_string(name),
+            encoder_type,
+            settings: state.data_values(settings),
+        };
+
+        let encoder_handle = state.next_handle();
+        state.encoders.insert(encoder_handle, encoder);
+        ptr_of(encoder_handle)
+    }
+}
@@ -0,0 +1,385 @@
+//! Properties of the fake backend, where only list properties can be registered.
+
+use std::{
+    ffi::{c_char, c_int},
+    ptr,
+};
+
+use libobs::{
+    obs_button_type, obs_combo_format, obs_combo_type, obs_editable_list_type, obs_number_type,
+    obs_output_t, obs_path_type, obs_properties_t, obs_property_t, obs_property_type, obs_source_t,
+    obs_text_info_type, obs_text_type,
+};
+use serde_json::Value;
+
+use super::{
+    handle, ptr_of, to_string, FakeListProperty, FakeObsBackend, FakePropertyEntry, FakeState,
+};
+
+/// An item of a list property, registered with [`FakeObsBackend::register_list_property`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeListItem {
+    pub name: String,
+    /// The value of the item, which also decides the format of the list:
+    /// strings, integers, floats or booleans.
+    pub value: Value,
+    pub disabled: bool,
+}
+
+impl FakeListItem {
+    pub fn new(name: &str, value: impl Into<Value>) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.into(),
+            disabled: false,
+        }
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.disabled = true;
+        self
+    }
+}
+
+impl FakeState {
+    /// Creates a property list with the list properties registered for the type id.
+    fn create_properties(&mut self, id: &str) -> *mut obs_properties_t {
+        let registered = self.list_properties.get(id).cloned().unwrap_or_default();
+        let properties = self.next_handle();
+        let mut handles = Vec::with_capacity(registered.len());
+        for property in registered {
+            let handle = self.next_handle();
+            self.property_entries.insert(
+                handle,
+                FakePropertyEntry {
+                    properties,
+                    property,
+                },
+            );
+            handles.push(handle);
+        }
+
+        self.properties.insert(properties, handles);
+        ptr_of(properties)
+    }
+
+    fn list_property(&self, p: *mut obs_property_t) -> Option<&FakeListProperty> {
+        self.property_entries.get(&handle(p)).map(|e| &e.property)
+    }
+
+    fn list_item(&self, p: *mut obs_property_t, idx: usize) -> Option<&FakeListItem> {
+        self.list_property(p)?.items.get(idx)
+    }
+}
+
+impl FakeObsBackend {
+    /// Registers a list property, which is returned when enumerating the properties of sources
+    /// or outputs with the given type id. Replaces a registered property with the same name.
+    pub fn register_list_property(
+        &self,
+        id: &str,
+        name: &str,
+        description: &str,
+        items: Vec<FakeListItem>,
+    ) {
+        let mut state = self.state();
+        let properties = state.list_properties.entry(id.to_string()).or_default();
+        properties.retain(|p| p.name != name);
+        properties.push(FakeListProperty {
+            name: name.to_string(),
+            description: description.to_string(),
+            items,
+        });
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_get_source_properties(
+        &self,
+        id: *const c_char,
+    ) -> *mut obs_properties_t {
+        self.state().create_properties(&to_string(id))
+    }
+
+    pub(crate) unsafe fn obs_source_properties(
+        &self,
+        source: *mut obs_source_t,
+    ) -> *mut obs_properties_t {
+        let mut state = self.state();
+        let Some(id) = state.sources.get(&handle(source)).map(|s| s.id.clone()) else {
+            return ptr::null_mut();
+        };
+
+        state.create_properties(&id)
+    }
+
+    pub(crate) unsafe fn obs_get_output_properties(
+        &self,
+        id: *const c_char,
+    ) -> *mut obs_properties_t {
+        self.state().create_properties(&to_string(id))
+    }
+
+    pub(crate) unsafe fn obs_output_properties(
+        &self,
+        output: *mut obs_output_t,
+    ) -> *mut obs_properties_t {
+        let mut state = self.state();
+        let Some(id) = state.outputs.get(&handle(output)).map(|o| o.id.clone()) else {
+            return ptr::null_mut();
+        };
+
+        state.create_properties(&id)
+    }
+
+    pub(crate) unsafe fn obs_properties_destroy(&self, props: *mut obs_properties_t) {
+        let mut state = self.state();
+        if let Some(handles) = state.properties.remove(&handle(props)) {
+            for property in handles {
+                state.property_entries.remove(&property);
+                state.strings.retain(|(object, _), _| *object != property);
+            }
+        }
+    }
+
+    pub(crate) unsafe fn obs_properties_first(
+        &self,
+        props: *mut obs_properties_t,
+    ) -> *mut obs_property_t {
+        self.state()
+            .properties
+            .get(&handle(props))
+            .and_then(|handles| handles.first())
+            .map_or(ptr::null_mut(), |p| ptr_of(*p))
+    }
+
+    pub(crate) unsafe fn obs_property_next(&self, p: *mut *mut obs_property_t) -> bool {
+        let state = self.state();
+        let current = handle(*p);
+        let next = state
+            .property_entries
+            .get(&current)
+            .and_then(|entry| state.properties.get(&entry.properties))
+            .and_then(|handles| {
+                let index = handles.iter().position(|h| *h == current)?;
+                handles.get(index + 1).copied()
+            });
+
+        *p = next.map_or(ptr::null_mut(), ptr_of);
+        next.is_some()
+    }
+
+    pub(crate) unsafe fn obs_property_name(&self, p: *mut obs_property_t) -> *const c_char {
+        let mut state = self.state();
+        let name = state.list_property(p).map(|p| p.name.clone());
+        match name {
+            Some(name) => state.store_string(handle(p), "name", &name),
+            None => ptr::null(),
+        }
+    }
+
+    pub(crate) unsafe fn obs_property_description(&self, p: *mut obs_property_t) -> *const c_char {
+        let mut state = self.state();
+        let description = state.list_property(p).map(|p| p.description.clone());
+        match description {
+            Some(description) => state.store_string(handle(p), "description", &description),
+            None => ptr::null(),
+        }
+    }
+
+    pub(crate) unsafe fn obs_property_get_type(&self, p: *mut obs_property_t) -> obs_property_type {
+        match self.state().list_property(p) {
+            Some(_) => libobs::obs_property_type_OBS_PROPERTY_LIST,
+            None => libobs::obs_property_type_OBS_PROPERTY_INVALID,
+        }
+    }
+
+    pub(crate) unsafe fn obs_property_int_min(&self, _p: *mut obs_property_t) -> c_int {
+        0
+    }
+
+    pub(crate) unsafe fn obs_property_int_max(&self, _p: *mut obs_property_t) -> c_int {
+        0
+    }
+
+    pub(crate) unsafe fn obs_property_int_step(&self, _p: *mut obs_property_t) -> c_int {
+        0
+    }
+
+    pub(crate) unsafe fn obs_property_int_type(&self, _p: *mut obs_property_t) -> obs_number_type {
+        libobs::obs_number_type_OBS_NUMBER_SCROLLER
+    }
+
+    pub(crate) unsafe fn obs_property_int_suffix(&self, _p: *mut obs_property_t) -> *const c_char {
+        ptr::null()
+    }
+
+    pub(crate) unsafe fn obs_property_float_min(&self, _p: *mut obs_property_t) -> f64 {
+        0.0
+    }
+
+    pub(crate) unsafe fn obs_property_float_max(&self, _p: *mut obs_property_t) -> f64 {
+        0.0
+    }
+
+    pub(crate) unsafe fn obs_property_float_step(&self, _p: *mut obs_property_t) -> f64 {
+        0.0
+    }
+
+    pub(crate) unsafe fn obs_property_float_type(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> obs_number_type {
+        libobs::obs_number_type_OBS_NUMBER_SCROLLER
+    }
+
+    pub(crate) unsafe fn obs_property_float_suffix(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> *const c_char {
+        ptr::null()
+    }
+
+    pub(crate) unsafe fn obs_property_text_type(&self, _p: *mut obs_property_t) -> obs_text_type {
+        libobs::obs_text_type_OBS_TEXT_DEFAULT
+    }
+
+    pub(crate) unsafe fn obs_property_text_monospace(&self, _p: *mut obs_property_t) -> bool {
+        false
+    }
+
+    pub(crate) unsafe fn obs_property_text_info_type(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> obs_text_info_type {
+        libobs::obs_text_info_type_OBS_TEXT_INFO_NORMAL
+    }
+
+    pub(crate) unsafe fn obs_property_text_info_word_wrap(&self, _p: *mut obs_property_t) -> bool {
+        false
+    }
+
+    pub(crate) unsafe fn obs_property_path_type(&self, _p: *mut obs_property_t) -> obs_path_type {
+        libobs::obs_path_type_OBS_PATH_FILE
+    }
+
+    pub(crate) unsafe fn obs_property_path_filter(&self, _p: *mut obs_property_t) -> *const c_char {
+        ptr::null()
+    }
+
+    pub(crate) unsafe fn obs_property_path_default_path(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> *const c_char {
+        ptr::null()
+    }
+
+    pub(crate) unsafe fn obs_property_list_type(&self, _p: *mut obs_property_t) -> obs_combo_type {
+        libobs::obs_combo_type_OBS_COMBO_TYPE_LIST
+    }
+
+    pub(crate) unsafe fn obs_property_list_format(
+        &self,
+        p: *mut obs_property_t,
+    ) -> obs_combo_format {
+        self.state()
+            .list_property(p)
+            .map_or(libobs::obs_combo_format_OBS_COMBO_FORMAT_INVALID, |p| {
+                p.format()
+            })
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_count(&self, p: *mut obs_property_t) -> usize {
+        self.state().list_property(p).map_or(0, |p| p.items.len())
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_name(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> *const c_char {
+        let mut state = self.state();
+        let name = state.list_item(p, idx).map(|item| item.name.clone());
+        match name {
+            Some(name) => state.store_string(handle(p), "item_name", &name),
+            None => ptr::null(),
+        }
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_disabled(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> bool {
+        self.state()
+            .list_item(p, idx)
+            .is_some_and(|item| item.disabled)
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_string(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> *const c_char {
+        let mut state = self.state();
+        let value = state
+            .list_item(p, idx)
+            .and_then(|item| item.value.as_str())
+            .map(str::to_string);
+        match value {
+            Some(value) => state.store_string(handle(p), "item_string", &value),
+            None => ptr::null(),
+        }
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_int(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> i64 {
+        self.state()
+            .list_item(p, idx)
+            .and_then(|item| item.value.as_i64())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_float(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> f64 {
+        self.state()
+            .list_item(p, idx)
+            .and_then(|item| item.value.as_f64())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_property_list_item_bool(
+        &self,
+        p: *mut obs_property_t,
+        idx: usize,
+    ) -> bool {
+        self.state()
+            .list_item(p, idx)
+            .and_then(|item| item.value.as_bool())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_property_editable_list_type(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> obs_editable_list_type {
+        libobs::obs_editable_list_type_OBS_EDITABLE_LIST_TYPE_STRINGS
+    }
+
+    pub(crate) unsafe fn obs_property_button_type(
+        &self,
+        _p: *mut obs_property_t,
+    ) -> obs_button_type {
+        libobs::obs_button_type_OBS_BUTTON_DEFAULT
+    }
+
+    pub(crate) unsafe fn obs_property_button_url(&self, _p: *mut obs_property_t) -> *const c_char {
+        ptr::null()
+    }
+}
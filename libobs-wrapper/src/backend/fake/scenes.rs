@@ -0,0 +1,1045 @@
+//! Scenes, scene items and groups of the fake backend.
+
+use std::{
+    ffi::{c_char, c_int, c_void},
+    ptr,
+};
+
+use libobs::{
+    obs_blending_method, obs_blending_type, obs_bounds_type, obs_order_movement, obs_scale_type,
+    obs_scene_t, obs_sceneitem_crop, obs_sceneitem_t, obs_source_t, obs_transform_info, vec2,
+};
+use num_traits::FromPrimitive;
+use serde_json::{json, Value};
+
+use crate::{
+    enums::{
+        ObsAlignment, ObsBlendingMethod, ObsBlendingType, ObsBoundsType, ObsOrderMovement,
+        ObsScaleType,
+    },
+    scenes::ObsSceneItemCrop,
+    Vec2,
+};
+
+use super::{
+    handle, ptr_of, to_string, FakeCalldata, FakeObsBackend, FakeSceneEntry, FakeSceneItemEntry,
+    FakeSettings, FakeSource, FakeState, GROUP_SOURCE_ID, SCENE_SOURCE_ID,
+};
+
+#[derive(Debug, Clone)]
+pub struct FakeSceneItem {
+    /// Name of the source this scene item shows.
+    pub source: String,
+    pub position: Vec2,
+    pub scale: Vec2,
+    pub rotation: f32,
+    pub alignment: ObsAlignment,
+    pub bounds_type: ObsBoundsType,
+    pub bounds_alignment: ObsAlignment,
+    pub bounds: Vec2,
+    pub crop_to_bounds: bool,
+    pub crop: ObsSceneItemCrop,
+    pub scale_filter: ObsScaleType,
+    pub blending_method: ObsBlendingMethod,
+    pub blending_mode: ObsBlendingType,
+    pub visible: bool,
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeScene {
+    pub name: String,
+    /// Whether this is the scene backing a group.
+    pub group: bool,
+    /// The items of the scene, from the bottom to the top.
+    pub items: Vec<FakeSceneItem>,
+}
+
+impl FakeState {
+    /// Removes the scene, its items and its source. Groups in the scene are removed as well,
+    /// as their items hold the only reference to them.
+    fn remove_scene(&mut self, scene_handle: usize) {
+        let Some(scene) = self.scenes.remove(&scene_handle) else {
+            return;
+        };
+
+        for item in scene.items {
+            if let Some(entry) = self.scene_items.remove(&item) {
+                if let Some(group) = self.group_scene_of(entry.source) {
+                    self.remove_scene(group);
+                }
+            }
+        }
+
+        self.sources.remove(&scene.source);
+        self.channels.retain(|_, s| *s != scene.source);
+        self.connections.retain(|c| c.handler != scene.source);
+    }
+
+    /// The scene backing the given source, if the source is a group.
+    pub(super) fn group_scene_of(&self, source: usize) -> Option<usize> {
+        if self.sources.get(&source)?.id != GROUP_SOURCE_ID {
+            return None;
+        }
+
+        self.scenes
+            .iter()
+            .find(|(_, s)| s.source == source)
+            .map(|(h, _)| *h)
+    }
+
+    pub(super) fn save_item(&self, item: usize) -> Option<Value> {
+        let item = self.scene_items.get(&item)?;
+        Some(json!({
+            "name": self.source_name(item.source),
+            "visible": item.visible,
+            "locked": item.locked,
+            "rot": item.rotation,
+            "pos": { "x": item.position.x, "y": item.position.y },
+            "scale": { "x": item.scale.x, "y": item.scale.y },
+            "align": item.alignment.bits(),
+            "bounds_type": item.bounds_type as i64,
+            "bounds_align": item.bounds_alignment.bits(),
+            "bounds": { "x": item.bounds.x, "y": item.bounds.y },
+            "bounds_crop": item.crop_to_bounds,
+            "crop_left": item.crop.left,
+            "crop_top": item.crop.top,
+            "crop_right": item.crop.right,
+            "crop_bottom": item.crop.bottom,
+            "scale_filter": item.scale_filter as i64,
+            "blend_method": item.blending_method as i64,
+            "blend_type": item.blending_mode as i64,
+        }))
+    }
+
+    /// Adds the saved items to the scene backing the source, looking up their sources by name.
+    pub(super) fn load_items(&mut self, source: usize) {
+        let Some(scene) = self
+            .scenes
+            .iter()
+            .find(|(_, s)| s.source == source)
+            .map(|(h, _)| *h)
+        else {
+            return;
+        };
+
+        let items = self
+            .sources
+            .get(&source)
+            .and_then(|s| s.settings.get("items"))
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for values in items.iter().filter_map(Value::as_object) {
+            let Some(item_source) = values
+                .get("name")
+                .and_then(Value::as_str)
+                .and_then(|name| self.source_by_name(name))
+            else {
+                continue;
+            };
+
+            let int = |key: &str| values.get(key).and_then(Value::as_i64).unwrap_or_default();
+            let float = |key: &str| values.get(key).and_then(Value::as_f64).unwrap_or_default();
+            let vec2 = |key: &str, default: f32| {
+                let value = values.get(key);
+                let get = |axis: &str| {
+                    value
+                        .and_then(|v| v.get(axis))
+                        .and_then(Value::as_f64)
+                        .map(|v| v as f32)
+                        .unwrap_or(default)
+                };
+                Vec2::new(get("x"), get("y"))
+            };
+            let bool = |key: &str, default: bool| {
+                values.get(key).and_then(Value::as_bool).unwrap_or(default)
+            };
+
+            let mut entry = FakeSceneItemEntry::new(scene, item_source);
+            entry.position = vec2("pos", 0.0);
+            entry.scale = vec2("scale", 1.0);
+            entry.rotation = float("rot") as f32;
+            entry.alignment = ObsAlignment::from_bits(int("align") as u32);
+            entry.bounds_type =
+                ObsBoundsType::from_i64(int("bounds_type")).unwrap_or(ObsBoundsType::None);
+            entry.bounds_alignment = ObsAlignment::from_bits(int("bounds_align") as u32);
+            entry.bounds = vec2("bounds", 0.0);
+            entry.crop_to_bounds = bool("bounds_crop", false);
+            entry.crop = ObsSceneItemCrop::new(
+                int("crop_left") as i32,
+                int("crop_top") as i32,
+                int("crop_right") as i32,
+                int("crop_bottom") as i32,
+            );
+            entry.scale_filter =
+                ObsScaleType::from_i64(int("scale_filter")).unwrap_or(ObsScaleType::Disable);
+            entry.blending_method = ObsBlendingMethod::from_i64(int("blend_method"))
+                .unwrap_or(ObsBlendingMethod::Default);
+            entry.blending_mode =
+                ObsBlendingType::from_i64(int("blend_type")).unwrap_or(ObsBlendingType::Normal);
+            entry.visible = bool("visible", true);
+            entry.locked = bool("locked", false);
+
+            let item = self.next_handle();
+            self.scene_items.insert(item, entry);
+            if let Some(scene) = self.scenes.get_mut(&scene) {
+                scene.items.push(item);
+            }
+        }
+    }
+
+    /// Moves the item to the given scene, inserting it at the given index.
+    fn move_item_to_scene(&mut self, item: usize, scene: usize, index: usize) {
+        let Some(entry) = self.scene_items.get_mut(&item) else {
+            return;
+        };
+
+        let old_scene = entry.scene;
+        entry.scene = scene;
+        if let Some(old_scene) = self.scenes.get_mut(&old_scene) {
+            old_scene.items.retain(|i| *i != item);
+        }
+
+        if let Some(scene) = self.scenes.get_mut(&scene) {
+            let index = index.min(scene.items.len());
+            scene.items.insert(index, item);
+        }
+    }
+}
+
+impl FakeObsBackend {
+    pub fn scenes(&self) -> Vec<FakeScene> {
+        let state = self.state();
+        state
+            .scenes
+            .iter()
+            .values()
+            .map(|scene| FakeScene {
+                name: scene.name.clone(),
+                group: state
+                    .sources
+                    .get(&scene.source)
+                    .is_some_and(|s| s.id == GROUP_SOURCE_ID),
+                items: scene
+                    .items
+                    .iter()
+                    .filter_map(|item| state.scene_items.get(item))
+                    .map(|item| FakeSceneItem {
+                        source: state.source_name(item.source),
+                        position: item.position,
+                        scale: item.scale,
+                        rotation: item.rotation,
+                        alignment: item.alignment,
+                        bounds_type: item.bounds_type,
+                        bounds_alignment: item.bounds_alignment,
+                        bounds: item.bounds,
+                        crop_to_bounds: item.crop_to_bounds,
+                        crop: item.crop,
+                        scale_filter: item.scale_filter,
+                        blending_method: item.blending_method,
+                        blending_mode: item.blending_mode,
+                        visible: item.visible,
+                        locked: item.locked,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn scene(&self, name: &str) -> Option<FakeScene> {
+        self.scenes().into_iter().find(|s| s.name == name)
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_scene_create(&self, name: *const c_char) -> *mut obs_scene_t {
+        let mut state = self.state();
+        let name = to_string(name);
+
+        let source_handle = state.next_handle();
+        state.sources.insert(
+            source_handle,
+            FakeSource {
+                id: SCENE_SOURCE_ID.to_string(),
+                name: name.clone(),
+                settings: FakeSettings::new(),
+                filters: Vec::new(),
+            },
+        );
+
+        let scene_handle = state.next_handle();
+        state.scenes.insert(
+            scene_handle,
+            FakeSceneEntry {
+                name,
+                source: source_handle,
+                items: Vec::new(),
+            },
+        );
+
+        ptr_of(scene_handle)
+    }
+
+    pub(crate) unsafe fn obs_scene_release(&self, scene: *mut obs_scene_t) {
+        let filters = {
+            let mut state = self.state();
+            let Some(source) = state.scenes.get(&handle(scene)).map(|s| s.source) else {
+                return;
+            };
+
+            // Releasing a scene releases its source, which might be referenced elsewhere
+            if let Some(refs) = state.source_refs.get_mut(&source) {
+                *refs -= 1;
+                if *refs == 0 {
+                    state.source_refs.remove(&source);
+                }
+                return;
+            }
+
+            let filters = state
+                .scenes
+                .get(&handle(scene))
+                .and_then(|s| state.sources.get(&s.source))
+                .map(|s| s.filters.clone())
+                .unwrap_or_default();
+
+            state.remove_scene(handle(scene));
+            filters
+                .iter()
+                .filter_map(|name| state.source_by_name(name))
+                .collect::<Vec<_>>()
+        };
+
+        for filter in filters {
+            self.obs_source_release(ptr_of(filter));
+        }
+    }
+
+    pub(crate) unsafe fn obs_scene_get_source(&self, scene: *mut obs_scene_t) -> *mut obs_source_t {
+        self.state()
+            .scenes
+            .get(&handle(scene))
+            .map(|s| ptr_of(s.source))
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_scene_add(
+        &self,
+        scene: *mut obs_scene_t,
+        source: *mut obs_source_t,
+    ) -> *mut obs_sceneitem_t {
+        let (scene_source, item) = {
+            let mut state = self.state();
+            let Some(scene_source) = state.scenes.get(&handle(scene)).map(|s| s.source) else {
+                return ptr::null_mut();
+            };
+
+            if !state.sources.contains_key(&handle(source)) {
+                return ptr::null_mut();
+            }
+
+            let item = state.next_handle();
+            state
+                .scene_items
+                .insert(item, FakeSceneItemEntry::new(handle(scene), handle(source)));
+            if let Some(scene) = state.scenes.get_mut(&handle(scene)) {
+                scene.items.push(item);
+            }
+
+            (scene_source, item)
+        };
+
+        let item = ptr_of::<obs_sceneitem_t>(item);
+        self.emit(
+            scene_source,
+            "item_add",
+            FakeCalldata::new().set_ptr("item", item),
+        );
+        item
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_remove(&self, item: *mut obs_sceneitem_t) {
+        let scene_source = {
+            let mut state = self.state();
+            let Some(entry) = state.scene_items.remove(&handle(item)) else {
+                return;
+            };
+
+            if let Some(group) = state.group_scene_of(entry.source) {
+                state.remove_scene(group);
+            }
+
+            state.scenes.get_mut(&entry.scene).map(|s| {
+                s.items.retain(|i| *i != handle(item));
+                s.source
+            })
+        };
+
+        if let Some(scene_source) = scene_source {
+            self.emit(
+                scene_source,
+                "item_remove",
+                FakeCalldata::new().set_ptr("item", item),
+            );
+        }
+    }
+
+    pub(crate) unsafe fn obs_scene_from_source(
+        &self,
+        source: *mut obs_source_t,
+    ) -> *mut obs_scene_t {
+        let state = self.state();
+        if state.sources.get(&handle(source)).map(|s| s.id.as_str()) != Some(SCENE_SOURCE_ID) {
+            return ptr::null_mut();
+        }
+
+        state
+            .scenes
+            .iter()
+            .find(|(_, s)| s.source == handle(source))
+            .map(|(h, _)| ptr_of(*h))
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_group_or_scene_from_source(
+        &self,
+        source: *mut obs_source_t,
+    ) -> *mut obs_scene_t {
+        self.state()
+            .scenes
+            .iter()
+            .find(|(_, s)| s.source == handle(source))
+            .map(|(h, _)| ptr_of(*h))
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_scene_enum_items(
+        &self,
+        scene: *mut obs_scene_t,
+        callback: Option<
+            unsafe extern "C" fn(
+                scene: *mut obs_scene_t,
+                item: *mut obs_sceneitem_t,
+                param: *mut c_void,
+            ) -> bool,
+        >,
+        param: *mut c_void,
+    ) {
+        let Some(callback) = callback else {
+            return;
+        };
+
+        let items = self
+            .state()
+            .scenes
+            .get(&handle(scene))
+            .map(|s| s.items.clone())
+            .unwrap_or_default();
+
+        for item in items {
+            if !callback(scene, ptr_of(item), param) {
+                break;
+            }
+        }
+    }
+
+    pub(crate) unsafe fn obs_scene_add_group2(
+        &self,
+        scene: *mut obs_scene_t,
+        name: *const c_char,
+        signal: bool,
+    ) -> *mut obs_sceneitem_t {
+        let (scene_source, item) = {
+            let mut state = self.state();
+            let Some(scene_source) = state.scenes.get(&handle(scene)).map(|s| s.source) else {
+                return ptr::null_mut();
+            };
+
+            let name = to_string(name);
+            let group_source = state.next_handle();
+            state.sources.insert(
+                group_source,
+                FakeSource {
+                    id: GROUP_SOURCE_ID.to_string(),
+                    name: name.clone(),
+                    settings: FakeSettings::new(),
+                    filters: Vec::new(),
+                },
+            );
+
+            let group_scene = state.next_handle();
+            state.scenes.insert(
+                group_scene,
+                FakeSceneEntry {
+                    name,
+                    source: group_source,
+                    items: Vec::new(),
+                },
+            );
+
+            let item = state.next_handle();
+            state
+                .scene_items
+                .insert(item, FakeSceneItemEntry::new(handle(scene), group_source));
+            if let Some(scene) = state.scenes.get_mut(&handle(scene)) {
+                scene.items.push(item);
+            }
+
+            (scene_source, item)
+        };
+
+        let item = ptr_of::<obs_sceneitem_t>(item);
+        if signal {
+            self.emit(
+                scene_source,
+                "item_add",
+                FakeCalldata::new().set_ptr("item", item),
+            );
+        }
+        item
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_is_group(&self, item: *mut obs_sceneitem_t) -> bool {
+        let state = self.state();
+        state
+            .scene_items
+            .get(&handle(item))
+            .and_then(|entry| state.group_scene_of(entry.source))
+            .is_some()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_group_get_scene(
+        &self,
+        group: *mut obs_sceneitem_t,
+    ) -> *mut obs_scene_t {
+        let state = self.state();
+        state
+            .scene_items
+            .get(&handle(group))
+            .and_then(|entry| state.group_scene_of(entry.source))
+            .map(ptr_of)
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_group_add_item(
+        &self,
+        group: *mut obs_sceneitem_t,
+        item: *mut obs_sceneitem_t,
+    ) {
+        let mut state = self.state();
+        let Some(group_entry) = state.scene_items.get(&handle(group)) else {
+            return;
+        };
+        let parent = group_entry.scene;
+        let Some(group_scene) = state.group_scene_of(group_entry.source) else {
+            return;
+        };
+
+        // Only items of the scene the group is in can be added to it
+        if state.scene_items.get(&handle(item)).map(|e| e.scene) != Some(parent) {
+            return;
+        }
+
+        state.move_item_to_scene(handle(item), group_scene, usize::MAX);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_group_remove_item(
+        &self,
+        group: *mut obs_sceneitem_t,
+        item: *mut obs_sceneitem_t,
+    ) {
+        let mut state = self.state();
+        let Some(group_entry) = state.scene_items.get(&handle(group)) else {
+            return;
+        };
+        let parent = group_entry.scene;
+        let Some(group_scene) = state.group_scene_of(group_entry.source) else {
+            return;
+        };
+
+        if state.scene_items.get(&handle(item)).map(|e| e.scene) != Some(group_scene) {
+            return;
+        }
+
+        // The item is put right above the group
+        let index = state
+            .scenes
+            .get(&parent)
+            .and_then(|s| s.items.iter().position(|i| *i == handle(group)))
+            .map(|i| i + 1)
+            .unwrap_or(usize::MAX);
+        state.move_item_to_scene(handle(item), parent, index);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_group_ungroup(&self, group: *mut obs_sceneitem_t) {
+        {
+            let mut state = self.state();
+            let Some(parent) = state.scene_items.get(&handle(group)).map(|e| e.scene) else {
+                return;
+            };
+            let Some(group_scene) = state
+                .scene_items
+                .get(&handle(group))
+                .and_then(|entry| state.group_scene_of(entry.source))
+            else {
+                return;
+            };
+
+            // Just like libobs, the items are recreated in the parent scene right above
+            // the group, the items in the group scene go away together with the group
+            let mut index = state
+                .scenes
+                .get(&parent)
+                .and_then(|s| s.items.iter().position(|i| *i == handle(group)))
+                .map(|i| i + 1)
+                .unwrap_or(usize::MAX);
+            let items = state.scenes[&group_scene].items.clone();
+            for item in items {
+                let Some(mut entry) = state.scene_items.get(&item).cloned() else {
+                    continue;
+                };
+                entry.scene = parent;
+
+                let copy = state.next_handle();
+                state.scene_items.insert(copy, entry);
+                if let Some(parent) = state.scenes.get_mut(&parent) {
+                    index = index.min(parent.items.len());
+                    parent.items.insert(index, copy);
+                    index += 1;
+                }
+            }
+        }
+
+        self.obs_sceneitem_remove(group);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_source(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> *mut obs_source_t {
+        self.item_value(item, |entry| ptr_of(entry.source))
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_release(&self, _item: *mut obs_sceneitem_t) {}
+
+    pub(crate) unsafe fn obs_sceneitem_get_pos(&self, item: *mut obs_sceneitem_t, pos: *mut vec2) {
+        if let Some(entry) = self.state().scene_items.get(&handle(item)) {
+            *pos = entry.position.into();
+        }
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_pos(
+        &self,
+        item: *mut obs_sceneitem_t,
+        pos: *const vec2,
+    ) {
+        self.set_item_transform(item, |entry| entry.position = Vec2::from(*pos));
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_scale(
+        &self,
+        item: *mut obs_sceneitem_t,
+        scale: *mut vec2,
+    ) {
+        if let Some(entry) = self.state().scene_items.get(&handle(item)) {
+            *scale = entry.scale.into();
+        }
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_scale(
+        &self,
+        item: *mut obs_sceneitem_t,
+        scale: *const vec2,
+    ) {
+        self.set_item_transform(item, |entry| entry.scale = Vec2::from(*scale));
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_addref(&self, _item: *mut obs_sceneitem_t) {}
+
+    pub(crate) unsafe fn obs_sceneitem_get_rot(&self, item: *mut obs_sceneitem_t) -> f32 {
+        self.item_value(item, |entry| entry.rotation)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_rot(&self, item: *mut obs_sceneitem_t, rot_deg: f32) {
+        self.set_item_transform(item, |entry| entry.rotation = rot_deg);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_alignment(&self, item: *mut obs_sceneitem_t) -> u32 {
+        self.item_value(item, |entry| entry.alignment.bits())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_alignment(
+        &self,
+        item: *mut obs_sceneitem_t,
+        alignment: u32,
+    ) {
+        self.set_item_transform(item, |entry| {
+            entry.alignment = ObsAlignment::from_bits(alignment)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_bounds_type(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> obs_bounds_type {
+        self.item_value(item, |entry| entry.bounds_type as obs_bounds_type)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_bounds_type(
+        &self,
+        item: *mut obs_sceneitem_t,
+        type_: obs_bounds_type,
+    ) {
+        self.set_item_transform(item, |entry| {
+            entry.bounds_type = ObsBoundsType::from_i64(type_ as i64).unwrap_or(ObsBoundsType::None)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_bounds_alignment(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> u32 {
+        self.item_value(item, |entry| entry.bounds_alignment.bits())
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_bounds_alignment(
+        &self,
+        item: *mut obs_sceneitem_t,
+        alignment: u32,
+    ) {
+        self.set_item_transform(item, |entry| {
+            entry.bounds_alignment = ObsAlignment::from_bits(alignment)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_bounds(
+        &self,
+        item: *mut obs_sceneitem_t,
+        bounds: *mut vec2,
+    ) {
+        if let Some(value) = self.item_value(item, |entry| entry.bounds) {
+            *bounds = value.into();
+        }
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_bounds(
+        &self,
+        item: *mut obs_sceneitem_t,
+        bounds: *const vec2,
+    ) {
+        self.set_item_transform(item, |entry| entry.bounds = Vec2::from(*bounds));
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_crop(
+        &self,
+        item: *mut obs_sceneitem_t,
+        crop: *mut obs_sceneitem_crop,
+    ) {
+        if let Some(value) = self.item_value(item, |entry| entry.crop) {
+            *crop = value.into();
+        }
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_crop(
+        &self,
+        item: *mut obs_sceneitem_t,
+        crop: *const obs_sceneitem_crop,
+    ) {
+        self.set_item_transform(item, |entry| entry.crop = ObsSceneItemCrop::from(*crop));
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_scale_filter(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> obs_scale_type {
+        self.item_value(item, |entry| entry.scale_filter as obs_scale_type)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_scale_filter(
+        &self,
+        item: *mut obs_sceneitem_t,
+        filter: obs_scale_type,
+    ) {
+        self.set_item_value(item, |entry| {
+            entry.scale_filter =
+                ObsScaleType::from_i64(filter as i64).unwrap_or(ObsScaleType::Disable)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_blending_method(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> obs_blending_method {
+        self.item_value(item, |entry| entry.blending_method as obs_blending_method)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_blending_method(
+        &self,
+        item: *mut obs_sceneitem_t,
+        method: obs_blending_method,
+    ) {
+        self.set_item_value(item, |entry| {
+            entry.blending_method =
+                ObsBlendingMethod::from_i64(method as i64).unwrap_or(ObsBlendingMethod::Default)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_blending_mode(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> obs_blending_type {
+        self.item_value(item, |entry| entry.blending_mode as obs_blending_type)
+            .unwrap_or_default()
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_blending_mode(
+        &self,
+        item: *mut obs_sceneitem_t,
+        type_: obs_blending_type,
+    ) {
+        self.set_item_value(item, |entry| {
+            entry.blending_mode =
+                ObsBlendingType::from_i64(type_ as i64).unwrap_or(ObsBlendingType::Normal)
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_info2(
+        &self,
+        item: *mut obs_sceneitem_t,
+        info: *mut obs_transform_info,
+    ) {
+        let Some(value) = self.item_value(item, |entry| obs_transform_info {
+            pos: entry.position.into(),
+            rot: entry.rotation,
+            scale: entry.scale.into(),
+            alignment: entry.alignment.bits(),
+            bounds_type: entry.bounds_type as obs_bounds_type,
+            bounds_alignment: entry.bounds_alignment.bits(),
+            bounds: entry.bounds.into(),
+            crop_to_bounds: entry.crop_to_bounds,
+        }) else {
+            return;
+        };
+
+        *info = value;
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_info2(
+        &self,
+        item: *mut obs_sceneitem_t,
+        info: *const obs_transform_info,
+    ) {
+        let info = &*info;
+        self.set_item_transform(item, |entry| {
+            entry.position = Vec2::from(info.pos);
+            entry.rotation = info.rot;
+            entry.scale = Vec2::from(info.scale);
+            entry.alignment = ObsAlignment::from_bits(info.alignment);
+            entry.bounds_type =
+                ObsBoundsType::from_i64(info.bounds_type as i64).unwrap_or(ObsBoundsType::None);
+            entry.bounds_alignment = ObsAlignment::from_bits(info.bounds_alignment);
+            entry.bounds = Vec2::from(info.bounds);
+            entry.crop_to_bounds = info.crop_to_bounds;
+        });
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_order(
+        &self,
+        item: *mut obs_sceneitem_t,
+        movement: obs_order_movement,
+    ) {
+        let Some(position) = self.item_order_position(item) else {
+            return;
+        };
+
+        let position = match ObsOrderMovement::from_i64(movement as i64) {
+            Some(ObsOrderMovement::MoveUp) => position + 1,
+            Some(ObsOrderMovement::MoveDown) => position.saturating_sub(1),
+            Some(ObsOrderMovement::MoveTop) => usize::MAX,
+            Some(ObsOrderMovement::MoveBottom) => 0,
+            None => return,
+        };
+
+        self.move_item(item, position);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_order_position(
+        &self,
+        item: *mut obs_sceneitem_t,
+        position: c_int,
+    ) {
+        self.move_item(item, position.max(0) as usize);
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_get_order_position(
+        &self,
+        item: *mut obs_sceneitem_t,
+    ) -> c_int {
+        self.item_order_position(item)
+            .map(|p| p as c_int)
+            .unwrap_or(0)
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_visible(&self, item: *mut obs_sceneitem_t) -> bool {
+        self.item_value(item, |entry| entry.visible)
+            .unwrap_or(false)
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_visible(
+        &self,
+        item: *mut obs_sceneitem_t,
+        visible: bool,
+    ) -> bool {
+        let scene_source = {
+            let mut state = self.state();
+            let Some(entry) = state.scene_items.get_mut(&handle(item)) else {
+                return false;
+            };
+
+            if entry.visible == visible {
+                return false;
+            }
+
+            entry.visible = visible;
+            let scene = entry.scene;
+            state.scenes.get(&scene).map(|s| s.source)
+        };
+
+        if let Some(scene_source) = scene_source {
+            self.emit(
+                scene_source,
+                "item_visible",
+                FakeCalldata::new()
+                    .set_ptr("item", item)
+                    .set_bool("visible", visible),
+            );
+        }
+        true
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_locked(&self, item: *mut obs_sceneitem_t) -> bool {
+        self.item_value(item, |entry| entry.locked).unwrap_or(false)
+    }
+
+    pub(crate) unsafe fn obs_sceneitem_set_locked(
+        &self,
+        item: *mut obs_sceneitem_t,
+        lock: bool,
+    ) -> bool {
+        let scene_source = {
+            let mut state = self.state();
+            let Some(entry) = state.scene_items.get_mut(&handle(item)) else {
+                return false;
+            };
+
+            if entry.locked == lock {
+                return false;
+            }
+
+            entry.locked = lock;
+            let scene = entry.scene;
+            state.scenes.get(&scene).map(|s| s.source)
+        };
+
+        if let Some(scene_source) = scene_source {
+            self.emit(
+                scene_source,
+                "item_locked",
+                FakeCalldata::new()
+                    .set_ptr("item", item)
+                    .set_bool("locked", lock),
+            );
+        }
+        true
+    }
+
+    fn item_value<T>(
+        &self,
+        item: *mut obs_sceneitem_t,
+        f: impl FnOnce(&FakeSceneItemEntry) -> T,
+    ) -> Option<T> {
+        self.state().scene_items.get(&handle(item)).map(f)
+    }
+
+    /// Changes an item without emitting `item_transform`, like libobs does for
+    /// properties that don't affect the transform.
+    fn set_item_value(&self, item: *mut obs_sceneitem_t, f: impl FnOnce(&mut FakeSceneItemEntry)) {
+        if let Some(entry) = self.state().scene_items.get_mut(&handle(item)) {
+            f(entry);
+        }
+    }
+
+    fn set_item_transform(
+        &self,
+        item: *mut obs_sceneitem_t,
+        f: impl FnOnce(&mut FakeSceneItemEntry),
+    ) {
+        let scene_source = {
+            let mut state = self.state();
+            let Some(entry) = state.scene_items.get_mut(&handle(item)) else {
+                return;
+            };
+
+            f(entry);
+            let scene = entry.scene;
+            state.scenes.get(&scene).map(|s| s.source)
+        };
+
+        if let Some(scene_source) = scene_source {
+            self.emit(
+                scene_source,
+                "item_transform",
+                FakeCalldata::new().set_ptr("item", item),
+            );
+        }
+    }
+
+    /// The index of the item in its scene, `0` being the bottom.
+    fn item_order_position(&self, item: *mut obs_sceneitem_t) -> Option<usize> {
+        let state = self.state();
+        let scene = state.scene_items.get(&handle(item))?.scene;
+        state
+            .scenes
+            .get(&scene)?
+            .items
+            .iter()
+            .position(|i| *i == handle(item))
+    }
+
+    /// Moves the item to the given index in its scene (clamped to the top) and
+    /// emits `reorder` if the order changed.
+    fn move_item(&self, item: *mut obs_sceneitem_t, position: usize) {
+        let scene_source = {
+            let mut state = self.state();
+            let Some(scene) = state.scene_items.get(&handle(item)).map(|e| e.scene) else {
+                return;
+            };
+            let Some(scene) = state.scenes.get_mut(&scene) else {
+                return;
+            };
+            let Some(current) = scene.items.iter().position(|i| *i == handle(item)) else {
+                return;
+            };
+
+            let position = position.min(scene.items.len() - 1);
+            if position == current {
+                return;
+            }
+
+            let item = scene.items.remove(current);
+            scene.items.insert(position, item);
+            scene.source
+        };
+
+        self.emit(scene_source, "reorder", FakeCalldata::new());
+    }
+}
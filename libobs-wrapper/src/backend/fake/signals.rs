@@ -0,0 +1,197 @@
+//! Signals and their parameters of the fake backend.
+
+use std::{
+    collections::HashMap,
+    ffi::{c_char, c_void, CString},
+    ptr,
+};
+
+use libobs::{calldata_t, signal_callback_t, signal_handler_t};
+
+use super::{handle, to_string, FakeConnection, FakeObsBackend};
+
+/// A signal that was emitted by the fake backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeSignal {
+    /// Name of the source, scene or output which emitted the signal.
+    pub object: String,
+    pub signal: String,
+}
+
+#[derive(Debug, Clone)]
+enum FakeCalldataValue {
+    Data(Vec<u8>),
+    String(CString),
+}
+
+/// Parameters of a signal that is emitted by the fake backend.
+/// This is the in-memory counterpart of libobs' `calldata_t`.
+#[derive(Debug, Clone, Default)]
+pub struct FakeCalldata {
+    values: HashMap<String, FakeCalldataValue>,
+}
+
+impl FakeCalldata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_int(self, name: &str, value: i64) -> Self {
+        self.set_data(name, value.to_ne_bytes().to_vec())
+    }
+
+    pub fn set_float(self, name: &str, value: f64) -> Self {
+        self.set_data(name, value.to_ne_bytes().to_vec())
+    }
+
+    pub fn set_bool(self, name: &str, value: bool) -> Self {
+        self.set_data(name, vec![value as u8])
+    }
+
+    pub fn set_ptr<T>(self, name: &str, value: *mut T) -> Self {
+        self.set_data(name, (value as usize).to_ne_bytes().to_vec())
+    }
+
+    pub fn set_string(mut self, name: &str, value: &str) -> Self {
+        let value = CString::new(value).unwrap_or_default();
+        self.values
+            .insert(name.to_string(), FakeCalldataValue::String(value));
+        self
+    }
+
+    fn set_data(mut self, name: &str, value: Vec<u8>) -> Self {
+        self.values
+            .insert(name.to_string(), FakeCalldataValue::Data(value));
+        self
+    }
+}
+
+impl FakeObsBackend {
+    /// The signals handlers are connected to on the source, scene or output with the given name.
+    pub fn connected_signals(&self, object: &str) -> Vec<String> {
+        let state = self.state();
+        let Some(handler) = state.handler_by_name(object) else {
+            return Vec::new();
+        };
+
+        state
+            .connections
+            .iter()
+            .filter(|c| c.handler == handler)
+            .map(|c| c.signal.clone())
+            .collect()
+    }
+
+    /// All signals that were emitted so far, in order.
+    pub fn emitted_signals(&self) -> Vec<FakeSignal> {
+        self.state().signals.clone()
+    }
+
+    pub fn clear_emitted_signals(&self) {
+        self.state().signals.clear();
+    }
+
+    /// Emits a signal on the source, scene or output with the given name,
+    /// the same way libobs would do it.
+    /// Returns `false` if there is no object with that name.
+    pub fn emit_signal(&self, object_name: &str, signal: &str, calldata: FakeCalldata) -> bool {
+        let handler = self.state().handler_by_name(object_name);
+        match handler {
+            Some(handler) => {
+                self.emit(handler, signal, calldata);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(super) fn emit(&self, handler: usize, signal: &str, mut calldata: FakeCalldata) {
+        let callbacks = {
+            let mut state = self.state();
+            let object = state.object_name(handler);
+            state.signals.push(FakeSignal {
+                object,
+                signal: signal.to_string(),
+            });
+
+            state
+                .connections
+                .iter()
+                .filter(|c| c.handler == handler && c.signal == signal)
+                .map(|c| (c.callback, c.data))
+                .collect::<Vec<_>>()
+        };
+
+        // The state must not be locked while calling the callbacks, as they
+        // are reading the calldata using this backend again.
+        let cd = &mut calldata as *mut FakeCalldata as *mut calldata_t;
+        for (callback, data) in callbacks {
+            if let Some(callback) = callback {
+                unsafe { callback(data as *mut c_void, cd) };
+            }
+        }
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn signal_handler_connect(
+        &self,
+        handler: *mut signal_handler_t,
+        signal: *const c_char,
+        callback: signal_callback_t,
+        data: *mut c_void,
+    ) {
+        self.state().connections.push(FakeConnection {
+            handler: handle(handler),
+            signal: to_string(signal),
+            callback,
+            data: data as usize,
+        });
+    }
+
+    pub(crate) unsafe fn signal_handler_disconnect(
+        &self,
+        handler: *mut signal_handler_t,
+        signal: *const c_char,
+        _callback: signal_callback_t,
+        data: *mut c_void,
+    ) {
+        let signal = to_string(signal);
+        self.state().connections.retain(|c| {
+            !(c.handler == handle(handler) && c.signal == signal && c.data == data as usize)
+        });
+    }
+
+    pub(crate) unsafe fn calldata_get_data(
+        &self,
+        data: *const calldata_t,
+        name: *const c_char,
+        out: *mut c_void,
+        size: usize,
+    ) -> bool {
+        let calldata = &*(data as *const FakeCalldata);
+        match calldata.values.get(&to_string(name)) {
+            Some(FakeCalldataValue::Data(value)) if value.len() == size => {
+                ptr::copy_nonoverlapping(value.as_ptr(), out as *mut u8, size);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) unsafe fn calldata_get_string(
+        &self,
+        data: *const calldata_t,
+        name: *const c_char,
+        str_: *mut *const c_char,
+    ) -> bool {
+        let calldata = &*(data as *const FakeCalldata);
+        match calldata.values.get(&to_string(name)) {
+            Some(FakeCalldataValue::String(value)) => {
+                *str_ = value.as_ptr();
+                true
+            }
+            _ => false,
+        }
+    }
+}
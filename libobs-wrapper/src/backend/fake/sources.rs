@@ -0,0 +1,667 @@
+//! Sources, filters and transitions of the fake backend.
+
+use std::{
+    ffi::{c_char, c_void},
+    ptr,
+};
+
+use libobs::{
+    obs_data_t, obs_source_enum_proc_t, obs_source_t, obs_transition_mode, signal_handler_t,
+};
+use serde_json::{json, Value};
+
+use super::{
+    enum_handles, fake_uuid, handle, merge, ptr_of, to_string, FakeCalldata, FakeObsBackend,
+    FakeSceneEntry, FakeSettings, FakeState, CUT_TRANSITION_ID, GROUP_SOURCE_ID, SCENE_SOURCE_ID,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeSource {
+    pub id: String,
+    pub name: String,
+    pub settings: FakeSettings,
+    /// Names of the filters that are attached to this source, in order.
+    pub filters: Vec<String>,
+}
+
+impl FakeState {
+    /// Whether the source is an input, as opposed to a scene, group, transition or filter.
+    fn is_input(&self, source: usize) -> bool {
+        let Some(entry) = self.sources.get(&source) else {
+            return false;
+        };
+
+        !self.scenes.values().any(|s| s.source == source)
+            && !self.transitions.contains_key(&source)
+            && !self
+                .sources
+                .values()
+                .any(|s| s.filters.contains(&entry.name))
+    }
+
+    pub(super) fn source_by_name(&self, name: &str) -> Option<usize> {
+        self.sources
+            .iter()
+            .find(|(_, s)| s.name == name)
+            .map(|(h, _)| *h)
+    }
+
+    /// Serializes the source like `obs_save_source` does, including its filters and,
+    /// for scenes and groups, their items.
+    fn save_source(&self, source: usize) -> Option<FakeSettings> {
+        let entry = self.sources.get(&source)?;
+        let mut settings = entry.settings.clone();
+        if let Some(scene) = self.scenes.values().find(|s| s.source == source) {
+            let items = scene
+                .items
+                .iter()
+                .filter_map(|item| self.save_item(*item))
+                .collect();
+            settings.insert("items".to_string(), Value::Array(items));
+        }
+
+        let filters = entry
+            .filters
+            .iter()
+            .filter_map(|name| self.source_by_name(name))
+            .filter_map(|filter| self.save_source(filter))
+            .map(Value::Object)
+            .collect();
+
+        let mut values = FakeSettings::new();
+        values.insert("id".to_string(), Value::from(entry.id.clone()));
+        values.insert("name".to_string(), Value::from(entry.name.clone()));
+        values.insert("settings".to_string(), Value::Object(settings));
+        values.insert("filters".to_string(), Value::Array(filters));
+        values.insert("hotkeys".to_string(), Value::Object(FakeSettings::new()));
+        Some(values)
+    }
+
+    /// Creates a source from data saved by [`FakeState::save_source`], like `obs_load_source`
+    /// does. Scene items are only added once `obs_source_load` is called.
+    fn load_source(&mut self, values: &FakeSettings) -> Option<usize> {
+        let id = values.get("id")?.as_str()?.to_string();
+        let name = values.get("name")?.as_str()?.to_string();
+        let settings = values
+            .get("settings")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+
+        let source = self.next_handle();
+        self.sources.insert(
+            source,
+            FakeSource {
+                id: id.clone(),
+                name: name.clone(),
+                settings,
+                filters: Vec::new(),
+            },
+        );
+
+        if id == SCENE_SOURCE_ID || id == GROUP_SOURCE_ID {
+            let scene = self.next_handle();
+            self.scenes.insert(
+                scene,
+                FakeSceneEntry {
+                    name,
+                    source,
+                    items: Vec::new(),
+                },
+            );
+        }
+
+        // The reference of a loaded filter is owned by the source it is attached to
+        let filters = values
+            .get("filters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for filter in filters.iter().filter_map(Value::as_object) {
+            if let Some(filter) = self.load_source(filter) {
+                let filter_name = self.source_name(filter);
+                if let Some(source) = self.sources.get_mut(&source) {
+                    source.filters.push(filter_name);
+                }
+            }
+        }
+
+        Some(source)
+    }
+}
+
+impl FakeObsBackend {
+    /// All sources which are currently alive. Sources backing a scene are not included,
+    /// use [`FakeObsBackend::scenes`] for those.
+    pub fn sources(&self) -> Vec<FakeSource> {
+        self.state()
+            .sources
+            .values()
+            .filter(|s| s.id != SCENE_SOURCE_ID && s.id != GROUP_SOURCE_ID)
+            .cloned()
+            .collect()
+    }
+
+    pub fn source(&self, name: &str) -> Option<FakeSource> {
+        self.sources().into_iter().find(|s| s.name == name)
+    }
+
+    /// Returns the name of the source (or scene) that is set to the given output channel.
+    pub fn channel_source(&self, channel: u32) -> Option<String> {
+        let state = self.state();
+        state
+            .channels
+            .get(&channel)
+            .map(|source| state.source_name(*source))
+    }
+
+    /// How often the source (or scene) with the given name is marked as showing, e.g. by
+    /// displays rendering it.
+    pub fn showing_count(&self, source: &str) -> usize {
+        let state = self.state();
+        state
+            .source_by_name(source)
+            .and_then(|source| state.showing.get(&source).copied())
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the source the transition shows, or transitions to if one is running.
+    pub fn transition_source(&self, transition: &str) -> Option<String> {
+        let state = self.state();
+        let handle = state.handler_by_name(transition)?;
+        let transition = state.transitions.get(&handle)?;
+        transition
+            .target
+            .or(transition.active)
+            .map(|source| state.source_name(source))
+    }
+
+    /// Whether the transition with the given name is currently running.
+    pub fn is_transitioning(&self, transition: &str) -> bool {
+        let state = self.state();
+        state
+            .handler_by_name(transition)
+            .and_then(|handle| state.transitions.get(&handle))
+            .is_some_and(|t| t.target.is_some())
+    }
+
+    /// Creates a source outside of the wrapper, like a plugin would. Sources with the
+    /// `scene` id are backed by a scene. The backend holds the only reference to the
+    /// source until [`FakeObsBackend::release_source`] is called.
+    pub fn create_source(&self, id: &str, name: &str) {
+        let values = json!({ "id": id, "name": name });
+        if let Value::Object(values) = values {
+            self.state().load_source(&values);
+        }
+    }
+
+    /// Releases a reference to the source with the given name, like `obs_source_release`.
+    /// Returns `false` if there is no source with that name.
+    pub fn release_source(&self, name: &str) -> bool {
+        let Some(source) = self.state().source_by_name(name) else {
+            return false;
+        };
+
+        unsafe { self.obs_source_release(ptr_of(source)) };
+        true
+    }
+}
+
+impl FakeObsBackend {
+    pub(crate) unsafe fn obs_source_create(
+        &self,
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        _hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_source_t {
+        let mut state = self.state();
+        let source = FakeSource {
+            id: to_string(id),
+            name: to_string(name),
+            settings: state.data_values(settings),
+            filters: Vec::new(),
+        };
+
+        let source_handle = state.next_handle();
+        state.sources.insert(source_handle, source);
+        ptr_of(source_handle)
+    }
+
+    pub(crate) unsafe fn obs_source_release(&self, source: *mut obs_source_t) {
+        let source_handle = handle(source);
+        {
+            let mut state = self.state();
+            if !state.sources.contains_key(&source_handle) {
+                return;
+            }
+
+            if let Some(refs) = state.source_refs.get_mut(&source_handle) {
+                *refs -= 1;
+                if *refs == 0 {
+                    state.source_refs.remove(&source_handle);
+                }
+                return;
+            }
+
+            // Groups are owned by the item they are shown in and removed together with it
+            let scene = state
+                .scenes
+                .iter()
+                .find(|(_, s)| s.source == source_handle)
+                .map(|(h, _)| *h)
+                .filter(|_| state.group_scene_of(source_handle).is_none());
+            if let Some(scene) = scene {
+                drop(state);
+                self.obs_scene_release(ptr_of(scene));
+                return;
+            }
+        }
+
+        self.emit(source_handle, "destroy", FakeCalldata::new());
+
+        let mut state = self.state();
+        let filters = state
+            .sources
+            .remove(&source_handle)
+            .map(|s| s.filters)
+            .unwrap_or_default();
+        state.channels.retain(|_, s| *s != source_handle);
+        state.connections.retain(|c| c.handler != source_handle);
+        state
+            .audio_capture_connections
+            .retain(|c| c.source != source_handle);
+        state.source_videos.remove(&source_handle);
+        state.source_audio.remove(&source_handle);
+        state.source_media.remove(&source_handle);
+        state.transitions.remove(&source_handle);
+        for volmeter in state.volmeters.values_mut() {
+            volmeter.source = volmeter.source.filter(|s| *s != source_handle);
+        }
+        for fader in state.faders.values_mut() {
+            fader.source = fader.source.filter(|s| *s != source_handle);
+        }
+        let filters = filters
+            .iter()
+            .filter_map(|name| state.source_by_name(name))
+            .collect::<Vec<_>>();
+        drop(state);
+
+        // The source held a reference to each of its filters
+        for filter in filters {
+            self.obs_source_release(ptr_of(filter));
+        }
+    }
+
+    pub(crate) unsafe fn obs_source_get_ref(&self, source: *mut obs_source_t) -> *mut obs_source_t {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return ptr::null_mut();
+        }
+
+        *state.source_refs.entry(handle(source)).or_default() += 1;
+        source
+    }
+
+    pub(crate) unsafe fn obs_source_get_id(&self, source: *mut obs_source_t) -> *const c_char {
+        let mut state = self.state();
+        let Some(id) = state.sources.get(&handle(source)).map(|s| s.id.clone()) else {
+            return ptr::null();
+        };
+
+        state.store_string(handle(source), "id", &id)
+    }
+
+    pub(crate) unsafe fn obs_source_get_name(&self, source: *mut obs_source_t) -> *const c_char {
+        let mut state = self.state();
+        let Some(name) = state.sources.get(&handle(source)).map(|s| s.name.clone()) else {
+            return ptr::null();
+        };
+
+        state.store_string(handle(source), "name", &name)
+    }
+
+    pub(crate) unsafe fn obs_source_get_uuid(&self, source: *mut obs_source_t) -> *const c_char {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(source)) {
+            return ptr::null();
+        }
+
+        state.store_string(handle(source), "uuid", &fake_uuid(handle(source)))
+    }
+
+    pub(crate) unsafe fn obs_get_source_by_name(&self, name: *const c_char) -> *mut obs_source_t {
+        let mut state = self.state();
+        let Some(source) = state.source_by_name(&to_string(name)) else {
+            return ptr::null_mut();
+        };
+
+        *state.source_refs.entry(source).or_default() += 1;
+        ptr_of(source)
+    }
+
+    pub(crate) unsafe fn obs_get_source_by_uuid(&self, uuid: *const c_char) -> *mut obs_source_t {
+        let uuid = to_string(uuid);
+        let mut state = self.state();
+        let Some(source) = state
+            .sources
+            .keys()
+            .copied()
+            .find(|h| fake_uuid(*h) == uuid)
+        else {
+            return ptr::null_mut();
+        };
+
+        *state.source_refs.entry(source).or_default() += 1;
+        ptr_of(source)
+    }
+
+    pub(crate) unsafe fn obs_enum_sources(
+        &self,
+        enum_proc: Option<unsafe extern "C" fn(*mut c_void, *mut obs_source_t) -> bool>,
+        param: *mut c_void,
+    ) {
+        let sources = {
+            let state = self.state();
+            state
+                .sources
+                .keys()
+                .copied()
+                .filter(|h| state.is_input(*h))
+                .collect::<Vec<_>>()
+        };
+
+        enum_handles(sources, enum_proc, param);
+    }
+
+    pub(crate) unsafe fn obs_enum_scenes(
+        &self,
+        enum_proc: Option<unsafe extern "C" fn(*mut c_void, *mut obs_source_t) -> bool>,
+        param: *mut c_void,
+    ) {
+        let scenes = self
+            .state()
+            .scenes
+            .values()
+            .map(|s| s.source)
+            .collect::<Vec<_>>();
+
+        enum_handles(scenes, enum_proc, param);
+    }
+
+    pub(crate) unsafe fn obs_source_update(
+        &self,
+        source: *mut obs_source_t,
+        settings: *mut obs_data_t,
+    ) {
+        {
+            let mut state = self.state();
+            let values = state.data_values(settings);
+            let Some(source) = state.sources.get_mut(&handle(source)) else {
+                return;
+            };
+
+            merge(&mut source.settings, values);
+        }
+
+        self.emit(handle(source), "update", FakeCalldata::new());
+    }
+
+    pub(crate) unsafe fn obs_source_reset_settings(
+        &self,
+        source: *mut obs_source_t,
+        settings: *mut obs_data_t,
+    ) {
+        {
+            let mut state = self.state();
+            let values = state.data_values(settings);
+            let Some(source) = state.sources.get_mut(&handle(source)) else {
+                return;
+            };
+
+            source.settings = values;
+        }
+
+        self.emit(handle(source), "update", FakeCalldata::new());
+    }
+
+    pub(crate) unsafe fn obs_source_get_settings(
+        &self,
+        source: *mut obs_source_t,
+    ) -> *mut obs_data_t {
+        let mut state = self.state();
+        let Some(values) = state
+            .sources
+            .get(&handle(source))
+            .map(|s| s.settings.clone())
+        else {
+            return ptr::null_mut();
+        };
+
+        ptr_of(state.create_data(values))
+    }
+
+    pub(crate) unsafe fn obs_source_get_signal_handler(
+        &self,
+        source: *mut obs_source_t,
+    ) -> *mut signal_handler_t {
+        ptr_of(handle(source))
+    }
+
+    pub(crate) unsafe fn obs_source_inc_showing(&self, source: *mut obs_source_t) {
+        let mut state = self.state();
+        if state.sources.contains_key(&handle(source)) {
+            *state.showing.entry(handle(source)).or_default() += 1;
+        }
+    }
+
+    pub(crate) unsafe fn obs_source_dec_showing(&self, source: *mut obs_source_t) {
+        let mut state = self.state();
+        if let Some(count) = state.showing.get_mut(&handle(source)) {
+            *count -= 1;
+            if *count == 0 {
+                state.showing.remove(&handle(source));
+            }
+        }
+    }
+
+    pub(crate) unsafe fn obs_source_filter_add(
+        &self,
+        source: *mut obs_source_t,
+        filter: *mut obs_source_t,
+    ) {
+        {
+            let mut state = self.state();
+            let filter_name = state.source_name(handle(filter));
+            let Some(source) = state.sources.get_mut(&handle(source)) else {
+                return;
+            };
+
+            source.filters.push(filter_name);
+            *state.source_refs.entry(handle(filter)).or_default() += 1;
+        }
+
+        self.emit(
+            handle(source),
+            "filter_add",
+            FakeCalldata::new().set_ptr("filter", filter),
+        );
+    }
+
+    pub(crate) unsafe fn obs_source_filter_remove(
+        &self,
+        source: *mut obs_source_t,
+        filter: *mut obs_source_t,
+    ) {
+        {
+            let mut state = self.state();
+            let filter_name = state.source_name(handle(filter));
+            let Some(source) = state.sources.get_mut(&handle(source)) else {
+                return;
+            };
+
+            let count = source.filters.len();
+            source.filters.retain(|f| *f != filter_name);
+            if source.filters.len() == count {
+                return;
+            }
+        }
+
+        self.emit(
+            handle(source),
+            "filter_remove",
+            FakeCalldata::new().set_ptr("filter", filter),
+        );
+        self.obs_source_release(filter);
+    }
+
+    pub(crate) unsafe fn obs_source_enum_filters(
+        &self,
+        source: *mut obs_source_t,
+        callback: obs_source_enum_proc_t,
+        param: *mut c_void,
+    ) {
+        let filters = {
+            let state = self.state();
+            let Some(entry) = state.sources.get(&handle(source)) else {
+                return;
+            };
+
+            entry
+                .filters
+                .iter()
+                .filter_map(|name| state.source_by_name(name))
+                .collect::<Vec<_>>()
+        };
+
+        let Some(callback) = callback else {
+            return;
+        };
+        for filter in filters {
+            callback(source, ptr_of(filter), param);
+        }
+    }
+
+    pub(crate) unsafe fn obs_save_source(&self, source: *mut obs_source_t) -> *mut obs_data_t {
+        let data = {
+            let mut state = self.state();
+            let Some(values) = state.save_source(handle(source)) else {
+                return ptr::null_mut();
+            };
+
+            state.create_data(values)
+        };
+
+        self.emit(handle(source), "save", FakeCalldata::new());
+        ptr_of(data)
+    }
+
+    pub(crate) unsafe fn obs_load_source(&self, data: *mut obs_data_t) -> *mut obs_source_t {
+        let mut state = self.state();
+        let values = state.data_values(data);
+        state
+            .load_source(&values)
+            .map(ptr_of)
+            .unwrap_or(ptr::null_mut())
+    }
+
+    pub(crate) unsafe fn obs_source_load(&self, source: *mut obs_source_t) {
+        self.state().load_items(handle(source));
+        self.emit(handle(source), "load", FakeCalldata::new());
+    }
+
+    pub(crate) unsafe fn obs_transition_set(
+        &self,
+        transition: *mut obs_source_t,
+        source: *mut obs_source_t,
+    ) {
+        let mut state = self.state();
+        if !state.sources.contains_key(&handle(transition)) {
+            return;
+        }
+
+        let transition = state.transitions.entry(handle(transition)).or_default();
+        transition.active = (!source.is_null()).then(|| handle(source));
+        transition.target = None;
+        transition.generation += 1;
+    }
+
+    pub(crate) unsafe fn obs_transition_start(
+        &self,
+        transition: *mut obs_source_t,
+        _mode: obs_transition_mode,
+        duration_ms: u32,
+        dest: *mut obs_source_t,
+    ) -> bool {
+        let transition_handle = handle(transition);
+        let (generation, is_cut) = {
+            let mut state = self.state();
+            let Some(id) = state.sources.get(&transition_handle).map(|s| s.id.clone()) else {
+                return false;
+            };
+            if !state.sources.contains_key(&handle(dest)) {
+                return false;
+            }
+
+            let transition = state.transitions.entry(transition_handle).or_default();
+            transition.target = Some(handle(dest));
+            transition.generation += 1;
+            (transition.generation, id == CUT_TRANSITION_ID)
+        };
+
+        self.emit(transition_handle, "transition_start", FakeCalldata::new());
+        if is_cut || duration_ms == 0 {
+            self.finish_transition(transition_handle, generation);
+            return true;
+        }
+
+        // libobs ends the transition on the video thread once the duration has passed
+        let backend = self.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(duration_ms as u64));
+            backend.finish_transition(transition_handle, generation);
+        });
+        true
+    }
+
+    pub(crate) unsafe fn obs_transition_force_stop(&self, transition: *mut obs_source_t) {
+        let generation = self
+            .state()
+            .transitions
+            .get(&handle(transition))
+            .map(|t| t.generation);
+
+        if let Some(generation) = generation {
+            self.finish_transition(handle(transition), generation);
+        }
+    }
+
+    pub(crate) unsafe fn obs_transition_get_time(&self, transition: *mut obs_source_t) -> f32 {
+        // Running transitions are reported halfway through, finished ones at the end
+        match self.state().transitions.get(&handle(transition)) {
+            Some(t) if t.target.is_some() => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Switches the transition to its target and emits the stop signals, unless a newer
+    /// transition was started in the meantime.
+    fn finish_transition(&self, transition: usize, generation: u64) {
+        {
+            let mut state = self.state();
+            let Some(entry) = state.transitions.get_mut(&transition) else {
+                return;
+            };
+            if entry.generation != generation {
+                return;
+            }
+
+            if let Some(target) = entry.target.take() {
+                entry.active = Some(target);
+            }
+        }
+
+        self.emit(transition, "transition_video_stop", FakeCalldata::new());
+        self.emit(transition, "transition_stop", FakeCalldata::new());
+    }
+}
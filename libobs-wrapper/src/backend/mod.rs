@@ -0,0 +1,483 @@
+//! Backend abstraction over the libobs functions used by this crate.
+//!
+//! Every call the wrapper makes for its core objects (startup / shutdown, `obs_data`,
+//! sources, scenes, outputs, encoders and signals) goes through the [`ObsBackend`]
+//! trait instead of calling `libobs` directly. By default [`LibObsBackend`] is used,
+//! which just forwards every call to libobs.
+//!
+//! With the `fake-backend` feature enabled, the [`fake::FakeObsBackend`] can be passed to
+//! [`StartupInfo::set_backend`](crate::utils::StartupInfo::set_backend). It never calls
+//! into libobs and instead records the created objects, their settings and the emitted
+//! signals in memory, so logic built on top of `ObsContext` can be tested without an
+//! OBS installation or a GPU.
+//!
+//! Note that only the functions listed in [`ObsBackend`] are routed through the backend.
+//! Displays and other less common APIs still talk to libobs directly and are not
+//! available when using the fake backend.
+
+#[cfg(feature = "fake-backend")]
+pub mod fake;
+
+use std::{
+    ffi::{c_char, c_int, c_long, c_void},
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
+
+use libobs::{
+    audio_convert_info, audio_output, audio_output_callback_t, calldata_t, gs_blend_type,
+    gs_color_format, gs_stagesurf_t, gs_texrender_t, gs_texture_t, gs_zstencil_format, log_handler_t,
+    obs_audio_info, obs_audio_info2, obs_blending_method, obs_blending_type, obs_bounds_type,
+    obs_button_type, obs_combo_format, obs_combo_type, obs_editable_list_type, obs_number_type,
+    obs_path_type, obs_properties_t, obs_property_t, obs_property_type, obs_text_info_type,
+    obs_text_type,
+    obs_data_array_t, obs_data_t, obs_encoder_t, obs_encoder_type, obs_module_failure_info, obs_order_movement, obs_output_t,
+    obs_scale_type, obs_scene_t, obs_sceneitem_crop, obs_sceneitem_t, obs_source_audio_capture_t,
+    obs_source_enum_proc_t, obs_source_t, obs_transform_info, obs_transition_mode, obs_monitoring_type, obs_media_state, obs_fader_t, obs_fader_type, obs_peak_meter_type, obs_volmeter_t, obs_volmeter_updated_t, obs_video_info, profiler_name_store_t, signal_callback_t, signal_handler_t, vec2,
+    vec4, video_data, video_output, video_scale_info,
+};
+
+use lazy_static::lazy_static;
+
+use crate::utils::VaList;
+
+lazy_static! {
+    /// The backend that is used by the currently running OBS context.
+    /// There can only be one OBS context at a time, so this is global as well.
+    static ref BACKEND: RwLock<Arc<dyn ObsBackend>> = RwLock::new(Arc::new(LibObsBackend));
+}
+
+/// Returns the backend of the currently running OBS context.
+pub(crate) fn backend() -> Arc<dyn ObsBackend> {
+    match BACKEND.read() {
+        Ok(backend) => backend.clone(),
+        Err(e) => e.into_inner().clone(),
+    }
+}
+
+/// Sets the backend that is used for all following libobs calls.
+pub(crate) fn set_backend(new_backend: Arc<dyn ObsBackend>) {
+    match BACKEND.write() {
+        Ok(mut backend) => *backend = new_backend,
+        Err(e) => *e.into_inner() = new_backend,
+    }
+}
+
+macro_rules! define_backend {
+    ($(
+        $(#[$meta:meta])*
+        fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)?;
+    )*) => {
+        /// The set of libobs functions the wrapper calls for its core objects.
+        ///
+        /// Every function has the same name and signature as its libobs counterpart,
+        /// so refer to the [libobs documentation](https://docs.obsproject.com/) for
+        /// their behavior. All functions are called on the OBS thread.
+        ///
+        /// # Safety
+        /// Implementors must uphold the same contracts as libobs does: pointers that are
+        /// returned by a create function must stay valid until they are released again.
+        pub trait ObsBackend: Debug + Send + Sync {
+            $(
+                $(#[$meta])*
+                #[allow(clippy::missing_safety_doc)]
+                unsafe fn $name(&self, $($arg: $arg_ty),*) $(-> $ret)?;
+            )*
+        }
+
+        /// The default backend which forwards every call to libobs.
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct LibObsBackend;
+
+        impl ObsBackend for LibObsBackend {
+            $(
+                $(#[$meta])*
+                unsafe fn $name(&self, $($arg: $arg_ty),*) $(-> $ret)? {
+                    libobs::$name($($arg),*)
+                }
+            )*
+        }
+
+        /// The fake backend implements every function as an inherent method,
+        /// split into modules by domain.
+        #[cfg(feature = "fake-backend")]
+        impl ObsBackend for fake::FakeObsBackend {
+            $(
+                $(#[$meta])*
+                unsafe fn $name(&self, $($arg: $arg_ty),*) $(-> $ret)? {
+                    fake::FakeObsBackend::$name(self, $($arg),*)
+                }
+            )*
+        }
+    };
+}
+
+define_backend! {
+    // Core
+    #[cfg(windows)]
+    fn obs_init_win32_crash_handler();
+    fn base_set_crash_handler(
+        handler: Option<unsafe extern "C" fn(*const c_char, VaList, *mut c_void)>,
+        param: *mut c_void,
+    );
+    fn base_set_log_handler(
+        handler: log_handler_t,
+        param: *mut c_void,
+    );
+    fn bnum_allocs() -> c_long;
+    fn obs_startup(
+        locale: *const c_char,
+        module_config_path: *const c_char,
+        store: *mut profiler_name_store_t,
+    ) -> bool;
+    fn obs_shutdown();
+    fn obs_get_version_string() -> *const c_char;
+    fn obs_add_data_path(path: *const c_char);
+    fn obs_remove_data_path(path: *const c_char) -> bool;
+    fn obs_add_module_path(bin: *const c_char, data: *const c_char);
+    fn obs_load_all_modules2(mfi: *mut obs_module_failure_info);
+    fn obs_log_loaded_modules();
+    fn obs_post_load_modules();
+    fn obs_reset_audio2(oai: *const obs_audio_info2) -> bool;
+    fn obs_reset_video(ovi: *mut obs_video_info) -> c_int;
+    fn obs_get_video() -> *mut video_output;
+    fn obs_get_video_info(ovi: *mut obs_video_info) -> bool;
+    fn obs_get_audio() -> *mut audio_output;
+    fn obs_get_audio_info(oai: *mut obs_audio_info) -> bool;
+    fn obs_set_output_source(channel: u32, source: *mut obs_source_t);
+    fn obs_get_output_source(channel: u32) -> *mut obs_source_t;
+    fn obs_enter_graphics();
+    fn obs_leave_graphics();
+    fn obs_render_main_texture();
+    fn obs_get_active_fps() -> f64;
+    fn obs_get_average_frame_time_ns() -> u64;
+    fn obs_get_total_frames() -> u32;
+    fn obs_get_lagged_frames() -> u32;
+
+    // Graphics
+    fn gs_texrender_create(format: gs_color_format, zsformat: gs_zstencil_format) -> *mut gs_texrender_t;
+    fn gs_texrender_destroy(texrender: *mut gs_texrender_t);
+    fn gs_texrender_begin(texrender: *mut gs_texrender_t, cx: u32, cy: u32) -> bool;
+    fn gs_texrender_end(texrender: *mut gs_texrender_t);
+    fn gs_texrender_get_texture(texrender: *const gs_texrender_t) -> *mut gs_texture_t;
+    fn gs_clear(clear_flags: u32, color: *const vec4, depth: f32, stencil: u8);
+    fn gs_ortho(left: f32, right: f32, top: f32, bottom: f32, znear: f32, zfar: f32);
+    fn gs_blend_state_push();
+    fn gs_blend_state_pop();
+    fn gs_blend_function(src: gs_blend_type, dest: gs_blend_type);
+    fn gs_stagesurface_create(width: u32, height: u32, color_format: gs_color_format) -> *mut gs_stagesurf_t;
+    fn gs_stagesurface_destroy(stagesurf: *mut gs_stagesurf_t);
+    fn gs_stage_texture(dst: *mut gs_stagesurf_t, src: *mut gs_texture_t);
+    fn gs_stagesurface_map(stagesurf: *mut gs_stagesurf_t, data: *mut *mut u8, linesize: *mut u32) -> bool;
+    fn gs_stagesurface_unmap(stagesurf: *mut gs_stagesurf_t);
+
+    // Raw media
+    fn obs_add_raw_video_callback(
+        conversion: *const video_scale_info,
+        callback: Option<unsafe extern "C" fn(param: *mut c_void, frame: *mut video_data)>,
+        param: *mut c_void,
+    );
+    fn obs_remove_raw_video_callback(
+        callback: Option<unsafe extern "C" fn(param: *mut c_void, frame: *mut video_data)>,
+        param: *mut c_void,
+    );
+    fn obs_add_raw_audio_callback(
+        mix_idx: usize,
+        conversion: *const audio_convert_info,
+        callback: audio_output_callback_t,
+        param: *mut c_void,
+    );
+    fn obs_remove_raw_audio_callback(
+        mix_idx: usize,
+        callback: audio_output_callback_t,
+        param: *mut c_void,
+    );
+
+    // Data
+    fn obs_data_create() -> *mut obs_data_t;
+    fn obs_data_create_from_json(json_string: *const c_char) -> *mut obs_data_t;
+    fn obs_data_get_json(data: *mut obs_data_t) -> *const c_char;
+    fn obs_data_release(data: *mut obs_data_t);
+    fn obs_data_set_string(data: *mut obs_data_t, name: *const c_char, val: *const c_char);
+    fn obs_data_set_int(data: *mut obs_data_t, name: *const c_char, val: i64);
+    fn obs_data_set_bool(data: *mut obs_data_t, name: *const c_char, val: bool);
+    fn obs_data_set_double(data: *mut obs_data_t, name: *const c_char, val: f64);
+    fn obs_data_set_obj(data: *mut obs_data_t, name: *const c_char, obj: *mut obs_data_t);
+    fn obs_data_set_array(data: *mut obs_data_t, name: *const c_char, array: *mut obs_data_array_t);
+    fn obs_data_get_string(data: *mut obs_data_t, name: *const c_char) -> *const c_char;
+    fn obs_data_get_array(data: *mut obs_data_t, name: *const c_char) -> *mut obs_data_array_t;
+    fn obs_data_array_create() -> *mut obs_data_array_t;
+    fn obs_data_array_release(array: *mut obs_data_array_t);
+    fn obs_data_array_count(array: *mut obs_data_array_t) -> usize;
+    fn obs_data_array_item(array: *mut obs_data_array_t, idx: usize) -> *mut obs_data_t;
+    fn obs_data_array_push_back(array: *mut obs_data_array_t, obj: *mut obs_data_t) -> usize;
+
+    // Sources
+    fn obs_source_create(
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_source_t;
+    fn obs_source_release(source: *mut obs_source_t);
+    fn obs_source_get_ref(source: *mut obs_source_t) -> *mut obs_source_t;
+    fn obs_source_get_id(source: *mut obs_source_t) -> *const c_char;
+    fn obs_source_get_name(source: *mut obs_source_t) -> *const c_char;
+    fn obs_source_get_uuid(source: *mut obs_source_t) -> *const c_char;
+    fn obs_get_source_by_name(name: *const c_char) -> *mut obs_source_t;
+    fn obs_get_source_by_uuid(uuid: *const c_char) -> *mut obs_source_t;
+    fn obs_enum_sources(
+        enum_proc: Option<unsafe extern "C" fn(param: *mut c_void, source: *mut obs_source_t) -> bool>,
+        param: *mut c_void,
+    );
+    fn obs_enum_scenes(
+        enum_proc: Option<unsafe extern "C" fn(param: *mut c_void, source: *mut obs_source_t) -> bool>,
+        param: *mut c_void,
+    );
+    fn obs_source_update(source: *mut obs_source_t, settings: *mut obs_data_t);
+    fn obs_source_reset_settings(source: *mut obs_source_t, settings: *mut obs_data_t);
+    fn obs_source_get_settings(source: *mut obs_source_t) -> *mut obs_data_t;
+    fn obs_source_get_signal_handler(source: *mut obs_source_t) -> *mut signal_handler_t;
+    fn obs_source_get_width(source: *mut obs_source_t) -> u32;
+    fn obs_source_get_height(source: *mut obs_source_t) -> u32;
+    fn obs_source_video_render(source: *mut obs_source_t);
+    fn obs_source_inc_showing(source: *mut obs_source_t);
+    fn obs_source_dec_showing(source: *mut obs_source_t);
+    fn obs_source_filter_add(source: *mut obs_source_t, filter: *mut obs_source_t);
+    fn obs_source_filter_remove(source: *mut obs_source_t, filter: *mut obs_source_t);
+    fn obs_source_enum_filters(source: *mut obs_source_t, callback: obs_source_enum_proc_t, param: *mut c_void);
+    fn obs_save_source(source: *mut obs_source_t) -> *mut obs_data_t;
+    fn obs_load_source(data: *mut obs_data_t) -> *mut obs_source_t;
+    fn obs_source_load(source: *mut obs_source_t);
+    fn obs_source_set_volume(source: *mut obs_source_t, volume: f32);
+    fn obs_source_get_volume(source: *mut obs_source_t) -> f32;
+    fn obs_source_set_muted(source: *mut obs_source_t, muted: bool);
+    fn obs_source_muted(source: *mut obs_source_t) -> bool;
+    fn obs_source_set_balance_value(source: *mut obs_source_t, balance: f32);
+    fn obs_source_get_balance_value(source: *mut obs_source_t) -> f32;
+    fn obs_source_set_sync_offset(source: *mut obs_source_t, offset: i64);
+    fn obs_source_get_sync_offset(source: *mut obs_source_t) -> i64;
+    fn obs_source_set_audio_mixers(source: *mut obs_source_t, mixers: u32);
+    fn obs_source_get_audio_mixers(source: *mut obs_source_t) -> u32;
+    fn obs_source_enable_push_to_talk(source: *mut obs_source_t, enabled: bool);
+    fn obs_source_push_to_talk_enabled(source: *mut obs_source_t) -> bool;
+    fn obs_source_set_push_to_talk_delay(source: *mut obs_source_t, delay: u64);
+    fn obs_source_get_push_to_talk_delay(source: *mut obs_source_t) -> u64;
+    fn obs_source_enable_push_to_mute(source: *mut obs_source_t, enabled: bool);
+    fn obs_source_push_to_mute_enabled(source: *mut obs_source_t) -> bool;
+    fn obs_source_set_push_to_mute_delay(source: *mut obs_source_t, delay: u64);
+    fn obs_source_get_push_to_mute_delay(source: *mut obs_source_t) -> u64;
+    fn obs_source_set_monitoring_type(source: *mut obs_source_t, type_: obs_monitoring_type);
+    fn obs_source_get_monitoring_type(source: *mut obs_source_t) -> obs_monitoring_type;
+    fn obs_source_add_audio_capture_callback(
+        source: *mut obs_source_t,
+        callback: obs_source_audio_capture_t,
+        param: *mut c_void,
+    );
+    fn obs_source_remove_audio_capture_callback(
+        source: *mut obs_source_t,
+        callback: obs_source_audio_capture_t,
+        param: *mut c_void,
+    );
+
+    // Media controls
+    fn obs_source_media_play_pause(source: *mut obs_source_t, pause: bool);
+    fn obs_source_media_restart(source: *mut obs_source_t);
+    fn obs_source_media_stop(source: *mut obs_source_t);
+    fn obs_source_media_next(source: *mut obs_source_t);
+    fn obs_source_media_previous(source: *mut obs_source_t);
+    fn obs_source_media_get_duration(source: *mut obs_source_t) -> i64;
+    fn obs_source_media_get_time(source: *mut obs_source_t) -> i64;
+    fn obs_source_media_set_time(source: *mut obs_source_t, ms: i64);
+    fn obs_source_media_get_state(source: *mut obs_source_t) -> obs_media_state;
+
+    // Volume meters and faders
+    fn obs_volmeter_create(type_: obs_fader_type) -> *mut obs_volmeter_t;
+    fn obs_volmeter_destroy(volmeter: *mut obs_volmeter_t);
+    fn obs_volmeter_attach_source(volmeter: *mut obs_volmeter_t, source: *mut obs_source_t) -> bool;
+    fn obs_volmeter_detach_source(volmeter: *mut obs_volmeter_t);
+    fn obs_volmeter_set_peak_meter_type(volmeter: *mut obs_volmeter_t, peak_meter_type: obs_peak_meter_type);
+    fn obs_volmeter_get_nr_channels(volmeter: *mut obs_volmeter_t) -> c_int;
+    fn obs_volmeter_add_callback(volmeter: *mut obs_volmeter_t, callback: obs_volmeter_updated_t, param: *mut c_void);
+    fn obs_volmeter_remove_callback(volmeter: *mut obs_volmeter_t, callback: obs_volmeter_updated_t, param: *mut c_void);
+    fn obs_fader_create(type_: obs_fader_type) -> *mut obs_fader_t;
+    fn obs_fader_destroy(fader: *mut obs_fader_t);
+    fn obs_fader_attach_source(fader: *mut obs_fader_t, source: *mut obs_source_t) -> bool;
+    fn obs_fader_detach_source(fader: *mut obs_fader_t);
+    fn obs_fader_set_db(fader: *mut obs_fader_t, db: f32) -> bool;
+    fn obs_fader_get_db(fader: *mut obs_fader_t) -> f32;
+    fn obs_fader_set_deflection(fader: *mut obs_fader_t, def: f32) -> bool;
+    fn obs_fader_get_deflection(fader: *mut obs_fader_t) -> f32;
+    fn obs_fader_set_mul(fader: *mut obs_fader_t, mul: f32) -> bool;
+    fn obs_fader_get_mul(fader: *mut obs_fader_t) -> f32;
+
+    // Transitions
+    fn obs_transition_set(transition: *mut obs_source_t, source: *mut obs_source_t);
+    fn obs_transition_start(
+        transition: *mut obs_source_t,
+        mode: obs_transition_mode,
+        duration_ms: u32,
+        dest: *mut obs_source_t,
+    ) -> bool;
+    fn obs_transition_force_stop(transition: *mut obs_source_t);
+    fn obs_transition_get_time(transition: *mut obs_source_t) -> f32;
+
+    // Scenes
+    fn obs_scene_create(name: *const c_char) -> *mut obs_scene_t;
+    fn obs_scene_release(scene: *mut obs_scene_t);
+    fn obs_scene_get_source(scene: *mut obs_scene_t) -> *mut obs_source_t;
+    fn obs_scene_add(scene: *mut obs_scene_t, source: *mut obs_source_t) -> *mut obs_sceneitem_t;
+    fn obs_scene_from_source(source: *mut obs_source_t) -> *mut obs_scene_t;
+    fn obs_group_or_scene_from_source(source: *mut obs_source_t) -> *mut obs_scene_t;
+    fn obs_scene_enum_items(
+        scene: *mut obs_scene_t,
+        callback: Option<unsafe extern "C" fn(scene: *mut obs_scene_t, item: *mut obs_sceneitem_t, param: *mut c_void) -> bool>,
+        param: *mut c_void,
+    );
+    fn obs_scene_add_group2(scene: *mut obs_scene_t, name: *const c_char, signal: bool) -> *mut obs_sceneitem_t;
+    fn obs_sceneitem_is_group(item: *mut obs_sceneitem_t) -> bool;
+    fn obs_sceneitem_group_get_scene(group: *mut obs_sceneitem_t) -> *mut obs_scene_t;
+    fn obs_sceneitem_group_add_item(group: *mut obs_sceneitem_t, item: *mut obs_sceneitem_t);
+    fn obs_sceneitem_group_remove_item(group: *mut obs_sceneitem_t, item: *mut obs_sceneitem_t);
+    fn obs_sceneitem_group_ungroup(group: *mut obs_sceneitem_t);
+    fn obs_sceneitem_get_source(item: *mut obs_sceneitem_t) -> *mut obs_source_t;
+    fn obs_sceneitem_remove(item: *mut obs_sceneitem_t);
+    fn obs_sceneitem_release(item: *mut obs_sceneitem_t);
+    fn obs_sceneitem_get_pos(item: *mut obs_sceneitem_t, pos: *mut vec2);
+    fn obs_sceneitem_set_pos(item: *mut obs_sceneitem_t, pos: *const vec2);
+    fn obs_sceneitem_get_scale(item: *mut obs_sceneitem_t, scale: *mut vec2);
+    fn obs_sceneitem_set_scale(item: *mut obs_sceneitem_t, scale: *const vec2);
+    fn obs_sceneitem_addref(item: *mut obs_sceneitem_t);
+    fn obs_sceneitem_get_rot(item: *mut obs_sceneitem_t) -> f32;
+    fn obs_sceneitem_set_rot(item: *mut obs_sceneitem_t, rot_deg: f32);
+    fn obs_sceneitem_get_alignment(item: *mut obs_sceneitem_t) -> u32;
+    fn obs_sceneitem_set_alignment(item: *mut obs_sceneitem_t, alignment: u32);
+    fn obs_sceneitem_get_bounds_type(item: *mut obs_sceneitem_t) -> obs_bounds_type;
+    fn obs_sceneitem_set_bounds_type(item: *mut obs_sceneitem_t, type_: obs_bounds_type);
+    fn obs_sceneitem_get_bounds_alignment(item: *mut obs_sceneitem_t) -> u32;
+    fn obs_sceneitem_set_bounds_alignment(item: *mut obs_sceneitem_t, alignment: u32);
+    fn obs_sceneitem_get_bounds(item: *mut obs_sceneitem_t, bounds: *mut vec2);
+    fn obs_sceneitem_set_bounds(item: *mut obs_sceneitem_t, bounds: *const vec2);
+    fn obs_sceneitem_get_crop(item: *mut obs_sceneitem_t, crop: *mut obs_sceneitem_crop);
+    fn obs_sceneitem_set_crop(item: *mut obs_sceneitem_t, crop: *const obs_sceneitem_crop);
+    fn obs_sceneitem_get_scale_filter(item: *mut obs_sceneitem_t) -> obs_scale_type;
+    fn obs_sceneitem_set_scale_filter(item: *mut obs_sceneitem_t, filter: obs_scale_type);
+    fn obs_sceneitem_get_blending_method(item: *mut obs_sceneitem_t) -> obs_blending_method;
+    fn obs_sceneitem_set_blending_method(item: *mut obs_sceneitem_t, method: obs_blending_method);
+    fn obs_sceneitem_get_blending_mode(item: *mut obs_sceneitem_t) -> obs_blending_type;
+    fn obs_sceneitem_set_blending_mode(item: *mut obs_sceneitem_t, type_: obs_blending_type);
+    fn obs_sceneitem_get_info2(item: *mut obs_sceneitem_t, info: *mut obs_transform_info);
+    fn obs_sceneitem_set_info2(item: *mut obs_sceneitem_t, info: *const obs_transform_info);
+    fn obs_sceneitem_set_order(item: *mut obs_sceneitem_t, movement: obs_order_movement);
+    fn obs_sceneitem_set_order_position(item: *mut obs_sceneitem_t, position: c_int);
+    fn obs_sceneitem_get_order_position(item: *mut obs_sceneitem_t) -> c_int;
+    fn obs_sceneitem_visible(item: *mut obs_sceneitem_t) -> bool;
+    fn obs_sceneitem_set_visible(item: *mut obs_sceneitem_t, visible: bool) -> bool;
+    fn obs_sceneitem_locked(item: *mut obs_sceneitem_t) -> bool;
+    fn obs_sceneitem_set_locked(item: *mut obs_sceneitem_t, lock: bool) -> bool;
+
+    // Outputs
+    fn obs_output_create(
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_output_t;
+    fn obs_output_release(output: *mut obs_output_t);
+    fn obs_output_active(output: *mut obs_output_t) -> bool;
+    fn obs_output_start(output: *mut obs_output_t) -> bool;
+    fn obs_output_stop(output: *mut obs_output_t);
+    fn obs_output_pause(output: *mut obs_output_t, pause: bool) -> bool;
+    fn obs_output_update(output: *mut obs_output_t, settings: *mut obs_data_t);
+    fn obs_output_get_last_error(output: *mut obs_output_t) -> *const c_char;
+    fn obs_output_set_video_encoder(output: *mut obs_output_t, encoder: *mut obs_encoder_t);
+    fn obs_output_set_audio_encoder(output: *mut obs_output_t, encoder: *mut obs_encoder_t, idx: usize);
+    fn obs_output_get_signal_handler(output: *mut obs_output_t) -> *mut signal_handler_t;
+    fn obs_output_get_total_bytes(output: *const obs_output_t) -> u64;
+    fn obs_output_get_frames_dropped(output: *const obs_output_t) -> c_int;
+    fn obs_output_get_total_frames(output: *const obs_output_t) -> c_int;
+    fn obs_output_get_congestion(output: *mut obs_output_t) -> f32;
+
+    // Encoders
+    fn obs_video_encoder_create(
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_encoder_t;
+    fn obs_audio_encoder_create(
+        id: *const c_char,
+        name: *const c_char,
+        settings: *mut obs_data_t,
+        mixer_idx: usize,
+        hotkey_data: *mut obs_data_t,
+    ) -> *mut obs_encoder_t;
+    fn obs_encoder_release(encoder: *mut obs_encoder_t);
+    fn obs_encoder_set_video(encoder: *mut obs_encoder_t, video: *mut video_output);
+    fn obs_encoder_set_audio(encoder: *mut obs_encoder_t, audio: *mut audio_output);
+    fn obs_enum_encoder_types(idx: usize, id: *mut *const c_char) -> bool;
+    fn obs_get_encoder_caps(encoder_id: *const c_char) -> u32;
+    fn obs_get_encoder_type(id: *const c_char) -> obs_encoder_type;
+
+    // Signals
+    fn signal_handler_connect(
+        handler: *mut signal_handler_t,
+        signal: *const c_char,
+        callback: signal_callback_t,
+        data: *mut c_void,
+    );
+    fn signal_handler_disconnect(
+        handler: *mut signal_handler_t,
+        signal: *const c_char,
+        callback: signal_callback_t,
+        data: *mut c_void,
+    );
+    fn calldata_get_data(
+        data: *const calldata_t,
+        name: *const c_char,
+        out: *mut c_void,
+        size: usize,
+    ) -> bool;
+    fn calldata_get_string(data: *const calldata_t, name: *const c_char, str_: *mut *const c_char) -> bool;
+
+    // Properties
+    fn obs_get_source_properties(id: *const c_char) -> *mut obs_properties_t;
+    fn obs_source_properties(source: *mut obs_source_t) -> *mut obs_properties_t;
+    fn obs_get_output_properties(id: *const c_char) -> *mut obs_properties_t;
+    fn obs_output_properties(output: *mut obs_output_t) -> *mut obs_properties_t;
+    fn obs_properties_destroy(props: *mut obs_properties_t);
+    fn obs_properties_first(props: *mut obs_properties_t) -> *mut obs_property_t;
+    fn obs_property_next(p: *mut *mut obs_property_t) -> bool;
+    fn obs_property_name(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_description(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_get_type(p: *mut obs_property_t) -> obs_property_type;
+    fn obs_property_int_min(p: *mut obs_property_t) -> c_int;
+    fn obs_property_int_max(p: *mut obs_property_t) -> c_int;
+    fn obs_property_int_step(p: *mut obs_property_t) -> c_int;
+    fn obs_property_int_type(p: *mut obs_property_t) -> obs_number_type;
+    fn obs_property_int_suffix(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_float_min(p: *mut obs_property_t) -> f64;
+    fn obs_property_float_max(p: *mut obs_property_t) -> f64;
+    fn obs_property_float_step(p: *mut obs_property_t) -> f64;
+    fn obs_property_float_type(p: *mut obs_property_t) -> obs_number_type;
+    fn obs_property_float_suffix(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_text_type(p: *mut obs_property_t) -> obs_text_type;
+    fn obs_property_text_monospace(p: *mut obs_property_t) -> bool;
+    fn obs_property_text_info_type(p: *mut obs_property_t) -> obs_text_info_type;
+    fn obs_property_text_info_word_wrap(p: *mut obs_property_t) -> bool;
+    fn obs_property_path_type(p: *mut obs_property_t) -> obs_path_type;
+    fn obs_property_path_filter(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_path_default_path(p: *mut obs_property_t) -> *const c_char;
+    fn obs_property_list_type(p: *mut obs_property_t) -> obs_combo_type;
+    fn obs_property_list_format(p: *mut obs_property_t) -> obs_combo_format;
+    fn obs_property_list_item_count(p: *mut obs_property_t) -> usize;
+    fn obs_property_list_item_name(p: *mut obs_property_t, idx: usize) -> *const c_char;
+    fn obs_property_list_item_disabled(p: *mut obs_property_t, idx: usize) -> bool;
+    fn obs_property_list_item_string(p: *mut obs_property_t, idx: usize) -> *const c_char;
+    fn obs_property_list_item_int(p: *mut obs_property_t, idx: usize) -> i64;
+    fn obs_property_list_item_float(p: *mut obs_property_t, idx: usize) -> f64;
+    fn obs_property_list_item_bool(p: *mut obs_property_t, idx: usize) -> bool;
+    fn obs_property_editable_list_type(p: *mut obs_property_t) -> obs_editable_list_type;
+    fn obs_property_button_type(p: *mut obs_property_t) -> obs_button_type;
+    fn obs_property_button_url(p: *mut obs_property_t) -> *const c_char;
+}
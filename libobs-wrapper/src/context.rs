@@ -34,23 +34,43 @@
 //!
 //! For more examples refer to the [examples](https://github.com/joshprk/libobs-rs/tree/main/examples) directory in the repository.
 
-use std::{collections::HashMap, ffi::CStr, pin::Pin, sync::Arc, thread::ThreadId};
+use std::{collections::VecDeque, ffi::CStr, sync::Arc, thread::ThreadId};
+use std::{collections::HashMap, pin::Pin};
+#[cfg(not(feature = "blocking"))]
+use std::time::Duration;
 
 use crate::{
-    data::{output::ObsOutputRef, video::ObsVideoInfo, ObsData},
-    display::{ObsDisplayCreationData, ObsDisplayRef},
+    backend::backend,
+    data::{
+        output::{ObsOutputRef, ObsOutputSignals},
+        video::ObsVideoInfo,
+        ObsData,
+    },
     enums::{ObsLogLevel, ObsResetVideoStatus},
     logger::LOGGER,
+    media::{
+        take_screenshot, ObsRawAudioOptions, ObsRawAudioStream, ObsRawVideoOptions,
+        ObsRawVideoStream, ObsScreenshot, ObsScreenshotOptions,
+    },
     run_with_obs,
-    runtime::{ObsRuntime, ObsRuntimeReturn},
-    scenes::ObsSceneRef,
-    sources::{ObsFilterRef, ObsSourceBuilder},
+    runtime::{
+        ObsRuntime, ObsRuntimeMetrics, ObsRuntimeReturn, ObsTransaction, ObsTransactionValue,
+    },
+    rw_lock_blocking_write,
+    scenes::{load_scene_collection, save_scene_collection, ObsSceneRef, ObsSceneSignals},
+    stats::ObsStats,
+    sources::{enumerate_sources, ObsFilterRef, ObsSourceBuilder, ObsSourceRef, ObsSourceSignals},
+    studio_mode::ObsStudioMode,
+    transitions::{ObsTransitionRef, ObsTransitionType},
     unsafe_send::Sendable,
     utils::{
         FilterInfo, ObsError, ObsModules, ObsString, OutputInfo, StartupInfo
     },
 };
 use crate::utils::async_sync::{Mutex, RwLock};
+#[cfg(not(feature = "blocking"))]
+use crate::stats::ObsStatsStream;
+use crate::display::{ObsDisplayCreationData, ObsDisplayRef};
 use getters0::Getters;
 use libobs::{audio_output, obs_scene_t, video_output};
 
@@ -94,6 +114,9 @@ pub struct ObsContext {
     #[get_mut]
     pub(crate) scenes: Arc<RwLock<Vec<ObsSceneRef>>>,
 
+    #[get_mut]
+    pub(crate) transitions: Arc<RwLock<Vec<ObsTransitionRef>>>,
+
     // Filters are on the level of the context because they are not scene specific
     #[get_mut]
     pub(crate) filters: Arc<RwLock<Vec<ObsFilterRef>>>,
@@ -131,6 +154,9 @@ impl ObsContext {
     /// Note that there can be only one ObsContext
     /// initialized at a time. This is because
     /// libobs is not completely thread-safe.
+    /// Once the previous context was shut down (by calling
+    /// [`ObsContext::shutdown`] or dropping it), a new one
+    /// can be created in the same process.
     ///
     /// Also note that this might leak a very tiny
     /// amount of memory. As a result, it is
@@ -162,6 +188,7 @@ impl ObsContext {
             displays: Default::default(),
             outputs: Default::default(),
             scenes: Default::default(),
+            transitions: Default::default(),
             filters: Default::default(),
             runtime,
             startup_info: Arc::new(RwLock::new(info)),
@@ -174,10 +201,147 @@ impl ObsContext {
         return Ok(context);
     }
 
+    /// Shuts down OBS explicitly instead of relying on the order in which
+    /// everything is dropped.
+    ///
+    /// The objects owned by this context are released in a defined order:
+    /// 1. Active outputs are stopped, then all outputs are released
+    /// 2. Displays are destroyed
+    /// 3. The signals of outputs, scenes and sources are disconnected (in that order),
+    ///    which ends all signal receivers
+    /// 4. Output channels are cleared, then transitions, scenes and their sources are released
+    /// 5. Filters are released
+    /// 6. libobs is shut down and the OBS thread is terminated
+    ///
+    /// Objects that are still
+    /// referenced somewhere else (for example a cloned `ObsSourceRef`) can not be
+    /// released before libobs shuts down and are listed in the returned report,
+    /// dropping them afterwards does nothing. Errors don't abort the shutdown but
+    /// are collected in the report.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn shutdown(self) -> ObsShutdownReport {
+        let mut report = ObsShutdownReport::default();
+
+        let outputs = std::mem::take(&mut *self.outputs.write().await);
+        for mut output in outputs {
+            let name = output.name().to_string();
+            match output.is_active().await {
+                Ok(true) => {
+                    if let Err(e) = output.stop().await {
+                        report.errors.push((format!("Stopping output {}", name), e));
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => report.errors.push((format!("Stopping output {}", name), e)),
+            }
+
+            if output.is_shared() {
+                report.leaked_objects.push(format!("Output {}", name));
+            }
+        }
+
+        let displays = std::mem::take(&mut *self.displays.write().await);
+        for (id, display) in displays {
+            if Arc::strong_count(&display) > 1 {
+                report.leaked_objects.push(format!("Display {}", id));
+            }
+        }
+
+        let res = run_with_obs!(self.runtime, || unsafe {
+            let mut failures = ObsOutputSignals::disconnect_all();
+            failures.extend(ObsSceneSignals::disconnect_all());
+            failures.extend(ObsSourceSignals::disconnect_all());
+            failures
+        }).await;
+
+        match res {
+            Ok(failures) => {
+                for failure in failures {
+                    report.errors.push((
+                        format!("Disconnecting signals of {}", failure),
+                        ObsError::NullPointer,
+                    ));
+                }
+            }
+            Err(e) => report.errors.push(("Disconnecting signals".to_string(), e)),
+        }
+
+        *self.active_scene.write().await = None;
+        let res = run_with_obs!(self.runtime, || unsafe {
+            let backend = backend();
+            for i in 0..libobs::MAX_CHANNELS {
+                backend.obs_set_output_source(i, std::ptr::null_mut());
+            }
+        }).await;
+
+        if let Err(e) = res {
+            report.errors.push(("Clearing output channels".to_string(), e));
+        }
+
+        let transitions = std::mem::take(&mut *self.transitions.write().await);
+        for transition in transitions {
+            if transition.is_shared() {
+                report
+                    .leaked_objects
+                    .push(format!("Transition {}", transition.name()));
+            }
+        }
+
+        let scenes = std::mem::take(&mut *self.scenes.write().await);
+        // Nested scenes and groups are referenced by the sources of their parent scene as
+        // well, so these references are dropped first to not report them as leaked.
+        let mut sources = VecDeque::new();
+        for scene in &scenes {
+            scene.scene_source.write().await.take();
+            scene.groups.write().await.clear();
+
+            let scene_name = scene.name().to_string();
+            let scene_sources = std::mem::take(&mut *scene.sources.write().await);
+            sources.extend(scene_sources.into_iter().map(|s| (scene_name.clone(), s)));
+        }
+
+        while let Some((scene_name, source)) = sources.pop_front() {
+            // A source shown in several scenes is only checked once the last scene released it
+            let shown_elsewhere = sources.iter().any(|(_, s)| s.source.0 == source.source.0);
+            if source.is_shared() && !shown_elsewhere {
+                report
+                    .leaked_objects
+                    .push(format!("Source {} in scene {}", source.name(), scene_name));
+            }
+        }
+
+        for scene in scenes {
+            if scene.is_shared() {
+                report.leaked_objects.push(format!("Scene {}", scene.name()));
+            }
+        }
+
+        let filters = std::mem::take(&mut *self.filters.write().await);
+        for filter in filters {
+            if filter.is_shared() {
+                report.leaked_objects.push(format!("Filter {}", filter.name()));
+            }
+        }
+
+        if Arc::strong_count(&self._obs_modules) > 1 {
+            report
+                .leaked_objects
+                .push("ObsContext (the context has been cloned)".to_string());
+        }
+        drop(self._obs_modules);
+
+        match self.runtime.shutdown().await {
+            Ok(memory_leaks) => report.memory_leaks = memory_leaks,
+            Err(e) => report.errors.push(("Shutting down libobs".to_string(), e)),
+        }
+
+        report
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn get_version(&self) -> Result<String, ObsError> {
         let res = run_with_obs!(self.runtime, || unsafe {
-            let version = libobs::obs_get_version_string();
+            let version = backend().obs_get_version_string();
             let version_cstr = CStr::from_ptr(version);
 
             version_cstr.to_string_lossy().into_owned()
@@ -191,6 +355,33 @@ impl ObsContext {
         log.log(level, msg.to_string());
     }
 
+    /// Returns a snapshot of the queue depth and command latencies of the OBS runtime.
+    /// See [`ObsRuntime::metrics`].
+    pub fn metrics(&self) -> ObsRuntimeMetrics {
+        self.runtime.metrics()
+    }
+
+    /// Subscribes to the metrics of the OBS runtime. See [`ObsRuntime::subscribe_metrics`].
+    pub fn subscribe_metrics(&self) -> tokio::sync::watch::Receiver<ObsRuntimeMetrics> {
+        self.runtime.subscribe_metrics()
+    }
+
+    /// Returns a snapshot of the render statistics and the statistics of every output.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn stats(&self) -> Result<ObsStats, ObsError> {
+        let outputs = self.outputs.read().await.clone();
+        ObsStats::collect(&self.runtime, &outputs).await
+    }
+
+    /// Takes a snapshot of the statistics every `period`, the first one right away.
+    ///
+    /// # Panics
+    /// If `period` is zero.
+    #[cfg(not(feature = "blocking"))]
+    pub fn stats_stream(&self, period: Duration) -> ObsStatsStream {
+        ObsStatsStream::new(self.runtime.clone(), self.outputs.clone(), period)
+    }
+
     /// Resets the OBS video context. This is often called
     /// when one wants to change a setting related to the
     /// OBS video info sent on startup.
@@ -204,7 +395,8 @@ impl ObsContext {
     ///
     /// Note that you cannot reset the graphics module
     /// without destroying the entire OBS context. Trying
-    /// so will result in an error.
+    /// so will result in an error. Shut down this context
+    /// and create a new one with the new graphics module instead.
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn reset_video(&mut self, ovi: ObsVideoInfo) -> Result<(), ObsError> {
         // You cannot change the graphics module without
@@ -230,7 +422,7 @@ impl ObsContext {
         let vid_ptr = Sendable(vid.obs_video_info.as_ptr());
 
         let reset_video_status = run_with_obs!(self.runtime, (vid_ptr), move || unsafe {
-            libobs::obs_reset_video(vid_ptr)
+            backend().obs_reset_video(vid_ptr)
         }).await?;
 
         drop(vid);
@@ -254,8 +446,9 @@ impl ObsContext {
 
             let vid_ptr = self.get_video_ptr().await?;
             run_with_obs!(self.runtime, (vid_ptr), move || unsafe {
+                let backend = backend();
                 for encoder_ptr in video_encoders.into_iter() {
-                    libobs::obs_encoder_set_video(encoder_ptr.0, vid_ptr);
+                    backend.obs_encoder_set_video(encoder_ptr.0, vid_ptr);
                 }
             }).await?;
 
@@ -268,7 +461,7 @@ impl ObsContext {
     pub async fn get_video_ptr(&self) -> Result<Sendable<*mut video_output>, ObsError> {
         // Removed safeguards here because ptr are not sendable and this OBS context should never be used across threads
         run_with_obs!(self.runtime, || unsafe {
-            Sendable(libobs::obs_get_video())
+            Sendable(backend().obs_get_video())
         }).await
     }
 
@@ -276,10 +469,40 @@ impl ObsContext {
     pub async fn get_audio_ptr(&self) -> Result<Sendable<*mut audio_output>, ObsError> {
         // Removed safeguards here because ptr are not sendable and this OBS context should never be used across threads
         run_with_obs!(self.runtime, || unsafe {
-            Sendable(libobs::obs_get_audio())
+            Sendable(backend().obs_get_audio())
         }).await
     }
 
+    /// Streams the frames that are rendered by the video context. Frames are buffered
+    /// and dropped as configured in the options if the stream isn't polled fast enough.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn raw_video_stream(
+        &self,
+        options: ObsRawVideoOptions,
+    ) -> Result<ObsRawVideoStream, ObsError> {
+        ObsRawVideoStream::new(self.runtime.clone(), options).await
+    }
+
+    /// Streams the mixed audio of an audio track. Audio is buffered and dropped
+    /// as configured in the options if the stream isn't polled fast enough.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn raw_audio_stream(
+        &self,
+        options: ObsRawAudioOptions,
+    ) -> Result<ObsRawAudioStream, ObsError> {
+        ObsRawAudioStream::new(self.runtime.clone(), options).await
+    }
+
+    /// Renders the program output (what is shown on channel 0) at the base resolution
+    /// of the video context and reads it back as RGBA pixels.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn screenshot(
+        &self,
+        options: ObsScreenshotOptions,
+    ) -> Result<ObsScreenshot, ObsError> {
+        take_screenshot(&self.runtime, None, options).await
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn data(&self) -> Result<ObsData, ObsError> {
         ObsData::new(self.runtime.clone()).await
@@ -395,6 +618,32 @@ impl ObsContext {
         Ok(tmp)
     }
 
+    /// Queues creating a new scene in the given transaction. Sources can be added to it
+    /// in the same transaction, see [`ObsSourceBuilder::add_to_scene_in_transaction`].
+    pub fn scene_in_transaction<T: Into<ObsString>>(
+        &self,
+        transaction: &mut ObsTransaction,
+        name: T,
+    ) -> ObsTransactionValue<ObsSceneRef> {
+        let name = name.into();
+        let active_scene = self.active_scene.clone();
+        let runtime = self.runtime.clone();
+        let scenes = self.scenes.clone();
+        let rollback_scenes = self.scenes.clone();
+
+        transaction.add_with_rollback(
+            move |_| {
+                let scene = unsafe { ObsSceneRef::new_on_obs_thread(name, active_scene, runtime) };
+                rw_lock_blocking_write!(scenes).push(scene.clone());
+
+                Ok(scene)
+            },
+            move |scene| {
+                rw_lock_blocking_write!(rollback_scenes).retain(|s| s.as_ptr().0 != scene.as_ptr().0);
+            },
+        )
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn get_scene(&mut self, name: &str) -> Option<ObsSceneRef> {
         self.scenes
@@ -405,6 +654,197 @@ impl ObsContext {
             .map(|e| e.clone())
     }
 
+    /// Creates a new transition, see [`ObsTransitionRef`] on how to switch scenes with it.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn transition<T: Into<ObsString> + Send + Sync>(
+        &mut self,
+        name: T,
+        transition_type: ObsTransitionType,
+    ) -> Result<ObsTransitionRef, ObsError> {
+        let transition = ObsTransitionRef::new(
+            name.into(),
+            transition_type,
+            self.active_scene.clone(),
+            self.runtime.clone(),
+        )
+        .await?;
+
+        self.transitions.write().await.push(transition.clone());
+        Ok(transition)
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_transition(&self, name: &str) -> Option<ObsTransitionRef> {
+        self.transitions
+            .read()
+            .await
+            .iter()
+            .find(|x| x.name() == name)
+            .cloned()
+    }
+
+    /// Lists all inputs that exist in libobs, including the ones created by plugins or
+    /// through other sources, e.g. the audio source of a window capture. Scenes,
+    /// transitions and filters are not listed.
+    ///
+    /// Each returned handle holds its own reference, the sources are not registered in
+    /// this context.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn enum_sources(&self) -> Result<Vec<ObsSourceRef>, ObsError> {
+        let runtime = self.runtime.clone();
+        run_with_obs!(self.runtime, move || unsafe {
+            enumerate_sources(|enum_proc, param| backend().obs_enum_sources(enum_proc, param))
+                .into_iter()
+                .map(|source| ObsSourceRef::from_raw_on_obs_thread(source, runtime.clone()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .await?
+    }
+
+    /// Lists all scenes that exist in libobs. Scenes of this context are returned as they
+    /// are, other scenes are wrapped together with their items, each holding its own
+    /// reference.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn enum_scenes(&self) -> Result<Vec<ObsSceneRef>, ObsError> {
+        let known = self.scenes.read().await.clone();
+        let active_scene = self.active_scene.clone();
+        let runtime = self.runtime.clone();
+
+        run_with_obs!(self.runtime, move || unsafe {
+            let backend = backend();
+            let sources =
+                enumerate_sources(|enum_proc, param| backend.obs_enum_scenes(enum_proc, param));
+
+            let mut scenes = Vec::new();
+            for source in sources {
+                let existing = known
+                    .iter()
+                    .find(|s| backend.obs_scene_get_source(s.as_ptr().0) == source.0);
+
+                match existing {
+                    Some(scene) => scenes.push(scene.clone()),
+                    None => scenes.extend(ObsSceneRef::adopt_on_obs_thread(
+                        source,
+                        active_scene.clone(),
+                        runtime.clone(),
+                    )?),
+                }
+            }
+
+            Ok::<_, ObsError>(scenes)
+        })
+        .await?
+    }
+
+    /// Looks up any source that exists in libobs by its name, including scenes and
+    /// sources created by plugins. The returned handle holds its own reference.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_source_by_name(&self, name: &str) -> Result<Option<ObsSourceRef>, ObsError> {
+        self.lookup_source(ObsString::new(name), false).await
+    }
+
+    /// Looks up any source that exists in libobs by its uuid, see [`ObsSourceRef::uuid`].
+    /// The returned handle holds its own reference.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_source_by_uuid(&self, uuid: &str) -> Result<Option<ObsSourceRef>, ObsError> {
+        self.lookup_source(ObsString::new(uuid), true).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn lookup_source(
+        &self,
+        key: ObsString,
+        by_uuid: bool,
+    ) -> Result<Option<ObsSourceRef>, ObsError> {
+        let runtime = self.runtime.clone();
+        run_with_obs!(self.runtime, move || unsafe {
+            let backend = backend();
+            let source = if by_uuid {
+                backend.obs_get_source_by_uuid(key.as_ptr().0)
+            } else {
+                backend.obs_get_source_by_name(key.as_ptr().0)
+            };
+
+            if source.is_null() {
+                return Ok(None);
+            }
+
+            // The wrapper takes its own reference, the one of the lookup is released
+            let source_ref = ObsSourceRef::from_raw_on_obs_thread(Sendable(source), runtime);
+            backend.obs_source_release(source);
+            source_ref.map(Some)
+        })
+        .await?
+    }
+
+    /// Enters studio mode: the program scene is output on the main channel, through the
+    /// transition if given, while the preview scene can be edited and shown on a display.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn studio_mode(
+        &self,
+        preview: &ObsSceneRef,
+        program: &ObsSceneRef,
+        transition: Option<&ObsTransitionRef>,
+    ) -> Result<ObsStudioMode, ObsError> {
+        ObsStudioMode::new(preview.clone(), program.clone(), transition.cloned()).await
+    }
+
+    /// Serializes all scenes of this context to a scene collection in the json format
+    /// OBS Studio uses. The collection contains the sources shown in the scenes with
+    /// their filters and hotkeys, the scene items with their transforms and groups.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn save_scene_collection(&self, name: &str) -> Result<String, ObsError> {
+        let scenes = self.scenes.read().await.clone();
+        let mut sources = Vec::new();
+        for scene in &scenes {
+            sources.extend(scene.sources.read().await.iter().cloned());
+        }
+
+        let active_scene = self.active_scene.read().await.as_ref().map(|s| s.0);
+        let current_scene = scenes
+            .iter()
+            .find(|s| Some(s.as_ptr().0) == active_scene)
+            .map(|s| s.name().clone());
+
+        let name = ObsString::from(name);
+        let json = run_with_obs!(self.runtime, move || unsafe {
+            save_scene_collection(&name, &scenes, &sources, current_scene.as_ref())
+        }).await??;
+
+        Ok(json)
+    }
+
+    /// Loads a scene collection that was saved with [`ObsContext::save_scene_collection`]
+    /// (or by OBS Studio) and returns the scenes in it. The scenes, their sources and
+    /// filters are registered in this context, as if they were created through it.
+    /// The scene that was shown when saving is set to channel 0 again.
+    ///
+    /// Names must be unique, so this should be called on a context without scenes.
+    /// Sources that aren't shown in any scene are not kept.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn load_scene_collection(&mut self, json: &str) -> Result<Vec<ObsSceneRef>, ObsError> {
+        let json = ObsString::from(json);
+        let active_scene = self.active_scene.clone();
+        let runtime = self.runtime.clone();
+
+        let collection = run_with_obs!(self.runtime, move || unsafe {
+            load_scene_collection(&json, active_scene, runtime)
+        }).await??;
+
+        self.scenes.write().await.extend(collection.scenes.iter().cloned());
+        self.filters.write().await.extend(collection.filters);
+
+        let current_scene = collection
+            .scenes
+            .iter()
+            .find(|s| Some(s.name().to_string()) == collection.current_scene);
+        if let Some(scene) = current_scene {
+            scene.set_to_channel(0).await?;
+        }
+
+        Ok(collection.scenes)
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn source_builder<T: ObsSourceBuilder, K: Into<ObsString> + Send + Sync>(
         &self,
@@ -413,3 +853,23 @@ impl ObsContext {
         T::new(name.into(), self.runtime.clone()).await
     }
 }
+
+/// Describes everything that went wrong during [`ObsContext::shutdown`].
+#[derive(Debug, Default)]
+pub struct ObsShutdownReport {
+    /// Errors that occurred while shutting down, together with the step that failed
+    pub errors: Vec<(String, ObsError)>,
+    /// Objects that were still referenced outside of the context and
+    /// could not be released before libobs was shut down
+    pub leaked_objects: Vec<String>,
+    /// The number of memory leaks libobs reported after shutting down.
+    /// Note that libobs itself always leaks one allocation.
+    pub memory_leaks: Option<i64>,
+}
+
+impl ObsShutdownReport {
+    /// Whether everything has been released without errors.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.leaked_objects.is_empty()
+    }
+}
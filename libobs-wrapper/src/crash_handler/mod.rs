@@ -1,8 +1,13 @@
-use std::{ffi::c_void, sync::Mutex};
+use std::{
+    ffi::{c_char, c_void},
+    sync::Mutex,
+};
 
 use lazy_static::lazy_static;
 
-#[cfg(feature = "dialog_crash_handler")]
+use crate::utils::VaList;
+
+#[cfg(all(windows, feature = "dialog_crash_handler"))]
 pub mod dialog;
 
 pub trait ObsCrashHandler: Send {
@@ -27,11 +32,11 @@ impl ObsCrashHandler for ConsoleCrashHandler {
 lazy_static! {
     /// We are using this as global variable because there can only be one obs context
     pub static ref CRASH_HANDLER: Mutex<Box<dyn ObsCrashHandler>> = {
-        #[cfg(feature="dialog_crash_handler")]
+        #[cfg(all(windows, feature="dialog_crash_handler"))]
         {
             Mutex::new(Box::new(dialog::DialogCrashHandler::new()))
         }
-        #[cfg(not(feature="dialog_crash_handler"))]
+        #[cfg(not(all(windows, feature="dialog_crash_handler")))]
         {
             Mutex::new(Box::new(ConsoleCrashHandler {}))
         }
@@ -39,8 +44,8 @@ lazy_static! {
 }
 
 pub(crate) unsafe extern "C" fn main_crash_handler(
-    format: *const i8,
-    args: *mut i8,
+    format: *const c_char,
+    args: VaList,
     _params: *mut c_void,
 ) {
     let res = vsprintf::vsprintf(format, args);
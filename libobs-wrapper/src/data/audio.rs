@@ -29,6 +29,14 @@ impl ObsAudioInfo {
         Self::new(samples_per_second, speakers, 20, true)
     }
 
+    pub fn samples_per_sec(&self) -> ObsSamplesPerSecond {
+        self.samples_per_sec
+    }
+
+    pub fn speakers(&self) -> ObsSpeakerLayout {
+        self.speakers
+    }
+
     pub fn as_ptr(&self) -> Sendable<*const obs_audio_info2> {
         Sendable(self as *const Self as *const obs_audio_info2)
     }
@@ -3,7 +3,7 @@ use std::{ffi::CStr, sync::Arc};
 use libobs::obs_data_t;
 
 use crate::{
-    impl_obs_drop, run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable, utils::ObsError,
+    backend::backend, impl_obs_drop, run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable, utils::ObsError,
 };
 
 use super::{ObsData, _ObsDataDropGuard};
@@ -21,7 +21,7 @@ impl ImmutableObsData {
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn new(runtime: &ObsRuntime) -> Result<Self, ObsError> {
         let ptr = run_with_obs!(runtime, move || unsafe {
-            Sendable(libobs::obs_data_create())
+            Sendable(backend().obs_data_create())
         }).await?;
 
         Ok(ImmutableObsData {
@@ -36,6 +36,10 @@ impl ImmutableObsData {
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn from_raw(data: Sendable<*mut obs_data_t>, runtime: ObsRuntime) -> Self {
+        Self::from_raw_sync(data, runtime)
+    }
+
+    pub(crate) fn from_raw_sync(data: Sendable<*mut obs_data_t>, runtime: ObsRuntime) -> Self {
         ImmutableObsData {
             ptr: data.clone(),
             runtime: runtime.clone(),
@@ -46,11 +50,20 @@ impl ImmutableObsData {
         }
     }
 
+    /// Refers to the same `obs_data` as the given `ObsData` instead of copying it.
+    pub(crate) fn shared(data: &ObsData) -> Self {
+        ImmutableObsData {
+            ptr: data.as_ptr(),
+            runtime: data.runtime.clone(),
+            _drop_guard: data._drop_guard.clone(),
+        }
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn to_mutable(&self) -> Result<ObsData, ObsError> {
         let ptr = self.ptr.clone();
         let json = run_with_obs!(self.runtime, (ptr), move || unsafe {
-            Sendable(libobs::obs_data_get_json(ptr))
+            Sendable(backend().obs_data_get_json(ptr))
         }).await?;
 
         let json = unsafe { CStr::from_ptr(json.0) }.to_str()
@@ -80,5 +93,5 @@ impl From<ObsData> for ImmutableObsData {
 }
 
 impl_obs_drop!(ImmutableObsData, (ptr), move || unsafe {
-    libobs::obs_data_release(ptr)
+    backend().obs_data_release(ptr)
 });
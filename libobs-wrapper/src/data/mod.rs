@@ -3,12 +3,10 @@ use std::{
     sync::Arc,
 };
 
-use libobs::{
-    obs_data, obs_data_create, obs_data_release, obs_data_set_bool, obs_data_set_double,
-    obs_data_set_int, obs_data_set_string,
-};
+use libobs::obs_data;
 
 use crate::{
+    backend::backend,
     impl_obs_drop, run_with_obs,
     runtime::ObsRuntime,
     unsafe_send::Sendable,
@@ -54,7 +52,7 @@ impl ObsData {
     /// using `obs_data` directly from libobs.
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn new(runtime: ObsRuntime) -> Result<Self, ObsError> {
-        let obs_data = run_with_obs!(runtime, move || unsafe { Sendable(obs_data_create()) }).await?;
+        let obs_data = run_with_obs!(runtime, move || unsafe { Sendable(backend().obs_data_create()) }).await?;
 
         Ok(ObsData {
             obs_data: obs_data.clone(),
@@ -95,7 +93,7 @@ impl ObsData {
         run_with_obs!(
             self.runtime,
             (data_ptr, key_ptr, value_ptr),
-            move || unsafe { obs_data_set_string(data_ptr, key_ptr, value_ptr) }
+            move || unsafe { backend().obs_data_set_string(data_ptr, key_ptr, value_ptr) }
         )
         .await?;
 
@@ -116,7 +114,7 @@ impl ObsData {
         let data_ptr = self.obs_data.clone();
 
         run_with_obs!(self.runtime, (key_ptr, data_ptr), move || unsafe {
-            obs_data_set_int(data_ptr, key_ptr, value.into());
+            backend().obs_data_set_int(data_ptr, key_ptr, value.into());
         }).await?;
 
         Ok(self)
@@ -135,7 +133,7 @@ impl ObsData {
         let key_ptr = key.as_ptr();
         let data_ptr = self.obs_data.clone();
         run_with_obs!(self.runtime, (key_ptr, data_ptr), move || unsafe {
-            obs_data_set_bool(data_ptr, key_ptr, value.into());
+            backend().obs_data_set_bool(data_ptr, key_ptr, value.into());
         }).await?;
 
         Ok(self)
@@ -155,7 +153,7 @@ impl ObsData {
         let data_ptr = self.obs_data.clone();
 
         run_with_obs!(self.runtime, (key_ptr, data_ptr), move || unsafe {
-            obs_data_set_double(data_ptr, key_ptr, value.into());
+            backend().obs_data_set_double(data_ptr, key_ptr, value.into());
         }).await?;
 
         Ok(self)
@@ -167,7 +165,7 @@ impl ObsData {
 
         let cstr_ptr = Sendable(cstr.as_ptr());
         let result = run_with_obs!(runtime, (cstr_ptr), move || unsafe {
-            Sendable(libobs::obs_data_create_from_json(cstr_ptr))
+            Sendable(backend().obs_data_create_from_json(cstr_ptr))
         }).await?;
 
         if result.0.is_null() {
@@ -188,7 +186,7 @@ impl ObsData {
     pub async fn get_json(&self) -> Result<String, ObsError> {
         let data_ptr = self.obs_data.clone();
         let ptr = run_with_obs!(self.runtime, (data_ptr), move || unsafe {
-            Sendable(libobs::obs_data_get_json(data_ptr))
+            Sendable(backend().obs_data_get_json(data_ptr))
         }).await?;
 
         if ptr.0.is_null() {
@@ -203,7 +201,7 @@ impl ObsData {
 }
 
 impl_obs_drop!(_ObsDataDropGuard, (obs_data), move || unsafe {
-    obs_data_release(obs_data)
+    backend().obs_data_release(obs_data)
 });
 
 impl Clone for ObsData {
@@ -4,12 +4,12 @@ use std::{ffi::CStr, ptr};
 
 use anyhow::bail;
 use getters0::Getters;
-use libobs::{
-    audio_output, obs_encoder_set_audio, obs_encoder_set_video, obs_output, obs_output_active, obs_output_create, obs_output_get_last_error, obs_output_pause, obs_output_release, obs_output_set_audio_encoder, obs_output_set_video_encoder, obs_output_start, obs_output_stop, obs_output_update, video_output
-};
+use libobs::{audio_output, obs_output, video_output};
 
+use crate::backend::backend;
 use crate::enums::ObsOutputStopSignal;
 use crate::runtime::ObsRuntime;
+use crate::stats::{output_stats, ObsOutputStats};
 use crate::unsafe_send::Sendable;
 use crate::utils::async_sync::RwLock;
 use crate::utils::{AudioEncoderInfo, OutputInfo, VideoEncoderInfo};
@@ -32,7 +32,7 @@ struct _ObsDropGuard {
 }
 
 impl_obs_drop!(_ObsDropGuard, (output), move || unsafe {
-    obs_output_release(output);
+    backend().obs_output_release(output);
 });
 
 #[derive(Debug, Getters, Clone)]
@@ -113,7 +113,7 @@ impl ObsOutputRef {
                 };
 
                 let output = unsafe {
-                    obs_output_create(
+                    backend().obs_output_create(
                         id.as_ptr().0,
                         name.as_ptr().0,
                         settings_ptr.0,
@@ -196,8 +196,8 @@ impl ObsOutputRef {
             self.runtime,
             (encoder_ptr, output_ptr, handler),
             move || unsafe {
-                obs_encoder_set_video(encoder_ptr, handler.0);
-                obs_output_set_video_encoder(output_ptr, encoder_ptr);
+                backend().obs_encoder_set_video(encoder_ptr, handler.0);
+                backend().obs_output_set_video_encoder(output_ptr, encoder_ptr);
             }
         )
         .await?;
@@ -225,7 +225,7 @@ impl ObsOutputRef {
         let encoder_ptr = encoder.as_ptr();
 
         run_with_obs!(self.runtime, (output, encoder_ptr), move || unsafe {
-            obs_output_set_video_encoder(output, encoder_ptr);
+            backend().obs_output_set_video_encoder(output, encoder_ptr);
         })
         .await?;
 
@@ -257,7 +257,7 @@ impl ObsOutputRef {
     pub async fn update_settings(&mut self, settings: ObsData) -> Result<(), ObsError> {
         let output = self.output.clone();
         let output_active = run_with_obs!(self.runtime, (output), move || unsafe {
-            obs_output_active(output)
+            backend().obs_output_active(output)
         })
         .await?;
 
@@ -265,7 +265,7 @@ impl ObsOutputRef {
             let settings_ptr = settings.as_ptr();
 
             run_with_obs!(self.runtime, (output, settings_ptr), move || unsafe {
-                obs_output_update(output, settings_ptr)
+                backend().obs_output_update(output, settings_ptr)
             })
             .await?;
 
@@ -312,8 +312,8 @@ impl ObsOutputRef {
             self.runtime,
             (handler, encoder_ptr, output_ptr),
             move || unsafe {
-                obs_encoder_set_audio(encoder_ptr, handler);
-                obs_output_set_audio_encoder(output_ptr, encoder_ptr, mixer_idx);
+                backend().obs_encoder_set_audio(encoder_ptr, handler);
+                backend().obs_output_set_audio_encoder(output_ptr, encoder_ptr, mixer_idx);
             }
         )
         .await?;
@@ -344,7 +344,7 @@ impl ObsOutputRef {
         let encoder_ptr = encoder.encoder.clone();
         let output_ptr = self.output.clone();
         run_with_obs!(self.runtime, (output_ptr, encoder_ptr), move || unsafe {
-            obs_output_set_audio_encoder(output_ptr, encoder_ptr, mixer_idx)
+            backend().obs_output_set_audio_encoder(output_ptr, encoder_ptr, mixer_idx)
         })
         .await?;
 
@@ -372,13 +372,13 @@ impl ObsOutputRef {
     pub async fn start(&self) -> Result<(), ObsError> {
         let output_ptr = self.output.clone();
         let output_active = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-            obs_output_active(output_ptr)
+            backend().obs_output_active(output_ptr)
         })
         .await?;
 
         if !output_active {
             let res = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-                obs_output_start(output_ptr)
+                backend().obs_output_start(output_ptr)
             })
             .await?;
 
@@ -387,7 +387,7 @@ impl ObsOutputRef {
             }
 
             let err = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-                Sendable(obs_output_get_last_error(output_ptr))
+                Sendable(backend().obs_output_get_last_error(output_ptr))
             })
             .await?;
 
@@ -400,6 +400,24 @@ impl ObsOutputRef {
         Err(ObsError::OutputAlreadyActive)
     }
 
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    /// Returns whether the output is currently active.
+    pub async fn is_active(&self) -> Result<bool, ObsError> {
+        let output_ptr = self.output.clone();
+        run_with_obs!(self.runtime, (output_ptr), move || unsafe {
+            backend().obs_output_active(output_ptr)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    /// Returns the dropped and total frames, written bytes and congestion of this output.
+    pub async fn stats(&self) -> Result<ObsOutputStats, ObsError> {
+        let name = self.name.to_string();
+        let output_ptr = self.output.clone();
+        run_with_obs!(self.runtime, move || unsafe { output_stats(name, output_ptr) }).await
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     /// Pause or resume the output.
     /// 
@@ -414,13 +432,13 @@ impl ObsOutputRef {
     pub async fn pause(&self, pause: bool) -> Result<(), ObsError> {
         let output_ptr = self.output.clone();
         let output_active = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-            obs_output_active(output_ptr)
+            backend().obs_output_active(output_ptr)
         })
         .await?;
 
         if output_active {
             let res = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-                obs_output_pause(output_ptr, pause)
+                backend().obs_output_pause(output_ptr, pause)
             })
             .await?;
 
@@ -428,7 +446,7 @@ impl ObsOutputRef {
                 Ok(())
             } else {
                 let err = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-                    Sendable(obs_output_get_last_error(output_ptr))
+                    Sendable(backend().obs_output_get_last_error(output_ptr))
                 })
                 .await?;
     
@@ -456,14 +474,14 @@ impl ObsOutputRef {
     pub async fn stop(&mut self) -> Result<(), ObsError> {
         let output_ptr = self.output.clone();
         let output_active = run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-            obs_output_active(output_ptr)
+            backend().obs_output_active(output_ptr)
         })
         .await?;
 
         if output_active {
             let mut rx = self.signal_manager.on_stop().await?;
             run_with_obs!(self.runtime, (output_ptr), move || unsafe {
-                obs_output_stop(output_ptr)
+                backend().obs_output_stop(output_ptr)
             })
             .await?;
 
@@ -484,6 +502,11 @@ impl ObsOutputRef {
     pub fn as_ptr(&self) -> Sendable<*mut obs_output> {
         self.output.clone()
     }
+
+    /// Whether there are other references to this output which keep it alive.
+    pub(crate) fn is_shared(&self) -> bool {
+        Arc::strong_count(&self._drop_guard) > 1
+    }
 }
 
 pub unsafe fn process_stop_signal(
@@ -491,7 +514,7 @@ pub unsafe fn process_stop_signal(
 ) -> anyhow::Result<ObsOutputStopSignal> {
     let mut code = 0i64;
     let code_str = CString::new("code").unwrap();
-    let got_code = libobs::calldata_get_data(
+    let got_code = backend().calldata_get_data(
         cd,
         code_str.as_ptr(),
         &mut code as *mut _ as *mut std::ffi::c_void,
@@ -510,7 +533,7 @@ pub unsafe fn process_stop_signal(
     Ok(signal.unwrap())
 }
 
-impl_signal_manager!(|ptr| libobs::obs_output_get_signal_handler(ptr), ObsOutputSignals for ObsOutputRef<*mut libobs::obs_output>, [
+impl_signal_manager!(|ptr| backend().obs_output_get_signal_handler(ptr), ObsOutputSignals for ObsOutputRef<*mut libobs::obs_output>, [
     "start": {},
     "stop": {code: crate::enums::ObsOutputStopSignal},
     "pause": {},
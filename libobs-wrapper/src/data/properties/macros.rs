@@ -3,8 +3,8 @@ macro_rules! assert_type {
         use crate::data::properties::ObsPropertyType;
         use num_traits::FromPrimitive;
 
-        let p_type = unsafe { libobs::obs_property_get_type($name) };
-        let p_type = ObsPropertyType::from_i32(p_type);
+        let p_type = unsafe { crate::backend::backend().obs_property_get_type($name) };
+        let p_type = ObsPropertyType::from_i64(p_type as i64);
         if p_type.is_none_or(|e| !matches!(e, ObsPropertyType::$prop_type)) {
             panic!("Invalid property type: expected {:?}, got {:?}", ObsPropertyType::$prop_type, p_type);
         }
@@ -41,8 +41,8 @@ macro_rules! get_enum {
         paste::paste! {
             {
                 use num_traits::FromPrimitive;
-                let v = unsafe { libobs::[<obs_property_ $name>]($pointer_name) };
-                let v = $enum_name::from_i32(v);
+                let v = unsafe { crate::backend::backend().[<obs_property_ $name>]($pointer_name) };
+                let v = $enum_name::from_i64(v as i64);
 
                 if v.is_none() {
                     panic!("Invalid {} type got none", stringify!($name));
@@ -58,7 +58,7 @@ macro_rules! get_opt_str {
     ($pointer_name: ident, $name: ident) => {
         {
             paste::paste! {
-                let v = unsafe { libobs::[<obs_property_ $name>]($pointer_name) };
+                let v = unsafe { crate::backend::backend().[<obs_property_ $name>]($pointer_name) };
             }
             if v.is_null() {
                 None
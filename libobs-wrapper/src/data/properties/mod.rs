@@ -12,7 +12,7 @@ pub use enums::*;
 use num_traits::FromPrimitive;
 use types::*;
 
-use crate::{run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable, utils::{ObsError, ObsString}};
+use crate::{backend::backend, run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable, utils::{ObsError, ObsString}};
 
 #[derive(Debug, Clone)]
 pub enum ObsProperty {
@@ -63,7 +63,7 @@ pub(crate) async fn get_properties_inner(
     if properties_raw.0.is_null() {
         let ptr_clone = properties_raw.clone();
         run_with_obs!(runtime, (ptr_clone), move || {
-            unsafe { libobs::obs_properties_destroy(ptr_clone) };
+            unsafe { backend().obs_properties_destroy(ptr_clone) };
         }).await?;
 
         return Ok(HashMap::new());
@@ -71,16 +71,16 @@ pub(crate) async fn get_properties_inner(
 
     run_with_obs!(runtime, (properties_raw), move || {
         let mut result = HashMap::new();
-        let mut property = unsafe { libobs::obs_properties_first(properties_raw) };
+        let mut property = unsafe { backend().obs_properties_first(properties_raw) };
         while !property.is_null() {
-            let name = unsafe { libobs::obs_property_name(property) };
+            let name = unsafe { backend().obs_property_name(property) };
             let name = unsafe { CStr::from_ptr(name as _) };
             let name = name.to_string_lossy().to_string();
 
-            let p_type = unsafe { libobs::obs_property_get_type(property) };
-            let p_type = ObsPropertyType::from_i32(p_type);
+            let p_type = unsafe { backend().obs_property_get_type(property) };
+            let p_type = ObsPropertyType::from_i64(p_type as i64);
 
-            println!("Property: {:?}", name);
+            log::trace!("Property: {:?}", name);
             match p_type {
                 Some(p_type) => {
                     result.insert(name, p_type.to_property_struct(property));
@@ -91,10 +91,10 @@ pub(crate) async fn get_properties_inner(
             }
 
             // Move to the next property
-            unsafe { libobs::obs_property_next(&mut property) };
+            unsafe { backend().obs_property_next(&mut property) };
         }
 
-        unsafe { libobs::obs_properties_destroy(properties_raw) };
+        unsafe { backend().obs_properties_destroy(properties_raw) };
         result
     }).await
 }
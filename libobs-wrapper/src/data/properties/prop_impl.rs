@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
+    backend::backend,
     data::output::ObsOutputRef,
     runtime::ObsRuntime,
     sources::ObsSourceRef,
@@ -30,7 +31,7 @@ impl ObsPropertyObjectPrivate for ObsSourceRef {
             .run_with_obs_result(move || unsafe {
                 let source_ptr = source_ptr;
 
-                Sendable(libobs::obs_source_properties(source_ptr.0))
+                Sendable(backend().obs_source_properties(source_ptr.0))
             })
             .await
             .map_err(|e| ObsError::InvocationError(e.to_string()))
@@ -45,7 +46,7 @@ impl ObsPropertyObjectPrivate for ObsSourceRef {
         runtime
             .run_with_obs_result(move || unsafe {
                 let id_ptr = id_ptr;
-                Sendable(libobs::obs_get_source_properties(id_ptr.0))
+                Sendable(backend().obs_get_source_properties(id_ptr.0))
             })
             .await
             .map_err(|e| ObsError::InvocationError(e.to_string()))
@@ -72,7 +73,7 @@ impl ObsPropertyObjectPrivate for ObsOutputRef {
             .run_with_obs_result(move || unsafe {
                 let output_ptr = output_ptr;
 
-                Sendable(libobs::obs_output_properties(output_ptr.0))
+                Sendable(backend().obs_output_properties(output_ptr.0))
             })
             .await
             .map_err(|e| ObsError::InvocationError(e.to_string()))
@@ -89,7 +90,7 @@ impl ObsPropertyObjectPrivate for ObsOutputRef {
             .run_with_obs_result(move || unsafe {
                 let id_ptr = id_ptr;
 
-                Sendable(libobs::obs_get_output_properties(id_ptr.0))
+                Sendable(backend().obs_get_output_properties(id_ptr.0))
             })
             .await
             .map_err(|e| ObsError::InvocationError(e.to_string()))
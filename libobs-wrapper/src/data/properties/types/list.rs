@@ -1,4 +1,5 @@
 use super::PropertyCreationInfo;
+use crate::backend::backend;
 use crate::data::properties::{assert_type, get_enum, ObsComboFormat, ObsComboType};
 use getters0::Getters;
 use std::ffi::CStr;
@@ -53,30 +54,30 @@ impl From<PropertyCreationInfo> for ObsListProperty {
         let list_type = get_enum!(pointer, list_type, ObsComboType);
         let format = get_enum!(pointer, list_format, ObsComboFormat);
 
-        let count = unsafe { libobs::obs_property_list_item_count(pointer) };
+        let count = unsafe { backend().obs_property_list_item_count(pointer) };
         let mut items = Vec::with_capacity(count);
 
         for i in 0..count {
             let list_name = unsafe {
-                CStr::from_ptr(libobs::obs_property_list_item_name(pointer, i))
+                CStr::from_ptr(backend().obs_property_list_item_name(pointer, i))
                     .to_str()
                     .unwrap_or_default()
                     .to_string()
             };
-            let is_disabled = unsafe { libobs::obs_property_list_item_disabled(pointer, i) };
+            let is_disabled = unsafe { backend().obs_property_list_item_disabled(pointer, i) };
             let value = match format {
                 ObsComboFormat::Invalid => ObsListItemValue::Invalid,
                 ObsComboFormat::Int => {
-                    let int_val = unsafe { libobs::obs_property_list_item_int(pointer, i) };
+                    let int_val = unsafe { backend().obs_property_list_item_int(pointer, i) };
                     ObsListItemValue::Int(int_val)
                 }
                 ObsComboFormat::Float => {
-                    let float_val = unsafe { libobs::obs_property_list_item_float(pointer, i) };
+                    let float_val = unsafe { backend().obs_property_list_item_float(pointer, i) };
                     ObsListItemValue::Float(float_val)
                 }
                 ObsComboFormat::String => {
                     let string_val = unsafe {
-                        CStr::from_ptr(libobs::obs_property_list_item_string(pointer, i))
+                        CStr::from_ptr(backend().obs_property_list_item_string(pointer, i))
                             .to_str()
                             .unwrap_or_default()
                             .to_string()
@@ -84,7 +85,7 @@ impl From<PropertyCreationInfo> for ObsListProperty {
                     ObsListItemValue::String(string_val)
                 }
                 ObsComboFormat::Bool => {
-                    let bool_val = unsafe { libobs::obs_property_list_item_bool(pointer, i) };
+                    let bool_val = unsafe { backend().obs_property_list_item_bool(pointer, i) };
                     ObsListItemValue::Bool(bool_val)
                 }
             };
@@ -24,6 +24,8 @@ use std::ffi::CStr;
 pub use button::*;
 pub use editable_list::*;
 use libobs::obs_property;
+
+use crate::backend::backend;
 pub use list::*;
 pub use number::*;
 pub use path::*;
@@ -33,11 +35,11 @@ use super::{macros::impl_general_property, ObsProperty, ObsPropertyType};
 
 impl ObsPropertyType {
     pub fn to_property_struct(&self, pointer: *mut obs_property) -> ObsProperty {
-        let name = unsafe { libobs::obs_property_name(pointer) };
+        let name = unsafe { backend().obs_property_name(pointer) };
         let name = unsafe { CStr::from_ptr(name) };
         let name = name.to_string_lossy().to_string();
 
-        let description = unsafe { libobs::obs_property_description(pointer) };
+        let description = unsafe { backend().obs_property_description(pointer) };
         let description = if description.is_null() {
             None
         } else {
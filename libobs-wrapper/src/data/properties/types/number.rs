@@ -31,11 +31,11 @@ macro_rules! impl_from_property {
                     use crate::data::properties::ObsNumberType;
                     use num_traits::FromPrimitive;
 
-                    let min = unsafe { libobs::[<obs_property_ $obs_number_name _min>](pointer) };
-                    let max = unsafe { libobs::[<obs_property_ $obs_number_name _max>](pointer) };
-                    let step = unsafe { libobs::[<obs_property_ $obs_number_name _step>](pointer)};
+                    let min = unsafe { crate::backend::backend().[<obs_property_ $obs_number_name _min>](pointer) };
+                    let max = unsafe { crate::backend::backend().[<obs_property_ $obs_number_name _max>](pointer) };
+                    let step = unsafe { crate::backend::backend().[<obs_property_ $obs_number_name _step>](pointer)};
 
-                    let suffix = unsafe { libobs::[<obs_property_ $obs_number_name _suffix>](pointer) };
+                    let suffix = unsafe { crate::backend::backend().[<obs_property_ $obs_number_name _suffix>](pointer) };
                     let suffix = if suffix.is_null() {
                         String::new()
                     } else {
@@ -44,8 +44,8 @@ macro_rules! impl_from_property {
                         suffix.to_string()
                     };
 
-                    let number_type = unsafe { libobs::[<obs_property_ $obs_number_name _type >](pointer) };
-                    let number_type = ObsNumberType::from_i32(number_type);
+                    let number_type = unsafe { crate::backend::backend().[<obs_property_ $obs_number_name _type >](pointer) };
+                    let number_type = ObsNumberType::from_i64(number_type as i64);
                     if number_type.is_none() {
                         panic!("Invalid number type got none");
                     }
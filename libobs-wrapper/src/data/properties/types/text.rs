@@ -1,5 +1,6 @@
 use getters0::Getters;
 
+use crate::backend::backend;
 use crate::data::properties::{get_enum, macros::assert_type, ObsTextInfoType, ObsTextType};
 
 use super::PropertyCreationInfo;
@@ -28,8 +29,8 @@ impl From<PropertyCreationInfo> for ObsTextProperty {
         let info_type = get_enum!(pointer, text_info_type, ObsTextInfoType);
         let text_type = get_enum!(pointer, text_type, ObsTextType);
 
-        let monospace = unsafe { libobs::obs_property_text_monospace(pointer) };
-        let word_wrap = unsafe { libobs::obs_property_text_info_word_wrap(pointer) };
+        let monospace = unsafe { backend().obs_property_text_monospace(pointer) };
+        let word_wrap = unsafe { backend().obs_property_text_info_word_wrap(pointer) };
 
         ObsTextProperty {
             name,
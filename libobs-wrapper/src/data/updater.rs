@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
-use libobs::{
-    obs_data, obs_data_set_bool, obs_data_set_double, obs_data_set_int, obs_data_set_string,
-};
+use libobs::obs_data;
 
 use crate::{
+    backend::backend,
     run_with_obs,
     unsafe_send::Sendable,
     utils::{ObsError, ObsString},
@@ -18,6 +17,10 @@ pub enum ObsDataChange {
     Int(ObsString, i64),
     Bool(ObsString, bool),
     Double(ObsString, f64),
+    /// A nested object with the given values
+    Object(ObsString, Vec<ObsDataChange>),
+    /// An array of objects, each with the given values
+    Array(ObsString, Vec<Vec<ObsDataChange>>),
 }
 
 #[derive(Debug)]
@@ -64,6 +67,28 @@ impl ObsDataUpdater {
         self
     }
 
+    /// Sets a nested object, like the `font` of text sources.
+    pub fn set_obj_ref(&mut self, key: impl Into<ObsString>, values: Vec<ObsDataChange>) {
+        let key = key.into();
+        self.changes.push(ObsDataChange::Object(key, values));
+    }
+
+    pub fn set_obj(mut self, key: impl Into<ObsString>, values: Vec<ObsDataChange>) -> Self {
+        self.set_obj_ref(key, values);
+        self
+    }
+
+    /// Sets an array of objects, like the `files` of the slideshow source.
+    pub fn set_array_ref(&mut self, key: impl Into<ObsString>, items: Vec<Vec<ObsDataChange>>) {
+        let key = key.into();
+        self.changes.push(ObsDataChange::Array(key, items));
+    }
+
+    pub fn set_array(mut self, key: impl Into<ObsString>, items: Vec<Vec<ObsDataChange>>) -> Self {
+        self.set_array_ref(key, items);
+        self
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn update(self) -> Result<(), ObsError> {
         let ObsDataUpdater {
@@ -74,22 +99,46 @@ impl ObsDataUpdater {
 
         let obs_data = obs_data.clone();
         run_with_obs!(_drop_guard.runtime, (obs_data), move || unsafe {
-            for change in changes {
-                match change {
-                    ObsDataChange::String(key, value) => {
-                        obs_data_set_string(obs_data, key.as_ptr().0, value.as_ptr().0)
-                    }
-                    ObsDataChange::Int(key, value) => {
-                        obs_data_set_int(obs_data, key.as_ptr().0, value.into())
-                    }
-                    ObsDataChange::Bool(key, value) => {
-                        obs_data_set_bool(obs_data, key.as_ptr().0, value.into())
-                    }
-                    ObsDataChange::Double(key, value) => {
-                        obs_data_set_double(obs_data, key.as_ptr().0, value)
-                    }
-                };
-            }
+            Self::apply_changes(obs_data, changes);
         }).await
     }
+
+    /// Applies the changes right away, this must be called on the OBS thread.
+    pub(crate) unsafe fn apply_changes(obs_data: *mut obs_data, changes: Vec<ObsDataChange>) {
+        let backend = backend();
+        for change in changes {
+            match change {
+                ObsDataChange::String(key, value) => {
+                    backend.obs_data_set_string(obs_data, key.as_ptr().0, value.as_ptr().0)
+                }
+                ObsDataChange::Int(key, value) => {
+                    backend.obs_data_set_int(obs_data, key.as_ptr().0, value.into())
+                }
+                ObsDataChange::Bool(key, value) => {
+                    backend.obs_data_set_bool(obs_data, key.as_ptr().0, value.into())
+                }
+                ObsDataChange::Double(key, value) => {
+                    backend.obs_data_set_double(obs_data, key.as_ptr().0, value)
+                }
+                ObsDataChange::Object(key, values) => {
+                    let obj = backend.obs_data_create();
+                    Self::apply_changes(obj, values);
+                    backend.obs_data_set_obj(obs_data, key.as_ptr().0, obj);
+                    backend.obs_data_release(obj);
+                }
+                ObsDataChange::Array(key, items) => {
+                    let array = backend.obs_data_array_create();
+                    for values in items {
+                        let item = backend.obs_data_create();
+                        Self::apply_changes(item, values);
+                        backend.obs_data_array_push_back(array, item);
+                        backend.obs_data_release(item);
+                    }
+
+                    backend.obs_data_set_array(obs_data, key.as_ptr().0, array);
+                    backend.obs_data_array_release(array);
+                }
+            };
+        }
+    }
 }
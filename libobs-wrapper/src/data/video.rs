@@ -107,7 +107,9 @@ impl ObsVideoInfoBuilder {
     /// if the OS supports DX11 (Windows)
     /// or not (OpenGL on MacOS and Unix).
     pub fn new() -> Self {
-        let display_infos = DisplayInfo::all().unwrap();
+        // Headless machines (e.g. a Linux server without an X11 / Wayland session)
+        // have no displays, so we just fall back to 1080p there.
+        let display_infos = DisplayInfo::all().unwrap_or_default();
         let (mut width, mut height) = (1920, 1080);
         for display_info in display_infos {
             if display_info.is_primary {
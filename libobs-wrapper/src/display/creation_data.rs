@@ -1,3 +1,5 @@
+use std::ffi::c_void;
+
 use libobs::{gs_init_data, gs_window};
 use num_traits::ToPrimitive;
 
@@ -10,9 +12,18 @@ use super::{GsColorFormat, GsZstencilFormat};
 pub struct ObsDisplayCreationData {
     #[cfg(target_family = "windows")]
     pub(super) window_handle: Sendable<windows::Win32::Foundation::HWND>,
+    #[cfg(target_family = "windows")]
     pub(super) create_child: bool,
+    #[cfg(target_family = "windows")]
     pub(super) x: i32,
+    #[cfg(target_family = "windows")]
     pub(super) y: i32,
+    /// The id of the X11 window to render to
+    #[cfg(target_os = "linux")]
+    pub(super) window_id: u32,
+    /// The X11 `Display*` connection the window belongs to
+    #[cfg(target_os = "linux")]
+    pub(super) x_display: Sendable<*mut c_void>,
     pub(super) width: u32,
     pub(super) height: u32,
     pub(super) format: GsColorFormat,
@@ -25,7 +36,6 @@ pub struct ObsDisplayCreationData {
 impl ObsDisplayCreationData {
     #[cfg(target_family = "windows")]
     pub fn new(window_handle: isize, x: i32, y: i32, width: u32, height: u32) -> Self {
-        use std::os::raw::c_void;
         use windows::Win32::Foundation::HWND;
 
         Self {
@@ -43,6 +53,24 @@ impl ObsDisplayCreationData {
         }
     }
 
+    /// Creates the data for a display rendering to the X11 window `window_id`, which belongs to
+    /// the connection `x_display` (an Xlib `Display*`). The display should be resized with
+    /// [`ObsDisplayRef::set_size`](super::ObsDisplayRef::set_size) whenever the window is.
+    #[cfg(target_os = "linux")]
+    pub fn new(window_id: u32, x_display: *mut c_void, width: u32, height: u32) -> Self {
+        Self {
+            window_id,
+            x_display: Sendable(x_display),
+            format: GsColorFormat::BGRA,
+            zsformat: GsZstencilFormat::ZSNone,
+            width,
+            height,
+            adapter: 0,
+            backbuffers: 0,
+            background_color: 0,
+        }
+    }
+
     pub fn set_format(mut self, format: GsColorFormat) -> Self {
         self.format = format;
         self
@@ -68,6 +96,7 @@ impl ObsDisplayCreationData {
         self
     }
 
+    #[cfg(target_family = "windows")]
     pub fn set_create_child(mut self, should_create: bool) -> Self {
         self.create_child = should_create;
         self
@@ -1,28 +1,38 @@
-//! For this display method to work, another preview window has to be created in order to create a swapchain
+//! For this display method to work on Windows, another preview window has to be created in order to create a swapchain
 //! This is because the main window renderer is already handled by other processes
+//!
+//! On Linux, OBS renders to the X11 window passed in the creation data directly.
 
 mod creation_data;
 mod enums;
+mod misc;
+#[cfg(windows)]
 mod window_manager;
 
 pub use creation_data::*;
 pub use enums::*;
+pub use misc::*;
+#[cfg(windows)]
 pub use window_manager::*;
 
 use std::{
     ffi::c_void,
     marker::PhantomPinned,
-    sync::{atomic::AtomicUsize, Arc},
+    sync::{atomic::AtomicUsize, Arc, Weak},
 };
 
 use libobs::{
     gs_ortho, gs_projection_pop, gs_projection_push, gs_set_viewport, gs_viewport_pop,
-    gs_viewport_push, obs_get_video_info, obs_render_main_texture, obs_video_info,
+    gs_viewport_push, obs_get_video_info, obs_render_main_texture, obs_source_t,
+    obs_source_video_render, obs_video_info,
 };
 
 use crate::{
-    run_with_obs,
+    backend::backend,
+    impl_obs_drop, run_with_obs,
     runtime::ObsRuntime,
+    rw_lock_blocking_read,
+    scenes::ObsSceneRef,
     unsafe_send::Sendable,
     utils::{async_sync::RwLock, ObsError},
 };
@@ -41,7 +51,12 @@ pub struct ObsDisplayRef {
     _guard: Arc<RwLock<_DisplayDropGuard>>,
 
     // Keep for window, manager is accessed by render thread as well so Arc and RwLock
+    #[cfg(windows)]
     manager: Arc<RwLock<DisplayWindowManager>>,
+
+    /// The scene this display renders, the output of the main channel if `None`.
+    /// Read by the render thread as well.
+    scene: Arc<RwLock<Option<ShownScene>>>,
     /// This must not be moved in memory as the draw callback is a raw pointer to this struct
     _fixed_in_heap: PhantomPinned,
 
@@ -52,7 +67,11 @@ pub struct ObsDisplayRef {
 unsafe extern "C" fn render_display(data: *mut c_void, _cx: u32, _cy: u32) {
     let s = &*(data as *mut ObsDisplayRef);
 
+    #[cfg(windows)]
     let (width, height) = s.get_size_blocking();
+    // There is no preview window on Linux, the display has the size of the window it renders to
+    #[cfg(not(windows))]
+    let (width, height) = (_cx, _cy);
 
     let mut ovi: obs_video_info = std::mem::zeroed();
     obs_get_video_info(&mut ovi);
@@ -71,14 +90,17 @@ unsafe extern "C" fn render_display(data: *mut c_void, _cx: u32, _cy: u32) {
     gs_set_viewport(0, 0, width as i32, height as i32);
     //draw_backdrop(&s.buffers, ovi.base_width as f32, ovi.base_height as f32);
 
-    obs_render_main_texture();
+    match rw_lock_blocking_read!(s.scene).as_ref() {
+        Some(shown) => obs_source_video_render(shown.showing.source.0),
+        None => obs_render_main_texture(),
+    }
 
     gs_projection_pop();
     gs_viewport_pop();
 }
 
 impl ObsDisplayRef {
-    #[cfg(target_family = "windows")]
+    #[cfg(windows)]
     /// Call initialize to ObsDisplay#create the display
     /// NOTE: This must be pinned to prevent the draw callbacks from having a invalid pointer. DO NOT UNPIN
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
@@ -86,14 +108,8 @@ impl ObsDisplayRef {
         data: creation_data::ObsDisplayCreationData,
         runtime: ObsRuntime,
     ) -> anyhow::Result<std::pin::Pin<Box<Self>>> {
-        use std::sync::atomic::Ordering;
-
-        use anyhow::bail;
         use creation_data::ObsDisplayCreationData;
         use libobs::gs_window;
-        use window_manager::DisplayWindowManager;
-
-        use crate::run_with_obs;
 
         let ObsDisplayCreationData {
             x,
@@ -106,7 +122,7 @@ impl ObsDisplayRef {
             ..
         } = data.clone();
 
-        let mut manager = if create_child {
+        let manager = if create_child {
             DisplayWindowManager::new_child(window_handle.clone(), x, y, width, height)?
         } else {
             DisplayWindowManager::new(window_handle.clone(), x, y, width, height)
@@ -117,6 +133,46 @@ impl ObsDisplayRef {
             hwnd: preview_window_handle.0 .0,
         }));
 
+        Self::create(init_data, background_color, manager, runtime).await
+    }
+
+    #[cfg(target_os = "linux")]
+    /// Creates the display on the X11 window of the creation data, OBS renders to it directly.
+    /// NOTE: This must be pinned to prevent the draw callbacks from having a invalid pointer. DO NOT UNPIN
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        data: creation_data::ObsDisplayCreationData,
+        runtime: ObsRuntime,
+    ) -> anyhow::Result<std::pin::Pin<Box<Self>>> {
+        use creation_data::ObsDisplayCreationData;
+        use libobs::gs_window;
+
+        let ObsDisplayCreationData {
+            window_id,
+            x_display,
+            background_color,
+            ..
+        } = data.clone();
+
+        let init_data = Sendable(data.build(gs_window {
+            id: window_id,
+            display: x_display.0,
+        }));
+
+        Self::create(init_data, background_color, runtime).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn create(
+        init_data: Sendable<libobs::gs_init_data>,
+        background_color: u32,
+        #[cfg(windows)] mut manager: DisplayWindowManager,
+        runtime: ObsRuntime,
+    ) -> anyhow::Result<std::pin::Pin<Box<Self>>> {
+        use std::sync::atomic::Ordering;
+
+        use anyhow::bail;
+
         log::trace!("Creating obs display...");
         let display = run_with_obs!(runtime, (init_data), move || unsafe {
             Sendable(libobs::obs_display_create(&init_data, background_color))
@@ -126,10 +182,16 @@ impl ObsDisplayRef {
             bail!("OBS failed to create display");
         }
 
-        manager.obs_display = Some(display.clone());
+        #[cfg(windows)]
+        {
+            manager.obs_display = Some(display.clone());
+        }
+
         let mut instance = Box::pin(Self {
             display: display.clone(),
+            #[cfg(windows)]
             manager: Arc::new(RwLock::new(manager)),
+            scene: Arc::new(RwLock::new(None)),
             id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             _guard: Arc::new(RwLock::new(_DisplayDropGuard {
                 display,
@@ -145,12 +207,10 @@ impl ObsDisplayRef {
 
         instance._guard.write().await.self_ptr = Some(instance_ptr.clone());
 
-        let pos = instance.get_pos().await;
         log::trace!(
-            "Adding draw callback with display {:?} and draw callback params at {:?} (pos is {:?})...",
+            "Adding draw callback with display {:?} and draw callback params at {:?}...",
             instance.display,
             instance_ptr,
-            pos
         );
         let display_ptr = instance.display.clone();
         run_with_obs!(runtime, (display_ptr, instance_ptr), move || unsafe {
@@ -160,9 +220,117 @@ impl ObsDisplayRef {
         Ok(instance)
     }
 
+    /// Resizes the display, call this whenever the window it renders to is resized.
+    #[cfg(not(windows))]
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_size(&self, width: u32, height: u32) -> Result<(), ObsError> {
+        let display_ptr = self.display.clone();
+        run_with_obs!(self.runtime, (display_ptr), move || unsafe {
+            libobs::obs_display_resize(display_ptr, width, height);
+        }).await
+    }
+
     pub fn id(&self) -> usize {
         self.id
     }
+
+    /// Renders the given scene instead of the output of the main channel, for example to
+    /// show a preview of a scene that isn't live. `None` renders the main channel again.
+    ///
+    /// The scene is marked as showing while the display renders it, so its sources are
+    /// active just like the ones in the preview of OBS Studio.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_scene(&self, scene: Option<&ObsSceneRef>) -> Result<(), ObsError> {
+        set_shown_scene(&self.scene, scene, &self.runtime).await
+    }
+
+    /// The scene this display renders, `None` if it renders the main channel.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn scene(&self) -> Option<ObsSceneRef> {
+        self.scene.read().await.as_ref().map(|s| s.scene.clone())
+    }
+
+    /// The scene slot of this display, which doesn't keep the display alive.
+    pub(crate) fn scene_slot(&self) -> DisplaySceneSlot {
+        DisplaySceneSlot {
+            scene: Arc::downgrade(&self.scene),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// Marks the source as showing until it is dropped.
+#[derive(Debug)]
+struct _ShowingGuard {
+    source: Sendable<*mut obs_source_t>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_ShowingGuard, (source), move || unsafe {
+    backend().obs_source_dec_showing(source);
+});
+
+/// A scene rendered by a display, shown for as long as it is set.
+#[derive(Debug)]
+struct ShownScene {
+    // Dropped first, so the scene is still alive when it stops showing
+    showing: _ShowingGuard,
+    scene: ObsSceneRef,
+}
+
+impl ShownScene {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn new(scene: &ObsSceneRef, runtime: &ObsRuntime) -> Result<Self, ObsError> {
+        let source = scene.get_scene_source_ptr().await?;
+        run_with_obs!(runtime, (source), move || unsafe {
+            backend().obs_source_inc_showing(source);
+        })
+        .await?;
+
+        Ok(Self {
+            showing: _ShowingGuard {
+                source,
+                runtime: runtime.clone(),
+            },
+            scene: scene.clone(),
+        })
+    }
+}
+
+/// Replaces the scene of the slot. The new scene is shown before the previous one is
+/// hidden, so sources in both scenes stay active.
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+async fn set_shown_scene(
+    slot: &RwLock<Option<ShownScene>>,
+    scene: Option<&ObsSceneRef>,
+    runtime: &ObsRuntime,
+) -> Result<(), ObsError> {
+    let shown = match scene {
+        Some(scene) => Some(ShownScene::new(scene, runtime).await?),
+        None => None,
+    };
+
+    let previous = std::mem::replace(&mut *slot.write().await, shown);
+    drop(previous);
+    Ok(())
+}
+
+/// The scene slot of a display, e.g. to keep showing the preview of studio mode on it.
+#[derive(Debug, Clone)]
+pub(crate) struct DisplaySceneSlot {
+    scene: Weak<RwLock<Option<ShownScene>>>,
+    runtime: ObsRuntime,
+}
+
+impl DisplaySceneSlot {
+    /// Sets the scene the display renders, unless the display has been dropped already.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn set_scene(&self, scene: &ObsSceneRef) -> Result<(), ObsError> {
+        match self.scene.upgrade() {
+            Some(slot) => set_shown_scene(&slot, Some(scene), &self.runtime).await,
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -192,6 +360,10 @@ impl _DisplayDropGuard {
 
 impl Drop for _DisplayDropGuard {
     fn drop(&mut self) {
+        if self.runtime.is_shut_down() {
+            return;
+        }
+
         let display = self.display.clone();
         let self_ptr = self.self_ptr.clone();
         let r = self.runtime.clone();
@@ -202,10 +374,8 @@ impl Drop for _DisplayDropGuard {
         #[cfg(feature = "blocking")]
         let r = _DisplayDropGuard::inner_drop(r, display, self_ptr);
 
-        if std::thread::panicking() {
-            return;
+        if let Err(e) = r {
+            log::error!("Failed to destroy the display: {:?}", e);
         }
-
-        r.unwrap();
     }
 }
@@ -31,8 +31,6 @@ use crate::unsafe_send::Sendable;
 
 mod position_trait;
 mod show_hide;
-mod misc;
-pub use misc::*;
 pub use position_trait::WindowPositionTrait;
 pub use show_hide::ShowHideTrait;
 
@@ -1,7 +1,8 @@
-use libobs::{audio_output, obs_audio_encoder_create, obs_encoder_release, obs_encoder_set_audio};
+use libobs::audio_output;
 use std::{borrow::Borrow, ptr};
 
 use crate::{
+    backend::backend,
     data::ObsData,
     impl_obs_drop, run_with_obs,
     runtime::ObsRuntime,
@@ -50,7 +51,7 @@ impl ObsAudioEncoder {
             runtime,
             (hotkey_data_ptr, settings_ptr, id_ptr, name_ptr),
             move || unsafe {
-                let ptr = obs_audio_encoder_create(id_ptr, name_ptr, settings_ptr, mixer_idx, hotkey_data_ptr);
+                let ptr = backend().obs_audio_encoder_create(id_ptr, name_ptr, settings_ptr, mixer_idx, hotkey_data_ptr);
                 Sendable(ptr)
             }
         ).await?;
@@ -78,11 +79,11 @@ impl ObsAudioEncoder {
         let encoder_ptr = self.encoder.clone();
 
         run_with_obs!(self.runtime, (handler, encoder_ptr), move || unsafe {
-            obs_encoder_set_audio(encoder_ptr, handler)
+            backend().obs_encoder_set_audio(encoder_ptr, handler)
         }).await
     }
 }
 
 impl_obs_drop!(ObsAudioEncoder, (encoder), move || unsafe {
-    obs_encoder_release(encoder)
+    backend().obs_encoder_release(encoder)
 });
@@ -1,10 +1,9 @@
 use std::{ffi::CStr, os::raw::c_char, str::FromStr};
 
-use num_traits::ToPrimitive;
-
 use crate::{
+    backend::backend,
     context::ObsContext,
-    enums::ObsEncoderType,
+    enums::{ObsEncoderType, OsEnumType},
     run_with_obs,
     runtime::ObsRuntime,
     utils::{ObsError, ENCODER_HIDE_FLAGS},
@@ -53,20 +52,21 @@ async fn get_encoders_raw(
     encoder_type: ObsEncoderType,
     runtime: &ObsRuntime,
 ) -> Result<Vec<String>, ObsError> {
-    let type_primitive = encoder_type.to_i32().unwrap();
+    let type_primitive = encoder_type as OsEnumType;
 
     run_with_obs!(runtime, move || {
+        let backend = backend();
         let mut n = 0;
         let mut encoders = Vec::new();
 
         let mut ptr: *const c_char = unsafe { std::mem::zeroed() };
-        while unsafe { libobs::obs_enum_encoder_types(n, &mut ptr) } {
+        while unsafe { backend.obs_enum_encoder_types(n, &mut ptr) } {
             n += 1;
             let cstring = unsafe { CStr::from_ptr(ptr) };
             if let Ok(enc) = cstring.to_str() {
                 unsafe {
-                    let is_hidden = libobs::obs_get_encoder_caps(ptr) & ENCODER_HIDE_FLAGS != 0;
-                    if is_hidden || libobs::obs_get_encoder_type(ptr) != type_primitive {
+                    let is_hidden = backend.obs_get_encoder_caps(ptr) & ENCODER_HIDE_FLAGS != 0;
+                    if is_hidden || backend.obs_get_encoder_type(ptr) != type_primitive {
                         continue;
                     }
                 }
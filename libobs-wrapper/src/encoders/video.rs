@@ -1,9 +1,8 @@
-use libobs::{
-    obs_encoder, obs_encoder_release, obs_encoder_set_video, obs_video_encoder_create, video_output,
-};
+use libobs::{obs_encoder, video_output};
 use std::ptr;
 
 use crate::{
+    backend::backend,
     data::ObsData,
     impl_obs_drop, run_with_obs,
     runtime::ObsRuntime,
@@ -50,7 +49,7 @@ impl ObsVideoEncoder {
             runtime,
             (id_ptr, name_ptr, hotkey_data_ptr, settings_ptr),
             move || unsafe {
-                let ptr = obs_video_encoder_create(id_ptr, name_ptr, settings_ptr, hotkey_data_ptr);
+                let ptr = backend().obs_video_encoder_create(id_ptr, name_ptr, settings_ptr, hotkey_data_ptr);
                 Sendable(ptr)
             }
         ).await?;
@@ -78,11 +77,11 @@ impl ObsVideoEncoder {
     pub async fn set_video_context(&mut self, handler: Sendable<*mut video_output>) -> Result<(), ObsError> {
         let self_ptr = self.as_ptr();
         run_with_obs!(self.runtime, (handler, self_ptr), move || unsafe {
-            Sendable(obs_encoder_set_video(self_ptr, handler));
+            Sendable(backend().obs_encoder_set_video(self_ptr, handler));
         }).await
     }
 }
 
 impl_obs_drop!(ObsVideoEncoder, (encoder), move || unsafe {
-    obs_encoder_release(encoder);
+    backend().obs_encoder_release(encoder);
 });
@@ -83,6 +83,160 @@ pub enum ObsScaleType {
     Point = libobs::obs_scale_type_OBS_SCALE_POINT,
 }
 
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how a scene item is scaled within its bounds,
+/// or whether it has bounds at all.
+pub enum ObsBoundsType {
+    /// No bounds
+    None = libobs::obs_bounds_type_OBS_BOUNDS_NONE,
+    /// Stretches to the bounds, ignoring the scale of the item
+    Stretch = libobs::obs_bounds_type_OBS_BOUNDS_STRETCH,
+    /// Scales to fit inside the bounds, keeping the aspect ratio
+    ScaleInner = libobs::obs_bounds_type_OBS_BOUNDS_SCALE_INNER,
+    /// Scales to cover the bounds, keeping the aspect ratio
+    ScaleOuter = libobs::obs_bounds_type_OBS_BOUNDS_SCALE_OUTER,
+    ScaleToWidth = libobs::obs_bounds_type_OBS_BOUNDS_SCALE_TO_WIDTH,
+    ScaleToHeight = libobs::obs_bounds_type_OBS_BOUNDS_SCALE_TO_HEIGHT,
+    /// No scaling, the bounds are only the maximum size
+    MaxOnly = libobs::obs_bounds_type_OBS_BOUNDS_MAX_ONLY,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes whether the audio of a source is played on the monitoring device.
+pub enum ObsMonitoringType {
+    /// The audio is only sent to the outputs
+    None = libobs::obs_monitoring_type_OBS_MONITORING_TYPE_NONE,
+    /// The audio is only played on the monitoring device, outputs don't get it
+    MonitorOnly = libobs::obs_monitoring_type_OBS_MONITORING_TYPE_MONITOR_ONLY,
+    /// The audio is played on the monitoring device and sent to the outputs
+    MonitorAndOutput = libobs::obs_monitoring_type_OBS_MONITORING_TYPE_MONITOR_AND_OUTPUT,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes the playback state of a media source, like the ffmpeg or VLC source.
+pub enum ObsMediaState {
+    /// The source doesn't play media or hasn't opened any yet
+    None = libobs::obs_media_state_OBS_MEDIA_STATE_NONE,
+    Playing = libobs::obs_media_state_OBS_MEDIA_STATE_PLAYING,
+    Opening = libobs::obs_media_state_OBS_MEDIA_STATE_OPENING,
+    Buffering = libobs::obs_media_state_OBS_MEDIA_STATE_BUFFERING,
+    Paused = libobs::obs_media_state_OBS_MEDIA_STATE_PAUSED,
+    Stopped = libobs::obs_media_state_OBS_MEDIA_STATE_STOPPED,
+    /// The media has been played until its end
+    Ended = libobs::obs_media_state_OBS_MEDIA_STATE_ENDED,
+    /// The media couldn't be opened or decoded
+    Error = libobs::obs_media_state_OBS_MEDIA_STATE_ERROR,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how the position of a fader or meter maps to a volume in dB.
+pub enum ObsFaderType {
+    /// The position is cubed to get the volume multiplier
+    #[default]
+    Cubic = libobs::obs_fader_type_OBS_FADER_CUBIC,
+    /// Linear segments as defined in IEC 60-268-18, like the meters of OBS Studio
+    Iec = libobs::obs_fader_type_OBS_FADER_IEC,
+    /// Logarithmic mapping
+    Log = libobs::obs_fader_type_OBS_FADER_LOG,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how a volume meter measures peaks.
+pub enum ObsPeakMeterType {
+    /// The maximum of all samples
+    #[default]
+    SamplePeak = libobs::obs_peak_meter_type_SAMPLE_PEAK_METER,
+    /// The maximum between samples, more accurate but more expensive to compute
+    TruePeak = libobs::obs_peak_meter_type_TRUE_PEAK_METER,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how a scene item is blended with the items below it.
+pub enum ObsBlendingType {
+    Normal = libobs::obs_blending_type_OBS_BLEND_NORMAL,
+    Additive = libobs::obs_blending_type_OBS_BLEND_ADDITIVE,
+    Subtract = libobs::obs_blending_type_OBS_BLEND_SUBTRACT,
+    Screen = libobs::obs_blending_type_OBS_BLEND_SCREEN,
+    Multiply = libobs::obs_blending_type_OBS_BLEND_MULTIPLY,
+    Lighten = libobs::obs_blending_type_OBS_BLEND_LIGHTEN,
+    Darken = libobs::obs_blending_type_OBS_BLEND_DARKEN,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes in which color space a scene item is blended.
+pub enum ObsBlendingMethod {
+    Default = libobs::obs_blending_method_OBS_BLEND_METHOD_DEFAULT,
+    /// Blends without converting to linear sRGB first
+    SrgbOff = libobs::obs_blending_method_OBS_BLEND_METHOD_SRGB_OFF,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Moves a scene item relative to the other items of its scene.
+pub enum ObsOrderMovement {
+    /// Moves the item one position up, in front of the item above it
+    MoveUp = libobs::obs_order_movement_OBS_ORDER_MOVE_UP,
+    /// Moves the item one position down, behind the item below it
+    MoveDown = libobs::obs_order_movement_OBS_ORDER_MOVE_DOWN,
+    /// Moves the item in front of all other items
+    MoveTop = libobs::obs_order_movement_OBS_ORDER_MOVE_TOP,
+    /// Moves the item behind all other items
+    MoveBottom = libobs::obs_order_movement_OBS_ORDER_MOVE_BOTTOM,
+}
+
+/// Alignment flags of a scene item, e.g. `ObsAlignment::TOP | ObsAlignment::LEFT`.
+/// Without any flags the item is centered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ObsAlignment(u32);
+
+impl ObsAlignment {
+    pub const CENTER: Self = Self(libobs::OBS_ALIGN_CENTER);
+    pub const LEFT: Self = Self(libobs::OBS_ALIGN_LEFT);
+    pub const RIGHT: Self = Self(libobs::OBS_ALIGN_RIGHT);
+    pub const TOP: Self = Self(libobs::OBS_ALIGN_TOP);
+    pub const BOTTOM: Self = Self(libobs::OBS_ALIGN_BOTTOM);
+    pub const TOP_LEFT: Self = Self(libobs::OBS_ALIGN_TOP | libobs::OBS_ALIGN_LEFT);
+    pub const TOP_RIGHT: Self = Self(libobs::OBS_ALIGN_TOP | libobs::OBS_ALIGN_RIGHT);
+    pub const BOTTOM_LEFT: Self = Self(libobs::OBS_ALIGN_BOTTOM | libobs::OBS_ALIGN_LEFT);
+    pub const BOTTOM_RIGHT: Self = Self(libobs::OBS_ALIGN_BOTTOM | libobs::OBS_ALIGN_RIGHT);
+
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Whether all flags of `other` are set.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for ObsAlignment {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// Describes which graphics backend should be used
 /// in the OBS video context. Used in `ObsVideoInfo`.
@@ -138,6 +292,64 @@ pub enum ObsSpeakerLayout {
     Unknown = libobs::speaker_layout_SPEAKERS_UNKNOWN,
 }
 
+impl ObsSpeakerLayout {
+    /// The number of audio channels of this layout.
+    pub fn channels(&self) -> u32 {
+        match self {
+            ObsSpeakerLayout::Unknown => 0,
+            ObsSpeakerLayout::Mono => 1,
+            ObsSpeakerLayout::Stereo => 2,
+            ObsSpeakerLayout::S2Point1 => 3,
+            ObsSpeakerLayout::S4Point0 => 4,
+            ObsSpeakerLayout::S4Point1 => 5,
+            ObsSpeakerLayout::S5Point1 => 6,
+            ObsSpeakerLayout::S7Point1 => 8,
+        }
+    }
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes the sample format of raw audio data.
+/// The planar formats store every channel in a separate plane.
+pub enum ObsAudioFormat {
+    Unknown = libobs::audio_format_AUDIO_FORMAT_UNKNOWN,
+    U8Bit = libobs::audio_format_AUDIO_FORMAT_U8BIT,
+    I16 = libobs::audio_format_AUDIO_FORMAT_16BIT,
+    I32 = libobs::audio_format_AUDIO_FORMAT_32BIT,
+    Float = libobs::audio_format_AUDIO_FORMAT_FLOAT,
+    U8BitPlanar = libobs::audio_format_AUDIO_FORMAT_U8BIT_PLANAR,
+    I16Planar = libobs::audio_format_AUDIO_FORMAT_16BIT_PLANAR,
+    I32Planar = libobs::audio_format_AUDIO_FORMAT_32BIT_PLANAR,
+    FloatPlanar = libobs::audio_format_AUDIO_FORMAT_FLOAT_PLANAR,
+}
+
+impl ObsAudioFormat {
+    pub fn is_planar(&self) -> bool {
+        matches!(
+            self,
+            ObsAudioFormat::U8BitPlanar
+                | ObsAudioFormat::I16Planar
+                | ObsAudioFormat::I32Planar
+                | ObsAudioFormat::FloatPlanar
+        )
+    }
+
+    /// The size of a single sample of one channel in bytes.
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            ObsAudioFormat::Unknown => 0,
+            ObsAudioFormat::U8Bit | ObsAudioFormat::U8BitPlanar => 1,
+            ObsAudioFormat::I16 | ObsAudioFormat::I16Planar => 2,
+            ObsAudioFormat::I32
+            | ObsAudioFormat::I32Planar
+            | ObsAudioFormat::Float
+            | ObsAudioFormat::FloatPlanar => 4,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ObsOutputStopSignal {
     /// Successfully stopped
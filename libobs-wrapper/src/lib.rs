@@ -1,9 +1,7 @@
 use getters0::Getters;
 
-#[cfg(not(windows))]
-compiler_error!("libobs-wrapper can only be used in windows");
-
 pub mod unsafe_send;
+pub mod backend;
 pub mod crash_handler;
 pub mod data;
 pub mod sources;
@@ -13,9 +11,13 @@ pub mod logger;
 pub mod signals;
 pub mod display;
 pub mod scenes;
+pub mod transitions;
+pub mod studio_mode;
+pub mod media;
 #[cfg(feature="bootstrapper")]
 pub mod bootstrap;
 pub mod runtime;
+pub mod stats;
 
 pub mod utils;
 pub mod enums;
@@ -23,7 +25,7 @@ pub mod enums;
 // Add the macros module to the public exports
 mod macros;
 
-#[derive(Debug, Clone, Copy, Getters)]
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
 pub struct Vec2 {
     #[get_mut]
     x: f32,
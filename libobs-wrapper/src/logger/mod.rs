@@ -4,14 +4,14 @@ pub use file::FileLogger;
 pub use console::ConsoleLogger;
 
 use std::{
-    fmt::Debug, os::raw::c_void, sync::Mutex
+    fmt::Debug, os::raw::{c_char, c_void}, sync::Mutex
 };
 
 use lazy_static::lazy_static;
 use num_traits::FromPrimitive;
 use vsprintf::vsprintf;
 
-use crate::enums::ObsLogLevel;
+use crate::{enums::ObsLogLevel, utils::VaList};
 
 
 lazy_static! {
@@ -21,8 +21,8 @@ lazy_static! {
 
 pub(crate) unsafe extern "C" fn extern_log_callback(
     log_level: i32,
-    msg: *const i8,
-    args: *mut i8,
+    msg: *const c_char,
+    args: VaList,
     _params: *mut c_void,
 ) {
     let level = ObsLogLevel::from_i32(log_level);
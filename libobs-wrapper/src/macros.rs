@@ -16,17 +16,42 @@ macro_rules! run_with_obs_impl {
             })
         }
     };
+    ($self:expr, $function:ident, label = $label:expr, ($($var:ident),* $(,)*), $operation:expr) => {
+        {
+            $(let $var = $var.clone();)*
+            $self.$function($label, move || {
+                $(let $var = $var;)*
+                let e = {
+                    $(let $var = $var.0;)*
+                    $operation
+                };
+                return e()
+            })
+        }
+    };
 }
 
 #[macro_export]
 macro_rules! run_with_obs {
+    ($self:expr, label = $label:expr, $operation:expr) => {
+        $crate::run_with_obs!($self, label = $label, (), $operation)
+    };
+    ($self:expr, label = $label:expr, ($($var:ident),* $(,)*), $operation:expr) => {
+        {
+            #[cfg(not(feature="blocking"))]
+            use futures_util::TryFutureExt;
+
+            $crate::run_with_obs_impl!($self, run_with_obs_result_labeled, label = $label, ($($var),*), $operation)
+                .map_err(crate::utils::ObsError::from_invocation_error)
+        }
+    };
     ($self:expr, $operation:expr) => {
         {
             #[cfg(not(feature="blocking"))]
             use futures_util::TryFutureExt;
 
             $crate::run_with_obs_impl!($self, run_with_obs_result, $operation)
-                .map_err(|e| crate::utils::ObsError::InvocationError(e.to_string()))
+                .map_err(crate::utils::ObsError::from_invocation_error)
         }
     };
     ($self:expr, ($($var:ident),* $(,)*), $operation:expr) => {
@@ -35,7 +60,7 @@ macro_rules! run_with_obs {
             use futures_util::TryFutureExt;
 
             $crate::run_with_obs_impl!($self, run_with_obs_result, ($($var),*), $operation)
-                .map_err(|e| crate::utils::ObsError::InvocationError(e.to_string()))
+                .map_err(crate::utils::ObsError::from_invocation_error)
         }
     };
 }
@@ -45,11 +70,11 @@ macro_rules! run_with_obs {
 macro_rules! run_with_obs_blocking {
     ($self:expr, $operation:expr) => {
         $crate::run_with_obs_impl!($self, run_with_obs_result_blocking, (), $operation)
-        .map_err(|e| crate::utils::ObsError::InvocationError(e.to_string()))
+        .map_err(crate::utils::ObsError::from_invocation_error)
     };
     ($self:expr, ($($var:ident),* $(,)*), $operation:expr) => {
         $crate::run_with_obs_impl!($self, run_with_obs_result_blocking, ($($var),*), $operation)
-        .map_err(|e| crate::utils::ObsError::InvocationError(e.to_string()))
+        .map_err(crate::utils::ObsError::from_invocation_error)
     };
 }
 
@@ -61,6 +86,11 @@ macro_rules! impl_obs_drop {
     ($struct_name: ident, ($($var:ident),* $(,)*), $operation:expr) => {
         impl Drop for $struct_name {
             fn drop(&mut self) {
+                // libobs already freed everything when the context was shut down explicitly
+                if self.runtime.is_shut_down() {
+                    return;
+                }
+
                 //TODO Make sure that we are not blocking when dropping the object
                 $(let $var = self.$var.clone();)*
                 #[cfg(not(feature="blocking"))]
@@ -70,11 +100,9 @@ macro_rules! impl_obs_drop {
 
                 #[cfg(feature="blocking")]
                 let r = crate::run_with_obs!(self.runtime, ($($var),*), $operation);
-                if std::thread::panicking() {
-                    return;
+                if let Err(e) = r {
+                    log::error!("Failed to release {}: {:?}", stringify!($struct_name), e);
                 }
-
-                r.unwrap();
             }
         }
     };
@@ -83,11 +111,9 @@ macro_rules! impl_obs_drop {
             fn drop(&mut self) {
                 $(let $var = self.$var.clone();)*
                 let r = crate::run_with_obs_blocking!(self, ($($var),*), $operation);
-                if std::thread::panicking() {
-                    return;
+                if let Err(e) = r {
+                    log::error!("Failed to release {}: {:?}", stringify!($struct_name), e);
                 }
-
-                r.unwrap();
             }
         }
     };
@@ -0,0 +1,336 @@
+use std::{
+    ffi::c_void,
+    pin::Pin,
+    ptr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use libobs::{audio_convert_info, audio_data, obs_audio_info};
+use num_traits::FromPrimitive;
+
+use crate::{
+    backend::backend,
+    enums::{ObsAudioFormat, ObsSpeakerLayout, OsEnumType},
+    impl_obs_drop, run_with_obs,
+    runtime::{ObsRuntime, ShutdownSignal},
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+use super::{buffer::MediaBuffer, ObsDropPolicy};
+
+/// The format the media-io resampler converts audio to before handing it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObsAudioConversion {
+    pub format: ObsAudioFormat,
+    /// The sample rate to resample to, `None` keeps the one of the audio context
+    pub samples_per_sec: Option<u32>,
+    /// The speaker layout to remix to, `None` keeps the one of the audio context
+    pub speakers: Option<ObsSpeakerLayout>,
+    /// Whether samples outside of the valid range are kept instead of being clamped
+    pub allow_clipping: bool,
+}
+
+impl ObsAudioConversion {
+    pub fn new(format: ObsAudioFormat) -> Self {
+        Self {
+            format,
+            samples_per_sec: None,
+            speakers: None,
+            allow_clipping: false,
+        }
+    }
+
+    /// Interleaved 32 bit float samples.
+    pub fn interleaved_f32() -> Self {
+        Self::new(ObsAudioFormat::Float)
+    }
+
+    /// Interleaved signed 16 bit samples.
+    pub fn interleaved_i16() -> Self {
+        Self::new(ObsAudioFormat::I16)
+    }
+
+    fn as_raw(&self) -> audio_convert_info {
+        audio_convert_info {
+            samples_per_sec: self.samples_per_sec.unwrap_or(0),
+            format: self.format as OsEnumType,
+            speakers: self.speakers.unwrap_or(ObsSpeakerLayout::Unknown) as OsEnumType,
+            allow_clipping: self.allow_clipping,
+        }
+    }
+}
+
+/// Configures a stream of the raw audio of a mix, see [`ObsRawAudioStream`].
+#[derive(Debug, Clone)]
+pub struct ObsRawAudioOptions {
+    mix: usize,
+    conversion: Option<ObsAudioConversion>,
+    capacity: usize,
+    drop_policy: ObsDropPolicy,
+}
+
+impl ObsRawAudioOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the mix (audio track) to capture, starting at 0 for the first track.
+    pub fn set_mix(mut self, mix: usize) -> Self {
+        self.mix = mix;
+        self
+    }
+
+    /// Converts the audio before handing it out. By default the audio is handed out as
+    /// planar float samples with the sample rate and speaker layout of the audio context.
+    pub fn set_conversion(mut self, conversion: Option<ObsAudioConversion>) -> Self {
+        self.conversion = conversion;
+        self
+    }
+
+    /// The number of audio buffers that are buffered until buffers are dropped
+    /// (32 by default, a buffer holds 1024 frames).
+    pub fn set_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn set_drop_policy(mut self, drop_policy: ObsDropPolicy) -> Self {
+        self.drop_policy = drop_policy;
+        self
+    }
+}
+
+impl Default for ObsRawAudioOptions {
+    fn default() -> Self {
+        Self {
+            mix: 0,
+            conversion: None,
+            capacity: 32,
+            drop_policy: ObsDropPolicy::default(),
+        }
+    }
+}
+
+/// Raw audio that has been copied out of libobs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObsAudioFrame {
+    pub format: ObsAudioFormat,
+    pub speakers: ObsSpeakerLayout,
+    pub samples_per_sec: u32,
+    /// The number of samples per channel
+    pub frames: u32,
+    /// The time of the first sample in nanoseconds (see `os_gettime_ns`)
+    pub timestamp: u64,
+    /// One plane per channel for planar formats, otherwise a single
+    /// plane containing the samples of all channels interleaved
+    pub planes: Vec<Vec<u8>>,
+}
+
+impl ObsAudioFrame {
+    pub(crate) unsafe fn from_raw(
+        audio: &audio_data,
+        format: ObsAudioFormat,
+        speakers: ObsSpeakerLayout,
+        samples_per_sec: u32,
+    ) -> Self {
+        let channels = speakers.channels() as usize;
+        let (plane_count, plane_size) = if format.is_planar() {
+            (channels, audio.frames as usize * format.bytes_per_sample())
+        } else {
+            (
+                1,
+                audio.frames as usize * format.bytes_per_sample() * channels,
+            )
+        };
+
+        let mut planes = Vec::with_capacity(plane_count);
+        for data in audio.data.iter().take(plane_count) {
+            if data.is_null() {
+                break;
+            }
+
+            planes.push(std::slice::from_raw_parts(*data, plane_size).to_vec());
+        }
+
+        Self {
+            format,
+            speakers,
+            samples_per_sec,
+            frames: audio.frames,
+            timestamp: audio.timestamp,
+            planes,
+        }
+    }
+
+    /// Returns the samples of a plane if the format is `Float` or `FloatPlanar`.
+    pub fn samples_f32(&self, plane: usize) -> Option<Vec<f32>> {
+        if !matches!(
+            self.format,
+            ObsAudioFormat::Float | ObsAudioFormat::FloatPlanar
+        ) {
+            return None;
+        }
+
+        let samples = self.planes.get(plane)?.chunks_exact(4);
+        Some(
+            samples
+                .map(|s| f32::from_ne_bytes([s[0], s[1], s[2], s[3]]))
+                .collect(),
+        )
+    }
+
+    /// Returns the samples of a plane if the format is `I16` or `I16Planar`.
+    pub fn samples_i16(&self, plane: usize) -> Option<Vec<i16>> {
+        if !matches!(self.format, ObsAudioFormat::I16 | ObsAudioFormat::I16Planar) {
+            return None;
+        }
+
+        let samples = self.planes.get(plane)?.chunks_exact(2);
+        Some(samples.map(|s| i16::from_ne_bytes([s[0], s[1]])).collect())
+    }
+}
+
+#[derive(Debug)]
+struct RawAudioCallbackData {
+    buffer: Arc<MediaBuffer<ObsAudioFrame>>,
+    mix: usize,
+    format: ObsAudioFormat,
+    speakers: ObsSpeakerLayout,
+    samples_per_sec: u32,
+}
+
+/// Called by the audio thread of libobs for every mixed audio buffer.
+unsafe extern "C" fn raw_audio_callback(
+    param: *mut c_void,
+    _mix_idx: usize,
+    audio: *mut audio_data,
+) {
+    if param.is_null() || audio.is_null() {
+        return;
+    }
+
+    let data = &*(param as *const RawAudioCallbackData);
+    let frame = ObsAudioFrame::from_raw(&*audio, data.format, data.speakers, data.samples_per_sec);
+    data.buffer.push(frame);
+}
+
+#[derive(Debug)]
+struct _RawAudioCallbackGuard {
+    data: Sendable<*mut RawAudioCallbackData>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_RawAudioCallbackGuard, (data), move || unsafe {
+    // Removing the callback waits for a running call to finish,
+    // so nothing uses the data afterwards
+    let mix = (*data).mix;
+    backend().obs_remove_raw_audio_callback(mix, Some(raw_audio_callback), data as *mut c_void);
+    drop(Box::from_raw(data));
+});
+
+/// A stream of the mixed audio of one audio track.
+/// Created by [`ObsContext::raw_audio_stream`](crate::context::ObsContext::raw_audio_stream).
+///
+/// The callback is removed from libobs once the stream is dropped. The stream ends
+/// once OBS is shut down.
+#[derive(Debug)]
+pub struct ObsRawAudioStream {
+    buffer: Arc<MediaBuffer<ObsAudioFrame>>,
+    shut_down: ShutdownSignal,
+    _guard: _RawAudioCallbackGuard,
+}
+
+impl ObsRawAudioStream {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        runtime: ObsRuntime,
+        options: ObsRawAudioOptions,
+    ) -> Result<Self, ObsError> {
+        let mix = options.mix;
+        if mix >= libobs::MAX_AUDIO_MIXES as usize {
+            return Err(ObsError::InvalidAudioMix(mix));
+        }
+
+        let buffer = Arc::new(MediaBuffer::new(options.capacity, options.drop_policy));
+        let conversion = options.conversion;
+
+        let callback_buffer = buffer.clone();
+        let data = run_with_obs!(runtime, move || unsafe {
+            let backend = backend();
+            let mut oai: obs_audio_info = std::mem::zeroed();
+            if !backend.obs_get_audio_info(&mut oai) {
+                return Err(ObsError::AudioNotInitialized);
+            }
+
+            // The mixes of libobs always consist of planar float samples
+            let format = conversion
+                .map(|c| c.format)
+                .filter(|f| *f != ObsAudioFormat::Unknown)
+                .unwrap_or(ObsAudioFormat::FloatPlanar);
+            let speakers = conversion
+                .and_then(|c| c.speakers)
+                .filter(|s| *s != ObsSpeakerLayout::Unknown)
+                .or_else(|| ObsSpeakerLayout::from_i64(oai.speakers as i64))
+                .unwrap_or(ObsSpeakerLayout::Stereo);
+            let samples_per_sec = conversion
+                .and_then(|c| c.samples_per_sec)
+                .filter(|s| *s != 0)
+                .unwrap_or(oai.samples_per_sec);
+
+            let data = Box::into_raw(Box::new(RawAudioCallbackData {
+                buffer: callback_buffer,
+                mix,
+                format,
+                speakers,
+                samples_per_sec,
+            }));
+
+            let raw_conversion = conversion.map(|c| c.as_raw());
+            let conversion_ptr = raw_conversion
+                .as_ref()
+                .map_or(ptr::null(), |c| c as *const audio_convert_info);
+            backend.obs_add_raw_audio_callback(
+                mix,
+                conversion_ptr,
+                Some(raw_audio_callback),
+                data as *mut c_void,
+            );
+
+            Ok(Sendable(data))
+        })
+        .await??;
+
+        Ok(Self {
+            buffer,
+            shut_down: runtime.shut_down_signal(),
+            _guard: _RawAudioCallbackGuard { data, runtime },
+        })
+    }
+
+    /// Returns the next buffered audio without waiting for new audio.
+    pub fn try_next_frame(&self) -> Option<ObsAudioFrame> {
+        self.buffer.try_pop()
+    }
+
+    /// The number of audio buffers that are currently buffered.
+    pub fn buffered_frames(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The number of audio buffers that were dropped because the buffer was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.buffer.dropped()
+    }
+}
+
+impl Stream for ObsRawAudioStream {
+    type Item = ObsAudioFrame;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.buffer.poll_next(cx, &mut this.shut_down)
+    }
+}
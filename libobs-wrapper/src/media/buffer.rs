@@ -0,0 +1,113 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Mutex, MutexGuard},
+    task::{Context, Poll},
+};
+
+use futures::task::AtomicWaker;
+
+use crate::runtime::ShutdownSignal;
+
+/// Decides which data is dropped when the buffer of a stream is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ObsDropPolicy {
+    /// Removes the oldest buffered item to make room for the new one
+    #[default]
+    DropOldest,
+    /// Discards the new item and keeps the buffered ones
+    DropNewest,
+}
+
+#[derive(Debug)]
+struct BufferState<T> {
+    items: VecDeque<T>,
+    dropped: u64,
+}
+
+/// A bounded queue that is filled by libobs callbacks and drained by a stream.
+#[derive(Debug)]
+pub(crate) struct MediaBuffer<T> {
+    state: Mutex<BufferState<T>>,
+    waker: AtomicWaker,
+    capacity: usize,
+    drop_policy: ObsDropPolicy,
+}
+
+impl<T> MediaBuffer<T> {
+    pub(crate) fn new(capacity: usize, drop_policy: ObsDropPolicy) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            state: Mutex::new(BufferState {
+                items: VecDeque::with_capacity(capacity),
+                dropped: 0,
+            }),
+            waker: AtomicWaker::new(),
+            capacity,
+            drop_policy,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, BufferState<T>> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    /// Queues an item. This is called from the threads of libobs, so it never waits for
+    /// the consumer.
+    pub(crate) fn push(&self, item: T) {
+        {
+            let mut state = self.state();
+            if state.items.len() >= self.capacity {
+                state.dropped += 1;
+                match self.drop_policy {
+                    ObsDropPolicy::DropOldest => {
+                        state.items.pop_front();
+                    }
+                    ObsDropPolicy::DropNewest => return,
+                }
+            }
+
+            state.items.push_back(item);
+        }
+
+        self.waker.wake();
+    }
+
+    pub(crate) fn try_pop(&self) -> Option<T> {
+        self.state().items.pop_front()
+    }
+
+    pub(crate) fn poll_pop(&self, cx: &mut Context<'_>) -> Poll<T> {
+        self.waker.register(cx.waker());
+        match self.try_pop() {
+            Some(item) => Poll::Ready(item),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Like `poll_pop`, but ends the stream once OBS has been shut down and all buffered
+    /// items have been handed out.
+    pub(crate) fn poll_next(
+        &self,
+        cx: &mut Context<'_>,
+        shut_down: &mut ShutdownSignal,
+    ) -> Poll<Option<T>> {
+        if let Poll::Ready(item) = self.poll_pop(cx) {
+            return Poll::Ready(Some(item));
+        }
+
+        Pin::new(shut_down).poll(cx).map(|_| self.try_pop())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.state().items.len()
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.state().dropped
+    }
+}
@@ -0,0 +1,26 @@
+//! Access to the raw media libobs produces.
+//!
+//! libobs hands out raw data through callbacks which are called on its video and audio
+//! threads. The streams in this module copy that data into owned buffers and queue them
+//! in a bounded buffer, so consumers can process them at their own pace. If a consumer
+//! doesn't keep up, data is dropped according to the configured [`ObsDropPolicy`]
+//! instead of blocking libobs.
+//!
+//! Single images of sources, scenes and the program output can be taken with
+//! [`ObsScreenshot`]s, which are rendered on demand instead.
+//!
+//! The audio levels of a source can be followed with an [`ObsVolumeMeter`].
+
+mod audio;
+mod buffer;
+mod screenshot;
+mod source_audio;
+mod video;
+mod volume_meter;
+
+pub use audio::*;
+pub use buffer::ObsDropPolicy;
+pub use screenshot::*;
+pub use source_audio::*;
+pub use video::*;
+pub use volume_meter::*;
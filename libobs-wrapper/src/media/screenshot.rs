@@ -0,0 +1,244 @@
+use libobs::{gs_stagesurf_t, gs_texrender_t, obs_source_t, obs_video_info, vec4};
+
+#[cfg(feature = "image-encoding")]
+use std::path::Path;
+
+use crate::{
+    backend::{backend, ObsBackend},
+    run_with_obs,
+    runtime::ObsRuntime,
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+/// Configures a screenshot, see [`ObsScreenshot`].
+#[derive(Debug, Clone, Default)]
+pub struct ObsScreenshotOptions {
+    resolution: Option<(u32, u32)>,
+}
+
+impl ObsScreenshotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The size of the screenshot. The target is scaled to fit exactly, so the aspect
+    /// ratio is not kept. Defaults to the size of the target.
+    pub fn set_resolution(mut self, width: u32, height: u32) -> Self {
+        self.resolution = Some((width, height));
+        self
+    }
+}
+
+/// The file format a screenshot is saved as.
+#[cfg(feature = "image-encoding")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsImageFormat {
+    Png,
+    /// JPEG with the given quality between 1 and 100. The alpha channel is dropped.
+    Jpeg {
+        quality: u8,
+    },
+}
+
+/// A single rendered image of a source, scene or the program output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObsScreenshot {
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed RGBA pixels, row by row
+    pub data: Vec<u8>,
+}
+
+impl ObsScreenshot {
+    /// Returns the RGBA value of the pixel at the given position.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        self.data
+            .get(offset..offset + 4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+    }
+
+    #[cfg(feature = "image-encoding")]
+    pub fn to_image(&self) -> Option<image::RgbaImage> {
+        image::RgbaImage::from_raw(self.width, self.height, self.data.clone())
+    }
+
+    /// Encodes the screenshot and writes it to the given path.
+    #[cfg(feature = "image-encoding")]
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: ObsImageFormat) -> Result<(), ObsError> {
+        let image = self
+            .to_image()
+            .ok_or_else(|| ObsError::ScreenshotFailure("Invalid image buffer".to_string()))?;
+
+        let res = match format {
+            ObsImageFormat::Png => image.save_with_format(path, image::ImageFormat::Png),
+            ObsImageFormat::Jpeg { quality } => std::fs::File::create(path)
+                .map_err(image::ImageError::IoError)
+                .and_then(|file| {
+                    let mut writer = std::io::BufWriter::new(file);
+                    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+                        &mut writer,
+                        quality.clamp(1, 100),
+                    );
+
+                    image::DynamicImage::ImageRgba8(image)
+                        .to_rgb8()
+                        .write_with_encoder(encoder)
+                }),
+        };
+
+        res.map_err(|e| ObsError::ScreenshotFailure(e.to_string()))
+    }
+}
+
+/// Renders the given source, or the program output if `source` is `None`, and reads it back.
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+pub(crate) async fn take_screenshot(
+    runtime: &ObsRuntime,
+    source: Option<Sendable<*mut obs_source_t>>,
+    options: ObsScreenshotOptions,
+) -> Result<ObsScreenshot, ObsError> {
+    run_with_obs!(runtime, move || unsafe {
+        let backend = backend();
+        let source = source.map(|s| s.0);
+
+        backend.obs_enter_graphics();
+        let res = render(&*backend, source, options.resolution);
+        backend.obs_leave_graphics();
+
+        res
+    })
+    .await?
+}
+
+/// Must be called on the OBS thread while holding the graphics context.
+unsafe fn render(
+    backend: &dyn ObsBackend,
+    source: Option<*mut obs_source_t>,
+    resolution: Option<(u32, u32)>,
+) -> Result<ObsScreenshot, ObsError> {
+    let (source_width, source_height) = match source {
+        Some(source) => (
+            backend.obs_source_get_width(source),
+            backend.obs_source_get_height(source),
+        ),
+        None => {
+            let mut ovi: obs_video_info = std::mem::zeroed();
+            if !backend.obs_get_video_info(&mut ovi) {
+                return Err(ObsError::VideoNotInitialized);
+            }
+
+            (ovi.base_width, ovi.base_height)
+        }
+    };
+
+    if source_width == 0 || source_height == 0 {
+        return Err(ObsError::ScreenshotFailure(
+            "The target has no size".to_string(),
+        ));
+    }
+
+    let (width, height) = resolution.unwrap_or((source_width, source_height));
+    if width == 0 || height == 0 {
+        return Err(ObsError::ScreenshotFailure(format!(
+            "Invalid resolution {}x{}",
+            width, height
+        )));
+    }
+
+    let texrender = backend.gs_texrender_create(
+        libobs::gs_color_format_GS_RGBA,
+        libobs::gs_zstencil_format_GS_ZS_NONE,
+    );
+    if texrender.is_null() {
+        return Err(ObsError::NullPointer);
+    }
+
+    let stagesurf = backend.gs_stagesurface_create(width, height, libobs::gs_color_format_GS_RGBA);
+    if stagesurf.is_null() {
+        backend.gs_texrender_destroy(texrender);
+        return Err(ObsError::NullPointer);
+    }
+
+    let res = render_to_surface(
+        backend,
+        source,
+        texrender,
+        stagesurf,
+        (source_width, source_height),
+        (width, height),
+    );
+
+    backend.gs_stagesurface_destroy(stagesurf);
+    backend.gs_texrender_destroy(texrender);
+    res
+}
+
+unsafe fn render_to_surface(
+    backend: &dyn ObsBackend,
+    source: Option<*mut obs_source_t>,
+    texrender: *mut gs_texrender_t,
+    stagesurf: *mut gs_stagesurf_t,
+    (source_width, source_height): (u32, u32),
+    (width, height): (u32, u32),
+) -> Result<ObsScreenshot, ObsError> {
+    if !backend.gs_texrender_begin(texrender, width, height) {
+        return Err(ObsError::ScreenshotFailure(
+            "Could not begin rendering".to_string(),
+        ));
+    }
+
+    let clear_color: vec4 = std::mem::zeroed();
+    backend.gs_clear(libobs::GS_CLEAR_COLOR, &clear_color, 0.0, 0);
+    // Mapping the whole target onto the texture scales it to the requested resolution
+    backend.gs_ortho(
+        0.0,
+        source_width as f32,
+        0.0,
+        source_height as f32,
+        -100.0,
+        100.0,
+    );
+
+    backend.gs_blend_state_push();
+    backend.gs_blend_function(
+        libobs::gs_blend_type_GS_BLEND_ONE,
+        libobs::gs_blend_type_GS_BLEND_ZERO,
+    );
+    match source {
+        Some(source) => backend.obs_source_video_render(source),
+        None => backend.obs_render_main_texture(),
+    }
+    backend.gs_blend_state_pop();
+    backend.gs_texrender_end(texrender);
+
+    backend.gs_stage_texture(stagesurf, backend.gs_texrender_get_texture(texrender));
+
+    let mut mapped = std::ptr::null_mut();
+    let mut linesize = 0;
+    if !backend.gs_stagesurface_map(stagesurf, &mut mapped, &mut linesize) || mapped.is_null() {
+        return Err(ObsError::ScreenshotFailure(
+            "Could not map the stage surface".to_string(),
+        ));
+    }
+
+    // Rows of the stage surface may be padded, so they are copied one by one
+    let row_len = width as usize * 4;
+    let mut data = Vec::with_capacity(row_len * height as usize);
+    for row in 0..height as usize {
+        let row_ptr = mapped.add(row * linesize as usize);
+        data.extend_from_slice(std::slice::from_raw_parts(row_ptr, row_len));
+    }
+    backend.gs_stagesurface_unmap(stagesurf);
+
+    Ok(ObsScreenshot {
+        width,
+        height,
+        data,
+    })
+}
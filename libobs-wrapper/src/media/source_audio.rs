@@ -0,0 +1,198 @@
+use std::{
+    ffi::c_void,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use libobs::{audio_data, obs_audio_info, obs_source_t};
+use num_traits::FromPrimitive;
+
+use crate::{
+    backend::backend,
+    enums::{ObsAudioFormat, ObsSpeakerLayout},
+    impl_obs_drop, run_with_obs,
+    runtime::{ObsRuntime, ShutdownSignal},
+    sources::ObsSourceRef,
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+use super::{buffer::MediaBuffer, ObsAudioFrame, ObsDropPolicy};
+
+/// Configures a stream of the audio of a single source, see [`ObsSourceAudioStream`].
+#[derive(Debug, Clone)]
+pub struct ObsSourceAudioOptions {
+    capacity: usize,
+    drop_policy: ObsDropPolicy,
+}
+
+impl ObsSourceAudioOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of audio buffers that are buffered until buffers are dropped (32 by default).
+    pub fn set_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn set_drop_policy(mut self, drop_policy: ObsDropPolicy) -> Self {
+        self.drop_policy = drop_policy;
+        self
+    }
+}
+
+impl Default for ObsSourceAudioOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 32,
+            drop_policy: ObsDropPolicy::default(),
+        }
+    }
+}
+
+/// Audio that has been output by a source, before it is mixed into the audio tracks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObsSourceAudioFrame {
+    /// The audio as planar float samples with the sample rate and speaker
+    /// layout of the audio context
+    pub audio: ObsAudioFrame,
+    /// Whether the source was muted. The audio is passed on unchanged in that case.
+    pub muted: bool,
+}
+
+#[derive(Debug)]
+struct AudioCaptureCallbackData {
+    buffer: Arc<MediaBuffer<ObsSourceAudioFrame>>,
+    speakers: ObsSpeakerLayout,
+    samples_per_sec: u32,
+}
+
+/// Called by libobs every time the source outputs audio.
+unsafe extern "C" fn audio_capture_callback(
+    param: *mut c_void,
+    _source: *mut obs_source_t,
+    audio: *const audio_data,
+    muted: bool,
+) {
+    if param.is_null() || audio.is_null() {
+        return;
+    }
+
+    let data = &*(param as *const AudioCaptureCallbackData);
+    let audio = ObsAudioFrame::from_raw(
+        &*audio,
+        ObsAudioFormat::FloatPlanar,
+        data.speakers,
+        data.samples_per_sec,
+    );
+    data.buffer.push(ObsSourceAudioFrame { audio, muted });
+}
+
+#[derive(Debug)]
+struct _AudioCaptureCallbackGuard {
+    source: Sendable<*mut obs_source_t>,
+    data: Sendable<*mut AudioCaptureCallbackData>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_AudioCaptureCallbackGuard, (source, data), move || unsafe {
+    // Removing the callback waits for a running call to finish,
+    // so nothing uses the data afterwards
+    backend().obs_source_remove_audio_capture_callback(
+        source,
+        Some(audio_capture_callback),
+        data as *mut c_void,
+    );
+    drop(Box::from_raw(data));
+});
+
+/// A stream of the audio a source outputs.
+/// Created by [`ObsSourceRef::audio_stream`].
+///
+/// The stream keeps the source alive and removes its callback once it is dropped.
+/// It ends once OBS is shut down.
+#[derive(Debug)]
+pub struct ObsSourceAudioStream {
+    buffer: Arc<MediaBuffer<ObsSourceAudioFrame>>,
+    shut_down: ShutdownSignal,
+    // Dropped before the source, so the callback is removed while the source still exists
+    _guard: _AudioCaptureCallbackGuard,
+    _source: ObsSourceRef,
+}
+
+impl ObsSourceAudioStream {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        source: ObsSourceRef,
+        options: ObsSourceAudioOptions,
+    ) -> Result<Self, ObsError> {
+        let buffer = Arc::new(MediaBuffer::new(options.capacity, options.drop_policy));
+        let runtime = source.runtime.clone();
+
+        let source_ptr = source.source.clone();
+        let callback_buffer = buffer.clone();
+        let data = run_with_obs!(runtime, (source_ptr), move || unsafe {
+            let backend = backend();
+            let mut oai: obs_audio_info = std::mem::zeroed();
+            if !backend.obs_get_audio_info(&mut oai) {
+                return Err(ObsError::AudioNotInitialized);
+            }
+
+            let speakers =
+                ObsSpeakerLayout::from_i64(oai.speakers as i64).unwrap_or(ObsSpeakerLayout::Stereo);
+            let data = Box::into_raw(Box::new(AudioCaptureCallbackData {
+                buffer: callback_buffer,
+                speakers,
+                samples_per_sec: oai.samples_per_sec,
+            }));
+
+            backend.obs_source_add_audio_capture_callback(
+                source_ptr,
+                Some(audio_capture_callback),
+                data as *mut c_void,
+            );
+
+            Ok(Sendable(data))
+        })
+        .await??;
+
+        Ok(Self {
+            buffer,
+            shut_down: runtime.shut_down_signal(),
+            _guard: _AudioCaptureCallbackGuard {
+                source: source.source.clone(),
+                data,
+                runtime,
+            },
+            _source: source,
+        })
+    }
+
+    /// Returns the next buffered audio without waiting for new audio.
+    pub fn try_next_frame(&self) -> Option<ObsSourceAudioFrame> {
+        self.buffer.try_pop()
+    }
+
+    /// The number of audio buffers that are currently buffered.
+    pub fn buffered_frames(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The number of audio buffers that were dropped because the buffer was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.buffer.dropped()
+    }
+}
+
+impl Stream for ObsSourceAudioStream {
+    type Item = ObsSourceAudioFrame;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.buffer.poll_next(cx, &mut this.shut_down)
+    }
+}
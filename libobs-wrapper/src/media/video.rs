@@ -0,0 +1,292 @@
+use std::{
+    ffi::c_void,
+    pin::Pin,
+    ptr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use libobs::{obs_video_info, video_data, video_scale_info};
+use num_traits::FromPrimitive;
+
+use crate::{
+    backend::backend,
+    enums::{ObsColorspace, ObsVideoFormat, ObsVideoRange, OsEnumType},
+    impl_obs_drop, run_with_obs,
+    runtime::{ObsRuntime, ShutdownSignal},
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+use super::{buffer::MediaBuffer, ObsDropPolicy};
+
+/// The format libobs converts the frames of a raw video stream to before handing them out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObsVideoScaleInfo {
+    pub format: ObsVideoFormat,
+    pub width: u32,
+    pub height: u32,
+    pub range: ObsVideoRange,
+    pub colorspace: ObsColorspace,
+}
+
+impl ObsVideoScaleInfo {
+    pub fn new(format: ObsVideoFormat, width: u32, height: u32) -> Self {
+        Self {
+            format,
+            width,
+            height,
+            range: ObsVideoRange::Default,
+            colorspace: ObsColorspace::Default,
+        }
+    }
+
+    fn as_raw(&self) -> video_scale_info {
+        video_scale_info {
+            format: self.format as OsEnumType,
+            width: self.width,
+            height: self.height,
+            range: self.range as OsEnumType,
+            colorspace: self.colorspace as OsEnumType,
+        }
+    }
+}
+
+/// Configures a stream of raw video frames, see [`ObsRawVideoStream`].
+#[derive(Debug, Clone)]
+pub struct ObsRawVideoOptions {
+    conversion: Option<ObsVideoScaleInfo>,
+    capacity: usize,
+    drop_policy: ObsDropPolicy,
+}
+
+impl ObsRawVideoOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts and scales the frames to the given format and size. By default the
+    /// frames are handed out in the format and output size of the video context.
+    pub fn set_conversion(mut self, conversion: Option<ObsVideoScaleInfo>) -> Self {
+        self.conversion = conversion;
+        self
+    }
+
+    /// The number of frames that are buffered until frames are dropped (4 by default).
+    pub fn set_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn set_drop_policy(mut self, drop_policy: ObsDropPolicy) -> Self {
+        self.drop_policy = drop_policy;
+        self
+    }
+}
+
+impl Default for ObsRawVideoOptions {
+    fn default() -> Self {
+        Self {
+            conversion: None,
+            capacity: 4,
+            drop_policy: ObsDropPolicy::default(),
+        }
+    }
+}
+
+/// A raw video frame that has been copied out of libobs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObsVideoFrame {
+    pub format: ObsVideoFormat,
+    pub width: u32,
+    pub height: u32,
+    /// The time the frame was rendered at in nanoseconds (see `os_gettime_ns`)
+    pub timestamp: u64,
+    /// The data of each plane, `linesize * rows` bytes each
+    pub planes: Vec<Vec<u8>>,
+    /// The number of bytes per row of each plane, including padding
+    pub linesize: Vec<u32>,
+}
+
+impl ObsVideoFrame {
+    unsafe fn from_raw(
+        frame: &video_data,
+        format: ObsVideoFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let mut planes = Vec::new();
+        let mut linesize = Vec::new();
+        for (i, rows) in plane_heights(format, height).into_iter().enumerate() {
+            let data = frame.data[i];
+            if data.is_null() {
+                break;
+            }
+
+            let size = frame.linesize[i] as usize * rows as usize;
+            planes.push(std::slice::from_raw_parts(data, size).to_vec());
+            linesize.push(frame.linesize[i]);
+        }
+
+        Self {
+            format,
+            width,
+            height,
+            timestamp: frame.timestamp,
+            planes,
+            linesize,
+        }
+    }
+}
+
+/// Returns the number of rows of each plane of a frame with the given format.
+fn plane_heights(format: ObsVideoFormat, height: u32) -> Vec<u32> {
+    let half = (height + 1) / 2;
+    match format {
+        ObsVideoFormat::NONE => vec![],
+        ObsVideoFormat::I420 | ObsVideoFormat::I010 => vec![height, half, half],
+        ObsVideoFormat::NV12 | ObsVideoFormat::P010 => vec![height, half],
+        ObsVideoFormat::I40A => vec![height, half, half, height],
+        ObsVideoFormat::I444
+        | ObsVideoFormat::I422
+        | ObsVideoFormat::I210
+        | ObsVideoFormat::I412 => vec![height; 3],
+        ObsVideoFormat::I42A | ObsVideoFormat::YUVA | ObsVideoFormat::YA2L => vec![height; 4],
+        ObsVideoFormat::P216 | ObsVideoFormat::P416 => vec![height; 2],
+        ObsVideoFormat::YVYU
+        | ObsVideoFormat::YUY2
+        | ObsVideoFormat::UYVY
+        | ObsVideoFormat::RGBA
+        | ObsVideoFormat::BGRA
+        | ObsVideoFormat::BGRX
+        | ObsVideoFormat::Y800
+        | ObsVideoFormat::BGR3
+        | ObsVideoFormat::AYUV
+        | ObsVideoFormat::V210
+        | ObsVideoFormat::R10L => vec![height],
+    }
+}
+
+#[derive(Debug)]
+struct RawVideoCallbackData {
+    buffer: Arc<MediaBuffer<ObsVideoFrame>>,
+    format: ObsVideoFormat,
+    width: u32,
+    height: u32,
+}
+
+/// Called by the video thread of libobs for every rendered frame.
+unsafe extern "C" fn raw_video_callback(param: *mut c_void, frame: *mut video_data) {
+    if param.is_null() || frame.is_null() {
+        return;
+    }
+
+    let data = &*(param as *const RawVideoCallbackData);
+    let frame = ObsVideoFrame::from_raw(&*frame, data.format, data.width, data.height);
+    data.buffer.push(frame);
+}
+
+#[derive(Debug)]
+struct _RawVideoCallbackGuard {
+    data: Sendable<*mut RawVideoCallbackData>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_RawVideoCallbackGuard, (data), move || unsafe {
+    // Removing the callback waits for a running call to finish,
+    // so nothing uses the data afterwards
+    backend().obs_remove_raw_video_callback(Some(raw_video_callback), data as *mut c_void);
+    drop(Box::from_raw(data));
+});
+
+/// A stream of the frames that are rendered by the video context.
+/// Created by [`ObsContext::raw_video_stream`](crate::context::ObsContext::raw_video_stream).
+///
+/// The callback is removed from libobs once the stream is dropped. The stream ends
+/// once OBS is shut down.
+#[derive(Debug)]
+pub struct ObsRawVideoStream {
+    buffer: Arc<MediaBuffer<ObsVideoFrame>>,
+    shut_down: ShutdownSignal,
+    _guard: _RawVideoCallbackGuard,
+}
+
+impl ObsRawVideoStream {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        runtime: ObsRuntime,
+        options: ObsRawVideoOptions,
+    ) -> Result<Self, ObsError> {
+        let buffer = Arc::new(MediaBuffer::new(options.capacity, options.drop_policy));
+        let conversion = options.conversion;
+
+        let callback_buffer = buffer.clone();
+        let data = run_with_obs!(runtime, move || unsafe {
+            let backend = backend();
+            let (format, width, height) = match conversion {
+                Some(c) => (c.format, c.width, c.height),
+                None => {
+                    let mut ovi: obs_video_info = std::mem::zeroed();
+                    if !backend.obs_get_video_info(&mut ovi) {
+                        return Err(ObsError::VideoNotInitialized);
+                    }
+
+                    let format = ObsVideoFormat::from_i64(ovi.output_format as i64)
+                        .unwrap_or(ObsVideoFormat::NONE);
+                    (format, ovi.output_width, ovi.output_height)
+                }
+            };
+
+            let data = Box::into_raw(Box::new(RawVideoCallbackData {
+                buffer: callback_buffer,
+                format,
+                width,
+                height,
+            }));
+
+            let raw_conversion = conversion.map(|c| c.as_raw());
+            let conversion_ptr = raw_conversion
+                .as_ref()
+                .map_or(ptr::null(), |c| c as *const video_scale_info);
+            backend.obs_add_raw_video_callback(
+                conversion_ptr,
+                Some(raw_video_callback),
+                data as *mut c_void,
+            );
+
+            Ok(Sendable(data))
+        })
+        .await??;
+
+        Ok(Self {
+            buffer,
+            shut_down: runtime.shut_down_signal(),
+            _guard: _RawVideoCallbackGuard { data, runtime },
+        })
+    }
+
+    /// Returns the next buffered frame without waiting for a new one.
+    pub fn try_next_frame(&self) -> Option<ObsVideoFrame> {
+        self.buffer.try_pop()
+    }
+
+    /// The number of frames that are currently buffered.
+    pub fn buffered_frames(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The number of frames that were dropped because the buffer was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.buffer.dropped()
+    }
+}
+
+impl Stream for ObsRawVideoStream {
+    type Item = ObsVideoFrame;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.buffer.poll_next(cx, &mut this.shut_down)
+    }
+}
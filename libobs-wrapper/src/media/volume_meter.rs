@@ -0,0 +1,448 @@
+use std::{
+    ffi::c_void,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::Stream;
+use libobs::{obs_fader_t, obs_volmeter_t};
+
+use crate::{
+    backend::backend,
+    enums::{ObsFaderType, ObsPeakMeterType, OsEnumType},
+    impl_obs_drop, run_with_obs,
+    runtime::{ObsRuntime, ShutdownSignal},
+    sources::ObsSourceRef,
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+use super::{buffer::MediaBuffer, ObsDropPolicy};
+
+const MAX_AUDIO_CHANNELS: usize = libobs::MAX_AUDIO_CHANNELS as usize;
+
+/// Configures a volume meter, see [`ObsVolumeMeter`].
+#[derive(Debug, Clone)]
+pub struct ObsVolumeMeterOptions {
+    fader_type: ObsFaderType,
+    peak_meter_type: ObsPeakMeterType,
+    interval: Duration,
+    capacity: usize,
+    drop_policy: ObsDropPolicy,
+}
+
+impl ObsVolumeMeterOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The curve libobs uses for the meter, [`ObsFaderType::Log`] by default like OBS Studio.
+    pub fn set_fader_type(mut self, fader_type: ObsFaderType) -> Self {
+        self.fader_type = fader_type;
+        self
+    }
+
+    pub fn set_peak_meter_type(mut self, peak_meter_type: ObsPeakMeterType) -> Self {
+        self.peak_meter_type = peak_meter_type;
+        self
+    }
+
+    /// The minimum time between two updates (50ms by default). libobs measures the levels
+    /// once per audio tick, the highest levels in between are reported with the next update.
+    pub fn set_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The number of updates that are buffered until updates are dropped (16 by default).
+    pub fn set_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn set_drop_policy(mut self, drop_policy: ObsDropPolicy) -> Self {
+        self.drop_policy = drop_policy;
+        self
+    }
+}
+
+impl Default for ObsVolumeMeterOptions {
+    fn default() -> Self {
+        Self {
+            fader_type: ObsFaderType::Log,
+            peak_meter_type: ObsPeakMeterType::default(),
+            interval: Duration::from_millis(50),
+            capacity: 16,
+            drop_policy: ObsDropPolicy::default(),
+        }
+    }
+}
+
+/// The audio levels of a source in dBFS, with one value per channel.
+/// Silence is `f32::NEG_INFINITY`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsVolumeLevels {
+    /// The RMS level, after the volume of the source has been applied
+    pub magnitude: Vec<f32>,
+    /// The peak level, after the volume of the source has been applied
+    pub peak: Vec<f32>,
+    /// The peak level of the audio the source put out, before its volume was applied
+    pub input_peak: Vec<f32>,
+}
+
+impl ObsVolumeLevels {
+    fn silence(channels: usize) -> Self {
+        Self {
+            magnitude: vec![f32::NEG_INFINITY; channels],
+            peak: vec![f32::NEG_INFINITY; channels],
+            input_peak: vec![f32::NEG_INFINITY; channels],
+        }
+    }
+
+    /// Keeps the higher level of each channel.
+    fn merge(&mut self, magnitude: &[f32], peak: &[f32], input_peak: &[f32]) {
+        let levels = [
+            (&mut self.magnitude, magnitude),
+            (&mut self.peak, peak),
+            (&mut self.input_peak, input_peak),
+        ];
+        for (current, new) in levels {
+            for (current, new) in current.iter_mut().zip(new) {
+                *current = current.max(*new);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PendingLevels {
+    levels: Option<ObsVolumeLevels>,
+    last_update: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct VolmeterCallbackData {
+    buffer: Arc<MediaBuffer<ObsVolumeLevels>>,
+    volmeter: Sendable<*mut obs_volmeter_t>,
+    interval: Duration,
+    pending: Mutex<PendingLevels>,
+}
+
+/// Called by libobs on its audio thread every time the levels of the source were measured.
+unsafe extern "C" fn volmeter_callback(
+    param: *mut c_void,
+    magnitude: *const f32,
+    peak: *const f32,
+    input_peak: *const f32,
+) {
+    if param.is_null() || magnitude.is_null() || peak.is_null() || input_peak.is_null() {
+        return;
+    }
+
+    let data = &*(param as *const VolmeterCallbackData);
+    let channels = (backend()
+        .obs_volmeter_get_nr_channels(data.volmeter.0)
+        .max(0) as usize)
+        .min(MAX_AUDIO_CHANNELS);
+    let magnitude = std::slice::from_raw_parts(magnitude, channels);
+    let peak = std::slice::from_raw_parts(peak, channels);
+    let input_peak = std::slice::from_raw_parts(input_peak, channels);
+
+    let mut pending = match data.pending.lock() {
+        Ok(pending) => pending,
+        Err(e) => e.into_inner(),
+    };
+
+    // The channel count changes with the speaker layout of the source
+    let levels = pending
+        .levels
+        .take()
+        .filter(|l| l.magnitude.len() == channels);
+    let mut levels = levels.unwrap_or_else(|| ObsVolumeLevels::silence(channels));
+    levels.merge(magnitude, peak, input_peak);
+
+    let now = Instant::now();
+    let due = pending
+        .last_update
+        .is_none_or(|last| now.duration_since(last) >= data.interval);
+    if due {
+        pending.last_update = Some(now);
+        data.buffer.push(levels);
+    } else {
+        pending.levels = Some(levels);
+    }
+}
+
+#[derive(Debug)]
+struct _VolmeterGuard {
+    volmeter: Sendable<*mut obs_volmeter_t>,
+    data: Sendable<*mut VolmeterCallbackData>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_VolmeterGuard, (volmeter, data), move || unsafe {
+    let backend = backend();
+    // Removing the callback waits for a running call to finish,
+    // so nothing uses the data afterwards
+    backend.obs_volmeter_remove_callback(volmeter, Some(volmeter_callback), data as *mut c_void);
+    backend.obs_volmeter_detach_source(volmeter);
+    backend.obs_volmeter_destroy(volmeter);
+    drop(Box::from_raw(data));
+});
+
+/// A live audio level meter of a source, like the ones of the audio mixer in OBS Studio.
+/// Created by [`ObsSourceRef::volume_meter`].
+///
+/// The levels are delivered as a stream of [`ObsVolumeLevels`], which ends once OBS is shut
+/// down. The meter keeps the source alive and is detached from it once it is dropped.
+#[derive(Debug)]
+pub struct ObsVolumeMeter {
+    buffer: Arc<MediaBuffer<ObsVolumeLevels>>,
+    volmeter: Sendable<*mut obs_volmeter_t>,
+    runtime: ObsRuntime,
+    shut_down: ShutdownSignal,
+    // Dropped before the source, so the meter is detached while the source still exists
+    _guard: _VolmeterGuard,
+    _source: ObsSourceRef,
+}
+
+impl ObsVolumeMeter {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        source: ObsSourceRef,
+        options: ObsVolumeMeterOptions,
+    ) -> Result<Self, ObsError> {
+        let buffer = Arc::new(MediaBuffer::new(options.capacity, options.drop_policy));
+        let runtime = source.runtime.clone();
+
+        let source_name = source.name();
+        let source_ptr = source.source.clone();
+        let callback_buffer = buffer.clone();
+        let fader_type = options.fader_type as OsEnumType;
+        let peak_meter_type = options.peak_meter_type as OsEnumType;
+        let interval = options.interval;
+        let (volmeter, data) = run_with_obs!(runtime, (source_ptr), move || unsafe {
+            let backend = backend();
+            let volmeter = backend.obs_volmeter_create(fader_type);
+            if volmeter.is_null() {
+                return Err(ObsError::NullPointer);
+            }
+
+            backend.obs_volmeter_set_peak_meter_type(volmeter, peak_meter_type);
+            if !backend.obs_volmeter_attach_source(volmeter, source_ptr) {
+                backend.obs_volmeter_destroy(volmeter);
+                return Err(ObsError::AudioControlAttachFailure(source_name));
+            }
+
+            let data = Box::into_raw(Box::new(VolmeterCallbackData {
+                buffer: callback_buffer,
+                volmeter: Sendable(volmeter),
+                interval,
+                pending: Mutex::new(PendingLevels {
+                    levels: None,
+                    last_update: None,
+                }),
+            }));
+            backend.obs_volmeter_add_callback(
+                volmeter,
+                Some(volmeter_callback),
+                data as *mut c_void,
+            );
+
+            Ok((Sendable(volmeter), Sendable(data)))
+        })
+        .await??;
+
+        Ok(Self {
+            buffer,
+            volmeter: volmeter.clone(),
+            runtime: runtime.clone(),
+            shut_down: runtime.shut_down_signal(),
+            _guard: _VolmeterGuard {
+                volmeter,
+                data,
+                runtime,
+            },
+            _source: source,
+        })
+    }
+
+    /// The number of audio channels that are measured, which depends on the speaker layout
+    /// of the source and the audio context.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn channels(&self) -> Result<usize, ObsError> {
+        let volmeter = self.volmeter.clone();
+        run_with_obs!(self.runtime, (volmeter), move || unsafe {
+            backend().obs_volmeter_get_nr_channels(volmeter).max(0) as usize
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_peak_meter_type(
+        &self,
+        peak_meter_type: ObsPeakMeterType,
+    ) -> Result<(), ObsError> {
+        let volmeter = self.volmeter.clone();
+        run_with_obs!(self.runtime, (volmeter), move || unsafe {
+            backend().obs_volmeter_set_peak_meter_type(volmeter, peak_meter_type as OsEnumType);
+        })
+        .await
+    }
+
+    /// Returns the next buffered levels without waiting for new ones.
+    pub fn try_next_levels(&self) -> Option<ObsVolumeLevels> {
+        self.buffer.try_pop()
+    }
+
+    /// The number of updates that are currently buffered.
+    pub fn buffered_levels(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The number of updates that were dropped because the buffer was full.
+    pub fn dropped_levels(&self) -> u64 {
+        self.buffer.dropped()
+    }
+}
+
+impl Stream for ObsVolumeMeter {
+    type Item = ObsVolumeLevels;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.buffer.poll_next(cx, &mut this.shut_down)
+    }
+}
+
+#[derive(Debug)]
+struct _FaderGuard {
+    fader: Sendable<*mut obs_fader_t>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_FaderGuard, (fader), move || unsafe {
+    let backend = backend();
+    backend.obs_fader_detach_source(fader);
+    backend.obs_fader_destroy(fader);
+});
+
+/// A volume slider of a source. Moving the fader changes the volume of the source, and
+/// changes of the volume move the fader.
+/// Created by [`ObsSourceRef::fader`].
+///
+/// The position of the fader is called deflection and ranges from `0.0` to `1.0`. How it
+/// maps to the volume depends on the [`ObsFaderType`].
+#[derive(Debug)]
+pub struct ObsFader {
+    fader: Sendable<*mut obs_fader_t>,
+    fader_type: ObsFaderType,
+    runtime: ObsRuntime,
+    // Dropped before the source, so the fader is detached while the source still exists
+    _guard: _FaderGuard,
+    _source: ObsSourceRef,
+}
+
+impl ObsFader {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        source: ObsSourceRef,
+        fader_type: ObsFaderType,
+    ) -> Result<Self, ObsError> {
+        let runtime = source.runtime.clone();
+
+        let source_name = source.name();
+        let source_ptr = source.source.clone();
+        let raw_fader_type = fader_type as OsEnumType;
+        let fader = run_with_obs!(runtime, (source_ptr), move || unsafe {
+            let backend = backend();
+            let fader = backend.obs_fader_create(raw_fader_type);
+            if fader.is_null() {
+                return Err(ObsError::NullPointer);
+            }
+
+            if !backend.obs_fader_attach_source(fader, source_ptr) {
+                backend.obs_fader_destroy(fader);
+                return Err(ObsError::AudioControlAttachFailure(source_name));
+            }
+
+            Ok(Sendable(fader))
+        })
+        .await??;
+
+        Ok(Self {
+            fader: fader.clone(),
+            fader_type,
+            runtime: runtime.clone(),
+            _guard: _FaderGuard { fader, runtime },
+            _source: source,
+        })
+    }
+
+    pub fn fader_type(&self) -> ObsFaderType {
+        self.fader_type
+    }
+
+    /// The volume in dB the fader is set to.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn db(&self) -> Result<f32, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_get_db(fader)
+        })
+        .await
+    }
+
+    /// Sets the volume in dB. Returns `false` if the value was clamped to the range of the fader.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_db(&self, db: f32) -> Result<bool, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_set_db(fader, db)
+        })
+        .await
+    }
+
+    /// The position of the fader, from `0.0` to `1.0`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn deflection(&self) -> Result<f32, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_get_deflection(fader)
+        })
+        .await
+    }
+
+    /// Moves the fader. Returns `false` if the value was clamped to `0.0..=1.0`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_deflection(&self, deflection: f32) -> Result<bool, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_set_deflection(fader, deflection)
+        })
+        .await
+    }
+
+    /// The volume as linear multiplier.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn mul(&self) -> Result<f32, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_get_mul(fader)
+        })
+        .await
+    }
+
+    /// Sets the volume as linear multiplier. Returns `false` if the value was clamped to the
+    /// range of the fader.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_mul(&self, mul: f32) -> Result<bool, ObsError> {
+        let fader = self.fader.clone();
+        run_with_obs!(self.runtime, (fader), move || unsafe {
+            backend().obs_fader_set_mul(fader, mul)
+        })
+        .await
+    }
+}
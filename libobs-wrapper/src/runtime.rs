@@ -16,6 +16,27 @@
 //! - By default, all operations are asynchronous
 //! - With the `blocking` feature enabled, operations are synchronous
 //!
+//! # Timeouts and Watchdog
+//!
+//! By default every call waits until the OBS thread executed it. A default deadline can be
+//! configured with [`ObsWatchdogOptions`] (or [`ObsRuntime::set_default_timeout`]) and
+//! per call with [`ObsRuntime::run_with_obs_result_timeout`]. Calls that miss their deadline
+//! return [`ObsError::CommandTimeout`]. A watchdog thread also reports when the OBS thread
+//! stops making progress, see [`ObsRuntime::watchdog_events`].
+//!
+//! # Metrics
+//!
+//! The queue depth as well as the queue wait and execution time of every command are recorded,
+//! see [`ObsRuntime::metrics`] and [`ObsRuntime::subscribe_metrics`]. Commands are grouped by
+//! the function that sent them, use [`ObsRuntime::run_with_obs_result_labeled`] (or
+//! `run_with_obs!(runtime, label = "...", ...)`) to give them a name of your own.
+//!
+//! # Transactions
+//!
+//! Every call is a separate round-trip to the OBS thread. To apply many changes at once,
+//! queue them in an [`ObsTransaction`] (see [`ObsRuntime::transaction`]). All operations
+//! of a transaction are executed in a single command, between two rendered frames.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -35,20 +56,27 @@
 //! }
 //! ```
 
+use std::cell::Cell;
 use std::ffi::CStr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 use std::{fmt::Debug, thread::JoinHandle};
-use std::{ptr, thread};
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot, watch};
 
+use crate::backend::{backend, set_backend, LibObsBackend};
 #[cfg(feature = "bootstrapper")]
 use crate::bootstrap::bootstrap;
 use crate::crash_handler::main_crash_handler;
 use crate::enums::{ObsLogLevel, ObsResetVideoStatus};
-use crate::logger::{extern_log_callback, internal_log_global, LOGGER};
+use crate::logger::{extern_log_callback, internal_log_global, ConsoleLogger, LOGGER};
 use crate::unsafe_send::Sendable;
+#[cfg(windows)]
 use crate::utils::initialization::load_debug_privilege;
 use crate::utils::{ObsBootstrapError, ObsError, ObsModules, ObsString};
 use crate::{
@@ -57,12 +85,29 @@ use crate::{
 };
 use crate::{mutex_blocking_lock, oneshot_rx_recv};
 
+mod metrics;
+mod transaction;
+mod watchdog;
+pub use metrics::{ObsCommandMetrics, ObsLatencyBucket, ObsLatencyHistogram, ObsRuntimeMetrics};
+use metrics::MetricsRecorder;
+pub use transaction::*;
+pub use watchdog::{ObsRuntimeEvent, ObsWatchdogOptions};
+use watchdog::{CommandResponder, CommandResult, Watchdog};
+
+thread_local! {
+    /// Set on the OBS thread, so commands that are issued from there
+    /// (for example by dropping an object in a transaction) run right away.
+    static IS_OBS_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
 /// Command type for operations to perform on the OBS thread
 enum ObsCommand {
-    /// Execute a function on the OBS thread and send result back
+    /// Execute a function on the OBS thread and send result back.
+    /// The id is the id the command was registered with at the watchdog.
     Execute(
         Box<dyn FnOnce() -> Box<dyn std::any::Any + Send> + Send>,
-        oneshot::Sender<Box<dyn std::any::Any + Send>>,
+        u64,
+        CommandResponder,
     ),
     /// Signal the OBS thread to terminate
     Terminate,
@@ -114,6 +159,8 @@ pub enum ObsRuntimeReturn {
 pub struct ObsRuntime {
     command_sender: Arc<Sender<ObsCommand>>,
     queued_commands: Arc<AtomicUsize>,
+    watchdog: Arc<Watchdog>,
+    metrics: Arc<MetricsRecorder>,
     _guard: Arc<_ObsRuntimeGuard>,
 }
 
@@ -240,11 +287,20 @@ impl ObsRuntime {
     async fn init(info: StartupInfo) -> anyhow::Result<(ObsRuntime, ObsModules, StartupInfo)> {
         let (command_sender, command_receiver) = channel();
         let (init_tx, init_rx) = oneshot::channel();
+        let (exit_tx, exit_rx) = oneshot::channel();
         let queued_commands = Arc::new(AtomicUsize::new(0));
+        let metrics = Arc::new(MetricsRecorder::new(queued_commands.clone()));
+        let watchdog = Arc::new(Watchdog::start(
+            info.watchdog_options.clone(),
+            queued_commands.clone(),
+            metrics.clone(),
+        ));
 
         let queued_commands_clone = queued_commands.clone();
+        let watchdog_clone = watchdog.clone();
         let handle = std::thread::spawn(move || {
             log::trace!("Starting OBS thread");
+            IS_OBS_THREAD.with(|t| t.set(true));
 
             let res = Self::initialize_inner(info);
 
@@ -259,20 +315,43 @@ impl ObsRuntime {
                     // Process commands until termination
                     while let Ok(command) = command_receiver.recv() {
                         match command {
-                            ObsCommand::Execute(func, result_sender) => {
+                            ObsCommand::Execute(func, id, responder) => {
+                                watchdog_clone.begin(id);
                                 let result = func();
-                                let _ = result_sender.send(result);
+                                watchdog_clone.finish(id);
+                                // Before answering, so the caller doesn't see its own command as queued
                                 queued_commands_clone.fetch_sub(1, Ordering::SeqCst);
+
+                                // The watchdog already answered if the command timed out
+                                let result_sender = match responder.lock() {
+                                    Ok(mut r) => r.take(),
+                                    Err(e) => e.into_inner().take(),
+                                };
+                                if let Some(result_sender) = result_sender {
+                                    let _ = result_sender.send(Ok(result));
+                                }
                             }
                             ObsCommand::Terminate => break,
                         }
                     }
 
-                    Self::shutdown_inner();
+                    let allocs = Some(Self::shutdown_inner());
+                    // Lets the runtime guard wait for the shutdown without blocking on `join`
+                    let _ = exit_tx.send(allocs);
+                    allocs
                 }
                 Err(err) => {
                     log::error!("Failed to initialize OBS context: {:?}", err);
+
+                    // Resetting everything that was already set up, so OBS can
+                    // be initialized again. If another context is running, there
+                    // is nothing to clean up.
+                    if err != ObsError::ThreadFailure {
+                        Self::shutdown_inner();
+                    }
+
                     let _ = init_tx.send(Err(err));
+                    None
                 }
             }
         });
@@ -281,14 +360,22 @@ impl ObsRuntime {
         // Wait for initialization to complete
         let (mut m, info) = oneshot_rx_recv!(init_rx)??;
 
-        let handle = Arc::new(Mutex::new(Some(handle)));
+        let thread = Arc::new(Mutex::new(Some(ObsThread {
+            handle,
+            exit: exit_rx,
+        })));
         let command_sender = Arc::new(command_sender);
         let runtime = Self {
             command_sender: command_sender.clone(),
             queued_commands,
+            watchdog: watchdog.clone(),
+            metrics,
             _guard: Arc::new(_ObsRuntimeGuard {
-                handle,
+                thread,
                 command_sender,
+                is_shut_down: AtomicBool::new(false),
+                shut_down_sender: watch::channel(false).0,
+                _watchdog: watchdog,
             }),
         };
 
@@ -326,7 +413,7 @@ impl ObsRuntime {
     where
         F: FnOnce() -> () + Send + 'static,
     {
-        self.run_with_obs_result(move || {
+        self.execute(std::any::type_name::<F>(), None, move || {
             operation();
             Result::<(), anyhow::Error>::Ok(())
         })
@@ -355,32 +442,13 @@ impl ObsRuntime {
         F: FnOnce() -> T + Send + 'static,
         T: Send + 'static,
     {
-        let (tx, rx) = oneshot::channel();
-
-        // Create a wrapper closure that boxes the result as Any
-        let wrapper = move || -> Box<dyn std::any::Any + Send> {
-            let result = operation();
-            Box::new(result)
-        };
-
-        let val = self.queued_commands.fetch_add(1, Ordering::SeqCst);
-        if val > 50 {
-            log::warn!("More than 50 queued commands. Try to batch them together.");
-        }
-
-        self.command_sender
-            .send(ObsCommand::Execute(Box::new(wrapper), tx))
-            .map_err(|_| anyhow::anyhow!("Failed to send command to OBS thread"))?;
+        let rx = self.dispatch(std::any::type_name::<F>(), None, operation)?;
 
         let result = rx
             .blocking_recv()
-            .map_err(|_| anyhow::anyhow!("OBS thread dropped the response channel"))?;
+            .map_err(|_| anyhow::anyhow!("OBS thread dropped the response channel"))??;
 
-        // Downcast the Any type back to T
-        result
-            .downcast::<T>()
-            .map(|boxed| *boxed)
-            .map_err(|_| anyhow::anyhow!("Failed to downcast result to the expected type"))
+        Self::downcast_result(result)
     }
 
     /// Executes an operation on the OBS thread and returns a result (async version)
@@ -411,11 +479,218 @@ impl ObsRuntime {
     /// ```
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn run_with_obs_result<F, T>(&self, operation: F) -> anyhow::Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.execute(std::any::type_name::<F>(), None, operation).await
+    }
+
+    /// Same as [`ObsRuntime::run_with_obs`], but the command is recorded under the given
+    /// label in the [metrics](ObsRuntime::metrics) and watchdog events.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn run_with_obs_labeled<F>(
+        &self,
+        label: &'static str,
+        operation: F,
+    ) -> anyhow::Result<()>
+    where
+        F: FnOnce() -> () + Send + 'static,
+    {
+        self.execute(label, None, operation).await
+    }
+
+    /// Same as [`ObsRuntime::run_with_obs_result`], but the command is recorded under the
+    /// given label in the [metrics](ObsRuntime::metrics) and watchdog events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libobs_wrapper::runtime::ObsRuntime;
+    ///
+    /// async fn example(runtime: &ObsRuntime) {
+    ///     let version = runtime.run_with_obs_result_labeled("get_version", || {
+    ///         unsafe { libobs::obs_get_version_string() as usize }
+    ///     }).await.unwrap();
+    ///
+    ///     let metrics = runtime.metrics();
+    ///     println!("{:x} took {:?}", version, metrics.labels["get_version"].execution.max);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn run_with_obs_result_labeled<F, T>(
+        &self,
+        label: &'static str,
+        operation: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.execute(label, None, operation).await
+    }
+
+    /// Executes an operation on the OBS thread and returns a result, giving up after `timeout`.
+    ///
+    /// If the OBS thread doesn't finish the operation in time, an
+    /// [`ObsError::CommandTimeout`] is returned. The operation itself is not cancelled
+    /// and still runs once the OBS thread gets to it, its result is discarded then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use libobs_wrapper::runtime::ObsRuntime;
+    /// use libobs_wrapper::utils::ObsError;
+    ///
+    /// async fn example(runtime: &ObsRuntime) {
+    ///     let res = runtime.run_with_obs_result_timeout(Duration::from_secs(1), || {
+    ///         unsafe { libobs::obs_get_version() }
+    ///     }).await;
+    ///
+    ///     if let Err(e) = res {
+    ///         if let Some(ObsError::CommandTimeout(command, _)) = e.downcast_ref::<ObsError>() {
+    ///             println!("OBS thread is blocked, {} timed out", command);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn run_with_obs_result_timeout<F, T>(
+        &self,
+        timeout: Duration,
+        operation: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.execute(std::any::type_name::<F>(), Some(timeout), operation)
+            .await
+    }
+
+    /// Returns the default deadline of commands that are sent to the OBS thread.
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.watchdog.default_timeout()
+    }
+
+    /// Sets the default deadline of commands that are sent to the OBS thread.
+    /// `None` waits forever.
+    pub fn set_default_timeout(&self, timeout: Option<Duration>) {
+        self.watchdog.set_default_timeout(timeout);
+    }
+
+    /// Subscribes to events of the runtime watchdog, for example when the OBS thread stalls.
+    pub fn watchdog_events(&self) -> broadcast::Receiver<ObsRuntimeEvent> {
+        self.watchdog.subscribe()
+    }
+
+    /// Shuts down libobs and terminates the OBS thread, even if there are still
+    /// other references to this runtime.
+    ///
+    /// Returns the number of memory leaks libobs reported after shutting down,
+    /// or `None` if the runtime has already been shut down.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn shutdown(&self) -> Result<Option<i64>, ObsError> {
+        self._guard
+            .shutdown()
+            .await
+            .map_err(|e| ObsError::InvocationError(e.to_string()))
+    }
+
+    /// Whether the runtime was shut down explicitly. Objects which are dropped
+    /// afterwards don't release anything as libobs already freed everything.
+    pub fn is_shut_down(&self) -> bool {
+        self._guard.is_shut_down.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future that resolves once libobs has been shut down, used to end streams.
+    pub(crate) fn shut_down_signal(&self) -> ShutdownSignal {
+        let mut receiver = self._guard.shut_down_sender.subscribe();
+        ShutdownSignal(Some(Box::pin(async move {
+            // An error means the runtime is gone, which only happens after shutting down
+            let _ = receiver.wait_for(|shut_down| *shut_down).await;
+        })))
+    }
+
+    /// Returns the number of commands that are currently queued or running on the OBS thread.
+    pub fn queued_commands(&self) -> usize {
+        self.queued_commands.load(Ordering::SeqCst)
+    }
+
+    /// Returns a snapshot of the queue depth and the latencies of the commands so far.
+    pub fn metrics(&self) -> ObsRuntimeMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// Subscribes to the metrics of the runtime. A new snapshot is sent at most every poll
+    /// interval of the watchdog (see [`ObsWatchdogOptions::set_poll_interval`]) if commands
+    /// finished in the meantime.
+    pub fn subscribe_metrics(&self) -> watch::Receiver<ObsRuntimeMetrics> {
+        self.metrics.subscribe()
+    }
+
+    /// Starts a new transaction. Operations that are added to it are executed
+    /// together once [`ObsTransaction::commit`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use libobs_wrapper::runtime::ObsRuntime;
+    ///
+    /// async fn example(runtime: &ObsRuntime) {
+    ///     let mut transaction = runtime.transaction();
+    ///     let version = transaction.add(|_| {
+    ///         Ok(unsafe { libobs::obs_get_version_string() } as usize)
+    ///     });
+    ///
+    ///     let mut values = transaction.commit().await.unwrap();
+    ///     println!("{:?}", values.take(version));
+    /// }
+    /// ```
+    pub fn transaction(&self) -> ObsTransaction {
+        ObsTransaction::new(self.clone())
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn execute<F, T>(
+        &self,
+        name: &'static str,
+        timeout: Option<Duration>,
+        operation: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let rx = self.dispatch(name, timeout, operation)?;
+
+        let result = oneshot_rx_recv!(rx)
+            .map_err(|_| anyhow::anyhow!("OBS thread dropped the response channel"))??;
+
+        Self::downcast_result(result)
+    }
+
+    /// Registers the operation at the watchdog and sends it to the OBS thread.
+    fn dispatch<F, T>(
+        &self,
+        name: &'static str,
+        timeout: Option<Duration>,
+        operation: F,
+    ) -> anyhow::Result<oneshot::Receiver<CommandResult>>
     where
         F: FnOnce() -> T + Send + 'static,
         T: Send + 'static,
     {
         let (tx, rx) = oneshot::channel();
+        if IS_OBS_THREAD.with(|t| t.get()) {
+            // Queuing the command would deadlock, as the OBS thread is waiting for it
+            let result: Box<dyn std::any::Any + Send> = Box::new(operation());
+            let _ = tx.send(Ok(result));
+            return Ok(rx);
+        }
+
+        let responder: CommandResponder = Arc::new(std::sync::Mutex::new(Some(tx)));
 
         // Create a wrapper closure that boxes the result as Any
         let wrapper = move || -> Box<dyn std::any::Any + Send> {
@@ -423,26 +698,32 @@ impl ObsRuntime {
             Box::new(result)
         };
 
-
         let val = self.queued_commands.fetch_add(1, Ordering::SeqCst);
+        self.metrics.record_queue_depth(val + 1);
         if val > 50 {
             log::warn!("More than 50 queued commands. Try to batch them together.");
         }
 
-        self.command_sender
-            .send(ObsCommand::Execute(Box::new(wrapper), tx))
-            .map_err(|_| anyhow::anyhow!("Failed to send command to OBS thread"))?;
+        let id = self.watchdog.register(name, timeout, responder.clone());
+        let res = self
+            .command_sender
+            .send(ObsCommand::Execute(Box::new(wrapper), id, responder));
 
-        let result =
-            oneshot_rx_recv!(rx).map_err(|_| anyhow::anyhow!("OBS thread dropped the response channel"))?;
+        if res.is_err() {
+            self.queued_commands.fetch_sub(1, Ordering::SeqCst);
+            self.watchdog.finish(id);
+            anyhow::bail!("Failed to send command to OBS thread");
+        }
 
+        Ok(rx)
+    }
+
+    fn downcast_result<T: 'static>(result: Box<dyn std::any::Any + Send>) -> anyhow::Result<T> {
         // Downcast the Any type back to T
-        let res = result
+        result
             .downcast::<T>()
             .map(|boxed| *boxed)
-            .map_err(|_| anyhow::anyhow!("Failed to downcast result to the expected type"))?;
-
-        Ok(res)
+            .map_err(|_| anyhow::anyhow!("Failed to downcast result to the expected type"))
     }
 
     /// Initializes the libobs context and prepares it for recording.
@@ -488,20 +769,27 @@ impl ObsRuntime {
         // the current thread is using it.
         *mutex_value = Some(thread::current().id());
 
+        // Every libobs call from now on goes through the backend of this context
+        set_backend(info.backend.clone());
+        let backend = backend();
+
         // Install DLL blocklist hook here
 
+        #[cfg(windows)]
         unsafe {
-            libobs::obs_init_win32_crash_handler();
+            backend.obs_init_win32_crash_handler();
         }
 
         // Set logger, load debug privileges and crash handler
         unsafe {
-            libobs::base_set_crash_handler(Some(main_crash_handler), std::ptr::null_mut());
+            backend.base_set_crash_handler(Some(main_crash_handler), std::ptr::null_mut());
+            #[cfg(windows)]
             load_debug_privilege();
-            libobs::base_set_log_handler(Some(extern_log_callback), std::ptr::null_mut());
+            backend.base_set_log_handler(Some(extern_log_callback), std::ptr::null_mut());
         }
 
-        let mut log_callback = LOGGER.lock().map_err(|_e| ObsError::MutexFailure)?;
+        // A logger of a previous context could have panicked, we are replacing it anyway
+        let mut log_callback = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
 
         *log_callback = info.logger.take().expect("Logger can never be null");
         drop(log_callback);
@@ -511,9 +799,9 @@ impl ObsRuntime {
         // unnecessary to support other languages.
         let locale_str = ObsString::new("en-US");
         let startup_status =
-            unsafe { libobs::obs_startup(locale_str.as_ptr().0, ptr::null(), ptr::null_mut()) };
+            unsafe { backend.obs_startup(locale_str.as_ptr().0, ptr::null(), ptr::null_mut()) };
 
-        let version = unsafe { libobs::obs_get_version_string() };
+        let version = unsafe { backend.obs_get_version_string() };
         let version_cstr = unsafe { CStr::from_ptr(version) };
         let version_str = version_cstr.to_string_lossy().into_owned();
 
@@ -534,7 +822,7 @@ impl ObsRuntime {
         //
         // https://docs.obsproject.com/frontends
         unsafe {
-            libobs::obs_reset_audio2(info.obs_audio_info.as_ptr().0);
+            backend.obs_reset_audio2(info.obs_audio_info.as_ptr().0);
         }
 
         // Resets the video context. Note that this
@@ -544,7 +832,7 @@ impl ObsRuntime {
         // and also because there is no need to free
         // anything tied to the OBS context.
         let reset_video_status = num_traits::FromPrimitive::from_i32(unsafe {
-            libobs::obs_reset_video(info.obs_video_info.as_ptr())
+            backend.obs_reset_video(info.obs_video_info.as_ptr())
         });
 
         let reset_video_status = match reset_video_status {
@@ -573,58 +861,82 @@ impl ObsRuntime {
     /// - Calling `obs_shutdown` to clean up OBS resources
     /// - Removing log and crash handlers
     /// - Checking for memory leaks
-    fn shutdown_inner() {
+    ///
+    /// Returns the number of memory leaks reported by libobs.
+    fn shutdown_inner() -> i64 {
+        let backend = backend();
+
         // Clean up sources
         for i in 0..libobs::MAX_CHANNELS {
-            unsafe { libobs::obs_set_output_source(i, ptr::null_mut()) };
+            unsafe { backend.obs_set_output_source(i, ptr::null_mut()) };
         }
 
-        unsafe { libobs::obs_shutdown() }
-
-        let r = LOGGER.lock();
-        match r {
-            Ok(mut logger) => {
-                logger.log(ObsLogLevel::Info, "OBS context shutdown.".to_string());
-                let allocs = unsafe { libobs::bnum_allocs() };
-
-                // Increasing this to 1 because of whats described below
-                let mut notice = "";
-                let level = if allocs > 1 {
-                    ObsLogLevel::Error
-                } else {
-                    notice = " (this is an issue in the OBS source code that cannot be fixed)";
-                    ObsLogLevel::Info
-                };
-                // One memory leak is expected here because OBS does not free array elements of the obs_data_path when calling obs_add_data_path
-                // even when obs_remove_data_path is called. This is a bug in OBS.
-                logger.log(level, format!("Number of memory leaks: {}{}", allocs, notice))
-            }
-            Err(_) => {
-                println!("OBS context shutdown. (but couldn't lock logger)");
-            }
-        }
+        unsafe { backend.obs_shutdown() }
+
+        // Signal managers that are still alive don't need their senders anymore
+        crate::signals::clear_all_senders();
+
+        let allocs = unsafe { backend.bnum_allocs() } as i64;
+        let mut logger = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
+        logger.log(ObsLogLevel::Info, "OBS context shutdown.".to_string());
+
+        // Increasing this to 1 because of whats described below
+        let mut notice = "";
+        let level = if allocs > 1 {
+            ObsLogLevel::Error
+        } else {
+            notice = " (this is an issue in the OBS source code that cannot be fixed)";
+            ObsLogLevel::Info
+        };
+        // One memory leak is expected here because OBS does not free array elements of the obs_data_path when calling obs_add_data_path
+        // even when obs_remove_data_path is called. This is a bug in OBS.
+        logger.log(
+            level,
+            format!("Number of memory leaks: {}{}", allocs, notice),
+        );
+
+        // Dropping the logger of this context, the next context brings its own
+        *logger = Box::new(ConsoleLogger::new());
+        drop(logger);
 
         unsafe {
             // Clean up log and crash handler
-            libobs::base_set_crash_handler(None, std::ptr::null_mut());
-            libobs::base_set_log_handler(None, std::ptr::null_mut());
+            backend.base_set_crash_handler(None, std::ptr::null_mut());
+            backend.base_set_log_handler(None, std::ptr::null_mut());
         }
 
+        set_backend(Arc::new(LibObsBackend));
+
         let mut mutex_value = mutex_blocking_lock!(OBS_THREAD_ID);
         *mutex_value = None;
+
+        allocs
     }
 }
 
+/// The OBS thread, together with the channel it reports the result of its shutdown on.
+#[derive(Debug)]
+struct ObsThread {
+    handle: JoinHandle<Option<i64>>,
+    exit: oneshot::Receiver<Option<i64>>,
+}
+
 /// Guard object to ensure proper cleanup when the runtime is dropped
 ///
 /// This guard ensures that when the last reference to the runtime is dropped,
 /// the OBS thread is properly terminated and all resources are cleaned up.
 #[derive(Debug)]
 pub struct _ObsRuntimeGuard {
-    /// Thread handle for the OBS thread
-    handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// The OBS thread, taken once it has been told to terminate
+    thread: Arc<Mutex<Option<ObsThread>>>,
     /// Sender channel for the OBS thread
     command_sender: Arc<Sender<ObsCommand>>,
+    /// Set once the OBS thread has been told to terminate
+    is_shut_down: AtomicBool,
+    /// Set once libobs has been shut down and the OBS thread terminated
+    shut_down_sender: watch::Sender<bool>,
+    /// Kept here so the watchdog thread is stopped after the OBS thread
+    _watchdog: Arc<Watchdog>,
 }
 
 impl _ObsRuntimeGuard {
@@ -635,39 +947,82 @@ impl _ObsRuntimeGuard {
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure
+    /// A `Result` containing the number of memory leaks reported by libobs,
+    /// or `None` if the runtime has already been shut down
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
-    async fn shutdown(&mut self) -> anyhow::Result<()> {
+    async fn shutdown(&self) -> anyhow::Result<Option<i64>> {
+        let mut thread = self.thread.lock().await;
+        let Some(ObsThread { handle, exit }) = thread.take() else {
+            // Already shut down explicitly
+            return Ok(None);
+        };
+
+        self.is_shut_down.store(true, Ordering::SeqCst);
+
         // Theoretically the queued_commands is zero and should be increased but because
         // we are shutting down, we don't care about that.
         self.command_sender
             .send(ObsCommand::Terminate)
             .map_err(|_| anyhow::anyhow!("Failed to send termination command to OBS thread"))?;
 
-        // Wait for the thread to finish
-        let mut handle = self.handle.lock().await;
-        let handle = handle.take().expect("Handle can not be empty");
-
-        if let Err(err) = handle.join() {
-            return Err(anyhow::anyhow!("OBS thread panicked: {:?}", err));
+        // Wait for the thread to finish. Its result is sent right before it exits, so
+        // joining afterwards doesn't block the executor. If the thread panicked, the
+        // sender is dropped while unwinding and `join` returns the panic.
+        let res = oneshot_rx_recv!(exit);
+        let joined = handle.join();
+        self.shut_down_sender.send_replace(true);
+
+        match (res, joined) {
+            (Ok(allocs), Ok(_)) => Ok(allocs),
+            (_, Err(err)) => Err(anyhow::anyhow!("OBS thread panicked: {:?}", err)),
+            (Err(_), Ok(_)) => Err(anyhow::anyhow!("OBS thread exited without shutting down")),
         }
-        Ok(())
     }
 }
 
 impl Drop for _ObsRuntimeGuard {
     /// Ensures the OBS thread is properly shut down when the runtime is dropped
     fn drop(&mut self) {
+        if self.is_shut_down.load(Ordering::SeqCst) {
+            // Shut down explicitly, nothing left to clean up
+            return;
+        }
+
         #[cfg(feature = "blocking")]
         let r = self.shutdown();
 
         #[cfg(not(feature = "blocking"))]
         let r = futures::executor::block_on(self.shutdown());
 
-        if thread::panicking() {
-            return;
+        if let Err(e) = r {
+            log::error!("Failed to shut down the OBS runtime: {:?}", e);
         }
+    }
+}
+
+/// Resolves once libobs has been shut down, see [`ObsRuntime::shut_down_signal`].
+/// Can be polled again after it resolved.
+pub(crate) struct ShutdownSignal(Option<Pin<Box<dyn Future<Output = ()> + Send>>>);
+
+impl Future for ShutdownSignal {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(inner) = self.0.as_mut() else {
+            return Poll::Ready(());
+        };
+
+        let res = inner.as_mut().poll(cx);
+        if res.is_ready() {
+            self.0 = None;
+        }
+
+        res
+    }
+}
 
-        r.unwrap();
+impl Debug for ShutdownSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownSignal").finish_non_exhaustive()
     }
 }
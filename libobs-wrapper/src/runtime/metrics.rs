@@ -0,0 +1,268 @@
+//! Metrics about the commands that are executed on the OBS thread.
+//!
+//! For every command the time it waited in the queue and the time it took to execute
+//! are recorded in histograms, both in total and per label. Commands are labeled by the
+//! function that sent them, or by the label that was passed to
+//! [`ObsRuntime::run_with_obs_result_labeled`](super::ObsRuntime::run_with_obs_result_labeled).
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+use tokio::sync::watch;
+
+/// Upper bounds of the histogram buckets.
+const BUCKET_BOUNDS: [Duration; 16] = [
+    Duration::from_micros(50),
+    Duration::from_micros(100),
+    Duration::from_micros(250),
+    Duration::from_micros(500),
+    Duration::from_millis(1),
+    Duration::from_micros(2500),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+];
+
+/// A bucket of an [`ObsLatencyHistogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObsLatencyBucket {
+    /// The largest duration that is counted in this bucket, `None` for the last bucket
+    pub upper_bound: Option<Duration>,
+    /// The number of samples in this bucket (not including the ones of the previous buckets)
+    pub count: u64,
+}
+
+/// Distribution of durations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObsLatencyHistogram {
+    /// The number of recorded samples
+    pub count: u64,
+    /// The sum of all recorded samples
+    pub sum: Duration,
+    /// The longest recorded sample
+    pub max: Duration,
+    pub buckets: Vec<ObsLatencyBucket>,
+}
+
+impl Default for ObsLatencyHistogram {
+    fn default() -> Self {
+        let buckets = BUCKET_BOUNDS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .map(|upper_bound| ObsLatencyBucket {
+                upper_bound,
+                count: 0,
+            })
+            .collect();
+
+        Self {
+            count: 0,
+            sum: Duration::ZERO,
+            max: Duration::ZERO,
+            buckets,
+        }
+    }
+}
+
+impl ObsLatencyHistogram {
+    fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.sum += duration;
+        self.max = self.max.max(duration);
+
+        let bucket = self
+            .buckets
+            .iter_mut()
+            .find(|b| match b.upper_bound {
+                Some(bound) => duration <= bound,
+                None => true,
+            });
+        if let Some(bucket) = bucket {
+            bucket.count += 1;
+        }
+    }
+
+    /// The average of all recorded samples.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(Duration::from_nanos(
+            (self.sum.as_nanos() / self.count as u128) as u64,
+        ))
+    }
+
+    /// Estimates the given quantile (between 0 and 1) as the upper bound of the
+    /// bucket it falls into. For the last bucket the longest sample is returned.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let target = (quantile.clamp(0.0, 1.0) * self.count as f64)
+            .ceil()
+            .max(1.0) as u64;
+        let mut seen = 0;
+        for bucket in &self.buckets {
+            seen += bucket.count;
+            if seen >= target {
+                return Some(bucket.upper_bound.unwrap_or(self.max).min(self.max));
+            }
+        }
+
+        Some(self.max)
+    }
+}
+
+/// Metrics of all commands with the same label.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObsCommandMetrics {
+    /// The number of commands that finished
+    pub count: u64,
+    /// The number of commands whose caller received a timeout
+    pub timed_out: u64,
+    /// The time commands waited in the queue before they were executed
+    pub queue_wait: ObsLatencyHistogram,
+    /// The time commands took to execute on the OBS thread
+    pub execution: ObsLatencyHistogram,
+}
+
+/// A snapshot of the metrics of the OBS runtime.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObsRuntimeMetrics {
+    /// The number of commands that are currently queued or running
+    pub queued_commands: usize,
+    /// The largest number of queued commands so far
+    pub max_queued_commands: usize,
+    /// Metrics of all commands together
+    pub total: ObsCommandMetrics,
+    /// Metrics per command label
+    pub labels: HashMap<String, ObsCommandMetrics>,
+}
+
+impl ObsRuntimeMetrics {
+    /// Returns the labels with the longest average execution time, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<(&str, Duration)> {
+        let mut labels = self
+            .labels
+            .iter()
+            .filter_map(|(label, m)| Some((label.as_str(), m.execution.mean()?)))
+            .collect::<Vec<_>>();
+
+        labels.sort_by(|a, b| b.1.cmp(&a.1));
+        labels.truncate(count);
+        labels
+    }
+}
+
+/// Returns the label of a command that was sent without an explicit label,
+/// which is the function the closure was defined in.
+pub(crate) fn command_label(name: &str) -> &str {
+    let mut label = name;
+    while let Some(stripped) = label.strip_suffix("::{{closure}}") {
+        label = stripped;
+    }
+
+    label
+}
+
+#[derive(Debug)]
+pub(crate) struct MetricsRecorder {
+    state: Mutex<ObsRuntimeMetrics>,
+    changed: AtomicBool,
+    sender: watch::Sender<ObsRuntimeMetrics>,
+    queued_commands: Arc<AtomicUsize>,
+}
+
+impl MetricsRecorder {
+    pub(crate) fn new(queued_commands: Arc<AtomicUsize>) -> Self {
+        let (sender, _) = watch::channel(ObsRuntimeMetrics::default());
+
+        Self {
+            state: Mutex::new(ObsRuntimeMetrics::default()),
+            changed: AtomicBool::new(false),
+            sender,
+            queued_commands,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, ObsRuntimeMetrics> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    pub(crate) fn record_queue_depth(&self, depth: usize) {
+        let mut state = self.state();
+        state.max_queued_commands = state.max_queued_commands.max(depth);
+    }
+
+    pub(crate) fn record(&self, name: &str, queue_wait: Duration, execution: Duration) {
+        let mut state = self.state();
+        let state = &mut *state;
+        for metrics in [
+            &mut state.total,
+            Self::label(&mut state.labels, command_label(name)),
+        ] {
+            metrics.count += 1;
+            metrics.queue_wait.record(queue_wait);
+            metrics.execution.record(execution);
+        }
+
+        self.changed.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn record_timeout(&self, name: &str) {
+        let mut state = self.state();
+        state.total.timed_out += 1;
+        Self::label(&mut state.labels, command_label(name)).timed_out += 1;
+
+        self.changed.store(true, Ordering::SeqCst);
+    }
+
+    fn label<'a>(
+        labels: &'a mut HashMap<String, ObsCommandMetrics>,
+        label: &str,
+    ) -> &'a mut ObsCommandMetrics {
+        labels.entry(label.to_string()).or_default()
+    }
+
+    pub(crate) fn snapshot(&self) -> ObsRuntimeMetrics {
+        let mut snapshot = self.state().clone();
+        snapshot.queued_commands = self.queued_commands.load(Ordering::SeqCst);
+
+        snapshot
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<ObsRuntimeMetrics> {
+        self.sender.subscribe()
+    }
+
+    /// Sends a new snapshot to the subscribers if something changed since the last one.
+    pub(crate) fn publish(&self) {
+        if !self.changed.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        self.sender.send_replace(self.snapshot());
+    }
+}
@@ -0,0 +1,272 @@
+//! Batching of many operations into a single command on the OBS thread.
+//!
+//! Every call to the runtime is a separate round-trip to the OBS thread. An [`ObsTransaction`]
+//! queues operations instead and executes all of them in one command once it is committed.
+//! The graphics context is held while the operations run, so the video thread can't render a
+//! frame in between and all changes show up at once.
+//!
+//! If one of the operations fails, the remaining ones are skipped and the operations that
+//! already ran are rolled back in reverse order (see [`ObsTransaction::add_with_rollback`]).
+
+use std::{any::Any, fmt::Debug, marker::PhantomData};
+
+use crate::{backend::backend, utils::ObsError};
+
+use super::ObsRuntime;
+
+type Operation =
+    Box<dyn FnOnce(&mut ObsTransactionValues) -> Result<Box<dyn Any + Send>, ObsError> + Send>;
+type Rollback = Box<dyn FnOnce(&mut (dyn Any + Send)) + Send>;
+
+/// A value that is returned by an operation of a transaction. It can be accessed by
+/// later operations of the same transaction and through the [`ObsTransactionValues`]
+/// that are returned once the transaction is committed.
+pub struct ObsTransactionValue<T> {
+    index: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for ObsTransactionValue<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ObsTransactionValue<T> {}
+
+impl<T> Debug for ObsTransactionValue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsTransactionValue")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Refers to an object that either exists already or is
+/// created by an earlier operation of the same transaction.
+#[derive(Debug, Clone)]
+pub enum ObsTransactionRef<T> {
+    Existing(T),
+    Pending(ObsTransactionValue<T>),
+}
+
+impl<T: Clone + 'static> ObsTransactionRef<T> {
+    /// Returns the object, or `None` if the pending value is not available.
+    pub fn resolve(&self, values: &ObsTransactionValues) -> Option<T> {
+        match self {
+            ObsTransactionRef::Existing(x) => Some(x.clone()),
+            ObsTransactionRef::Pending(value) => values.get(*value).cloned(),
+        }
+    }
+}
+
+impl<T: Clone> From<&T> for ObsTransactionRef<T> {
+    fn from(value: &T) -> Self {
+        ObsTransactionRef::Existing(value.clone())
+    }
+}
+
+impl<T> From<ObsTransactionValue<T>> for ObsTransactionRef<T> {
+    fn from(value: ObsTransactionValue<T>) -> Self {
+        ObsTransactionRef::Pending(value)
+    }
+}
+
+/// The values that were returned by the operations of a transaction.
+#[derive(Default)]
+pub struct ObsTransactionValues {
+    values: Vec<Option<Box<dyn Any + Send>>>,
+}
+
+impl Debug for ObsTransactionValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsTransactionValues")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+impl ObsTransactionValues {
+    /// Returns the value of an operation, or `None` if it was taken already.
+    pub fn get<T: 'static>(&self, value: ObsTransactionValue<T>) -> Option<&T> {
+        self.values.get(value.index)?.as_ref()?.downcast_ref::<T>()
+    }
+
+    /// Takes the value of an operation out, or returns `None` if it was taken already.
+    pub fn take<T: 'static>(&mut self, value: ObsTransactionValue<T>) -> Option<T> {
+        let slot = self.values.get_mut(value.index)?;
+        if !slot.as_ref()?.is::<T>() {
+            return None;
+        }
+
+        slot.take()?.downcast::<T>().ok().map(|x| *x)
+    }
+}
+
+/// Queues operations that are executed together in a single command on the OBS thread.
+/// Created by [`ObsRuntime::transaction`].
+///
+/// Note that the operations run on the OBS thread, so they must not wait for other
+/// commands that are sent to the runtime.
+pub struct ObsTransaction {
+    pub(crate) runtime: ObsRuntime,
+    operations: Vec<(Operation, Option<Rollback>)>,
+}
+
+impl Debug for ObsTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsTransaction")
+            .field("operations", &self.operations.len())
+            .finish()
+    }
+}
+
+impl ObsTransaction {
+    pub(crate) fn new(runtime: ObsRuntime) -> Self {
+        Self {
+            runtime,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Queues an operation that runs once the transaction is committed. Values of
+    /// earlier operations can be accessed through the given [`ObsTransactionValues`].
+    pub fn add<F, T>(&mut self, operation: F) -> ObsTransactionValue<T>
+    where
+        F: FnOnce(&mut ObsTransactionValues) -> Result<T, ObsError> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.push(operation, None)
+    }
+
+    /// Same as [`ObsTransaction::add`], but `rollback` is called with the returned
+    /// value if a later operation of this transaction fails.
+    pub fn add_with_rollback<F, R, T>(
+        &mut self,
+        operation: F,
+        rollback: R,
+    ) -> ObsTransactionValue<T>
+    where
+        F: FnOnce(&mut ObsTransactionValues) -> Result<T, ObsError> + Send + 'static,
+        R: FnOnce(&mut T) + Send + 'static,
+        T: Send + 'static,
+    {
+        let rollback: Rollback = Box::new(move |value| {
+            if let Some(value) = value.downcast_mut::<T>() {
+                rollback(value);
+            }
+        });
+
+        self.push(operation, Some(rollback))
+    }
+
+    fn push<F, T>(&mut self, operation: F, rollback: Option<Rollback>) -> ObsTransactionValue<T>
+    where
+        F: FnOnce(&mut ObsTransactionValues) -> Result<T, ObsError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let index = self.operations.len();
+        let operation: Operation = Box::new(move |values| {
+            let value = operation(values)?;
+            Ok(Box::new(value) as Box<dyn Any + Send>)
+        });
+
+        self.operations.push((operation, rollback));
+        ObsTransactionValue {
+            index,
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns the number of queued operations.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Executes all operations in a single command on the OBS thread and returns their values.
+    ///
+    /// If an operation fails, the remaining ones are skipped, the operations that already
+    /// ran are rolled back in reverse order and the error is returned.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn commit(self) -> Result<ObsTransactionValues, ObsError> {
+        let ObsTransaction {
+            runtime,
+            operations,
+        } = self;
+
+        if operations.is_empty() {
+            return Ok(ObsTransactionValues::default());
+        }
+
+        log::trace!(
+            "Committing transaction with {} operations",
+            operations.len()
+        );
+        let (values, error) = runtime
+            .run_with_obs_result(move || Self::execute(operations))
+            .await
+            .map_err(ObsError::from_invocation_error)?;
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(values),
+        }
+    }
+
+    fn execute(
+        operations: Vec<(Operation, Option<Rollback>)>,
+    ) -> (ObsTransactionValues, Option<ObsError>) {
+        let mut values = ObsTransactionValues {
+            values: Vec::with_capacity(operations.len()),
+        };
+        let mut rollbacks = Vec::with_capacity(operations.len());
+        let mut error = None;
+
+        // The video thread holds the graphics context while rendering a frame,
+        // so holding it here applies all operations between two frames
+        let _graphics = GraphicsGuard::enter();
+        for (operation, rollback) in operations {
+            match operation(&mut values) {
+                Ok(value) => {
+                    values.values.push(Some(value));
+                    rollbacks.push(rollback);
+                }
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if error.is_some() {
+            let executed = rollbacks.into_iter().zip(values.values.iter_mut());
+            for (rollback, value) in executed.rev() {
+                if let (Some(rollback), Some(value)) = (rollback, value) {
+                    rollback(&mut **value);
+                }
+            }
+        }
+
+        (values, error)
+    }
+}
+
+/// Holds the graphics context until dropped, so it is left even if an operation panics.
+struct GraphicsGuard;
+
+impl GraphicsGuard {
+    fn enter() -> Self {
+        unsafe { backend().obs_enter_graphics() };
+        Self
+    }
+}
+
+impl Drop for GraphicsGuard {
+    fn drop(&mut self) {
+        unsafe { backend().obs_leave_graphics() };
+    }
+}
@@ -0,0 +1,372 @@
+//! Deadlines and stall detection for commands running on the OBS thread.
+//!
+//! Every command that is sent to the OBS thread is registered with the [`Watchdog`].
+//! A separate watchdog thread periodically checks the registered commands and
+//! - answers commands whose deadline passed with [`ObsError::CommandTimeout`], so the
+//!   caller doesn't wait forever if the OBS thread is blocked
+//! - reports a [`ObsRuntimeEvent::Stalled`] event if the command queue didn't make any
+//!   progress for longer than the stall threshold, naming the command that is stuck
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{broadcast, oneshot};
+
+use crate::utils::ObsError;
+
+use super::metrics::MetricsRecorder;
+
+pub(crate) type CommandResult = Result<Box<dyn Any + Send>, ObsError>;
+
+/// The sender half of a command response. It is shared between the OBS thread and the
+/// watchdog, whoever takes it first answers the caller.
+pub(crate) type CommandResponder = Arc<Mutex<Option<oneshot::Sender<CommandResult>>>>;
+
+/// Events that are emitted by the watchdog of the OBS runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsRuntimeEvent {
+    /// The command queue didn't make any progress for longer than the stall threshold.
+    Stalled {
+        /// The name of the command that is currently blocking the OBS thread
+        command: String,
+        /// For how long the command has been running
+        running_for: Duration,
+        /// The number of commands that are waiting to be executed (including the stuck one)
+        queued_commands: usize,
+    },
+    /// A command that was reported as stalled finished.
+    Recovered {
+        command: String,
+        /// How long the command took in total
+        took: Duration,
+    },
+    /// The deadline of a command passed and the caller received a timeout error.
+    /// The command itself will still be executed once the OBS thread gets to it.
+    TimedOut { command: String, timeout: Duration },
+}
+
+/// Configures command deadlines and stall detection of the OBS runtime.
+#[derive(Debug, Clone)]
+pub struct ObsWatchdogOptions {
+    pub(crate) command_timeout: Option<Duration>,
+    pub(crate) stall_threshold: Duration,
+    pub(crate) poll_interval: Duration,
+}
+
+impl ObsWatchdogOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The default deadline of every command sent to the OBS thread.
+    /// `None` (the default) waits forever.
+    pub fn set_command_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// After how long without progress in the command queue a
+    /// [`ObsRuntimeEvent::Stalled`] event is emitted. Defaults to 5 seconds.
+    pub fn set_stall_threshold(mut self, threshold: Duration) -> Self {
+        self.stall_threshold = threshold;
+        self
+    }
+
+    /// How often the watchdog checks deadlines and the command queue. Defaults to 100ms.
+    pub fn set_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+impl Default for ObsWatchdogOptions {
+    fn default() -> Self {
+        Self {
+            command_timeout: None,
+            stall_threshold: Duration::from_secs(5),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PendingCommand {
+    name: &'static str,
+    queued_at: Instant,
+    deadline: Option<(Instant, Duration)>,
+    responder: CommandResponder,
+}
+
+#[derive(Debug)]
+struct RunningCommand {
+    id: u64,
+    name: &'static str,
+    started: Instant,
+}
+
+#[derive(Debug)]
+struct WatchdogState {
+    next_id: u64,
+    default_timeout: Option<Duration>,
+    pending: HashMap<u64, PendingCommand>,
+    running: Option<RunningCommand>,
+    last_progress: Instant,
+    stall_reported: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Watchdog {
+    state: Arc<Mutex<WatchdogState>>,
+    metrics: Arc<MetricsRecorder>,
+    events: broadcast::Sender<ObsRuntimeEvent>,
+    shutdown: Arc<AtomicBool>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+fn lock(state: &Mutex<WatchdogState>) -> MutexGuard<'_, WatchdogState> {
+    match state.lock() {
+        Ok(state) => state,
+        Err(e) => e.into_inner(),
+    }
+}
+
+impl Watchdog {
+    /// Spawns the watchdog thread. It also publishes the runtime metrics to their subscribers.
+    pub(crate) fn start(
+        options: ObsWatchdogOptions,
+        queued_commands: Arc<AtomicUsize>,
+        metrics: Arc<MetricsRecorder>,
+    ) -> Self {
+        let state = Arc::new(Mutex::new(WatchdogState {
+            next_id: 0,
+            default_timeout: options.command_timeout,
+            pending: HashMap::new(),
+            running: None,
+            last_progress: Instant::now(),
+            stall_reported: false,
+        }));
+
+        let (events, _) = broadcast::channel(16);
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread_state = state.clone();
+        let thread_events = events.clone();
+        let thread_shutdown = shutdown.clone();
+        let thread_metrics = metrics.clone();
+        let handle = thread::Builder::new()
+            .name("obs-watchdog".to_string())
+            .spawn(move || {
+                while !thread_shutdown.load(Ordering::SeqCst) {
+                    thread::park_timeout(options.poll_interval);
+                    Self::check(
+                        &thread_state,
+                        &thread_events,
+                        &thread_metrics,
+                        &queued_commands,
+                        options.stall_threshold,
+                    );
+                    thread_metrics.publish();
+                }
+            })
+            .ok();
+
+        if handle.is_none() {
+            log::error!("Failed to spawn OBS watchdog thread, deadlines will not be enforced");
+        }
+
+        Self {
+            state,
+            metrics,
+            events,
+            shutdown,
+            handle: Mutex::new(handle),
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<ObsRuntimeEvent> {
+        self.events.subscribe()
+    }
+
+    pub(crate) fn default_timeout(&self) -> Option<Duration> {
+        lock(&self.state).default_timeout
+    }
+
+    pub(crate) fn set_default_timeout(&self, timeout: Option<Duration>) {
+        lock(&self.state).default_timeout = timeout;
+    }
+
+    /// Registers a command that is about to be queued and returns its id.
+    pub(crate) fn register(
+        &self,
+        name: &'static str,
+        timeout: Option<Duration>,
+        responder: CommandResponder,
+    ) -> u64 {
+        let mut state = lock(&self.state);
+        let timeout = timeout.or(state.default_timeout);
+
+        if state.pending.is_empty() {
+            // The queue was idle until now, so there is nothing that could have stalled
+            state.last_progress = Instant::now();
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.pending.insert(
+            id,
+            PendingCommand {
+                name,
+                queued_at: Instant::now(),
+                deadline: timeout.map(|t| (Instant::now() + t, t)),
+                responder,
+            },
+        );
+
+        id
+    }
+
+    /// Called by the OBS thread right before the command is executed.
+    pub(crate) fn begin(&self, id: u64) {
+        let mut state = lock(&self.state);
+        let Some(name) = state.pending.get(&id).map(|c| c.name) else {
+            return;
+        };
+
+        state.running = Some(RunningCommand {
+            id,
+            name,
+            started: Instant::now(),
+        });
+    }
+
+    /// Called by the OBS thread after the command was executed.
+    pub(crate) fn finish(&self, id: u64) {
+        let mut state = lock(&self.state);
+        let pending = state.pending.remove(&id);
+        state.last_progress = Instant::now();
+
+        let is_running = state.running.as_ref().is_some_and(|r| r.id == id);
+        let running = if is_running {
+            state.running.take()
+        } else {
+            None
+        };
+
+        if let (Some(pending), Some(running)) = (&pending, &running) {
+            self.metrics.record(
+                pending.name,
+                running.started.duration_since(pending.queued_at),
+                running.started.elapsed(),
+            );
+        }
+        if !state.stall_reported {
+            return;
+        }
+
+        state.stall_reported = false;
+        if let Some(running) = running {
+            log::info!("OBS thread recovered, `{}` finished", running.name);
+            let _ = self.events.send(ObsRuntimeEvent::Recovered {
+                command: running.name.to_string(),
+                took: running.started.elapsed(),
+            });
+        }
+    }
+
+    fn check(
+        state: &Mutex<WatchdogState>,
+        events: &broadcast::Sender<ObsRuntimeEvent>,
+        metrics: &MetricsRecorder,
+        queued_commands: &AtomicUsize,
+        stall_threshold: Duration,
+    ) {
+        let now = Instant::now();
+        let mut state = lock(state);
+
+        for command in state.pending.values_mut() {
+            let Some((deadline, timeout)) = command.deadline else {
+                continue;
+            };
+
+            if deadline > now {
+                continue;
+            }
+
+            // Only checking the deadline once
+            command.deadline = None;
+            let responder = match command.responder.lock() {
+                Ok(mut r) => r.take(),
+                Err(e) => e.into_inner().take(),
+            };
+
+            if let Some(responder) = responder {
+                log::warn!(
+                    "Command `{}` did not finish within {:?} on the OBS thread",
+                    command.name,
+                    timeout
+                );
+
+                metrics.record_timeout(command.name);
+                let _ = responder.send(Err(ObsError::CommandTimeout(
+                    command.name.to_string(),
+                    timeout,
+                )));
+                let _ = events.send(ObsRuntimeEvent::TimedOut {
+                    command: command.name.to_string(),
+                    timeout,
+                });
+            }
+        }
+
+        let queued = queued_commands.load(Ordering::SeqCst);
+        if queued == 0 || state.stall_reported {
+            return;
+        }
+
+        if now.duration_since(state.last_progress) < stall_threshold {
+            return;
+        }
+
+        state.stall_reported = true;
+        let (command, running_for) = match &state.running {
+            Some(running) => (running.name, now.duration_since(running.started)),
+            None => ("<none>", now.duration_since(state.last_progress)),
+        };
+
+        log::warn!(
+            "OBS thread is stalled: `{}` has been running for {:?} with {} queued commands",
+            command,
+            running_for,
+            queued
+        );
+        let _ = events.send(ObsRuntimeEvent::Stalled {
+            command: command.to_string(),
+            running_for,
+            queued_commands: queued,
+        });
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        let handle = match self.handle.lock() {
+            Ok(mut h) => h.take(),
+            Err(e) => e.into_inner().take(),
+        };
+
+        if let Some(handle) = handle {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
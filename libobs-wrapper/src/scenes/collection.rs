@@ -0,0 +1,322 @@
+//! Saving and loading scene collections in the json format OBS Studio uses, see
+//! [`ObsContext::save_scene_collection`](crate::context::ObsContext::save_scene_collection).
+
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::{c_void, CStr},
+    sync::Arc,
+};
+
+use libobs::{obs_data_array_t, obs_data_t, obs_scene_t, obs_source_t};
+
+use crate::{
+    backend::backend,
+    runtime::ObsRuntime,
+    rw_lock_blocking_write,
+    sources::{ObsFilterRef, ObsSourceRef},
+    unsafe_send::Sendable,
+    utils::{async_sync::RwLock, ObsError, ObsString},
+};
+
+use super::{scene_items, ObsGroupRef, ObsSceneRef};
+
+/// The objects that were created by [`load_scene_collection`].
+pub(crate) struct LoadedSceneCollection {
+    /// The scenes in the order they were saved in
+    pub scenes: Vec<ObsSceneRef>,
+    pub filters: Vec<ObsFilterRef>,
+    /// The scene that was shown on the main channel when the collection was saved
+    pub current_scene: Option<String>,
+}
+
+/// Serializes the scenes and the sources shown in them to a scene collection.
+/// Must be called on the OBS thread.
+pub(crate) unsafe fn save_scene_collection(
+    name: &ObsString,
+    scenes: &[ObsSceneRef],
+    sources: &[ObsSourceRef],
+    current_scene: Option<&ObsString>,
+) -> Result<String, ObsError> {
+    let backend = backend();
+    let source_array = backend.obs_data_array_create();
+    let group_array = backend.obs_data_array_create();
+    let order_array = backend.obs_data_array_create();
+    let name_key = ObsString::new("name");
+
+    // Sources can be shown in several scenes, but are only saved once
+    let mut saved = HashSet::new();
+    let mut save = |source: *mut obs_source_t, array: *mut obs_data_array_t| {
+        if source.is_null() || !saved.insert(source as usize) {
+            return;
+        }
+
+        let data = backend.obs_save_source(source);
+        if !data.is_null() {
+            backend.obs_data_array_push_back(array, data);
+            backend.obs_data_release(data);
+        }
+    };
+
+    for scene in scenes {
+        save(backend.obs_scene_get_source(scene.as_ptr().0), source_array);
+
+        let entry = backend.obs_data_create();
+        backend.obs_data_set_string(entry, name_key.as_ptr().0, scene.name().as_ptr().0);
+        backend.obs_data_array_push_back(order_array, entry);
+        backend.obs_data_release(entry);
+    }
+
+    for source in sources {
+        let source = source.source.0;
+        let is_group = backend.obs_scene_from_source(source).is_null()
+            && !backend.obs_group_or_scene_from_source(source).is_null();
+
+        save(source, if is_group { group_array } else { source_array });
+    }
+
+    let data = backend.obs_data_create();
+    backend.obs_data_set_string(data, name_key.as_ptr().0, name.as_ptr().0);
+    if let Some(current_scene) = current_scene {
+        for key in ["current_scene", "current_program_scene"] {
+            let key = ObsString::new(key);
+            backend.obs_data_set_string(data, key.as_ptr().0, current_scene.as_ptr().0);
+        }
+    }
+
+    for (key, array) in [
+        ("sources", source_array),
+        ("groups", group_array),
+        ("scene_order", order_array),
+    ] {
+        let key = ObsString::new(key);
+        backend.obs_data_set_array(data, key.as_ptr().0, array);
+        backend.obs_data_array_release(array);
+    }
+
+    let json = backend.obs_data_get_json(data);
+    let json = if json.is_null() {
+        Err(ObsError::NullPointer)
+    } else {
+        Ok(CStr::from_ptr(json).to_string_lossy().into_owned())
+    };
+
+    backend.obs_data_release(data);
+    json
+}
+
+/// Creates all scenes, groups, sources and filters of the scene collection and wraps them.
+/// Must be called on the OBS thread.
+pub(crate) unsafe fn load_scene_collection(
+    json: &ObsString,
+    active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+    runtime: ObsRuntime,
+) -> Result<LoadedSceneCollection, ObsError> {
+    let backend = backend();
+    let data = backend.obs_data_create_from_json(json.as_ptr().0);
+    if data.is_null() {
+        return Err(ObsError::JsonParseError);
+    }
+
+    // Groups are created first, as scenes refer to them by name
+    let mut loaded = load_sources(data, "groups");
+    loaded.extend(load_sources(data, "sources"));
+
+    let order = get_array(data, "scene_order")
+        .into_iter()
+        .filter_map(|entry| {
+            let name = get_string(entry, "name");
+            backend.obs_data_release(entry);
+            name
+        })
+        .collect::<Vec<_>>();
+    let current_scene =
+        get_string(data, "current_program_scene").or_else(|| get_string(data, "current_scene"));
+    backend.obs_data_release(data);
+
+    // Items are added once all sources exist, so they can be shown in any scene
+    for source in &loaded {
+        backend.obs_source_load(*source);
+    }
+
+    let mut scenes = loaded
+        .iter()
+        .filter_map(|source| {
+            let scene = backend.obs_scene_from_source(*source);
+            (!scene.is_null()).then(|| {
+                ObsSceneRef::from_raw_on_obs_thread(
+                    Sendable(scene),
+                    active_scene.clone(),
+                    runtime.clone(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    scenes.sort_by_key(|scene| {
+        let name = scene.name().to_string();
+        order.iter().position(|n| *n == name).unwrap_or(order.len())
+    });
+
+    let mut wrapped = HashMap::new();
+    let mut result = Ok(());
+    for scene in &scenes {
+        result = result.and_then(|_| register_items(scene, &scenes, &mut wrapped, &runtime));
+    }
+
+    let mut filters = HashMap::new();
+    for source in &loaded {
+        for filter in source_filters(*source) {
+            if filters.contains_key(&(filter as usize)) {
+                continue;
+            }
+
+            match ObsSourceRef::from_raw_on_obs_thread(Sendable(filter), runtime.clone()) {
+                Ok(filter_ref) => {
+                    filters.insert(filter as usize, filter_ref);
+                }
+                Err(e) => result = result.and(Err(e)),
+            }
+        }
+    }
+
+    // The wrappers hold their own references now. Scenes took over the reference of
+    // the load, sources that aren't shown in any scene are destroyed.
+    for source in &loaded {
+        if backend.obs_scene_from_source(*source).is_null() {
+            backend.obs_source_release(*source);
+        }
+    }
+
+    result?;
+    Ok(LoadedSceneCollection {
+        scenes,
+        filters: filters.into_values().collect(),
+        current_scene,
+    })
+}
+
+/// Wraps the items of the scene and registers them on it.
+pub(crate) unsafe fn register_items(
+    scene: &ObsSceneRef,
+    scenes: &[ObsSceneRef],
+    wrapped: &mut HashMap<usize, ObsSourceRef>,
+    runtime: &ObsRuntime,
+) -> Result<(), ObsError> {
+    let backend = backend();
+    let mut sources = Vec::new();
+    let mut groups = Vec::new();
+
+    for item in scene_items(scene.as_ptr().0) {
+        if !backend.obs_sceneitem_is_group(item.0) {
+            sources.push(wrap_item(item, scenes, wrapped, runtime)?);
+            continue;
+        }
+
+        let group = ObsGroupRef::new_on_obs_thread(
+            item,
+            scene.as_ptr(),
+            scene.events.clone(),
+            runtime.clone(),
+        )?;
+        sources.push(group.source().clone());
+        // Grouped items are tracked by the scene the group is in
+        for grouped in scene_items(group.as_scene_ptr().0) {
+            sources.push(wrap_item(grouped, scenes, wrapped, runtime)?);
+        }
+
+        groups.push(group);
+    }
+
+    rw_lock_blocking_write!(scene.sources).extend(sources);
+    rw_lock_blocking_write!(scene.groups).extend(groups);
+    Ok(())
+}
+
+/// Wraps the source of the item. Sources shown in several scenes share one wrapper, just
+/// like the scene source of nested scenes.
+unsafe fn wrap_item(
+    item: Sendable<*mut libobs::obs_sceneitem_t>,
+    scenes: &[ObsSceneRef],
+    wrapped: &mut HashMap<usize, ObsSourceRef>,
+    runtime: &ObsRuntime,
+) -> Result<ObsSourceRef, ObsError> {
+    let backend = backend();
+    let source = backend.obs_sceneitem_get_source(item.0);
+
+    let mut source_ref = match wrapped.get(&(source as usize)) {
+        Some(source_ref) => source_ref.clone(),
+        None => {
+            let source_ref =
+                ObsSourceRef::from_raw_on_obs_thread(Sendable(source), runtime.clone())?;
+            wrapped.insert(source as usize, source_ref.clone());
+
+            let nested = scenes
+                .iter()
+                .find(|s| backend.obs_scene_get_source(s.as_ptr().0) == source);
+            if let Some(nested) = nested {
+                *rw_lock_blocking_write!(nested.scene_source) = Some(source_ref.clone());
+            }
+
+            source_ref
+        }
+    };
+
+    source_ref.scene_item = Some(item);
+    Ok(source_ref)
+}
+
+/// Loads every source of the array with the given key, returning their references.
+unsafe fn load_sources(data: *mut obs_data_t, key: &str) -> Vec<*mut obs_source_t> {
+    let backend = backend();
+    get_array(data, key)
+        .into_iter()
+        .filter_map(|entry| {
+            let source = backend.obs_load_source(entry);
+            backend.obs_data_release(entry);
+            (!source.is_null()).then_some(source)
+        })
+        .collect()
+}
+
+/// Returns the items of the array with the given key, each of them must be released.
+unsafe fn get_array(data: *mut obs_data_t, key: &str) -> Vec<*mut obs_data_t> {
+    let backend = backend();
+    let key = ObsString::new(key);
+    let array = backend.obs_data_get_array(data, key.as_ptr().0);
+    if array.is_null() {
+        return Vec::new();
+    }
+
+    let items = (0..backend.obs_data_array_count(array))
+        .map(|i| backend.obs_data_array_item(array, i))
+        .filter(|item| !item.is_null())
+        .collect();
+
+    backend.obs_data_array_release(array);
+    items
+}
+
+unsafe fn get_string(data: *mut obs_data_t, key: &str) -> Option<String> {
+    let key = ObsString::new(key);
+    let value = backend().obs_data_get_string(data, key.as_ptr().0);
+    if value.is_null() {
+        return None;
+    }
+
+    let value = CStr::from_ptr(value).to_string_lossy().into_owned();
+    (!value.is_empty()).then_some(value)
+}
+
+unsafe fn source_filters(source: *mut obs_source_t) -> Vec<*mut obs_source_t> {
+    unsafe extern "C" fn collect(
+        _parent: *mut obs_source_t,
+        child: *mut obs_source_t,
+        param: *mut c_void,
+    ) {
+        let filters = &mut *(param as *mut Vec<*mut obs_source_t>);
+        filters.push(child);
+    }
+
+    let mut filters = Vec::new();
+    backend().obs_source_enum_filters(source, Some(collect), &mut filters as *mut _ as *mut c_void);
+    filters
+}
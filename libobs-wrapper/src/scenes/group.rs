@@ -0,0 +1,119 @@
+use libobs::{obs_scene_t, obs_sceneitem_t};
+use tokio::sync::broadcast;
+
+use crate::{
+    backend::backend, run_with_obs, runtime::ObsRuntime, sources::ObsSourceRef,
+    unsafe_send::Sendable, utils::ObsError,
+};
+
+use super::{ObsSceneEvent, ObsSceneItemRef};
+
+/// A group of scene items, created with [`ObsSceneRef::add_group`](super::ObsSceneRef::add_group).
+///
+/// A group is placed in its scene like any other source, so it can be moved, scaled,
+/// hidden or locked through [`ObsGroupRef::item`]. Items of the same scene can be moved
+/// into the group, their transform is then relative to the group.
+///
+/// The group is kept alive by its scene, until it is removed with
+/// [`ObsSceneRef::remove_group`](super::ObsSceneRef::remove_group) or
+/// [`ObsSceneRef::ungroup`](super::ObsSceneRef::ungroup).
+#[derive(Debug, Clone)]
+pub struct ObsGroupRef {
+    item: ObsSceneItemRef,
+    group_scene: Sendable<*mut obs_scene_t>,
+    runtime: ObsRuntime,
+}
+
+impl ObsGroupRef {
+    /// Wraps the scene item of a group, this must be called on the OBS thread.
+    pub(crate) unsafe fn new_on_obs_thread(
+        item: Sendable<*mut obs_sceneitem_t>,
+        scene: Sendable<*mut obs_scene_t>,
+        events: broadcast::Sender<ObsSceneEvent>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let backend = backend();
+        let group_scene = backend.obs_sceneitem_group_get_scene(item.0);
+        if group_scene.is_null() {
+            return Err(ObsError::NullPointer);
+        }
+
+        let mut source = ObsSourceRef::from_raw_on_obs_thread(
+            Sendable(backend.obs_sceneitem_get_source(item.0)),
+            runtime.clone(),
+        )?;
+        source.scene_item = Some(item.clone());
+
+        Ok(Self {
+            item: ObsSceneItemRef::new_on_obs_thread(item, scene, source, events, runtime.clone()),
+            group_scene: Sendable(group_scene),
+            runtime,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.item.source().name()
+    }
+
+    /// The scene item of the group in its scene.
+    pub fn item(&self) -> &ObsSceneItemRef {
+        &self.item
+    }
+
+    /// The source backing the group.
+    pub fn source(&self) -> &ObsSourceRef {
+        self.item.source()
+    }
+
+    /// Moves an item of the scene the group is in into the group.
+    /// Items of other scenes are ignored.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn add_item(&self, item: &ObsSceneItemRef) -> Result<(), ObsError> {
+        let group_ptr = self.item.as_ptr();
+        let item_ptr = item.as_ptr();
+        run_with_obs!(self.runtime, (group_ptr, item_ptr), move || unsafe {
+            backend().obs_sceneitem_group_add_item(group_ptr, item_ptr);
+        })
+        .await
+    }
+
+    /// Moves an item out of the group, back into the scene the group is in.
+    /// It is placed right above the group.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn remove_item(&self, item: &ObsSceneItemRef) -> Result<(), ObsError> {
+        let group_ptr = self.item.as_ptr();
+        let item_ptr = item.as_ptr();
+        run_with_obs!(self.runtime, (group_ptr, item_ptr), move || unsafe {
+            backend().obs_sceneitem_group_remove_item(group_ptr, item_ptr);
+        })
+        .await
+    }
+
+    /// Whether the item is in this group.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn contains(&self, item: &ObsSceneItemRef) -> Result<bool, ObsError> {
+        let group_scene = self.group_scene.clone();
+        let item_ptr = item.as_ptr();
+        run_with_obs!(self.runtime, (group_scene, item_ptr), move || unsafe {
+            super::scene_items(group_scene)
+                .iter()
+                .any(|i| i.0 == item_ptr)
+        })
+        .await
+    }
+
+    /// The number of items in this group.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn item_count(&self) -> Result<usize, ObsError> {
+        let group_scene = self.group_scene.clone();
+        run_with_obs!(self.runtime, (group_scene), move || unsafe {
+            super::scene_items(group_scene).len()
+        })
+        .await
+    }
+
+    /// The scene backing the group, which holds the grouped items.
+    pub fn as_scene_ptr(&self) -> Sendable<*mut obs_scene_t> {
+        self.group_scene.clone()
+    }
+}
@@ -0,0 +1,568 @@
+use std::sync::Arc;
+
+use libobs::{obs_scene_t, obs_sceneitem_crop, obs_sceneitem_t, obs_transform_info};
+use num_traits::FromPrimitive;
+use tokio::sync::broadcast;
+
+use crate::{
+    backend::backend,
+    enums::{
+        ObsAlignment, ObsBlendingMethod, ObsBlendingType, ObsBoundsType, ObsOrderMovement,
+        ObsScaleType, OsEnumType,
+    },
+    impl_obs_drop, run_with_obs,
+    runtime::ObsRuntime,
+    sources::ObsSourceRef,
+    unsafe_send::Sendable,
+    utils::ObsError,
+    Vec2,
+};
+
+/// How many pixels are cut off each side of a scene item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObsSceneItemCrop {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl ObsSceneItemCrop {
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+impl From<obs_sceneitem_crop> for ObsSceneItemCrop {
+    fn from(raw: obs_sceneitem_crop) -> Self {
+        Self::new(raw.left, raw.top, raw.right, raw.bottom)
+    }
+}
+
+impl From<ObsSceneItemCrop> for obs_sceneitem_crop {
+    fn from(crop: ObsSceneItemCrop) -> Self {
+        obs_sceneitem_crop {
+            left: crop.left,
+            top: crop.top,
+            right: crop.right,
+            bottom: crop.bottom,
+        }
+    }
+}
+
+/// The complete transform of a scene item, which can be read and applied at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObsTransformInfo {
+    pub position: Vec2,
+    /// Rotation in degrees, clockwise
+    pub rotation: f32,
+    pub scale: Vec2,
+    /// Which point of the item is placed at its position
+    pub alignment: ObsAlignment,
+    pub bounds_type: ObsBoundsType,
+    /// Where the item is placed within its bounds
+    pub bounds_alignment: ObsAlignment,
+    pub bounds: Vec2,
+    /// Whether the parts of the item outside its bounds are cut off
+    pub crop_to_bounds: bool,
+}
+
+impl From<obs_transform_info> for ObsTransformInfo {
+    fn from(raw: obs_transform_info) -> Self {
+        Self {
+            position: Vec2::from(raw.pos),
+            rotation: raw.rot,
+            scale: Vec2::from(raw.scale),
+            alignment: ObsAlignment::from_bits(raw.alignment),
+            bounds_type: ObsBoundsType::from_i64(raw.bounds_type as i64)
+                .unwrap_or(ObsBoundsType::None),
+            bounds_alignment: ObsAlignment::from_bits(raw.bounds_alignment),
+            bounds: Vec2::from(raw.bounds),
+            crop_to_bounds: raw.crop_to_bounds,
+        }
+    }
+}
+
+impl From<ObsTransformInfo> for obs_transform_info {
+    fn from(info: ObsTransformInfo) -> Self {
+        obs_transform_info {
+            pos: info.position.into(),
+            rot: info.rotation,
+            scale: info.scale.into(),
+            alignment: info.alignment.bits(),
+            bounds_type: info.bounds_type as OsEnumType,
+            bounds_alignment: info.bounds_alignment.bits(),
+            bounds: info.bounds.into(),
+            crop_to_bounds: info.crop_to_bounds,
+        }
+    }
+}
+
+/// Changes made to the items of a scene through the wrapper, see
+/// [`ObsSceneRef::events`](super::ObsSceneRef::events). Changes made by plugins or
+/// libobs itself are only reported by the [`ObsSceneSignals`](super::ObsSceneSignals).
+#[derive(Debug, Clone)]
+pub enum ObsSceneEvent {
+    /// The item was moved relative to the other items of the scene.
+    Reordered {
+        item: Sendable<*mut obs_sceneitem_t>,
+    },
+    VisibilityChanged {
+        item: Sendable<*mut obs_sceneitem_t>,
+        visible: bool,
+    },
+    LockChanged {
+        item: Sendable<*mut obs_sceneitem_t>,
+        locked: bool,
+    },
+}
+
+#[derive(Debug)]
+struct _SceneItemDropGuard {
+    item: Sendable<*mut obs_sceneitem_t>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_SceneItemDropGuard, (item), move || unsafe {
+    backend().obs_sceneitem_release(item);
+});
+
+/// A reference to a source placed in a scene. The same source can be placed in several
+/// scenes (or several times in one scene), each placement has its own scene item and transform.
+///
+/// The scene item is kept alive as long as there are references to it, but it is not
+/// removed from its scene once they are dropped.
+#[derive(Debug, Clone)]
+pub struct ObsSceneItemRef {
+    pub(crate) item: Sendable<*mut obs_sceneitem_t>,
+    pub(crate) scene: Sendable<*mut obs_scene_t>,
+    source: ObsSourceRef,
+    events: broadcast::Sender<ObsSceneEvent>,
+
+    _guard: Arc<_SceneItemDropGuard>,
+    pub(crate) runtime: ObsRuntime,
+}
+
+impl ObsSceneItemRef {
+    /// Takes a new reference to the scene item, this must be called on the OBS thread.
+    pub(crate) unsafe fn new_on_obs_thread(
+        item: Sendable<*mut obs_sceneitem_t>,
+        scene: Sendable<*mut obs_scene_t>,
+        source: ObsSourceRef,
+        events: broadcast::Sender<ObsSceneEvent>,
+        runtime: ObsRuntime,
+    ) -> Self {
+        backend().obs_sceneitem_addref(item.0);
+
+        Self {
+            _guard: Arc::new(_SceneItemDropGuard {
+                item: item.clone(),
+                runtime: runtime.clone(),
+            }),
+            item,
+            scene,
+            source,
+            events,
+            runtime,
+        }
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        item: Sendable<*mut obs_sceneitem_t>,
+        scene: Sendable<*mut obs_scene_t>,
+        source: ObsSourceRef,
+        events: broadcast::Sender<ObsSceneEvent>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let item_ptr = item.clone();
+        run_with_obs!(runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_addref(item_ptr);
+        })
+        .await?;
+
+        Ok(Self {
+            _guard: Arc::new(_SceneItemDropGuard {
+                item: item.clone(),
+                runtime: runtime.clone(),
+            }),
+            item,
+            scene,
+            source,
+            events,
+            runtime,
+        })
+    }
+
+    /// The source this scene item shows.
+    pub fn source(&self) -> &ObsSourceRef {
+        &self.source
+    }
+
+    pub fn as_ptr(&self) -> Sendable<*mut obs_sceneitem_t> {
+        self.item.clone()
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn position(&self) -> Result<Vec2, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mut pos: libobs::vec2 = std::mem::zeroed();
+            backend().obs_sceneitem_get_pos(item_ptr, &mut pos);
+            Vec2::from(pos)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_position(&self, position: Vec2) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_pos(item_ptr, &position.into());
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn scale(&self) -> Result<Vec2, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mut scale: libobs::vec2 = std::mem::zeroed();
+            backend().obs_sceneitem_get_scale(item_ptr, &mut scale);
+            Vec2::from(scale)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_scale(&self, scale: Vec2) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_scale(item_ptr, &scale.into());
+        })
+        .await
+    }
+
+    /// The rotation in degrees, clockwise.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn rotation(&self) -> Result<f32, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_get_rot(item_ptr)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_rotation(&self, degrees: f32) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_rot(item_ptr, degrees);
+        })
+        .await
+    }
+
+    /// Which point of the item is placed at its position.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn alignment(&self) -> Result<ObsAlignment, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            ObsAlignment::from_bits(backend().obs_sceneitem_get_alignment(item_ptr))
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_alignment(&self, alignment: ObsAlignment) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_alignment(item_ptr, alignment.bits());
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn bounds_type(&self) -> Result<ObsBoundsType, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let bounds_type = backend().obs_sceneitem_get_bounds_type(item_ptr);
+            ObsBoundsType::from_i64(bounds_type as i64).unwrap_or(ObsBoundsType::None)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_bounds_type(&self, bounds_type: ObsBoundsType) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_bounds_type(item_ptr, bounds_type as OsEnumType);
+        })
+        .await
+    }
+
+    /// Where the item is placed within its bounds.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn bounds_alignment(&self) -> Result<ObsAlignment, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            ObsAlignment::from_bits(backend().obs_sceneitem_get_bounds_alignment(item_ptr))
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_bounds_alignment(&self, alignment: ObsAlignment) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_bounds_alignment(item_ptr, alignment.bits());
+        })
+        .await
+    }
+
+    /// The size of the bounds, only used if the bounds type is not [`ObsBoundsType::None`].
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn bounds(&self) -> Result<Vec2, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mut bounds: libobs::vec2 = std::mem::zeroed();
+            backend().obs_sceneitem_get_bounds(item_ptr, &mut bounds);
+            Vec2::from(bounds)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_bounds(&self, bounds: Vec2) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_bounds(item_ptr, &bounds.into());
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn crop(&self) -> Result<ObsSceneItemCrop, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mut crop: obs_sceneitem_crop = std::mem::zeroed();
+            backend().obs_sceneitem_get_crop(item_ptr, &mut crop);
+            ObsSceneItemCrop::from(crop)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_crop(&self, crop: ObsSceneItemCrop) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_crop(item_ptr, &crop.into());
+        })
+        .await
+    }
+
+    /// The filter that is used to scale the item.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn scale_filter(&self) -> Result<ObsScaleType, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let filter = backend().obs_sceneitem_get_scale_filter(item_ptr);
+            ObsScaleType::from_i64(filter as i64).unwrap_or(ObsScaleType::Disable)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_scale_filter(&self, filter: ObsScaleType) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_scale_filter(item_ptr, filter as OsEnumType);
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn blending_mode(&self) -> Result<ObsBlendingType, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mode = backend().obs_sceneitem_get_blending_mode(item_ptr);
+            ObsBlendingType::from_i64(mode as i64).unwrap_or(ObsBlendingType::Normal)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_blending_mode(&self, mode: ObsBlendingType) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_blending_mode(item_ptr, mode as OsEnumType);
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn blending_method(&self) -> Result<ObsBlendingMethod, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let method = backend().obs_sceneitem_get_blending_method(item_ptr);
+            ObsBlendingMethod::from_i64(method as i64).unwrap_or(ObsBlendingMethod::Default)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_blending_method(&self, method: ObsBlendingMethod) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_blending_method(item_ptr, method as OsEnumType);
+        })
+        .await
+    }
+
+    /// Reads position, rotation, scale, alignment and bounds in one call.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn transform_info(&self) -> Result<ObsTransformInfo, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let mut info: obs_transform_info = std::mem::zeroed();
+            backend().obs_sceneitem_get_info2(item_ptr, &mut info);
+            ObsTransformInfo::from(info)
+        })
+        .await
+    }
+
+    /// Applies position, rotation, scale, alignment and bounds at once,
+    /// so the item is only updated (and `item_transform` emitted) once.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_transform_info(&self, info: ObsTransformInfo) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_set_info2(item_ptr, &info.into());
+        })
+        .await
+    }
+
+    /// Moves the item relative to the other items of the scene. Emits `reorder`
+    /// and [`ObsSceneEvent::Reordered`] if the item was moved.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_order(&self, movement: ObsOrderMovement) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        let moved = run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let backend = backend();
+            let before = backend.obs_sceneitem_get_order_position(item_ptr);
+            backend.obs_sceneitem_set_order(item_ptr, movement as OsEnumType);
+            backend.obs_sceneitem_get_order_position(item_ptr) != before
+        })
+        .await?;
+
+        if moved {
+            self.emit(ObsSceneEvent::Reordered {
+                item: self.as_ptr(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The index of the item in its scene, `0` being the bottom-most item.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn order_position(&self) -> Result<usize, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_get_order_position(item_ptr).max(0) as usize
+        })
+        .await
+    }
+
+    /// Moves the item to the given index, `0` being the bottom. Indices past the
+    /// top-most item move it to the top. Emits `reorder` and [`ObsSceneEvent::Reordered`]
+    /// if the item was moved.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_order_position(&self, position: usize) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        let position = position.min(i32::MAX as usize) as i32;
+        let moved = run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let backend = backend();
+            let before = backend.obs_sceneitem_get_order_position(item_ptr);
+            backend.obs_sceneitem_set_order_position(item_ptr, position);
+            backend.obs_sceneitem_get_order_position(item_ptr) != before
+        })
+        .await?;
+
+        if moved {
+            self.emit(ObsSceneEvent::Reordered {
+                item: self.as_ptr(),
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn visible(&self) -> Result<bool, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_visible(item_ptr)
+        })
+        .await
+    }
+
+    /// Shows or hides the item. Emits `item_visible` and [`ObsSceneEvent::VisibilityChanged`]
+    /// if the visibility changed.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_visible(&self, visible: bool) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        let changed = run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let backend = backend();
+            let changed = backend.obs_sceneitem_visible(item_ptr) != visible;
+            backend.obs_sceneitem_set_visible(item_ptr, visible);
+            changed
+        })
+        .await?;
+
+        if changed {
+            self.emit(ObsSceneEvent::VisibilityChanged {
+                item: self.as_ptr(),
+                visible,
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn locked(&self) -> Result<bool, ObsError> {
+        let item_ptr = self.item.clone();
+        run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            backend().obs_sceneitem_locked(item_ptr)
+        })
+        .await
+    }
+
+    /// Locks the item, so it can't be moved in the preview anymore. Emits `item_locked`
+    /// and [`ObsSceneEvent::LockChanged`] if the lock state changed.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_locked(&self, locked: bool) -> Result<(), ObsError> {
+        let item_ptr = self.item.clone();
+        let changed = run_with_obs!(self.runtime, (item_ptr), move || unsafe {
+            let backend = backend();
+            let changed = backend.obs_sceneitem_locked(item_ptr) != locked;
+            backend.obs_sceneitem_set_locked(item_ptr, locked);
+            changed
+        })
+        .await?;
+
+        if changed {
+            self.emit(ObsSceneEvent::LockChanged {
+                item: self.as_ptr(),
+                locked,
+            });
+        }
+        Ok(())
+    }
+
+    fn emit(&self, event: ObsSceneEvent) {
+        let _ = self.events.send(event);
+    }
+}
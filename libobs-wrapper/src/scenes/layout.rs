@@ -0,0 +1,206 @@
+//! Helpers to place scene items on the canvas.
+//!
+//! The items are placed with bounds instead of a fixed scale, so they keep their layout
+//! when the size of their source changes, e.g. when a captured window is resized.
+
+use crate::{
+    backend::backend,
+    enums::{ObsAlignment, ObsBoundsType},
+    run_with_obs,
+    runtime::ObsRuntime,
+    utils::ObsError,
+    Vec2,
+};
+
+use super::{ObsSceneItemRef, ObsTransformInfo};
+
+/// A rectangle on the canvas, in pixels of the base resolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObsLayoutRect {
+    /// The top left corner
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl ObsLayoutRect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            position: Vec2::new(x, y),
+            size: Vec2::new(width, height),
+        }
+    }
+
+    /// Shrinks the rectangle by the margin on every side.
+    pub fn inset(&self, margin: f32) -> Self {
+        Self::new(
+            self.position.x + margin,
+            self.position.y + margin,
+            (self.size.x - 2.0 * margin).max(0.0),
+            (self.size.y - 2.0 * margin).max(0.0),
+        )
+    }
+}
+
+/// Returns the whole canvas, which has the base resolution of the video context.
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+pub async fn canvas_rect(runtime: &ObsRuntime) -> Result<ObsLayoutRect, ObsError> {
+    let size = run_with_obs!(runtime, move || unsafe {
+        let mut ovi: libobs::obs_video_info = std::mem::zeroed();
+        if !backend().obs_get_video_info(&mut ovi) {
+            return Err(ObsError::VideoNotInitialized);
+        }
+
+        Ok(Vec2::new(ovi.base_width as f32, ovi.base_height as f32))
+    })
+    .await??;
+
+    Ok(ObsLayoutRect {
+        position: Vec2::new(0.0, 0.0),
+        size,
+    })
+}
+
+impl ObsSceneItemRef {
+    /// Scales the item to fit inside the canvas, keeping its aspect ratio, and centers it.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn fit_to_canvas(&self) -> Result<(), ObsError> {
+        let canvas = canvas_rect(&self.runtime).await?;
+        self.place_in_rect(canvas, ObsBoundsType::ScaleInner, ObsAlignment::CENTER)
+            .await
+    }
+
+    /// Scales the item to cover the whole canvas, keeping its aspect ratio. The parts
+    /// outside of the canvas are cropped.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn fill_canvas(&self) -> Result<(), ObsError> {
+        let canvas = canvas_rect(&self.runtime).await?;
+        self.place_in_rect(canvas, ObsBoundsType::ScaleOuter, ObsAlignment::CENTER)
+            .await
+    }
+
+    /// Stretches the item to the size of the canvas, ignoring its aspect ratio.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn stretch_to_canvas(&self) -> Result<(), ObsError> {
+        let canvas = canvas_rect(&self.runtime).await?;
+        self.place_in_rect(canvas, ObsBoundsType::Stretch, ObsAlignment::CENTER)
+            .await
+    }
+
+    /// Moves the center of the item to the center of the canvas, keeping its size.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn center_on_canvas(&self) -> Result<(), ObsError> {
+        let canvas = canvas_rect(&self.runtime).await?;
+        let mut info = self.transform_info().await?;
+        info.alignment = ObsAlignment::CENTER;
+        info.position = Vec2::new(
+            canvas.position.x + canvas.size.x / 2.0,
+            canvas.position.y + canvas.size.y / 2.0,
+        );
+
+        self.set_transform_info(info).await
+    }
+
+    /// Places the item in the rectangle, scaled with the given bounds type. The alignment
+    /// decides where the item ends up if it doesn't fill the whole rectangle.
+    ///
+    /// Items scaled with [`ObsBoundsType::ScaleOuter`] are cropped to the rectangle.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn place_in_rect(
+        &self,
+        rect: ObsLayoutRect,
+        bounds_type: ObsBoundsType,
+        alignment: ObsAlignment,
+    ) -> Result<(), ObsError> {
+        self.set_transform_info(ObsTransformInfo {
+            position: rect.position,
+            rotation: 0.0,
+            scale: Vec2::new(1.0, 1.0),
+            alignment: ObsAlignment::TOP_LEFT,
+            bounds_type,
+            bounds_alignment: alignment,
+            bounds: rect.size,
+            crop_to_bounds: bounds_type == ObsBoundsType::ScaleOuter,
+        })
+        .await
+    }
+}
+
+/// Arranges the items in a grid covering the canvas, filling it row by row. Every item
+/// fits into its cell, which is surrounded by `spacing` pixels.
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+pub async fn arrange_grid(items: &[ObsSceneItemRef], spacing: f32) -> Result<(), ObsError> {
+    let Some(first) = items.first() else {
+        return Ok(());
+    };
+
+    let canvas = canvas_rect(&first.runtime).await?;
+    let columns = (items.len() as f32).sqrt().ceil() as usize;
+    let rows = items.len().div_ceil(columns);
+    let cell = Vec2::new(canvas.size.x / columns as f32, canvas.size.y / rows as f32);
+
+    for (i, item) in items.iter().enumerate() {
+        let rect = ObsLayoutRect::new(
+            canvas.position.x + (i % columns) as f32 * cell.x,
+            canvas.position.y + (i / columns) as f32 * cell.y,
+            cell.x,
+            cell.y,
+        );
+
+        item.place_in_rect(
+            rect.inset(spacing / 2.0),
+            ObsBoundsType::ScaleInner,
+            ObsAlignment::CENTER,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Fits the main item to the canvas and places the overlays in the given corner on top
+/// of it, stacked vertically. Each overlay fits into a box of `scale` times the canvas
+/// size, `margin` pixels away from the edges of the canvas and the other overlays.
+///
+/// Overlays should be above the main item in the scene, see
+/// [`ObsSceneItemRef::set_order`].
+#[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+pub async fn arrange_picture_in_picture(
+    main: &ObsSceneItemRef,
+    overlays: &[ObsSceneItemRef],
+    corner: ObsAlignment,
+    scale: f32,
+    margin: f32,
+) -> Result<(), ObsError> {
+    let canvas = canvas_rect(&main.runtime).await?;
+    main.place_in_rect(canvas, ObsBoundsType::ScaleInner, ObsAlignment::CENTER)
+        .await?;
+
+    let size = Vec2::new(canvas.size.x * scale, canvas.size.y * scale);
+    let x = if corner.contains(ObsAlignment::RIGHT) {
+        canvas.position.x + canvas.size.x - margin - size.x
+    } else if corner.contains(ObsAlignment::LEFT) {
+        canvas.position.x + margin
+    } else {
+        canvas.position.x + (canvas.size.x - size.x) / 2.0
+    };
+
+    let from_bottom = corner.contains(ObsAlignment::BOTTOM);
+    for (i, overlay) in overlays.iter().enumerate() {
+        let offset = margin + i as f32 * (size.y + margin);
+        let y = if from_bottom {
+            canvas.position.y + canvas.size.y - offset - size.y
+        } else {
+            canvas.position.y + offset
+        };
+
+        let rect = ObsLayoutRect {
+            position: Vec2::new(x, y),
+            size,
+        };
+        overlay
+            .place_in_rect(rect, ObsBoundsType::ScaleInner, corner)
+            .await?;
+    }
+
+    Ok(())
+}
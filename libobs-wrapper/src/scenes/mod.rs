@@ -1,10 +1,24 @@
-use std::sync::Arc;
+mod collection;
+mod group;
+mod item;
+mod layout;
+pub(crate) use collection::*;
+pub use group::*;
+pub use item::*;
+pub use layout::*;
+
+use std::{
+    collections::HashMap,
+    ffi::{c_void, CStr},
+    sync::Arc,
+};
 
 use getters0::Getters;
-use libobs::{obs_scene_t, obs_set_output_source, obs_source_t};
+use libobs::{obs_scene_t, obs_sceneitem_t, obs_source_t};
+use tokio::sync::broadcast;
 
 use crate::{
-    impl_obs_drop, impl_signal_manager, run_with_obs, runtime::ObsRuntime, sources::{ObsFilterRef, ObsSourceRef}, unsafe_send::Sendable, utils::{async_sync::RwLock, ObsError, ObsString, SourceInfo}, Vec2
+    backend::backend, enums::ObsOrderMovement, data::{immutable::ImmutableObsData, ObsDataChange, ObsDataUpdater}, impl_obs_drop, impl_signal_manager, media::{take_screenshot, ObsScreenshot, ObsScreenshotOptions}, run_with_obs, runtime::{ObsRuntime, ObsTransaction, ObsTransactionRef, ObsTransactionValue}, rw_lock_blocking_write, sources::{ObsFilterRef, ObsSourceRef}, unsafe_send::Sendable, utils::{async_sync::RwLock, ObsError, ObsString, SourceInfo}, Vec2
 };
 
 #[derive(Debug)]
@@ -14,7 +28,7 @@ struct _SceneDropGuard {
 }
 
 impl_obs_drop!(_SceneDropGuard, (scene), move || unsafe {
-    libobs::obs_scene_release(scene);
+    backend().obs_scene_release(scene);
 });
 
 #[derive(Debug, Clone, Getters)]
@@ -27,6 +41,11 @@ pub struct ObsSceneRef {
     pub(crate) sources: Arc<RwLock<Vec<ObsSourceRef>>>,
     #[skip_getter]
     pub(crate) active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+    /// The source backing this scene, once it has been nested into another scene.
+    #[skip_getter]
+    pub(crate) scene_source: Arc<RwLock<Option<ObsSourceRef>>>,
+    #[skip_getter]
+    pub(crate) groups: Arc<RwLock<Vec<ObsGroupRef>>>,
 
     #[skip_getter]
     _guard: Arc<_SceneDropGuard>,
@@ -35,6 +54,8 @@ pub struct ObsSceneRef {
     runtime: ObsRuntime,
 
     pub(crate) signals: Arc<ObsSceneSignals>,
+    #[skip_getter]
+    pub(crate) events: broadcast::Sender<ObsSceneEvent>,
 }
 
 impl ObsSceneRef {
@@ -46,22 +67,88 @@ impl ObsSceneRef {
     ) -> Result<Self, ObsError> {
         let name_ptr = name.as_ptr();
         let scene = run_with_obs!(runtime, (name_ptr), move || unsafe {
-            Sendable(libobs::obs_scene_create(name_ptr))
+            Sendable(backend().obs_scene_create(name_ptr))
         }).await?;
 
-        let signals = Arc::new(ObsSceneSignals::new(&scene, runtime.clone()).await?);
-        Ok(Self {
+        let signals = ObsSceneSignals::new(&scene, runtime.clone()).await?;
+        Ok(Self::from_parts(name, scene, signals, active_scene, runtime))
+    }
+
+    /// Creates the scene right away, this must be called on the OBS thread.
+    pub(crate) unsafe fn new_on_obs_thread(
+        name: ObsString,
+        active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+        runtime: ObsRuntime,
+    ) -> Self {
+        let scene = Sendable(backend().obs_scene_create(name.as_ptr().0));
+
+        let signals = ObsSceneSignals::new_on_obs_thread(&scene, runtime.clone());
+        Self::from_parts(name, scene, signals, active_scene, runtime)
+    }
+
+    /// Wraps a scene that already exists in libobs, e.g. because it was loaded from a
+    /// scene collection. This takes over the reference the caller holds and must be called
+    /// on the OBS thread.
+    pub(crate) unsafe fn from_raw_on_obs_thread(
+        scene: Sendable<*mut obs_scene_t>,
+        active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+        runtime: ObsRuntime,
+    ) -> Self {
+        let backend = backend();
+        let name = backend.obs_source_get_name(backend.obs_scene_get_source(scene.0));
+        let name = if name.is_null() {
+            ObsString::from("")
+        } else {
+            ObsString::from(CStr::from_ptr(name).to_string_lossy().into_owned())
+        };
+
+        let signals = ObsSceneSignals::new_on_obs_thread(&scene, runtime.clone());
+        Self::from_parts(name, scene, signals, active_scene, runtime)
+    }
+
+    /// Takes a new reference to the scene backing the source and wraps it together with
+    /// its items, e.g. for scenes created by plugins. Returns `None` if the source is not
+    /// a scene. This must be called on the OBS thread.
+    pub(crate) unsafe fn adopt_on_obs_thread(
+        source: Sendable<*mut obs_source_t>,
+        active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+        runtime: ObsRuntime,
+    ) -> Result<Option<Self>, ObsError> {
+        let backend = backend();
+        let scene = backend.obs_scene_from_source(source.0);
+        if scene.is_null() || backend.obs_source_get_ref(source.0).is_null() {
+            return Ok(None);
+        }
+
+        // Releasing the scene releases its source, so the reference is taken over
+        let scene = Self::from_raw_on_obs_thread(Sendable(scene), active_scene, runtime.clone());
+        register_items(&scene, &[], &mut HashMap::new(), &runtime)?;
+        Ok(Some(scene))
+    }
+
+    fn from_parts(
+        name: ObsString,
+        scene: Sendable<*mut obs_scene_t>,
+        signals: ObsSceneSignals,
+        active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+        runtime: ObsRuntime,
+    ) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
             name,
             scene: Arc::new(scene.clone()),
             sources: Arc::new(RwLock::new(vec![])),
-            active_scene: active_scene.clone(),
+            active_scene,
+            scene_source: Arc::new(RwLock::new(None)),
+            groups: Arc::new(RwLock::new(vec![])),
             _guard: Arc::new(_SceneDropGuard {
                 scene,
                 runtime: runtime.clone(),
             }),
             runtime,
-            signals,
-        })
+            signals: Arc::new(signals),
+            events,
+        }
     }
 
     #[deprecated = "Use ObsSceneRef::set_to_channel instead"]
@@ -77,7 +164,7 @@ impl ObsSceneRef {
 
         let scene_source_ptr = self.get_scene_source_ptr().await?;
         run_with_obs!(self.runtime, (scene_source_ptr), move || unsafe {
-            obs_set_output_source(channel, scene_source_ptr);
+            backend().obs_set_output_source(channel, scene_source_ptr);
         }).await
     }
 
@@ -85,7 +172,7 @@ impl ObsSceneRef {
     pub async fn get_scene_source_ptr(&self) -> Result<Sendable<*mut obs_source_t>, ObsError> {
         let scene_ptr = self.scene.clone();
         run_with_obs!(self.runtime, (scene_ptr), move || unsafe {
-            Sendable(libobs::obs_scene_get_source(scene_ptr))
+            Sendable(backend().obs_scene_get_source(scene_ptr))
         }).await
     }
 
@@ -104,7 +191,7 @@ impl ObsSceneRef {
         let source_ptr = source.source.clone();
 
         let ptr = run_with_obs!(self.runtime, (scene_ptr, source_ptr), move || unsafe {
-            Sendable(libobs::obs_scene_add(scene_ptr, source_ptr))
+            Sendable(backend().obs_scene_add(scene_ptr, source_ptr))
         }).await?;
 
         if ptr.0.is_null() {
@@ -116,6 +203,309 @@ impl ObsSceneRef {
         Ok(source)
     }
 
+    /// Adds a source that already exists (e.g. because it is shown in another scene) to
+    /// this scene. Every call creates a new scene item with its own transform.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn add_existing_source(&mut self, source: &ObsSourceRef) -> Result<ObsSceneItemRef, ObsError> {
+        let scene_ptr = self.as_ptr();
+        let events = self.events.clone();
+        let runtime = self.runtime.clone();
+        let source = source.clone();
+
+        let item = run_with_obs!(self.runtime, (scene_ptr), move || unsafe {
+            let mut source = source;
+            let item = backend().obs_scene_add(scene_ptr, source.source.0);
+            if item.is_null() {
+                return Err(ObsError::NullPointer);
+            }
+
+            source.scene_item = Some(Sendable(item));
+            Ok(ObsSceneItemRef::new_on_obs_thread(Sendable(item), Sendable(scene_ptr), source, events, runtime))
+        }).await??;
+
+        self.sources.write().await.push(item.source().clone());
+        Ok(item)
+    }
+
+    /// Adds another scene as a source of this scene, e.g. to compose the same camera
+    /// setup into several layouts. Fails with [`ObsError::SceneCycle`] if the scene is
+    /// this scene or already contains it, directly or through other scenes and groups.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn add_scene(&mut self, scene: &ObsSceneRef) -> Result<ObsSceneItemRef, ObsError> {
+        let parent_source = self.get_scene_source_ptr().await?;
+        let child_ptr = scene.as_ptr();
+        let is_cycle = child_ptr.0 == self.as_ptr().0
+            || run_with_obs!(self.runtime, (child_ptr, parent_source), move || unsafe {
+                scene_contains_source(child_ptr, parent_source)
+            }).await?;
+
+        if is_cycle {
+            return Err(ObsError::SceneCycle(scene.name().to_string()));
+        }
+
+        let source = scene.as_source().await?;
+        self.add_existing_source(&source).await
+    }
+
+    /// Removes a scene that was added with [`ObsSceneRef::add_scene`] from this scene.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn remove_scene(&mut self, scene: &ObsSceneRef) -> Result<(), ObsError> {
+        let source = scene
+            .scene_source
+            .read()
+            .await
+            .clone()
+            .ok_or(ObsError::SceneNotFound)?;
+
+        self.remove_source(&source).await
+    }
+
+    /// The source backing this scene. It is created once and shared by all scenes
+    /// this scene is nested into.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn as_source(&self) -> Result<ObsSourceRef, ObsError> {
+        let mut scene_source = self.scene_source.write().await;
+        if let Some(source) = scene_source.as_ref() {
+            return Ok(source.clone());
+        }
+
+        let scene_source_ptr = self.get_scene_source_ptr().await?;
+        let runtime = self.runtime.clone();
+        let source = run_with_obs!(self.runtime, (scene_source_ptr), move || unsafe {
+            ObsSourceRef::from_raw_on_obs_thread(Sendable(scene_source_ptr), runtime)
+        }).await??;
+
+        *scene_source = Some(source.clone());
+        Ok(source)
+    }
+
+    /// Creates an empty group in this scene. Items of this scene can be moved into it
+    /// with [`ObsGroupRef::add_item`].
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn add_group<T: Into<ObsString> + Send + Sync>(&mut self, name: T) -> Result<ObsGroupRef, ObsError> {
+        let name = name.into();
+        let name_ptr = name.as_ptr();
+        let scene_ptr = self.as_ptr();
+        let events = self.events.clone();
+        let runtime = self.runtime.clone();
+
+        let group = run_with_obs!(self.runtime, (scene_ptr, name_ptr), move || unsafe {
+            let item = backend().obs_scene_add_group2(scene_ptr, name_ptr, true);
+            if item.is_null() {
+                return Err(ObsError::NullPointer);
+            }
+
+            ObsGroupRef::new_on_obs_thread(Sendable(item), Sendable(scene_ptr), events, runtime)
+        }).await??;
+
+        self.sources.write().await.push(group.source().clone());
+        self.groups.write().await.push(group.clone());
+        Ok(group)
+    }
+
+    /// Returns all groups of this scene.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn groups(&self) -> Vec<ObsGroupRef> {
+        self.groups.read().await.clone()
+    }
+
+    /// Removes the group together with all items in it.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn remove_group(&mut self, group: &ObsGroupRef) -> Result<(), ObsError> {
+        let group_scene = group.as_scene_ptr();
+        let grouped = run_with_obs!(self.runtime, (group_scene), move || unsafe {
+            scene_items(group_scene)
+        }).await?;
+
+        self.remove_source(group.source()).await?;
+        for item in grouped {
+            self.forget_item(item).await;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the group, but keeps its items in this scene.
+    ///
+    /// libobs recreates the grouped items in this scene, so scene items of them that
+    /// were returned before are no longer valid. Use [`ObsSceneRef::scene_item`] or
+    /// [`ObsSceneRef::items`] to get the new ones.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn ungroup(&mut self, group: &ObsGroupRef) -> Result<(), ObsError> {
+        let group_ptr = group.item().as_ptr();
+        let group_scene = group.as_scene_ptr();
+        let scene_ptr = self.as_ptr();
+
+        let rebound = run_with_obs!(self.runtime, (group_ptr, group_scene, scene_ptr), move || unsafe {
+            let before = scene_items(scene_ptr);
+            let grouped = scene_items(group_scene);
+            backend().obs_sceneitem_group_ungroup(group_ptr);
+
+            // The new items are added in the same order the items had in the group
+            let added = scene_items(scene_ptr)
+                .into_iter()
+                .filter(|item| !before.iter().any(|b| b.0 == item.0));
+            grouped.into_iter().zip(added).collect::<Vec<_>>()
+        }).await?;
+
+        for source in self.sources.write().await.iter_mut() {
+            let Some(item) = source.scene_item.as_ref() else {
+                continue;
+            };
+
+            if let Some((_, new_item)) = rebound.iter().find(|(old, _)| old.0 == item.0) {
+                source.scene_item = Some(new_item.clone());
+            }
+        }
+
+        self.forget_item(group_ptr).await;
+        Ok(())
+    }
+
+    /// Stops tracking the scene item after it has been removed from this scene.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn forget_item(&self, item: Sendable<*mut obs_sceneitem_t>) {
+        self.sources
+            .write()
+            .await
+            .retain(|s| s.scene_item.as_ref().map(|i| i.0) != Some(item.0));
+        self.groups
+            .write()
+            .await
+            .retain(|g| g.item().as_ptr().0 != item.0);
+    }
+
+    /// Returns the scene item of the given source in this scene. Fails with
+    /// [`ObsError::AmbiguousSceneItem`] if the source was added to this scene several
+    /// times, use the scene items returned by [`ObsSceneRef::items`] in that case.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn scene_item(&self, source: &ObsSourceRef) -> Result<ObsSceneItemRef, ObsError> {
+        let entry = self.tracked_source(source).await?;
+        let item = entry.scene_item.clone().ok_or(ObsError::SourceNotFound)?;
+        ObsSceneItemRef::new(item, self.as_ptr(), entry, self.events.clone(), self.runtime.clone()).await
+    }
+
+    /// Returns all scene items of this scene that were added through the wrapper.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn items(&self) -> Result<Vec<ObsSceneItemRef>, ObsError> {
+        let sources = self.sources.read().await.clone();
+        let scene_ptr = self.as_ptr();
+        let events = self.events.clone();
+        let runtime = self.runtime.clone();
+
+        run_with_obs!(self.runtime, move || unsafe {
+            sources
+                .into_iter()
+                .filter_map(|source| {
+                    let item = source.scene_item.clone()?;
+                    Some(ObsSceneItemRef::new_on_obs_thread(item, scene_ptr.clone(), source, events.clone(), runtime.clone()))
+                })
+                .collect()
+        }).await
+    }
+
+    /// Returns the scene item pointer of the source in this scene. The item stored on the
+    /// source itself may belong to another scene, if the source is shown in several ones.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn item_ptr(&self, source: &ObsSourceRef) -> Result<Sendable<*mut obs_sceneitem_t>, ObsError> {
+        self.tracked_source(source)
+            .await?
+            .scene_item
+            .ok_or(ObsError::SourceNotFound)
+    }
+
+    /// Returns the entry of the source placed in this scene, failing if it was placed
+    /// several times, as it would be unclear which placement should be changed.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn tracked_source(&self, source: &ObsSourceRef) -> Result<ObsSourceRef, ObsError> {
+        let sources = self.sources.read().await;
+        let mut matching = sources
+            .iter()
+            .filter(|s| s.source.0 == source.source.0 && s.scene_item.is_some());
+
+        let entry = matching.next().ok_or(ObsError::SourceNotFound)?;
+        if matching.next().is_some() {
+            return Err(ObsError::AmbiguousSceneItem(source.name()));
+        }
+
+        Ok(entry.clone())
+    }
+
+    /// Queues creating the source and adding it to this scene in the given transaction.
+    pub fn add_source_in_transaction(
+        &self,
+        transaction: &mut ObsTransaction,
+        info: SourceInfo,
+    ) -> ObsTransactionValue<ObsSourceRef> {
+        Self::queue_source(
+            transaction,
+            self.into(),
+            info.id,
+            info.name,
+            info.settings.map(ImmutableObsData::from),
+            info.hotkey_data.map(ImmutableObsData::from),
+            (Vec::new(), Vec::new()),
+        )
+    }
+
+    /// Creates the source and adds it to the scene once the transaction is committed.
+    /// The pending settings and hotkey changes are applied right before the source is created.
+    pub(crate) fn queue_source(
+        transaction: &mut ObsTransaction,
+        scene: ObsTransactionRef<ObsSceneRef>,
+        id: ObsString,
+        name: ObsString,
+        settings: Option<ImmutableObsData>,
+        hotkey_data: Option<ImmutableObsData>,
+        (settings_changes, hotkey_changes): (Vec<ObsDataChange>, Vec<ObsDataChange>),
+    ) -> ObsTransactionValue<ObsSourceRef> {
+        let runtime = transaction.runtime.clone();
+        // The scene might only be known once the transaction runs
+        let added_to = Arc::new(std::sync::Mutex::new(None::<ObsSceneRef>));
+        let rollback_added_to = added_to.clone();
+
+        transaction.add_with_rollback(
+            move |values| unsafe {
+                let scene = scene.resolve(values).ok_or(ObsError::SceneNotFound)?;
+                let backend = backend();
+
+                let settings = settings.unwrap_or_else(|| {
+                    ImmutableObsData::from_raw_sync(Sendable(backend.obs_data_create()), runtime.clone())
+                });
+                let hotkey_data = hotkey_data.unwrap_or_else(|| {
+                    ImmutableObsData::from_raw_sync(Sendable(backend.obs_data_create()), runtime.clone())
+                });
+
+                ObsDataUpdater::apply_changes(settings.as_ptr().0, settings_changes);
+                ObsDataUpdater::apply_changes(hotkey_data.as_ptr().0, hotkey_changes);
+
+                let mut source =
+                    ObsSourceRef::new_on_obs_thread(id, name, settings, hotkey_data, runtime)?;
+
+                let item = backend.obs_scene_add(scene.scene.0, source.source.0);
+                if item.is_null() {
+                    return Err(ObsError::NullPointer);
+                }
+
+                source.scene_item = Some(Sendable(item));
+                rw_lock_blocking_write!(scene.sources).push(source.clone());
+                *added_to.lock().unwrap_or_else(|e| e.into_inner()) = Some(scene);
+
+                Ok(source)
+            },
+            move |source| unsafe {
+                if let Some(item) = &source.scene_item {
+                    backend().obs_sceneitem_remove(item.0);
+                }
+
+                let scene = rollback_added_to.lock().unwrap_or_else(|e| e.into_inner()).take();
+                if let Some(scene) = scene {
+                    rw_lock_blocking_write!(scene.sources).retain(|s| s.source.0 != source.source.0);
+                }
+            },
+        )
+    }
+
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn get_source_by_index(&self, index: usize) -> Option<ObsSourceRef> {
         self.sources.read().await.get(index).map(|x| x.clone())
@@ -133,18 +523,14 @@ impl ObsSceneRef {
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn remove_source(&mut self, source: &ObsSourceRef) -> Result<(), ObsError> {
-        let scene_item = source.scene_item.clone();
-        let Some(scene_item_ptr) = scene_item else {
-            return Err(ObsError::SourceNotFound);
-        };
+        let scene_item_ptr = self.item_ptr(source).await?;
 
         run_with_obs!(self.runtime, (scene_item_ptr), move || unsafe {
-            // Remove the scene item
-            libobs::obs_sceneitem_remove(scene_item_ptr);
-            // Release the scene item reference
-            libobs::obs_sceneitem_release(scene_item_ptr);
+            // Releases the reference the scene holds, the wrapper doesn't own one
+            backend().obs_sceneitem_remove(scene_item_ptr);
         }).await?;
 
+        self.forget_item(scene_item_ptr).await;
         Ok(())
     }
 
@@ -153,7 +539,7 @@ impl ObsSceneRef {
         let source_ptr = source.source.clone();
         let filter_ptr = filter_ref.source.clone();
         run_with_obs!(self.runtime, (source_ptr, filter_ptr), move || unsafe {
-            Sendable(libobs::obs_source_filter_add(source_ptr, filter_ptr))
+            Sendable(backend().obs_source_filter_add(source_ptr, filter_ptr))
         }).await?;
         Ok(())
     }
@@ -163,21 +549,18 @@ impl ObsSceneRef {
         let source_ptr = source.source.clone();
         let filter_ptr = filter_ref.source.clone();
         run_with_obs!(self.runtime, (source_ptr, filter_ptr), move || unsafe {
-            Sendable(libobs::obs_source_filter_remove(source_ptr, filter_ptr))
+            Sendable(backend().obs_source_filter_remove(source_ptr, filter_ptr))
         }).await?;
         Ok(())
     }
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn get_source_position(&self, source: &ObsSourceRef) -> Result<Vec2, ObsError> {
-        let scene_item = source.scene_item.clone();
-        let Some(scene_item_ptr) = scene_item else {
-            return Err(ObsError::SourceNotFound);
-        };
+        let scene_item_ptr = self.item_ptr(source).await?;
 
         let position = run_with_obs!(self.runtime, (scene_item_ptr), move || unsafe {
             let mut main_pos: libobs::vec2 = std::mem::zeroed();
-            Sendable(libobs::obs_sceneitem_get_pos(scene_item_ptr, &mut main_pos));
+            Sendable(backend().obs_sceneitem_get_pos(scene_item_ptr, &mut main_pos));
             Vec2::from(main_pos)
         }).await?;
 
@@ -186,14 +569,11 @@ impl ObsSceneRef {
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn get_source_scale(&self, source: &ObsSourceRef) -> Result<Vec2, ObsError> {
-        let scene_item = source.scene_item.clone();
-        let Some(scene_item_ptr) = scene_item else {
-            return Err(ObsError::SourceNotFound);
-        };
+        let scene_item_ptr = self.item_ptr(source).await?;
 
         let scale = run_with_obs!(self.runtime, (scene_item_ptr), move || unsafe {
             let mut main_pos: libobs::vec2 = std::mem::zeroed();
-            Sendable(libobs::obs_sceneitem_get_scale(scene_item_ptr, &mut main_pos));
+            Sendable(backend().obs_sceneitem_get_scale(scene_item_ptr, &mut main_pos));
             Vec2::from(main_pos)
         }).await?;
         
@@ -202,13 +582,10 @@ impl ObsSceneRef {
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn set_source_position(&self, source: &ObsSourceRef, position: Vec2) -> Result<(), ObsError> {
-        let scene_item = source.scene_item.clone();
-        let Some(scene_item_ptr) = scene_item else {
-            return Err(ObsError::SourceNotFound);
-        };
+        let scene_item_ptr = self.item_ptr(source).await?;
 
         run_with_obs!(self.runtime, (scene_item_ptr), move || unsafe {
-            Sendable(libobs::obs_sceneitem_set_pos(scene_item_ptr, &position.into()));
+            Sendable(backend().obs_sceneitem_set_pos(scene_item_ptr, &position.into()));
         }).await?;
         
         Ok(())
@@ -216,27 +593,117 @@ impl ObsSceneRef {
 
     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
     pub async fn set_source_scale(&self, source: &ObsSourceRef, scale: Vec2) -> Result<(), ObsError> {
-        let scene_item = source.scene_item.clone();
-        let Some(scene_item_ptr) = scene_item else {
-            return Err(ObsError::SourceNotFound);
-        };
+        let scene_item_ptr = self.item_ptr(source).await?;
 
         run_with_obs!(self.runtime, (scene_item_ptr), move || unsafe {
-            Sendable(libobs::obs_sceneitem_set_scale(scene_item_ptr, &scale.into()));
+            Sendable(backend().obs_sceneitem_set_scale(scene_item_ptr, &scale.into()));
         }).await?;
 
         Ok(())
     }
 
+    /// Moves the source relative to the other sources of this scene.
+    /// See [`ObsSceneItemRef::set_order`].
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_source_order(&self, source: &ObsSourceRef, movement: ObsOrderMovement) -> Result<(), ObsError> {
+        self.scene_item(source).await?.set_order(movement).await
+    }
+
+    /// Returns the index of the source in this scene, `0` being the bottom-most one.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_source_order_position(&self, source: &ObsSourceRef) -> Result<usize, ObsError> {
+        self.scene_item(source).await?.order_position().await
+    }
+
+    /// Moves the source to the given index in this scene, `0` being the bottom.
+    /// See [`ObsSceneItemRef::set_order_position`].
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_source_order_position(&self, source: &ObsSourceRef, position: usize) -> Result<(), ObsError> {
+        self.scene_item(source).await?.set_order_position(position).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_source_visible(&self, source: &ObsSourceRef) -> Result<bool, ObsError> {
+        self.scene_item(source).await?.visible().await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_source_visible(&self, source: &ObsSourceRef, visible: bool) -> Result<(), ObsError> {
+        self.scene_item(source).await?.set_visible(visible).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn get_source_locked(&self, source: &ObsSourceRef) -> Result<bool, ObsError> {
+        self.scene_item(source).await?.locked().await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_source_locked(&self, source: &ObsSourceRef, locked: bool) -> Result<(), ObsError> {
+        self.scene_item(source).await?.set_locked(locked).await
+    }
+
+    /// Renders the current frame of this scene at the base resolution of
+    /// the video context and reads it back as RGBA pixels.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn screenshot(&self, options: ObsScreenshotOptions) -> Result<ObsScreenshot, ObsError> {
+        let scene_source_ptr = self.get_scene_source_ptr().await?;
+        take_screenshot(&self.runtime, Some(scene_source_ptr), options).await
+    }
+
     pub fn as_ptr(&self) -> Sendable<*mut obs_scene_t> {
         Sendable(self.scene.0)
     }
+
+    /// The signals of this scene, e.g. to keep a UI in sync when items are
+    /// added, reordered, shown or hidden.
+    pub fn signal_manager(&self) -> Arc<ObsSceneSignals> {
+        self.signals.clone()
+    }
+
+    /// Subscribes to the changes made to the items of this scene through the wrapper.
+    pub fn events(&self) -> broadcast::Receiver<ObsSceneEvent> {
+        self.events.subscribe()
+    }
+
+    /// Whether there are other references to this scene which keep it alive.
+    pub(crate) fn is_shared(&self) -> bool {
+        Arc::strong_count(&self._guard) > 1
+    }
+}
+
+/// Returns the items of the scene from the bottom to the top, must be called on the OBS thread.
+pub(crate) unsafe fn scene_items(scene: *mut obs_scene_t) -> Vec<Sendable<*mut obs_sceneitem_t>> {
+    unsafe extern "C" fn collect(_scene: *mut obs_scene_t, item: *mut obs_sceneitem_t, param: *mut c_void) -> bool {
+        let items = &mut *(param as *mut Vec<Sendable<*mut obs_sceneitem_t>>);
+        items.push(Sendable(item));
+        true
+    }
+
+    let mut items = Vec::new();
+    backend().obs_scene_enum_items(scene, Some(collect), &mut items as *mut _ as *mut c_void);
+    items
+}
+
+/// Whether the scene shows the source, either directly or through nested scenes and groups.
+/// Must be called on the OBS thread.
+unsafe fn scene_contains_source(scene: *mut obs_scene_t, source: *mut obs_source_t) -> bool {
+    let backend = backend();
+    scene_items(scene).into_iter().any(|item| {
+        let item_source = backend.obs_sceneitem_get_source(item.0);
+        if item_source == source {
+            return true;
+        }
+
+        let nested = backend.obs_group_or_scene_from_source(item_source);
+        !nested.is_null() && scene_contains_source(nested, source)
+    })
 }
 
 impl_signal_manager!(|scene_ptr| {
-    let source_ptr = libobs::obs_scene_get_source(scene_ptr);
+    let backend = backend();
+    let source_ptr = backend.obs_scene_get_source(scene_ptr);
 
-    libobs::obs_source_get_signal_handler(source_ptr)
+    backend.obs_source_get_signal_handler(source_ptr)
 }, ObsSceneSignals for ObsSceneRef<*mut libobs::obs_scene_t>, [
     "item_add": {
         struct ItemAddSignal {
@@ -30,7 +30,7 @@ macro_rules! __signals_impl_primitive_handler {
         move |__internal_calldata|  {
             let mut $field_name = std::ptr::null_mut();
             let obs_str = crate::utils::ObsString::new(stringify!($field_name));
-            let success = libobs::calldata_get_string(
+            let success = crate::backend::backend().calldata_get_string(
                 __internal_calldata,
                 obs_str.as_ptr().0,
                 &mut $field_name as *const _ as _,
@@ -56,7 +56,7 @@ macro_rules! __signals_impl_primitive_handler {
         move |__internal_calldata| {
             let mut $field_name = std::mem::zeroed::<$field_type>();
             let obs_str = crate::utils::ObsString::new(stringify!($field_name));
-            let success = libobs::calldata_get_data(
+            let success = crate::backend::backend().calldata_get_data(
                 __internal_calldata,
                 obs_str.as_ptr().0,
                 &mut $field_name as *const _ as *mut std::ffi::c_void,
@@ -77,7 +77,7 @@ macro_rules! __signals_impl_primitive_handler {
         move |__internal_calldata| {
             let mut $field_name = std::mem::zeroed::<$field_type>();
             let obs_str = crate::utils::ObsString::new(stringify!($field_name));
-            let success = libobs::calldata_get_data(
+            let success = crate::backend::backend().calldata_get_data(
                 __internal_calldata,
                 obs_str.as_ptr().0,
                 &mut $field_name as *const _ as *mut std::ffi::c_void,
@@ -107,15 +107,30 @@ macro_rules! __signals_impl_primitive_handler {
     }
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __signals_impl_senders {
+    ($ptr: ty, $senders: ident, $gen_type: ty) => {
+        lazy_static::lazy_static! {
+            static ref $senders: std::sync::Arc<$crate::utils::async_sync::RwLock<std::collections::HashMap<$crate::unsafe_send::SendableComp<$ptr>, tokio::sync::broadcast::Sender<$gen_type>>>> = {
+                fn clear() {
+                    crate::rw_lock_blocking_write!($senders).clear();
+                }
+
+                crate::signals::__register_senders_cleanup(clear);
+                std::sync::Arc::new($crate::utils::async_sync::RwLock::new(std::collections::HashMap::new()))
+            };
+        }
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __signals_impl_signal {
     ($ptr: ty, $signal_name: literal, $field_name: ident: $gen_type:ty) => {
         paste::paste! {
             type [<__Private $signal_name:camel Type >] = $gen_type;
-            lazy_static::lazy_static! {
-                static ref [<$signal_name:snake:upper _SENDERS>]: std::sync::Arc<$crate::utils::async_sync::RwLock<std::collections::HashMap<$crate::unsafe_send::SendableComp<$ptr>, tokio::sync::broadcast::Sender<$gen_type>>>> = std::sync::Arc::new($crate::utils::async_sync::RwLock::new(std::collections::HashMap::new()));
-            }
+            crate::__signals_impl_senders!($ptr, [<$signal_name:snake:upper _SENDERS>], $gen_type);
 
             unsafe fn [< $signal_name:snake _handler_inner>](cd: *mut libobs::calldata_t) -> anyhow::Result<$gen_type> {
                 let e = crate::__signals_impl_primitive_handler!($field_name, $gen_type)(cd);
@@ -128,9 +143,7 @@ macro_rules! __signals_impl_signal {
     ($ptr: ty, $signal_name: literal, ) => {
         paste::paste! {
             type [<__Private $signal_name:camel Type >] = ();
-            lazy_static::lazy_static! {
-                static ref [<$signal_name:snake:upper _SENDERS>]: std::sync::Arc<$crate::utils::async_sync::RwLock<std::collections::HashMap<$crate::unsafe_send::SendableComp<$ptr>, tokio::sync::broadcast::Sender<()>>>> = std::sync::Arc::new($crate::utils::async_sync::RwLock::new(std::collections::HashMap::new()));
-            }
+            crate::__signals_impl_senders!($ptr, [<$signal_name:snake:upper _SENDERS>], ());
 
             unsafe fn [< $signal_name:snake _handler_inner>](_cd: *mut libobs::calldata_t) -> anyhow::Result<()> {
                 Ok(())
@@ -161,9 +174,7 @@ macro_rules! __signals_impl_signal {
     }) => {
         paste::paste! {
             type [<__Private $signal_name:camel Type >] = $name;
-            lazy_static::lazy_static! {
-                static ref [<$signal_name:snake:upper _SENDERS>]: std::sync::Arc<$crate::utils::async_sync::RwLock<std::collections::HashMap<$crate::unsafe_send::SendableComp<$ptr>, tokio::sync::broadcast::Sender<$name>>>> = std::sync::Arc::new($crate::utils::async_sync::RwLock::new(std::collections::HashMap::new()));
-            }
+            crate::__signals_impl_senders!($ptr, [<$signal_name:snake:upper _SENDERS>], $name);
 
             #[derive(Debug, Clone)]
             pub struct $name {
@@ -220,6 +231,20 @@ macro_rules! impl_signal_manager {
                 let _ = senders.send(res);
             })*
 
+            lazy_static::lazy_static! {
+                /// The number of signal managers alive per object. They share the senders,
+                /// e.g. when a source is looked up again, so dropping one of them doesn't
+                /// close the receivers of the others.
+                static ref [<$name:snake:upper _HANDLES>]: std::sync::Mutex<std::collections::HashMap<$crate::unsafe_send::SendableComp<$ptr>, usize>> = {
+                    fn clear() {
+                        [<$name:snake:upper _HANDLES>].lock().unwrap_or_else(|e| e.into_inner()).clear();
+                    }
+
+                    crate::signals::__register_senders_cleanup(clear);
+                    std::sync::Mutex::new(std::collections::HashMap::new())
+                };
+            }
+
             #[derive(Debug)]
             pub struct $name {
                 pointer: $crate::unsafe_send::SendableComp<$ptr>,
@@ -229,27 +254,26 @@ macro_rules! impl_signal_manager {
             impl $name {
                 #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
                 pub(crate) async fn new(ptr: &Sendable<$ptr>, runtime: $crate::runtime::ObsRuntime) -> Result<Self, crate::utils::ObsError> {
-                    use crate::{utils::ObsString, unsafe_send::SendableComp};
+                    use crate::unsafe_send::SendableComp;
                     let pointer =  SendableComp(ptr.0);
+                    if !Self::acquire(&pointer) {
+                        return Ok(Self {
+                            pointer,
+                            runtime
+                        });
+                    }
 
                     $(
-                        let senders = [<$signal_name:snake:upper _SENDERS>].clone();
-                        let mut senders = senders.write().await;
-                        let (tx, [<_ $signal_name:snake _rx>]) = tokio::sync::broadcast::channel(16);
-                        senders.insert(pointer.clone(), tx);
+                        // Not holding the lock while waiting for the OBS thread, it might need it as well
+                        {
+                            let mut senders = [<$signal_name:snake:upper _SENDERS>].write().await;
+                            let (tx, [<_ $signal_name:snake _rx>]) = tokio::sync::broadcast::channel(16);
+                            senders.insert(pointer.clone(), tx);
+                        }
                     )*
 
                     crate::run_with_obs!(runtime, (pointer), move || unsafe {
-                            let handler = ($handler_getter)(pointer);
-                            $(
-                                let signal = ObsString::new($signal_name);
-                                libobs::signal_handler_connect(
-                                    handler,
-                                    signal.as_ptr().0,
-                                    Some([< $signal_name:snake _handler>]),
-                                    pointer as *mut std::ffi::c_void,
-                                );
-                            )*
+                        Self::connect_handlers(pointer);
                     }).await?;
 
                     Ok(Self {
@@ -258,6 +282,112 @@ macro_rules! impl_signal_manager {
                     })
                 }
 
+                /// Creates the signal manager while already running on the OBS thread,
+                /// for example in an [`ObsTransaction`](crate::runtime::ObsTransaction).
+                pub(crate) unsafe fn new_on_obs_thread(ptr: &Sendable<$ptr>, runtime: $crate::runtime::ObsRuntime) -> Self {
+                    let pointer = $crate::unsafe_send::SendableComp(ptr.0);
+                    if !Self::acquire(&pointer) {
+                        return Self {
+                            pointer,
+                            runtime
+                        };
+                    }
+
+                    $(
+                        let (tx, [<_ $signal_name:snake _rx>]) = tokio::sync::broadcast::channel(16);
+                        crate::rw_lock_blocking_write!([<$signal_name:snake:upper _SENDERS>]).insert(pointer.clone(), tx);
+                    )*
+
+                    Self::connect_handlers(pointer.0);
+                    Self {
+                        pointer,
+                        runtime
+                    }
+                }
+
+                /// Counts a new signal manager of the object, returns whether it is the first one.
+                fn acquire(pointer: &$crate::unsafe_send::SendableComp<$ptr>) -> bool {
+                    let mut handles = [<$name:snake:upper _HANDLES>].lock().unwrap_or_else(|e| e.into_inner());
+                    let count = handles.entry(pointer.clone()).or_default();
+                    *count += 1;
+                    *count == 1
+                }
+
+                /// Returns whether the last signal manager of the object was released.
+                /// The handlers have already been disconnected if the object isn't counted anymore.
+                fn release(pointer: &$crate::unsafe_send::SendableComp<$ptr>) -> bool {
+                    let mut handles = [<$name:snake:upper _HANDLES>].lock().unwrap_or_else(|e| e.into_inner());
+                    match handles.get_mut(pointer) {
+                        Some(count) if *count > 1 => {
+                            *count -= 1;
+                            false
+                        }
+                        Some(_) => {
+                            handles.remove(pointer);
+                            true
+                        }
+                        None => false,
+                    }
+                }
+
+                /// Disconnects the handlers of every object that still has a signal manager and
+                /// removes their senders, which ends all receivers. Dropping the signal managers
+                /// afterwards does nothing.
+                /// Must be called on the OBS thread, returns the objects without signal handler.
+                pub(crate) unsafe fn disconnect_all() -> Vec<String> {
+                    let handles = std::mem::take(&mut *[<$name:snake:upper _HANDLES>].lock().unwrap_or_else(|e| e.into_inner()));
+
+                    let mut failures = Vec::new();
+                    for pointer in handles.into_keys() {
+                        if !Self::disconnect_handlers(pointer.0) {
+                            failures.push(format!("{} {:?}", stringify!($name), pointer.0));
+                        }
+
+                        $(
+                            crate::rw_lock_blocking_write!([<$signal_name:snake:upper _SENDERS>]).remove(&pointer);
+                        )*
+                    }
+
+                    failures
+                }
+
+                unsafe fn connect_handlers(pointer: $ptr) {
+                    use crate::utils::ObsString;
+
+                    let handler = ($handler_getter)(pointer);
+                    $(
+                        let signal = ObsString::new($signal_name);
+                        crate::backend::backend().signal_handler_connect(
+                            handler,
+                            signal.as_ptr().0,
+                            Some([< $signal_name:snake _handler>]),
+                            pointer as *mut std::ffi::c_void,
+                        );
+                    )*
+                }
+
+                /// Returns false if the object has no signal handler.
+                unsafe fn disconnect_handlers(pointer: $ptr) -> bool {
+                    use crate::utils::ObsString;
+
+                    let handler = ($handler_getter)(pointer);
+                    if handler.is_null() {
+                        return false;
+                    }
+
+                    $(
+                        let signal = ObsString::new($signal_name);
+                        crate::backend::backend().signal_handler_disconnect(
+                            handler,
+                            signal.as_ptr().0,
+                            Some([< $signal_name:snake _handler>]),
+                            pointer as *mut std::ffi::c_void,
+                        );
+                    )*
+
+                    true
+                }
+
                 $(
                     #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
                     pub async fn [<on_ $signal_name:snake>](&self) -> Result<tokio::sync::broadcast::Receiver<[<__Private $signal_name:camel Type >]>, crate::utils::ObsError> {
@@ -277,19 +407,19 @@ macro_rules! impl_signal_manager {
                     let ptr = self.pointer.clone();
                     #[allow(unused_variables)]
                     let runtime = self.runtime.clone();
+                    if runtime.is_shut_down() {
+                        // libobs already destroyed the signal handlers and the senders
+                        // were cleared once it was shut down
+                        return;
+                    }
+
+                    if !Self::release(&ptr) {
+                        // Other signal managers of the object still use the senders
+                        return;
+                    }
 
                     let future = crate::run_with_obs!(runtime, (ptr), move || unsafe {
-                        #[allow(unused_variables)]
-                        let handler = ($handler_getter)(ptr);
-                        $(
-                            let signal = crate::utils::ObsString::new($signal_name);
-                            libobs::signal_handler_disconnect(
-                                handler,
-                                signal.as_ptr().0,
-                                Some([< $signal_name:snake _handler>]),
-                                ptr as *mut std::ffi::c_void,
-                            );
-                        )*
+                        Self::disconnect_handlers(ptr);
                     });
 
                     #[allow(unused_variables)]
@@ -297,8 +427,7 @@ macro_rules! impl_signal_manager {
                     #[cfg(not(feature="blocking"))]
                     let r = futures::executor::block_on(async move {
                         $(
-                            let mut handlers = [<$signal_name:snake:upper _SENDERS>].write().await;
-                            handlers.remove(&tmp_ptr);
+                            [<$signal_name:snake:upper _SENDERS>].write().await.remove(&tmp_ptr);
                         )*
 
                         future.await
@@ -314,13 +443,36 @@ macro_rules! impl_signal_manager {
                         future
                     };
 
-                    if std::thread::panicking() {
-                        return;
+                    if let Err(e) = r {
+                        log::error!("Failed to disconnect the signals of {}: {:?}", stringify!($name), e);
                     }
-
-                    r.unwrap();
                 }
             }
         }
     };
-}
\ No newline at end of file
+}
+lazy_static::lazy_static! {
+    /// Clears the senders of one signal each, registered once the senders are first used.
+    static ref SENDERS_CLEANUPS: std::sync::Mutex<Vec<fn()>> = std::sync::Mutex::new(Vec::new());
+}
+
+#[doc(hidden)]
+pub fn __register_senders_cleanup(cleanup: fn()) {
+    SENDERS_CLEANUPS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(cleanup);
+}
+
+/// Removes the senders of all signal managers. Must only be called after libobs
+/// has been shut down, as it destroyed the signal handlers that used them.
+pub(crate) fn clear_all_senders() {
+    let cleanups = SENDERS_CLEANUPS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    for cleanup in cleanups {
+        cleanup();
+    }
+}
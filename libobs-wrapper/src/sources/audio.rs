@@ -0,0 +1,309 @@
+//! Audio controls of sources, like the ones of the audio mixer in OBS Studio.
+
+use std::time::Duration;
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    backend::backend,
+    enums::{ObsFaderType, ObsMonitoringType, OsEnumType},
+    media::{ObsFader, ObsVolumeMeter, ObsVolumeMeterOptions},
+    run_with_obs,
+    utils::ObsError,
+};
+
+use super::ObsSourceRef;
+
+/// Converts a volume in decibels to a linear multiplier, `-inf` dB is silence.
+pub fn db_to_mul(db: f32) -> f32 {
+    if db.is_finite() {
+        10f32.powf(db / 20.0)
+    } else if db > 0.0 {
+        f32::INFINITY
+    } else {
+        0.0
+    }
+}
+
+/// Converts a linear volume multiplier to decibels, silence is `-inf` dB.
+pub fn mul_to_db(mul: f32) -> f32 {
+    if mul <= 0.0 {
+        f32::NEG_INFINITY
+    } else {
+        20.0 * mul.log10()
+    }
+}
+
+impl ObsSourceRef {
+    /// The volume as linear multiplier, `1.0` is the original volume.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn volume(&self) -> Result<f32, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_volume(source_ptr)
+        })
+        .await
+    }
+
+    /// Sets the volume as linear multiplier. Negative values are treated as silence.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_volume(&self, volume: f32) -> Result<(), ObsError> {
+        let volume = volume.max(0.0);
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_volume(source_ptr, volume);
+        })
+        .await
+    }
+
+    /// The volume in decibels, `0.0` is the original volume.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn volume_db(&self) -> Result<f32, ObsError> {
+        Ok(mul_to_db(self.volume().await?))
+    }
+
+    /// Sets the volume in decibels, `f32::NEG_INFINITY` is silence.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_volume_db(&self, db: f32) -> Result<(), ObsError> {
+        self.set_volume(db_to_mul(db)).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn muted(&self) -> Result<bool, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_muted(source_ptr)
+        })
+        .await
+    }
+
+    /// Mutes or unmutes the source. Emits `mute`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_muted(&self, muted: bool) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_muted(source_ptr, muted);
+        })
+        .await
+    }
+
+    /// The stereo balance, from `0.0` (left) over `0.5` (center) to `1.0` (right).
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn balance(&self) -> Result<f32, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_balance_value(source_ptr)
+        })
+        .await
+    }
+
+    /// Sets the stereo balance, the value is clamped to `0.0..=1.0`. Emits `audio_balance`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_balance(&self, balance: f32) -> Result<(), ObsError> {
+        let balance = balance.clamp(0.0, 1.0);
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_balance_value(source_ptr, balance);
+        })
+        .await
+    }
+
+    /// The audio sync offset in nanoseconds. Positive values delay the audio.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn sync_offset(&self) -> Result<i64, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_sync_offset(source_ptr)
+        })
+        .await
+    }
+
+    /// Sets the audio sync offset in nanoseconds. Emits `audio_sync`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_sync_offset(&self, offset_ns: i64) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_sync_offset(source_ptr, offset_ns);
+        })
+        .await
+    }
+
+    /// The audio tracks this source is mixed into, bit `n` stands for track `n`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn audio_mixers(&self) -> Result<u32, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_audio_mixers(source_ptr)
+        })
+        .await
+    }
+
+    /// Sets the audio tracks this source is mixed into. Emits `audio_mixers`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_audio_mixers(&self, mixers: u32) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_audio_mixers(source_ptr, mixers);
+        })
+        .await
+    }
+
+    /// Whether this source is mixed into the given audio track.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn audio_track_enabled(&self, track: usize) -> Result<bool, ObsError> {
+        let bit = track_bit(track)?;
+        Ok(self.audio_mixers().await? & bit != 0)
+    }
+
+    /// Adds this source to or removes it from the given audio track.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_audio_track_enabled(
+        &self,
+        track: usize,
+        enabled: bool,
+    ) -> Result<(), ObsError> {
+        let bit = track_bit(track)?;
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            let backend = backend();
+            let mixers = backend.obs_source_get_audio_mixers(source_ptr);
+            let mixers = if enabled { mixers | bit } else { mixers & !bit };
+            backend.obs_source_set_audio_mixers(source_ptr, mixers);
+        })
+        .await
+    }
+
+    /// Whether push-to-talk is enabled, which mutes the source unless the hotkey is held.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn push_to_talk(&self) -> Result<bool, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_push_to_talk_enabled(source_ptr)
+        })
+        .await
+    }
+
+    /// Enables or disables push-to-talk. Emits `push_to_talk_changed`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_push_to_talk(&self, enabled: bool) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_enable_push_to_talk(source_ptr, enabled);
+        })
+        .await
+    }
+
+    /// For how long the source stays unmuted after the push-to-talk hotkey is released.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn push_to_talk_delay(&self) -> Result<Duration, ObsError> {
+        let source_ptr = self.source.clone();
+        let delay_ms = run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_push_to_talk_delay(source_ptr)
+        })
+        .await?;
+
+        Ok(Duration::from_millis(delay_ms))
+    }
+
+    /// Emits `push_to_talk_delay`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_push_to_talk_delay(&self, delay: Duration) -> Result<(), ObsError> {
+        let delay_ms = delay.as_millis().min(u64::MAX as u128) as u64;
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_push_to_talk_delay(source_ptr, delay_ms);
+        })
+        .await
+    }
+
+    /// Whether push-to-mute is enabled, which mutes the source while the hotkey is held.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn push_to_mute(&self) -> Result<bool, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_push_to_mute_enabled(source_ptr)
+        })
+        .await
+    }
+
+    /// Enables or disables push-to-mute. Emits `push_to_mute_changed`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_push_to_mute(&self, enabled: bool) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_enable_push_to_mute(source_ptr, enabled);
+        })
+        .await
+    }
+
+    /// For how long the source stays muted after the push-to-mute hotkey is released.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn push_to_mute_delay(&self) -> Result<Duration, ObsError> {
+        let source_ptr = self.source.clone();
+        let delay_ms = run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_get_push_to_mute_delay(source_ptr)
+        })
+        .await?;
+
+        Ok(Duration::from_millis(delay_ms))
+    }
+
+    /// Emits `push_to_mute_delay`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_push_to_mute_delay(&self, delay: Duration) -> Result<(), ObsError> {
+        let delay_ms = delay.as_millis().min(u64::MAX as u128) as u64;
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_push_to_mute_delay(source_ptr, delay_ms);
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn monitoring_type(&self) -> Result<ObsMonitoringType, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            let monitoring_type = backend().obs_source_get_monitoring_type(source_ptr);
+            ObsMonitoringType::from_i64(monitoring_type as i64).unwrap_or(ObsMonitoringType::None)
+        })
+        .await
+    }
+
+    /// Sets whether the audio of this source is played on the monitoring device.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_monitoring_type(
+        &self,
+        monitoring_type: ObsMonitoringType,
+    ) -> Result<(), ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_set_monitoring_type(source_ptr, monitoring_type as OsEnumType);
+        })
+        .await
+    }
+
+    /// Creates a live level meter of the audio of this source.
+    /// The meter keeps the source alive until it is dropped.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn volume_meter(
+        &self,
+        options: ObsVolumeMeterOptions,
+    ) -> Result<ObsVolumeMeter, ObsError> {
+        ObsVolumeMeter::new(self.clone(), options).await
+    }
+
+    /// Creates a fader that controls the volume of this source with the given curve.
+    /// The fader keeps the source alive until it is dropped.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn fader(&self, fader_type: ObsFaderType) -> Result<ObsFader, ObsError> {
+        ObsFader::new(self.clone(), fader_type).await
+    }
+}
+
+fn track_bit(track: usize) -> Result<u32, ObsError> {
+    if track >= libobs::MAX_AUDIO_MIXES as usize {
+        return Err(ObsError::InvalidAudioMix(track));
+    }
+
+    Ok(1 << track)
+}
@@ -1,5 +1,6 @@
 use crate::{
-    data::ObsObjectBuilder,
+    data::{immutable::ImmutableObsData, ObsObjectBuilder},
+    runtime::{ObsTransaction, ObsTransactionRef, ObsTransactionValue},
     scenes::ObsSceneRef,
     utils::ObsError,
 };
@@ -16,4 +17,29 @@ pub trait ObsSourceBuilder: ObsObjectBuilder {
         let s = self.build().await?;
         scene.add_source(s).await
     }
-}
\ No newline at end of file
+
+    /// Queues creating the source and adding it to the scene in the given transaction.
+    /// The scene can also be one that is created earlier in the same transaction.
+    fn add_to_scene_in_transaction(
+        mut self,
+        transaction: &mut ObsTransaction,
+        scene: impl Into<ObsTransactionRef<ObsSceneRef>>,
+    ) -> ObsTransactionValue<ObsSourceRef>
+    where
+        Self: Sized,
+    {
+        // The changes are applied in the transaction instead of calling `build`
+        let settings_changes = std::mem::take(&mut self.get_settings_updater().changes);
+        let hotkey_changes = std::mem::take(&mut self.get_hotkeys_updater().changes);
+
+        ObsSceneRef::queue_source(
+            transaction,
+            scene.into(),
+            Self::get_id(),
+            self.get_name(),
+            Some(ImmutableObsData::shared(self.get_settings())),
+            Some(ImmutableObsData::shared(self.get_hotkeys())),
+            (settings_changes, hotkey_changes),
+        )
+    }
+}
@@ -0,0 +1,141 @@
+//! Playback controls of media sources, like the ones below the preview in OBS Studio.
+
+use std::time::Duration;
+
+use num_traits::FromPrimitive;
+
+use crate::{backend::backend, enums::ObsMediaState, run_with_obs, utils::ObsError};
+
+use super::ObsSourceRef;
+
+/// Controls the playback of a media source, like `ffmpeg_source` or `vlc_source`.
+/// Created by [`ObsSourceRef::media_controller`].
+///
+/// The controller keeps the source alive. Changes are reported through the `media_*`
+/// signals of the source, see [`super::ObsSourceSignals`]. Sources that don't play media
+/// ignore all commands.
+#[derive(Debug, Clone)]
+pub struct ObsMediaController {
+    source: ObsSourceRef,
+}
+
+impl ObsMediaController {
+    pub fn source(&self) -> &ObsSourceRef {
+        &self.source
+    }
+
+    /// Resumes playback. Emits `media_play`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn play(&self) -> Result<(), ObsError> {
+        self.play_pause(false).await
+    }
+
+    /// Pauses playback. Emits `media_pause`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn pause(&self) -> Result<(), ObsError> {
+        self.play_pause(true).await
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn play_pause(&self, pause: bool) -> Result<(), ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_play_pause(source_ptr, pause);
+        })
+        .await
+    }
+
+    /// Stops playback and unloads the media. Emits `media_stopped`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn stop(&self) -> Result<(), ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_stop(source_ptr);
+        })
+        .await
+    }
+
+    /// Plays the media from the beginning. Emits `media_restart`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn restart(&self) -> Result<(), ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_restart(source_ptr);
+        })
+        .await
+    }
+
+    /// Skips to the next item of a playlist. Emits `media_next`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn next(&self) -> Result<(), ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_next(source_ptr);
+        })
+        .await
+    }
+
+    /// Goes back to the previous item of a playlist. Emits `media_previous`.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn previous(&self) -> Result<(), ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_previous(source_ptr);
+        })
+        .await
+    }
+
+    /// Seeks to the given position, counted from the beginning of the media.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn seek(&self, position: Duration) -> Result<(), ObsError> {
+        let ms = position.as_millis().min(i64::MAX as u128) as i64;
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_set_time(source_ptr, ms);
+        })
+        .await
+    }
+
+    /// The length of the media, zero if it isn't known (yet), e.g. for live streams.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn duration(&self) -> Result<Duration, ObsError> {
+        let source_ptr = self.source.source.clone();
+        let ms = run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_get_duration(source_ptr)
+        })
+        .await?;
+
+        Ok(Duration::from_millis(ms.max(0) as u64))
+    }
+
+    /// The current playback position, counted from the beginning of the media.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn position(&self) -> Result<Duration, ObsError> {
+        let source_ptr = self.source.source.clone();
+        let ms = run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            backend().obs_source_media_get_time(source_ptr)
+        })
+        .await?;
+
+        Ok(Duration::from_millis(ms.max(0) as u64))
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn state(&self) -> Result<ObsMediaState, ObsError> {
+        let source_ptr = self.source.source.clone();
+        run_with_obs!(self.source.runtime, (source_ptr), move || unsafe {
+            let state = backend().obs_source_media_get_state(source_ptr);
+            ObsMediaState::from_i64(state as i64).unwrap_or(ObsMediaState::None)
+        })
+        .await
+    }
+}
+
+impl ObsSourceRef {
+    /// Returns a controller for the media playback of this source.
+    pub fn media_controller(&self) -> ObsMediaController {
+        ObsMediaController {
+            source: self.clone(),
+        }
+    }
+}
@@ -1,20 +1,31 @@
+mod audio;
 mod builder;
+mod media;
+pub use audio::*;
 pub use builder::*;
+pub use media::*;
 
-use libobs::{
-    obs_scene_item, obs_source_create, obs_source_release, obs_source_reset_settings, obs_source_t,
-    obs_source_update,
-};
+use libobs::{obs_scene_item, obs_source_t};
 
 use crate::{
+    backend::backend,
     data::{immutable::ImmutableObsData, ObsData},
-    impl_obs_drop, impl_signal_manager, run_with_obs,
+    impl_obs_drop, impl_signal_manager,
+    media::{
+        take_screenshot, ObsScreenshot, ObsScreenshotOptions, ObsSourceAudioOptions,
+        ObsSourceAudioStream,
+    },
+    run_with_obs,
     runtime::ObsRuntime,
     unsafe_send::Sendable,
     utils::{traits::ObsUpdatable, ObsError, ObsString},
 };
 
-use std::{ptr, sync::Arc};
+use std::{
+    ffi::{c_void, CStr},
+    ptr,
+    sync::Arc,
+};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -62,7 +73,7 @@ impl ObsSourceRef {
             runtime,
             (hotkey_data_ptr, settings_ptr, id_ptr, name_ptr),
             move || unsafe {
-                Sendable(obs_source_create(
+                Sendable(backend().obs_source_create(
                     id_ptr,
                     name_ptr,
                     settings_ptr,
@@ -93,6 +104,88 @@ impl ObsSourceRef {
         })
     }
 
+    /// Creates the source right away, this must be called on the OBS thread.
+    pub(crate) unsafe fn new_on_obs_thread(
+        id: ObsString,
+        name: ObsString,
+        settings: ImmutableObsData,
+        hotkey_data: ImmutableObsData,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let source = Sendable(backend().obs_source_create(
+            id.as_ptr().0,
+            name.as_ptr().0,
+            settings.as_ptr().0,
+            hotkey_data.as_ptr().0,
+        ));
+
+        if source.0.is_null() {
+            return Err(ObsError::NullPointer);
+        }
+
+        let signals = ObsSourceSignals::new_on_obs_thread(&source, runtime.clone());
+        Ok(Self {
+            source: source.clone(),
+            id,
+            name,
+            settings: Arc::new(settings),
+            hotkey_data: Arc::new(hotkey_data),
+            _guard: Arc::new(_ObsSourceGuard {
+                source,
+                runtime: runtime.clone(),
+            }),
+            scene_item: None,
+            runtime,
+            signal_manager: Arc::new(signals),
+        })
+    }
+
+    /// Takes a new reference to a source that already exists in libobs, e.g. the source
+    /// backing a scene. This must be called on the OBS thread.
+    pub(crate) unsafe fn from_raw_on_obs_thread(
+        source: Sendable<*mut obs_source_t>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let backend = backend();
+        let source = Sendable(backend.obs_source_get_ref(source.0));
+        if source.0.is_null() {
+            return Err(ObsError::NullPointer);
+        }
+
+        let to_obs_string = |s: *const std::ffi::c_char| {
+            if s.is_null() {
+                return ObsString::from("");
+            }
+
+            ObsString::from(CStr::from_ptr(s).to_string_lossy().into_owned())
+        };
+
+        let id = to_obs_string(backend.obs_source_get_id(source.0));
+        let name = to_obs_string(backend.obs_source_get_name(source.0));
+        let settings = ImmutableObsData::from_raw_sync(
+            Sendable(backend.obs_source_get_settings(source.0)),
+            runtime.clone(),
+        );
+        let hotkey_data =
+            ImmutableObsData::from_raw_sync(Sendable(backend.obs_data_create()), runtime.clone());
+
+        let signals = ObsSourceSignals::new_on_obs_thread(&source, runtime.clone());
+        Ok(Self {
+            source: source.clone(),
+            id,
+            name,
+            settings: Arc::new(settings),
+            hotkey_data: Arc::new(hotkey_data),
+            _guard: Arc::new(_ObsSourceGuard {
+                source,
+                runtime: runtime.clone(),
+            }),
+            scene_item: None,
+            runtime,
+            signal_manager: Arc::new(signals),
+        })
+    }
+
     pub fn settings(&self) -> &ImmutableObsData {
         &self.settings
     }
@@ -112,6 +205,65 @@ impl ObsSourceRef {
     pub fn signal_manager(&self) -> Arc<ObsSourceSignals> {
         self.signal_manager.clone()
     }
+
+    /// The unique id libobs assigned to this source, stays the same across scene
+    /// collection saves and loads.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn uuid(&self) -> Result<String, ObsError> {
+        let source_ptr = self.source.clone();
+        run_with_obs!(self.runtime, (source_ptr), move || unsafe {
+            let uuid = backend().obs_source_get_uuid(source_ptr);
+            if uuid.is_null() {
+                return Err(ObsError::NullPointer);
+            }
+
+            Ok(CStr::from_ptr(uuid).to_string_lossy().into_owned())
+        })
+        .await?
+    }
+
+    /// Streams the audio this source outputs, before it is mixed into the audio tracks.
+    /// The stream keeps the source alive until it is dropped.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn audio_stream(
+        &self,
+        options: ObsSourceAudioOptions,
+    ) -> Result<ObsSourceAudioStream, ObsError> {
+        ObsSourceAudioStream::new(self.clone(), options).await
+    }
+
+    /// Renders the current frame of this source and reads it back as RGBA pixels.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn screenshot(
+        &self,
+        options: ObsScreenshotOptions,
+    ) -> Result<ObsScreenshot, ObsError> {
+        take_screenshot(&self.runtime, Some(self.source.clone()), options).await
+    }
+
+    /// Whether there are other references to this source which keep it alive.
+    pub(crate) fn is_shared(&self) -> bool {
+        Arc::strong_count(&self._guard) > 1
+    }
+}
+
+/// Collects the sources an enumeration function like `obs_enum_sources` lists, without
+/// taking references to them. Must be called on the OBS thread.
+pub(crate) unsafe fn enumerate_sources(
+    enumerate: impl FnOnce(
+        Option<unsafe extern "C" fn(*mut c_void, *mut obs_source_t) -> bool>,
+        *mut c_void,
+    ),
+) -> Vec<Sendable<*mut obs_source_t>> {
+    unsafe extern "C" fn collect(param: *mut c_void, source: *mut obs_source_t) -> bool {
+        let sources = &mut *(param as *mut Vec<Sendable<*mut obs_source_t>>);
+        sources.push(Sendable(source));
+        true
+    }
+
+    let mut sources = Vec::new();
+    enumerate(Some(collect), &mut sources as *mut _ as *mut c_void);
+    sources
 }
 
 #[cfg_attr(not(feature = "blocking"), async_trait::async_trait)]
@@ -122,7 +274,7 @@ impl ObsUpdatable for ObsSourceRef {
         let source_ptr = self.source.clone();
         log::trace!("Updating source: {:?}", self.source);
         run_with_obs!(self.runtime, (source_ptr, data_ptr), move || unsafe {
-            obs_source_update(source_ptr, data_ptr);
+            backend().obs_source_update(source_ptr, data_ptr);
         })
         .await
     }
@@ -131,7 +283,7 @@ impl ObsUpdatable for ObsSourceRef {
     async fn reset_and_update_raw(&mut self, data: ObsData) -> Result<(), ObsError> {
         let source_ptr = self.source.clone();
         run_with_obs!(self.runtime, (source_ptr), move || unsafe {
-            obs_source_reset_settings(source_ptr, data.as_ptr().0);
+            backend().obs_source_reset_settings(source_ptr, data.as_ptr().0);
         })
         .await
     }
@@ -145,7 +297,7 @@ impl ObsUpdatable for ObsSourceRef {
         log::trace!("Getting settings for source: {:?}", self.source);
         let source_ptr = self.source.clone();
         let res = run_with_obs!(self.runtime, (source_ptr), move || unsafe {
-            Sendable(libobs::obs_source_get_settings(source_ptr))
+            Sendable(backend().obs_source_get_settings(source_ptr))
         })
         .await?;
 
@@ -154,7 +306,7 @@ impl ObsUpdatable for ObsSourceRef {
     }
 }
 
-impl_signal_manager!(|ptr| libobs::obs_source_get_signal_handler(ptr), ObsSourceSignals for ObsSourceRef<*mut libobs::obs_source_t>, [
+impl_signal_manager!(|ptr| backend().obs_source_get_signal_handler(ptr), ObsSourceSignals for ObsSourceRef<*mut libobs::obs_source_t>, [
     "destroy": {},
     "remove": {},
     "update": {},
@@ -190,6 +342,9 @@ impl_signal_manager!(|ptr| libobs::obs_source_get_signal_handler(ptr), ObsSource
     "update_flags": {struct UpdateFlagsSignal {
         flags: i64
     }},
+    "volume": {struct VolumeSignal {
+        volume: f64,
+    }},
     "audio_sync": {struct AudioSyncSignal {
         offset: i64,
     }},
@@ -232,7 +387,7 @@ struct _ObsSourceGuard {
 }
 
 impl_obs_drop!(_ObsSourceGuard, (source), move || unsafe {
-    obs_source_release(source);
+    backend().obs_source_release(source);
 });
 
 pub type ObsFilterRef = ObsSourceRef;
\ No newline at end of file
@@ -0,0 +1,188 @@
+//! Render and encoding statistics, like the stats dock of OBS Studio shows them.
+//!
+//! An [`ObsStats`] snapshot is taken with [`ObsContext::stats`](crate::context::ObsContext::stats),
+//! or periodically with [`ObsContext::stats_stream`](crate::context::ObsContext::stats_stream).
+//! The frame counters are totals since OBS was started (or since the output was started for
+//! the per-output ones), so compare two snapshots to get the values of a time span.
+
+use std::time::Duration;
+#[cfg(not(feature = "blocking"))]
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use libobs::obs_output;
+
+use crate::{
+    backend::backend, data::output::ObsOutputRef, run_with_obs, runtime::ObsRuntime,
+    unsafe_send::Sendable, utils::ObsError,
+};
+
+#[cfg(not(feature = "blocking"))]
+use crate::utils::async_sync::RwLock;
+#[cfg(not(feature = "blocking"))]
+use futures::{Stream, StreamExt};
+
+/// Statistics of a single output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsOutputStats {
+    /// The name of the output
+    pub name: String,
+    pub active: bool,
+    /// The number of frames the output dropped, e.g. because the network couldn't keep up
+    pub dropped_frames: u32,
+    /// The number of frames the output received
+    pub total_frames: u32,
+    /// The number of bytes the output wrote or sent
+    pub total_bytes: u64,
+    /// How congested the output is, between 0 (not at all) and 1
+    pub congestion: f32,
+}
+
+impl ObsOutputStats {
+    /// The percentage of dropped frames, between 0 and 100.
+    pub fn dropped_percentage(&self) -> f64 {
+        percentage(self.dropped_frames, self.total_frames)
+    }
+}
+
+/// A snapshot of the render and encoding statistics of OBS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsStats {
+    /// The frames per second the video thread currently renders at
+    pub active_fps: f64,
+    /// The average time it takes to render a frame
+    pub average_frame_time: Duration,
+    /// The number of frames that were rendered
+    pub total_frames: u32,
+    /// The number of frames that were missed because rendering took too long
+    pub lagged_frames: u32,
+    /// Statistics of every output of the context
+    pub outputs: Vec<ObsOutputStats>,
+}
+
+impl ObsStats {
+    /// The percentage of frames that were missed due to rendering lag, between 0 and 100.
+    pub fn lagged_percentage(&self) -> f64 {
+        percentage(self.lagged_frames, self.total_frames)
+    }
+
+    /// Returns the statistics of the output with the given name.
+    pub fn output(&self, name: &str) -> Option<&ObsOutputStats> {
+        self.outputs.iter().find(|o| o.name == name)
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn collect(
+        runtime: &ObsRuntime,
+        outputs: &[ObsOutputRef],
+    ) -> Result<Self, ObsError> {
+        let outputs = outputs
+            .iter()
+            .map(|o| (o.name().to_string(), o.as_ptr()))
+            .collect::<Vec<_>>();
+
+        run_with_obs!(runtime, move || unsafe {
+            let backend = backend();
+            let outputs = outputs
+                .into_iter()
+                .map(|(name, output)| output_stats(name, output))
+                .collect();
+
+            ObsStats {
+                active_fps: backend.obs_get_active_fps(),
+                average_frame_time: Duration::from_nanos(backend.obs_get_average_frame_time_ns()),
+                total_frames: backend.obs_get_total_frames(),
+                lagged_frames: backend.obs_get_lagged_frames(),
+                outputs,
+            }
+        })
+        .await
+    }
+}
+
+/// Must be called on the OBS thread.
+pub(crate) unsafe fn output_stats(
+    name: String,
+    output: Sendable<*mut obs_output>,
+) -> ObsOutputStats {
+    let backend = backend();
+    let output = output.0;
+
+    ObsOutputStats {
+        name,
+        active: backend.obs_output_active(output),
+        dropped_frames: backend.obs_output_get_frames_dropped(output).max(0) as u32,
+        total_frames: backend.obs_output_get_total_frames(output).max(0) as u32,
+        total_bytes: backend.obs_output_get_total_bytes(output),
+        congestion: backend.obs_output_get_congestion(output),
+    }
+}
+
+fn percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    part as f64 / total as f64 * 100.0
+}
+
+/// A stream of [`ObsStats`] snapshots that are taken periodically.
+/// Created by [`ObsContext::stats_stream`](crate::context::ObsContext::stats_stream).
+///
+/// Outputs which are added to the context later are included in the following snapshots.
+/// The stream ends once OBS is shut down.
+#[cfg(not(feature = "blocking"))]
+pub struct ObsStatsStream {
+    inner: Pin<Box<dyn Stream<Item = ObsStats> + Send>>,
+}
+
+#[cfg(not(feature = "blocking"))]
+impl ObsStatsStream {
+    pub(crate) fn new(
+        runtime: ObsRuntime,
+        outputs: Arc<RwLock<Vec<ObsOutputRef>>>,
+        period: Duration,
+    ) -> Self {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let shut_down = runtime.shut_down_signal();
+        let inner = futures::stream::unfold(
+            (runtime, outputs, interval, shut_down),
+            |(runtime, outputs, mut interval, mut shut_down)| async move {
+                // Ending the stream drops the runtime, so it doesn't outlive the context
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = &mut shut_down => return None,
+                }
+
+                let current_outputs = outputs.read().await.clone();
+                let stats = ObsStats::collect(&runtime, &current_outputs).await.ok()?;
+                Some((stats, (runtime, outputs, interval, shut_down)))
+            },
+        );
+
+        Self {
+            inner: Box::pin(inner.fuse()),
+        }
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl std::fmt::Debug for ObsStatsStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsStatsStream").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl Stream for ObsStatsStream {
+    type Item = ObsStats;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
@@ -0,0 +1,171 @@
+//! Studio mode, where a preview scene can be edited while a different program scene is live.
+//!
+//! The program scene is output on the main channel, the preview scene can be shown on a
+//! display. Once the preview is ready, it is transitioned to program and the previous
+//! program scene becomes the preview, just like in OBS Studio:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use libobs_wrapper::{context::ObsContext, transitions::ObsTransitionType};
+//!
+//! # async fn example(mut context: ObsContext) -> Result<(), Box<dyn std::error::Error>> {
+//! let live = context.scene("live").await?;
+//! let next = context.scene("next").await?;
+//! let fade = context.transition("fade", ObsTransitionType::Fade).await?;
+//!
+//! let studio = context.studio_mode(&next, &live, Some(&fade)).await?;
+//! let mut events = studio.events();
+//!
+//! // ... edit the preview scene, then take it live
+//! studio.transition_to_program(Duration::from_millis(300)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::broadcast;
+
+use crate::{
+    display::{DisplaySceneSlot, ObsDisplayRef},
+    scenes::ObsSceneRef,
+    transitions::ObsTransitionRef,
+    utils::{async_sync::RwLock, ObsError},
+};
+
+/// The output channel the program scene is shown on.
+pub const PROGRAM_CHANNEL: u32 = 0;
+
+/// Events emitted by [`ObsStudioMode`], containing the name of the new scene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsStudioModeEvent {
+    PreviewSceneChanged(String),
+    ProgramSceneChanged(String),
+}
+
+#[derive(Debug)]
+struct StudioModeScenes {
+    preview: ObsSceneRef,
+    program: ObsSceneRef,
+}
+
+/// Holds a preview and a program scene. Created with
+/// [`ObsContext::studio_mode`](crate::context::ObsContext::studio_mode).
+#[derive(Debug, Clone)]
+pub struct ObsStudioMode {
+    scenes: Arc<RwLock<StudioModeScenes>>,
+    transition: Option<ObsTransitionRef>,
+    /// The scene slot of the display the preview is rendered to. The display itself is
+    /// not stored, so it can still be removed from the context.
+    display_scene: Arc<RwLock<Option<DisplaySceneSlot>>>,
+    events: broadcast::Sender<ObsStudioModeEvent>,
+}
+
+impl ObsStudioMode {
+    /// Outputs the program scene on [`PROGRAM_CHANNEL`], through the transition if given.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        preview: ObsSceneRef,
+        program: ObsSceneRef,
+        transition: Option<ObsTransitionRef>,
+    ) -> Result<Self, ObsError> {
+        match &transition {
+            Some(transition) => {
+                transition.set_scene(&program).await?;
+                transition.set_to_channel(PROGRAM_CHANNEL).await?;
+            }
+            None => program.set_to_channel(PROGRAM_CHANNEL).await?,
+        }
+
+        let (events, _) = broadcast::channel(16);
+        Ok(Self {
+            scenes: Arc::new(RwLock::new(StudioModeScenes { preview, program })),
+            transition,
+            display_scene: Arc::new(RwLock::new(None)),
+            events,
+        })
+    }
+
+    /// Subscribes to changes of the preview and program scene.
+    pub fn events(&self) -> broadcast::Receiver<ObsStudioModeEvent> {
+        self.events.subscribe()
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn preview_scene(&self) -> ObsSceneRef {
+        self.scenes.read().await.preview.clone()
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn program_scene(&self) -> ObsSceneRef {
+        self.scenes.read().await.program.clone()
+    }
+
+    pub fn transition(&self) -> Option<&ObsTransitionRef> {
+        self.transition.as_ref()
+    }
+
+    /// Renders the preview scene to the given display from now on.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_display(&self, display: &ObsDisplayRef) -> Result<(), ObsError> {
+        let preview = self.scenes.read().await.preview.clone();
+        display.set_scene(Some(&preview)).await?;
+
+        *self.display_scene.write().await = Some(display.scene_slot());
+        Ok(())
+    }
+
+    /// Changes the scene that is shown as preview, without affecting the program.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_preview_scene(&self, scene: &ObsSceneRef) -> Result<(), ObsError> {
+        // Only replaced once the display shows it, so a failure keeps the previous preview
+        let mut scenes = self.scenes.write().await;
+        self.show_preview(scene).await?;
+        scenes.preview = scene.clone();
+        drop(scenes);
+
+        let _ = self.events.send(ObsStudioModeEvent::PreviewSceneChanged(
+            scene.name().to_string(),
+        ));
+        Ok(())
+    }
+
+    /// Transitions the preview scene to program and returns once the transition has
+    /// finished. The previous program scene becomes the preview.
+    ///
+    /// The duration is ignored if there is no transition, the scene is cut to right away.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn transition_to_program(&self, duration: Duration) -> Result<(), ObsError> {
+        // Held for the whole transition, so a second call waits for this one
+        let mut scenes = self.scenes.write().await;
+        match &self.transition {
+            Some(transition) => transition.transition_to(&scenes.preview, duration).await?,
+            None => scenes.preview.set_to_channel(PROGRAM_CHANNEL).await?,
+        }
+
+        let StudioModeScenes { preview, program } = &mut *scenes;
+        std::mem::swap(preview, program);
+        self.show_preview(preview).await?;
+
+        let program = program.name().to_string();
+        let preview = preview.name().to_string();
+        drop(scenes);
+
+        let _ = self
+            .events
+            .send(ObsStudioModeEvent::ProgramSceneChanged(program));
+        let _ = self
+            .events
+            .send(ObsStudioModeEvent::PreviewSceneChanged(preview));
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn show_preview(&self, scene: &ObsSceneRef) -> Result<(), ObsError> {
+        if let Some(display_scene) = self.display_scene.read().await.as_ref() {
+            display_scene.set_scene(scene).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+//! Scene transitions, which switch the scene of an output channel smoothly.
+//!
+//! A transition is created with [`ObsContext::transition`](crate::context::ObsContext::transition)
+//! and set to an output channel in place of a scene. Afterwards scenes are switched
+//! through the transition:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use libobs_wrapper::{context::ObsContext, transitions::ObsTransitionType};
+//!
+//! # async fn example(mut context: ObsContext) -> Result<(), Box<dyn std::error::Error>> {
+//! let intro = context.scene("intro").await?;
+//! let main = context.scene("main").await?;
+//!
+//! let fade = context.transition("fade", ObsTransitionType::Fade).await?;
+//! fade.set_scene(&intro).await?;
+//! fade.set_to_channel(0).await?;
+//!
+//! // Returns once the fade has finished
+//! fade.transition_to(&main, Duration::from_millis(500)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use libobs::{obs_scene_t, obs_source_t};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    backend::backend,
+    data::ObsData,
+    run_with_obs,
+    runtime::ObsRuntime,
+    rx_recv,
+    scenes::ObsSceneRef,
+    sources::ObsSourceRef,
+    unsafe_send::Sendable,
+    utils::{async_sync::RwLock, ObsError, ObsString},
+};
+
+/// The direction swipe and slide transitions move the scenes in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ObsTransitionDirection {
+    #[default]
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl ObsTransitionDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ObsTransitionDirection::Left => "left",
+            ObsTransitionDirection::Right => "right",
+            ObsTransitionDirection::Up => "up",
+            ObsTransitionDirection::Down => "down",
+        }
+    }
+}
+
+/// The transitions of the obs-transitions plugin, together with their settings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObsTransitionType {
+    /// Switches the scene right away
+    Cut,
+    /// Crossfades between the scenes
+    Fade,
+    /// Moves the new scene over the old one
+    Swipe {
+        direction: ObsTransitionDirection,
+        /// Whether the new scene is swiped in, instead of the old scene being swiped out
+        swipe_in: bool,
+    },
+    /// Pushes the old scene out with the new one
+    Slide { direction: ObsTransitionDirection },
+    /// Plays a video over the scenes and switches them while the video covers the screen
+    Stinger {
+        /// Path to the video file
+        path: String,
+        /// When the scene is switched, relative to the start of the video
+        transition_point: Duration,
+    },
+    /// Fades to a color, then fades to the new scene
+    FadeToColor {
+        /// The color as RGBA
+        color: [u8; 4],
+        /// When the scene is switched, in percent of the duration
+        switch_point: u8,
+    },
+}
+
+impl ObsTransitionType {
+    /// The id of the transition in libobs.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ObsTransitionType::Cut => "cut_transition",
+            ObsTransitionType::Fade => "fade_transition",
+            ObsTransitionType::Swipe { .. } => "swipe_transition",
+            ObsTransitionType::Slide { .. } => "slide_transition",
+            ObsTransitionType::Stinger { .. } => "obs_stinger_transition",
+            ObsTransitionType::FadeToColor { .. } => "fade_to_color_transition",
+        }
+    }
+
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn settings(&self, runtime: &ObsRuntime) -> Result<ObsData, ObsError> {
+        let mut settings = ObsData::new(runtime.clone()).await?;
+        let mut updater = settings.bulk_update();
+
+        match self {
+            ObsTransitionType::Cut | ObsTransitionType::Fade => {}
+            ObsTransitionType::Swipe {
+                direction,
+                swipe_in,
+            } => {
+                updater.set_string_ref("direction", direction.as_str());
+                updater.set_bool_ref("swipe_in", *swipe_in);
+            }
+            ObsTransitionType::Slide { direction } => {
+                updater.set_string_ref("direction", direction.as_str());
+            }
+            ObsTransitionType::Stinger {
+                path,
+                transition_point,
+            } => {
+                updater.set_string_ref("path", path.as_str());
+                // Timing in milliseconds instead of frames
+                updater.set_int_ref("tp_type", 0);
+                updater.set_int_ref("transition_point", transition_point.as_millis() as i64);
+            }
+            ObsTransitionType::FadeToColor {
+                color,
+                switch_point,
+            } => {
+                // OBS stores colors as ABGR
+                let [r, g, b, a] = *color;
+                updater.set_int_ref("color", u32::from_le_bytes([r, g, b, a]) as i64);
+                updater.set_int_ref("switch_point", (*switch_point).min(100) as i64);
+            }
+        }
+
+        updater.update().await?;
+        Ok(settings)
+    }
+}
+
+/// A transition which switches between scenes. Created with
+/// [`ObsContext::transition`](crate::context::ObsContext::transition).
+#[derive(Debug, Clone)]
+pub struct ObsTransitionRef {
+    source: ObsSourceRef,
+    transition_type: ObsTransitionType,
+    active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+    runtime: ObsRuntime,
+}
+
+impl ObsTransitionRef {
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub(crate) async fn new(
+        name: ObsString,
+        transition_type: ObsTransitionType,
+        active_scene: Arc<RwLock<Option<Sendable<*mut obs_scene_t>>>>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let settings = transition_type.settings(&runtime).await?;
+        let source = ObsSourceRef::new(
+            transition_type.id(),
+            name,
+            Some(settings),
+            None,
+            runtime.clone(),
+        )
+        .await?;
+
+        Ok(Self {
+            source,
+            transition_type,
+            active_scene,
+            runtime,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.source.name()
+    }
+
+    pub fn transition_type(&self) -> &ObsTransitionType {
+        &self.transition_type
+    }
+
+    /// The source backing this transition, e.g. to listen to its signals.
+    pub fn source(&self) -> &ObsSourceRef {
+        &self.source
+    }
+
+    pub fn as_ptr(&self) -> Sendable<*mut obs_source_t> {
+        self.source.source.clone()
+    }
+
+    /// Outputs this transition (and with it the scene it shows) on the given channel.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_to_channel(&self, channel: u32) -> Result<(), ObsError> {
+        let transition_ptr = self.as_ptr();
+        run_with_obs!(self.runtime, (transition_ptr), move || unsafe {
+            backend().obs_set_output_source(channel, transition_ptr);
+        })
+        .await
+    }
+
+    /// Switches to the scene right away, without transitioning.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn set_scene(&self, scene: &ObsSceneRef) -> Result<(), ObsError> {
+        let transition_ptr = self.as_ptr();
+        let scene_source_ptr = scene.get_scene_source_ptr().await?;
+        run_with_obs!(
+            self.runtime,
+            (transition_ptr, scene_source_ptr),
+            move || unsafe {
+                backend().obs_transition_set(transition_ptr, scene_source_ptr);
+            }
+        )
+        .await?;
+
+        *self.active_scene.write().await = Some(scene.as_ptr());
+        Ok(())
+    }
+
+    /// Starts transitioning to the scene and returns right away. The source of this
+    /// transition emits `transition_start` and `transition_stop` while it runs.
+    ///
+    /// The duration is ignored by transitions with a fixed duration, like cut and stinger.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn start(&self, scene: &ObsSceneRef, duration: Duration) -> Result<(), ObsError> {
+        let transition_ptr = self.as_ptr();
+        let scene_source_ptr = scene.get_scene_source_ptr().await?;
+        let duration_ms = duration.as_millis().min(u32::MAX as u128) as u32;
+
+        let started = run_with_obs!(
+            self.runtime,
+            (transition_ptr, scene_source_ptr),
+            move || unsafe {
+                backend().obs_transition_start(
+                    transition_ptr,
+                    libobs::obs_transition_mode_OBS_TRANSITION_MODE_AUTO,
+                    duration_ms,
+                    scene_source_ptr,
+                )
+            }
+        )
+        .await?;
+
+        if !started {
+            return Err(ObsError::TransitionFailure(format!(
+                "Could not start transition {} to scene {}",
+                self.name(),
+                scene.name()
+            )));
+        }
+
+        *self.active_scene.write().await = Some(scene.as_ptr());
+        Ok(())
+    }
+
+    /// Transitions to the scene and returns once the transition has finished.
+    ///
+    /// Transitions only run while they are rendered, so this fails with
+    /// [`ObsError::TransitionFailure`] if the transition isn't set to an output channel.
+    /// It also fails while the transition is still running, use [`Self::force_stop`] first
+    /// to interrupt it.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn transition_to(
+        &self,
+        scene: &ObsSceneRef,
+        duration: Duration,
+    ) -> Result<(), ObsError> {
+        if !self.is_on_channel().await? {
+            return Err(ObsError::TransitionFailure(format!(
+                "Transition {} is not set to an output channel",
+                self.name()
+            )));
+        }
+
+        // The stop of the running transition would end the wait early
+        if self.is_running().await? {
+            return Err(ObsError::TransitionFailure(format!(
+                "Transition {} is still running",
+                self.name()
+            )));
+        }
+
+        // Subscribing first, short transitions might already be done once `start` returns
+        let mut rx = self.source.signal_manager().on_transition_stop().await?;
+        self.start(scene, duration).await?;
+
+        loop {
+            match rx_recv!(rx) {
+                Ok(_) => return Ok(()),
+                // Only the oldest signals are dropped, the latest stop is still received
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Err(ObsError::NoSenderError),
+            }
+        }
+    }
+
+    /// Finishes a running transition right away.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn force_stop(&self) -> Result<(), ObsError> {
+        let transition_ptr = self.as_ptr();
+        run_with_obs!(self.runtime, (transition_ptr), move || unsafe {
+            backend().obs_transition_force_stop(transition_ptr);
+        })
+        .await
+    }
+
+    /// Whether the transition is currently switching between two scenes.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    pub async fn is_running(&self) -> Result<bool, ObsError> {
+        let transition_ptr = self.as_ptr();
+        run_with_obs!(self.runtime, (transition_ptr), move || unsafe {
+            backend().obs_transition_get_time(transition_ptr) < 1.0
+        })
+        .await
+    }
+
+    /// Whether this transition is set to any output channel.
+    #[cfg_attr(feature = "blocking", remove_async_await::remove_async_await)]
+    async fn is_on_channel(&self) -> Result<bool, ObsError> {
+        let transition_ptr = self.as_ptr();
+        run_with_obs!(self.runtime, (transition_ptr), move || unsafe {
+            let backend = backend();
+            (0..libobs::MAX_CHANNELS).any(|channel| {
+                let source = backend.obs_get_output_source(channel);
+                if source.is_null() {
+                    return false;
+                }
+
+                backend.obs_source_release(source);
+                source == transition_ptr
+            })
+        })
+        .await
+    }
+
+    /// Whether there are other references to this transition which keep it alive.
+    pub(crate) fn is_shared(&self) -> bool {
+        self.source.is_shared()
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use crate::enums::ObsResetVideoStatus;
 
@@ -36,6 +36,7 @@ pub enum ObsError {
     OutputPauseFailure(Option<String>),
     OutputNotFound,
     SourceNotFound,
+    SceneNotFound,
 
     /// Native error from the Windows API when creating a display
     DisplayCreationError(String),
@@ -49,6 +50,28 @@ pub enum ObsError {
     /// Couldn't get the sender of the signal
     NoSenderError,
     NoAvailableEncoders,
+    /// A command didn't finish on the OBS thread before its deadline.
+    /// Contains the name of the command and the timeout that was used.
+    CommandTimeout(String, Duration),
+    /// libobs has no video output, so `obs_reset_video` hasn't succeeded yet.
+    VideoNotInitialized,
+    /// libobs has no audio output, so `obs_reset_audio2` hasn't succeeded yet.
+    AudioNotInitialized,
+    /// The given audio mix (track) index is not below `MAX_AUDIO_MIXES`.
+    InvalidAudioMix(usize),
+    /// A screenshot could not be rendered, read back or saved.
+    ScreenshotFailure(String),
+    /// The scene with the given name can't be nested, because it is the scene it should
+    /// be added to or already contains it.
+    SceneCycle(String),
+    /// The source with the given name was added to the scene several times, so it is
+    /// unclear which scene item is meant. Use the [`ObsSceneItemRef`](crate::scenes::ObsSceneItemRef)
+    /// of the placement instead.
+    AmbiguousSceneItem(String),
+    /// A transition could not be started, e.g. because the target isn't a valid source.
+    TransitionFailure(String),
+    /// A volume meter or fader could not be attached to the source with the given name.
+    AudioControlAttachFailure(String),
 }
 
 impl Display for ObsError {
@@ -69,6 +92,7 @@ impl Display for ObsError {
             ObsError::DisplayCreationError(e) => write!(f, "Native error from the Windows API when creating a display: {:?}", e),
             ObsError::OutputSaveBufferFailure(e) => write!(f, "Couldn't save output buffer: {:?}", e),
             ObsError::SourceNotFound => write!(f, "Source not found."),
+            ObsError::SceneNotFound => write!(f, "Scene not found."),
             ObsError::BootstrapperFailure(error) => match error {
                                         ObsBootstrapError::GeneralError(e) => write!(f, "Bootstrapper error: {:?}", e),
                                         ObsBootstrapError::DownloadError(e) => write!(f, "Bootstrapper download error: {:?}", e),
@@ -79,8 +103,28 @@ impl Display for ObsError {
             ObsError::NoSenderError => write!(f, "Couldn't get the sender of the signal."),
             ObsError::NoAvailableEncoders => write!(f, "No available encoders found."),
             ObsError::OutputPauseFailure(s) => write!(f, "Output failed to pause. Error is {:?}", s),
+            ObsError::CommandTimeout(command, timeout) => write!(f, "Command {:?} did not finish on the OBS thread within {:?}", command, timeout),
+            ObsError::VideoNotInitialized => write!(f, "The video output of OBS is not initialized."),
+            ObsError::AudioNotInitialized => write!(f, "The audio output of OBS is not initialized."),
+            ObsError::InvalidAudioMix(mix) => write!(f, "Invalid audio mix index {}, there are only {} mixes.", mix, libobs::MAX_AUDIO_MIXES),
+            ObsError::ScreenshotFailure(e) => write!(f, "Failed to take screenshot: {}", e),
+            ObsError::SceneCycle(name) => write!(f, "Scene {} can't be nested, as it would end up containing itself.", name),
+            ObsError::AmbiguousSceneItem(name) => write!(f, "Source {} was added to the scene several times, use its scene item instead.", name),
+            ObsError::TransitionFailure(e) => write!(f, "Transition failed: {}", e),
+            ObsError::AudioControlAttachFailure(name) => write!(f, "Couldn't attach audio control to source {}.", name),
         }
     }
 }
 
 impl std::error::Error for ObsError {}
+
+impl ObsError {
+    /// Converts an error returned by the runtime into an `ObsError`, keeping
+    /// errors like `CommandTimeout` intact.
+    pub(crate) fn from_invocation_error(e: anyhow::Error) -> Self {
+        match e.downcast::<ObsError>() {
+            Ok(e) => e,
+            Err(e) => ObsError::InvocationError(e.to_string()),
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use crate::{
-    context::{ObsContext, ObsContextReturn}, data::{audio::ObsAudioInfo, video::ObsVideoInfo}, logger::{ConsoleLogger, ObsLogger}, utils::{ObsError, ObsPath, ObsString}
+    backend::{LibObsBackend, ObsBackend}, context::{ObsContext, ObsContextReturn}, data::{audio::ObsAudioInfo, video::ObsVideoInfo}, logger::{ConsoleLogger, ObsLogger}, runtime::ObsWatchdogOptions, utils::{ObsError, ObsPath, ObsString}
 };
 
 /// Contains information to start a libobs context.
@@ -16,6 +18,8 @@ pub struct StartupInfo {
     pub(crate) obs_audio_info: ObsAudioInfo,
     // Option because logger is taken when creating
     pub(crate) logger: Option<Box<dyn ObsLogger + Sync + Send>>,
+    pub(crate) backend: Arc<dyn ObsBackend>,
+    pub(crate) watchdog_options: ObsWatchdogOptions,
 }
 
 impl StartupInfo {
@@ -42,6 +46,19 @@ impl StartupInfo {
         self
     }
 
+    /// Sets the backend every libobs call of this context is routed through.
+    /// Defaults to [`LibObsBackend`], which calls libobs directly.
+    pub fn set_backend(mut self, backend: impl ObsBackend + 'static) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Configures command deadlines and stall detection of the OBS thread.
+    pub fn set_watchdog_options(mut self, options: ObsWatchdogOptions) -> Self {
+        self.watchdog_options = options;
+        self
+    }
+
     #[cfg(feature = "bootstrapper")]
     pub fn enable_bootstrapper<T>(
         mut self,
@@ -69,6 +86,8 @@ impl Default for StartupInfo {
             obs_video_info: ObsVideoInfo::default(),
             obs_audio_info: ObsAudioInfo::default(),
             logger: Some(Box::new(ConsoleLogger::new())),
+            backend: Arc::new(LibObsBackend),
+            watchdog_options: ObsWatchdogOptions::default(),
             #[cfg(feature = "bootstrapper")]
             bootstrap_handler: None,
 
@@ -1,5 +1,6 @@
 mod error;
 mod info;
+#[cfg(windows)]
 pub(crate) mod initialization;
 mod obs_string;
 mod path;
@@ -15,7 +16,7 @@ pub use obs_string::*;
 pub use path::*;
 
 use crate::{
-    enums::ObsLogLevel, logger::internal_log_global, run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable
+    backend::backend, enums::ObsLogLevel, logger::internal_log_global, run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable
 };
 
 #[derive(Debug)]
@@ -29,9 +30,10 @@ pub struct ObsModules {
 
 impl ObsModules {
     pub fn add_paths(paths: &StartupPaths) -> Self {
+        let backend = backend();
         unsafe {
-            libobs::obs_add_data_path(paths.libobs_data_path().as_ptr().0);
-            libobs::obs_add_module_path(
+            backend.obs_add_data_path(paths.libobs_data_path().as_ptr().0);
+            backend.obs_add_module_path(
                 paths.plugin_bin_path().as_ptr().0,
                 paths.plugin_data_path().as_ptr().0,
             );
@@ -45,23 +47,24 @@ impl ObsModules {
     }
 
     pub fn load_modules(&mut self) {
+        let backend = backend();
         unsafe {
             let mut failure_info: obs_module_failure_info = std::mem::zeroed();
             internal_log_global(
                 ObsLogLevel::Info,
                 "---------------------------------".to_string(),
             );
-            libobs::obs_load_all_modules2(&mut failure_info);
+            backend.obs_load_all_modules2(&mut failure_info);
             internal_log_global(
                 ObsLogLevel::Info,
                 "---------------------------------".to_string(),
             );
-            libobs::obs_log_loaded_modules();
+            backend.obs_log_loaded_modules();
             internal_log_global(
                 ObsLogLevel::Info,
                 "---------------------------------".to_string(),
             );
-            libobs::obs_post_load_modules();
+            backend.obs_post_load_modules();
             self.info = Some(Sendable(failure_info));
         }
 
@@ -91,27 +94,39 @@ impl ObsModules {
 impl Drop for ObsModules {
     fn drop(&mut self) {
         let paths = self.paths.clone();
-        let runtime = self.runtime.take().unwrap();
+        // The runtime is not set if initialization failed, OBS is shut down right away then
+        let Some(runtime) = self.runtime.take() else {
+            return;
+        };
+
+        if runtime.is_shut_down() {
+            return;
+        }
 
         #[cfg(not(feature="blocking"))]
         let r = futures::executor::block_on(async {
             return run_with_obs!(runtime, move || unsafe {
-                libobs::obs_remove_data_path(paths.libobs_data_path().as_ptr().0);
+                backend().obs_remove_data_path(paths.libobs_data_path().as_ptr().0);
             }).await
         });
 
         #[cfg(feature="blocking")]
         let r = run_with_obs!(runtime, move || unsafe {
-            libobs::obs_remove_data_path(paths.libobs_data_path().as_ptr().0);
+            backend().obs_remove_data_path(paths.libobs_data_path().as_ptr().0);
         });
 
-        if std::thread::panicking() {
-            return;
+        if let Err(e) = r {
+            log::error!("Failed to remove the libobs data path: {:?}", e);
         }
-
-        r.unwrap();
     }
 }
 
+/// The `va_list` type as it is passed to the log and crash handlers of libobs.
+#[cfg(windows)]
+pub(crate) type VaList = libobs::va_list;
+/// The `va_list` type as it is passed to the log and crash handlers of libobs.
+#[cfg(not(windows))]
+pub(crate) type VaList = *mut libobs::__va_list_tag;
+
 pub const ENCODER_HIDE_FLAGS: u32 =
     libobs::OBS_ENCODER_CAP_DEPRECATED | libobs::OBS_ENCODER_CAP_INTERNAL;
\ No newline at end of file
@@ -0,0 +1,116 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use libobs_wrapper::enums::ObsMonitoringType;
+    use libobs_wrapper::sources::{db_to_mul, mul_to_db};
+    use libobs_wrapper::utils::{ObsError, SourceInfo};
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn audio_controls_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let mut scene = context.scene("main").await.unwrap();
+            let mic = scene
+                .add_source(SourceInfo::new("wasapi_input_capture", "mic", None, None))
+                .await
+                .unwrap();
+
+            let signals = mic.signal_manager();
+            let mut volume_rx = signals.on_volume().await.unwrap();
+            let mut mute_rx = signals.on_mute().await.unwrap();
+            let mut balance_rx = signals.on_audio_balance().await.unwrap();
+            let mut sync_rx = signals.on_audio_sync().await.unwrap();
+            let mut mixers_rx = signals.on_audio_mixers().await.unwrap();
+            let mut ptt_rx = signals.on_push_to_talk_changed().await.unwrap();
+
+            assert_eq!(mic.volume().await.unwrap(), 1.0);
+            assert_eq!(mic.volume_db().await.unwrap(), 0.0);
+
+            mic.set_volume(0.5).await.unwrap();
+            assert_eq!(backend.source_audio("mic").unwrap().volume, 0.5);
+            assert_eq!(volume_rx.try_recv().unwrap().volume, 0.5);
+
+            mic.set_volume_db(-20.0).await.unwrap();
+            assert!((mic.volume().await.unwrap() - 0.1).abs() < 1e-6);
+            assert!((mic.volume_db().await.unwrap() + 20.0).abs() < 1e-4);
+            mic.set_volume_db(f32::NEG_INFINITY).await.unwrap();
+            assert_eq!(mic.volume().await.unwrap(), 0.0);
+            assert_eq!(mul_to_db(0.0), f32::NEG_INFINITY);
+            assert_eq!(db_to_mul(0.0), 1.0);
+
+            mic.set_muted(true).await.unwrap();
+            assert!(mic.muted().await.unwrap());
+            assert!(mute_rx.try_recv().unwrap().muted);
+
+            mic.set_balance(2.0).await.unwrap();
+            assert_eq!(mic.balance().await.unwrap(), 1.0);
+            assert_eq!(balance_rx.try_recv().unwrap().balance, 1.0);
+
+            mic.set_sync_offset(-50_000_000).await.unwrap();
+            assert_eq!(mic.sync_offset().await.unwrap(), -50_000_000);
+            assert_eq!(sync_rx.try_recv().unwrap().offset, -50_000_000);
+
+            mic.set_audio_mixers(0b000011).await.unwrap();
+            assert_eq!(mixers_rx.try_recv().unwrap().mixers, 0b000011);
+            mic.set_audio_track_enabled(0, false).await.unwrap();
+            mic.set_audio_track_enabled(5, true).await.unwrap();
+            assert_eq!(mic.audio_mixers().await.unwrap(), 0b100010);
+            assert!(!mic.audio_track_enabled(0).await.unwrap());
+            assert!(mic.audio_track_enabled(5).await.unwrap());
+            assert!(matches!(
+                mic.set_audio_track_enabled(6, true).await,
+                Err(ObsError::InvalidAudioMix(6))
+            ));
+
+            mic.set_push_to_talk(true).await.unwrap();
+            mic.set_push_to_talk_delay(Duration::from_millis(300))
+                .await
+                .unwrap();
+            assert!(mic.push_to_talk().await.unwrap());
+            assert!(ptt_rx.try_recv().unwrap().enabled);
+            assert_eq!(
+                mic.push_to_talk_delay().await.unwrap(),
+                Duration::from_millis(300)
+            );
+
+            mic.set_push_to_mute(true).await.unwrap();
+            mic.set_push_to_mute_delay(Duration::from_millis(150))
+                .await
+                .unwrap();
+            assert!(mic.push_to_mute().await.unwrap());
+            assert_eq!(
+                mic.push_to_mute_delay().await.unwrap(),
+                Duration::from_millis(150)
+            );
+
+            assert_eq!(
+                mic.monitoring_type().await.unwrap(),
+                ObsMonitoringType::None
+            );
+            mic.set_monitoring_type(ObsMonitoringType::MonitorAndOutput)
+                .await
+                .unwrap();
+            assert_eq!(
+                mic.monitoring_type().await.unwrap(),
+                ObsMonitoringType::MonitorAndOutput
+            );
+
+            let audio = backend.source_audio("mic").unwrap();
+            assert!(audio.muted);
+            assert!(audio.push_to_mute);
+            assert_eq!(audio.push_to_mute_delay, 150);
+            assert_eq!(audio.monitoring_type, ObsMonitoringType::MonitorAndOutput);
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+    }
+}
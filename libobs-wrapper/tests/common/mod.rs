@@ -0,0 +1,42 @@
+//! Helpers shared by the tests that run on the fake backend.
+#![allow(dead_code)]
+
+use libobs_wrapper::backend::fake::FakeObsBackend;
+use libobs_wrapper::context::{ObsContext, ObsShutdownReport};
+use libobs_wrapper::utils::StartupInfo;
+
+/// Starts a context on a new fake backend.
+pub async fn fake_context() -> (FakeObsBackend, ObsContext) {
+    fake_context_with(|startup_info| startup_info).await
+}
+
+/// Starts a context on a new fake backend, `configure` can adjust the startup info.
+pub async fn fake_context_with(
+    configure: impl FnOnce(StartupInfo) -> StartupInfo,
+) -> (FakeObsBackend, ObsContext) {
+    let backend = FakeObsBackend::new();
+    let startup_info = configure(StartupInfo::default().set_backend(backend.clone()));
+
+    (backend, start_context(startup_info).await)
+}
+
+/// Starts another context on an existing fake backend, e.g. after a shutdown.
+pub async fn restart_fake_context(backend: &FakeObsBackend) -> ObsContext {
+    start_context(StartupInfo::default().set_backend(backend.clone())).await
+}
+
+/// Starts a context, panicking if the bootstrapper asks for a restart.
+pub async fn start_context(startup_info: StartupInfo) -> ObsContext {
+    let context = ObsContext::new(startup_info).await.unwrap();
+    #[cfg(feature = "bootstrapper")]
+    let context = match context {
+        libobs_wrapper::context::ObsContextReturn::Done(c) => c,
+        libobs_wrapper::context::ObsContextReturn::Restart => panic!("Restart not supported"),
+    };
+
+    context
+}
+
+pub fn assert_clean_shutdown(report: ObsShutdownReport) {
+    assert!(report.is_clean(), "{:?}", report);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::data::ObsDataChange;
+    use libobs_wrapper::utils::SourceInfo;
+    use serde_json::json;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn data_updater_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let mut scene = context.scene("main").await.unwrap();
+            let mut settings = context.data().await.unwrap();
+            settings
+                .bulk_update()
+                .set_obj(
+                    "font",
+                    vec![
+                        ObsDataChange::String("face".into(), "Sans Serif".into()),
+                        ObsDataChange::Int("size".into(), 48),
+                    ],
+                )
+                .set_array(
+                    "files",
+                    vec![
+                        vec![ObsDataChange::String("value".into(), "a.png".into())],
+                        vec![
+                            ObsDataChange::String("value".into(), "b.png".into()),
+                            ObsDataChange::Bool("hidden".into(), true),
+                        ],
+                    ],
+                )
+                .update()
+                .await
+                .unwrap();
+
+            scene
+                .add_source(SourceInfo::new(
+                    "text_ft2_source_v2",
+                    "text",
+                    Some(settings),
+                    None,
+                ))
+                .await
+                .unwrap();
+
+            let fake_source = backend.source("text").unwrap();
+            assert_eq!(
+                fake_source.settings["font"],
+                json!({ "face": "Sans Serif", "size": 48 })
+            );
+            assert_eq!(
+                fake_source.settings["files"],
+                json!([{ "value": "a.png" }, { "value": "b.png", "hidden": true }])
+            );
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+    }
+}
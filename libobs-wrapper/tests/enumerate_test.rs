@@ -0,0 +1,102 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::transitions::ObsTransitionType;
+    use libobs_wrapper::utils::{FilterInfo, SourceInfo};
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn enumerate_test() {
+        let (backend, mut context) = fake_context().await;
+
+        // Created by a plugin, the context doesn't know about these
+        backend.create_source("wasapi_output_capture", "desktop audio");
+        backend.create_source("scene", "plugin scene");
+
+        {
+            let mut main = context.scene("main").await.unwrap();
+            let camera = main
+                .add_source(SourceInfo::new("color_source", "camera", None, None))
+                .await
+                .unwrap();
+            let filter = context
+                .obs_filter(FilterInfo::new("color_filter", "grade", None, None))
+                .await
+                .unwrap();
+            main.add_source_filter(&camera, &filter).await.unwrap();
+            context
+                .transition("fade", ObsTransitionType::Fade)
+                .await
+                .unwrap();
+
+            // Only inputs are listed
+            let sources = context.enum_sources().await.unwrap();
+            let names = sources.iter().map(|s| s.name()).collect::<Vec<_>>();
+            assert_eq!(names, ["desktop audio", "camera"]);
+            assert_eq!(sources[0].id(), "wasapi_output_capture");
+
+            let scenes = context.enum_scenes().await.unwrap();
+            let names = scenes
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["plugin scene", "main"]);
+
+            // Adopted scenes can be used like the ones created through the wrapper
+            let mut plugin_scene = scenes[0].clone();
+            plugin_scene
+                .add_source(SourceInfo::new("image_source", "logo", None, None))
+                .await
+                .unwrap();
+            assert_eq!(backend.scene("plugin scene").unwrap().items.len(), 1);
+
+            let audio = context
+                .get_source_by_name("desktop audio")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(audio.id(), "wasapi_output_capture");
+
+            let uuid = audio.uuid().await.unwrap();
+            let by_uuid = context.get_source_by_uuid(&uuid).await.unwrap().unwrap();
+            assert_eq!(by_uuid.name(), "desktop audio");
+
+            let scene_source = context.get_source_by_name("main").await.unwrap().unwrap();
+            assert_eq!(scene_source.id(), "scene");
+
+            // Looked up handles share the signals of the handles that already exist
+            let mut volume_rx = camera.signal_manager().on_volume().await.unwrap();
+            let lookup = context.get_source_by_name("camera").await.unwrap().unwrap();
+            let mut lookup_rx = lookup.signal_manager().on_volume().await.unwrap();
+            drop(lookup);
+            drop(sources);
+
+            camera.set_volume(0.5).await.unwrap();
+            assert_eq!(volume_rx.try_recv().unwrap().volume, 0.5);
+            assert_eq!(lookup_rx.try_recv().unwrap().volume, 0.5);
+            assert!(camera.signal_manager().on_volume().await.is_ok());
+
+            assert!(context
+                .get_source_by_name("missing")
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        // The handles only released their own references
+        assert!(backend.source("desktop audio").is_some());
+        assert!(backend.scene("plugin scene").is_some());
+        assert!(backend.release_source("desktop audio"));
+        assert!(backend.release_source("plugin scene"));
+        assert!(backend.source("desktop audio").is_none());
+        assert!(backend.scene("plugin scene").is_none());
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.scenes().is_empty());
+    }
+}
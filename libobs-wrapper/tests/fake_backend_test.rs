@@ -0,0 +1,103 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::utils::{AudioEncoderInfo, OutputInfo, SourceInfo, VideoEncoderInfo};
+    use libobs_wrapper::Vec2;
+
+    use crate::common::fake_context;
+
+    #[tokio::test]
+    pub async fn fake_backend_test() {
+        let (backend, mut context) = fake_context().await;
+
+        assert!(backend.is_started());
+
+        let mut scene = context.scene("main").await.unwrap();
+
+        let mut source_data = context.data().await.unwrap();
+        source_data
+            .bulk_update()
+            .set_string("window", "test")
+            .set_bool("capture_cursor", true)
+            .update()
+            .await
+            .unwrap();
+
+        let source_info = SourceInfo::new("game_capture", "video_source", Some(source_data), None);
+        let source = scene.add_source(source_info).await.unwrap();
+        scene
+            .set_source_position(&source, Vec2::new(10.0, 20.0))
+            .await
+            .unwrap();
+        scene.set_to_channel(0).await.unwrap();
+
+        let fake_source = backend.source("video_source").unwrap();
+        assert_eq!(fake_source.id, "game_capture");
+        assert_eq!(fake_source.settings["window"], "test");
+        assert_eq!(fake_source.settings["capture_cursor"], true);
+
+        let fake_scene = backend.scene("main").unwrap();
+        assert_eq!(fake_scene.items.len(), 1);
+        assert_eq!(fake_scene.items[0].source, "video_source");
+        assert_eq!(
+            format!("{:?}", fake_scene.items[0].position),
+            format!("{:?}", Vec2::new(10.0, 20.0))
+        );
+        assert_eq!(backend.channel_source(0).as_deref(), Some("main"));
+
+        let output_info = OutputInfo::new("ffmpeg_muxer", "output", None, None);
+        let mut output = context.output(output_info).await.unwrap();
+
+        let video_info = VideoEncoderInfo::new("obs_x264", "video_encoder", None, None);
+        let video_handler = context.get_video_ptr().await.unwrap();
+        output
+            .video_encoder(video_info, video_handler)
+            .await
+            .unwrap();
+
+        let audio_info = AudioEncoderInfo::new("ffmpeg_aac", "audio_encoder", None, None);
+        let audio_handler = context.get_audio_ptr().await.unwrap();
+        output
+            .audio_encoder(audio_info, 0, audio_handler)
+            .await
+            .unwrap();
+
+        output.start().await.unwrap();
+
+        let fake_output = backend.output("output").unwrap();
+        assert!(fake_output.active);
+        assert_eq!(fake_output.video_encoder.as_deref(), Some("video_encoder"));
+        assert_eq!(
+            fake_output.audio_encoders.get(&0).map(|x| x.as_str()),
+            Some("audio_encoder")
+        );
+
+        output.stop().await.unwrap();
+        assert!(!backend.output("output").unwrap().active);
+
+        let signals = backend
+            .emitted_signals()
+            .into_iter()
+            .filter(|s| s.object == "output")
+            .map(|s| s.signal)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            signals,
+            [
+                "starting",
+                "activate",
+                "start",
+                "stopping",
+                "deactivate",
+                "stop"
+            ]
+        );
+
+        backend.fail_next_output_start("no encoder available");
+        assert!(output.start().await.is_err());
+    }
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::backend::fake::{FakeObsBackend, FakeSceneItem};
+    use libobs_wrapper::data::video::ObsVideoInfoBuilder;
+    use libobs_wrapper::enums::{ObsAlignment, ObsBoundsType};
+    use libobs_wrapper::scenes::{
+        arrange_grid, arrange_picture_in_picture, ObsLayoutRect, ObsSceneItemRef,
+    };
+    use libobs_wrapper::utils::SourceInfo;
+    use libobs_wrapper::Vec2;
+
+    use crate::common::{assert_clean_shutdown, fake_context_with};
+
+    fn fake_item(backend: &FakeObsBackend, source: &str) -> FakeSceneItem {
+        backend
+            .scene("main")
+            .unwrap()
+            .items
+            .into_iter()
+            .find(|i| i.source == source)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    pub async fn layout_test() {
+        let video_info = ObsVideoInfoBuilder::new()
+            .base_width(1920)
+            .base_height(1080)
+            .output_width(1920)
+            .output_height(1080)
+            .build();
+        let (backend, mut context) =
+            fake_context_with(|startup_info| startup_info.set_video_info(video_info)).await;
+
+        {
+            let mut scene = context.scene("main").await.unwrap();
+            let mut items: Vec<ObsSceneItemRef> = Vec::new();
+            for name in ["a", "b", "c", "d", "e"] {
+                let source = scene
+                    .add_source(SourceInfo::new("color_source", name, None, None))
+                    .await
+                    .unwrap();
+                items.push(scene.scene_item(&source).await.unwrap());
+            }
+
+            items[0].fit_to_canvas().await.unwrap();
+            let a = fake_item(&backend, "a");
+            assert_eq!(a.position, Vec2::new(0.0, 0.0));
+            assert_eq!(a.bounds, Vec2::new(1920.0, 1080.0));
+            assert_eq!(a.bounds_type, ObsBoundsType::ScaleInner);
+            assert_eq!(a.bounds_alignment, ObsAlignment::CENTER);
+            assert!(!a.crop_to_bounds);
+
+            items[0].fill_canvas().await.unwrap();
+            let a = fake_item(&backend, "a");
+            assert_eq!(a.bounds_type, ObsBoundsType::ScaleOuter);
+            assert!(a.crop_to_bounds);
+
+            items[0].stretch_to_canvas().await.unwrap();
+            assert_eq!(fake_item(&backend, "a").bounds_type, ObsBoundsType::Stretch);
+
+            // Centering keeps the bounds
+            items[0].center_on_canvas().await.unwrap();
+            let a = fake_item(&backend, "a");
+            assert_eq!(a.position, Vec2::new(960.0, 540.0));
+            assert_eq!(a.alignment, ObsAlignment::CENTER);
+            assert_eq!(a.bounds, Vec2::new(1920.0, 1080.0));
+
+            items[1]
+                .place_in_rect(
+                    ObsLayoutRect::new(100.0, 200.0, 300.0, 400.0),
+                    ObsBoundsType::ScaleInner,
+                    ObsAlignment::BOTTOM_RIGHT,
+                )
+                .await
+                .unwrap();
+            let b = fake_item(&backend, "b");
+            assert_eq!(b.position, Vec2::new(100.0, 200.0));
+            assert_eq!(b.alignment, ObsAlignment::TOP_LEFT);
+            assert_eq!(b.bounds, Vec2::new(300.0, 400.0));
+            assert_eq!(b.bounds_alignment, ObsAlignment::BOTTOM_RIGHT);
+
+            // Five items need a 3x2 grid
+            arrange_grid(&items, 10.0).await.unwrap();
+            let e = fake_item(&backend, "e");
+            assert_eq!(e.position, Vec2::new(645.0, 545.0));
+            assert_eq!(e.bounds, Vec2::new(630.0, 530.0));
+            assert_eq!(e.bounds_type, ObsBoundsType::ScaleInner);
+
+            arrange_picture_in_picture(
+                &items[0],
+                &items[1..3],
+                ObsAlignment::BOTTOM_RIGHT,
+                0.25,
+                20.0,
+            )
+            .await
+            .unwrap();
+            assert_eq!(fake_item(&backend, "a").bounds, Vec2::new(1920.0, 1080.0));
+            let b = fake_item(&backend, "b");
+            assert_eq!(b.position, Vec2::new(1420.0, 790.0));
+            assert_eq!(b.bounds, Vec2::new(480.0, 270.0));
+            assert_eq!(b.bounds_alignment, ObsAlignment::BOTTOM_RIGHT);
+            assert_eq!(fake_item(&backend, "c").position, Vec2::new(1420.0, 500.0));
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use libobs_wrapper::backend::fake::FakeSourceMedia;
+    use libobs_wrapper::enums::ObsMediaState;
+    use libobs_wrapper::utils::SourceInfo;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn media_controller_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let mut scene = context.scene("main").await.unwrap();
+            let video = scene
+                .add_source(SourceInfo::new("ffmpeg_source", "video", None, None))
+                .await
+                .unwrap();
+
+            let media = video.media_controller();
+            assert_eq!(media.state().await.unwrap(), ObsMediaState::None);
+            assert_eq!(media.duration().await.unwrap(), Duration::ZERO);
+
+            // Pretend the file has been opened
+            backend.set_source_media(
+                "video",
+                FakeSourceMedia {
+                    state: ObsMediaState::Playing,
+                    time: 0,
+                    duration: 60_000,
+                },
+            );
+            assert_eq!(media.duration().await.unwrap(), Duration::from_secs(60));
+
+            let signals = video.signal_manager();
+            let mut pause_rx = signals.on_media_pause().await.unwrap();
+            let mut play_rx = signals.on_media_play().await.unwrap();
+            let mut stopped_rx = signals.on_media_stopped().await.unwrap();
+            let mut restart_rx = signals.on_media_restart().await.unwrap();
+            let mut next_rx = signals.on_media_next().await.unwrap();
+            let mut previous_rx = signals.on_media_previous().await.unwrap();
+            let mut ended_rx = signals.on_media_ended().await.unwrap();
+
+            media.seek(Duration::from_millis(12_500)).await.unwrap();
+            assert_eq!(
+                media.position().await.unwrap(),
+                Duration::from_millis(12_500)
+            );
+            // Seeking past the end stops at the end
+            media.seek(Duration::from_secs(120)).await.unwrap();
+            assert_eq!(media.position().await.unwrap(), Duration::from_secs(60));
+
+            media.pause().await.unwrap();
+            assert_eq!(media.state().await.unwrap(), ObsMediaState::Paused);
+            assert!(pause_rx.try_recv().is_ok());
+
+            media.play().await.unwrap();
+            assert_eq!(media.state().await.unwrap(), ObsMediaState::Playing);
+            assert!(play_rx.try_recv().is_ok());
+
+            media.restart().await.unwrap();
+            assert_eq!(media.position().await.unwrap(), Duration::ZERO);
+            assert!(restart_rx.try_recv().is_ok());
+
+            media.next().await.unwrap();
+            media.previous().await.unwrap();
+            assert!(next_rx.try_recv().is_ok());
+            assert!(previous_rx.try_recv().is_ok());
+
+            assert!(backend.end_media("video"));
+            assert_eq!(media.state().await.unwrap(), ObsMediaState::Ended);
+            assert!(ended_rx.try_recv().is_ok());
+
+            media.stop().await.unwrap();
+            assert_eq!(media.state().await.unwrap(), ObsMediaState::Stopped);
+            assert!(stopped_rx.try_recv().is_ok());
+            assert_eq!(
+                backend.source_media("video").unwrap(),
+                FakeSourceMedia {
+                    state: ObsMediaState::Stopped,
+                    time: 0,
+                    duration: 60_000,
+                }
+            );
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+    }
+}
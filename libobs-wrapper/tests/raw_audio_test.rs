@@ -0,0 +1,94 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use libobs_wrapper::enums::{ObsAudioFormat, ObsSpeakerLayout};
+    use libobs_wrapper::media::{ObsAudioConversion, ObsDropPolicy, ObsRawAudioOptions};
+    use libobs_wrapper::utils::ObsError;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    fn f32_bytes(samples: &[f32]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_ne_bytes()).collect()
+    }
+
+    fn i16_bytes(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_ne_bytes()).collect()
+    }
+
+    #[tokio::test]
+    pub async fn raw_audio_test() {
+        let (backend, context) = fake_context().await;
+
+        // By default the audio of the first track is handed out as planar floats
+        let mut planar = context
+            .raw_audio_stream(ObsRawAudioOptions::new())
+            .await
+            .unwrap();
+
+        let left = f32_bytes(&[0.5, -0.5, 0.25, 1.0]);
+        let right = f32_bytes(&[0.0; 4]);
+        backend.emit_audio(0, &[left.as_slice(), right.as_slice()], 4, 100);
+        // Other tracks are not included
+        backend.emit_audio(1, &[left.as_slice(), right.as_slice()], 4, 200);
+
+        let frame = planar.next().await.unwrap();
+        assert_eq!(frame.format, ObsAudioFormat::FloatPlanar);
+        assert_eq!(frame.speakers, ObsSpeakerLayout::Stereo);
+        assert_eq!(frame.samples_per_sec, 44100);
+        assert_eq!(frame.frames, 4);
+        assert_eq!(frame.timestamp, 100);
+        assert_eq!(frame.planes.len(), 2);
+        assert_eq!(frame.samples_f32(0).unwrap(), vec![0.5, -0.5, 0.25, 1.0]);
+        assert!(frame.samples_i16(0).is_none());
+        assert!(planar.try_next_frame().is_none());
+
+        // Converted to interleaved 16 bit mono on the second track
+        let mut conversion = ObsAudioConversion::interleaved_i16();
+        conversion.speakers = Some(ObsSpeakerLayout::Mono);
+        conversion.samples_per_sec = Some(48000);
+        let options = ObsRawAudioOptions::new()
+            .set_mix(1)
+            .set_conversion(Some(conversion))
+            .set_capacity(1)
+            .set_drop_policy(ObsDropPolicy::DropNewest);
+        let converted = context.raw_audio_stream(options).await.unwrap();
+        assert_eq!(backend.raw_audio_callbacks(), 2);
+
+        let samples = i16_bytes(&[1, -2, 3]);
+        backend.emit_audio(1, &[samples.as_slice()], 3, 300);
+        backend.emit_audio(1, &[samples.as_slice()], 3, 400);
+
+        assert_eq!(converted.dropped_frames(), 1);
+        let frame = converted.try_next_frame().unwrap();
+        assert_eq!(frame.format, ObsAudioFormat::I16);
+        assert_eq!(frame.speakers, ObsSpeakerLayout::Mono);
+        assert_eq!(frame.samples_per_sec, 48000);
+        assert_eq!(frame.timestamp, 300);
+        assert_eq!(frame.samples_i16(0).unwrap(), vec![1, -2, 3]);
+
+        let res = context
+            .raw_audio_stream(ObsRawAudioOptions::new().set_mix(6))
+            .await;
+        assert_eq!(res.unwrap_err(), ObsError::InvalidAudioMix(6));
+
+        drop(planar);
+        drop(converted);
+        assert_eq!(backend.raw_audio_callbacks(), 0);
+
+        // The stream ends once OBS is shut down
+        let mut stream = context
+            .raw_audio_stream(ObsRawAudioOptions::new())
+            .await
+            .unwrap();
+        assert_clean_shutdown(context.shutdown().await);
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next());
+        assert!(next.await.unwrap().is_none());
+    }
+}
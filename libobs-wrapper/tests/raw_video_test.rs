@@ -0,0 +1,97 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use futures::StreamExt;
+    use libobs_wrapper::data::video::ObsVideoInfoBuilder;
+    use libobs_wrapper::enums::ObsVideoFormat;
+    use libobs_wrapper::media::{ObsDropPolicy, ObsRawVideoOptions, ObsVideoScaleInfo};
+
+    use crate::common::{assert_clean_shutdown, fake_context_with};
+
+    #[tokio::test]
+    pub async fn raw_video_test() {
+        let video_info = ObsVideoInfoBuilder::new()
+            .output_width(8)
+            .output_height(4)
+            .output_format(ObsVideoFormat::NV12)
+            .build();
+        let (backend, context) =
+            fake_context_with(|startup_info| startup_info.set_video_info(video_info)).await;
+
+        // 4x2 RGBA frames with 4 bytes of padding per row
+        let options = ObsRawVideoOptions::new()
+            .set_conversion(Some(ObsVideoScaleInfo::new(ObsVideoFormat::RGBA, 4, 2)))
+            .set_capacity(2)
+            .set_drop_policy(ObsDropPolicy::DropOldest);
+        let mut stream = context.raw_video_stream(options).await.unwrap();
+        assert_eq!(backend.raw_video_callbacks(), 1);
+
+        for i in 0..3u8 {
+            let plane = vec![i; 40];
+            backend.emit_video_frame(&[plane.as_slice()], &[20], i as u64 * 1000);
+        }
+
+        assert_eq!(stream.dropped_frames(), 1);
+        assert_eq!(stream.buffered_frames(), 2);
+
+        let frame = stream.next().await.unwrap();
+        assert_eq!(frame.format, ObsVideoFormat::RGBA);
+        assert_eq!((frame.width, frame.height), (4, 2));
+        assert_eq!(frame.timestamp, 1000);
+        assert_eq!(frame.linesize, vec![20]);
+        assert_eq!(frame.planes, vec![vec![1; 40]]);
+
+        assert_eq!(stream.try_next_frame().unwrap().timestamp, 2000);
+        assert!(stream.try_next_frame().is_none());
+
+        // Waiting for a frame that is rendered later
+        let emitter = backend.clone();
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            emitter.emit_video_frame(&[&[7; 40][..]], &[20], 3000);
+        });
+        assert_eq!(stream.next().await.unwrap().timestamp, 3000);
+        handle.await.unwrap();
+
+        // Without a conversion the frames have the format of the video context,
+        // newer frames are dropped with `DropNewest`
+        let options = ObsRawVideoOptions::new()
+            .set_capacity(1)
+            .set_drop_policy(ObsDropPolicy::DropNewest);
+        let native = context.raw_video_stream(options).await.unwrap();
+        assert_eq!(backend.raw_video_callbacks(), 2);
+        drop(stream);
+        assert_eq!(backend.raw_video_callbacks(), 1);
+
+        let y = vec![0; 8 * 4];
+        let uv = vec![0; 8 * 2];
+        backend.emit_video_frame(&[y.as_slice(), uv.as_slice()], &[8, 8], 1);
+        backend.emit_video_frame(&[y.as_slice(), uv.as_slice()], &[8, 8], 2);
+
+        assert_eq!(native.dropped_frames(), 1);
+        let frame = native.try_next_frame().unwrap();
+        assert_eq!(frame.timestamp, 1);
+        assert_eq!(frame.format, ObsVideoFormat::NV12);
+        assert_eq!((frame.width, frame.height), (8, 4));
+        assert_eq!(frame.planes.len(), 2);
+        assert_eq!(frame.planes[1].len(), uv.len());
+
+        drop(native);
+        assert_eq!(backend.raw_video_callbacks(), 0);
+
+        // Buffered frames are still handed out after OBS was shut down, then the stream ends
+        let mut stream = context
+            .raw_video_stream(ObsRawVideoOptions::new())
+            .await
+            .unwrap();
+        backend.emit_video_frame(&[y.as_slice(), uv.as_slice()], &[8, 8], 3);
+        assert_clean_shutdown(context.shutdown().await);
+        assert_eq!(stream.next().await.unwrap().timestamp, 3);
+        assert!(stream.next().await.is_none());
+        assert!(stream.next().await.is_none());
+    }
+}
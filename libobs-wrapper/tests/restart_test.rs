@@ -0,0 +1,69 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::backend::fake::FakeObsBackend;
+    use libobs_wrapper::utils::SourceInfo;
+
+    use crate::common::restart_fake_context;
+
+    #[tokio::test]
+    pub async fn restart_test() {
+        let backend = FakeObsBackend::new();
+
+        for i in 0..3 {
+            let mut context = restart_fake_context(&backend).await;
+            assert!(backend.is_started());
+
+            let mut scene = context.scene(format!("scene {}", i)).await.unwrap();
+            scene
+                .add_source(SourceInfo::new(
+                    "color_source",
+                    format!("source {}", i),
+                    None,
+                    None,
+                ))
+                .await
+                .unwrap();
+            scene.set_to_channel(0).await.unwrap();
+            assert_eq!(
+                backend.scene(&format!("scene {}", i)).unwrap().items.len(),
+                1
+            );
+
+            if i % 2 == 0 {
+                drop(scene);
+                drop(context);
+            } else {
+                drop(scene);
+                let report = context.shutdown().await;
+                assert!(report.is_clean(), "{:?}", report.leaked_objects);
+            }
+
+            assert!(!backend.is_started());
+        }
+
+        // Objects of an old context are dropped after the new one was created
+        let mut context = restart_fake_context(&backend).await;
+        let mut scene = context.scene("old").await.unwrap();
+        let leaked = scene
+            .add_source(SourceInfo::new("color_source", "leaked", None, None))
+            .await
+            .unwrap();
+        drop(scene);
+        context.shutdown().await;
+
+        let mut context = restart_fake_context(&backend).await;
+        drop(leaked);
+
+        let mut scene = context.scene("new").await.unwrap();
+        scene
+            .add_source(SourceInfo::new("color_source", "source", None, None))
+            .await
+            .unwrap();
+        assert!(backend.source("source").is_some());
+    }
+}
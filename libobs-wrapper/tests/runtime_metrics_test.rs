@@ -0,0 +1,65 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use libobs_wrapper::runtime::ObsWatchdogOptions;
+
+    use crate::common::fake_context_with;
+
+    #[tokio::test]
+    pub async fn runtime_metrics_test() {
+        let (_backend, context) = fake_context_with(|startup_info| {
+            startup_info.set_watchdog_options(
+                ObsWatchdogOptions::default().set_poll_interval(Duration::from_millis(10)),
+            )
+        })
+        .await;
+
+        let mut receiver = context.subscribe_metrics();
+        let runtime = context.runtime().clone();
+
+        runtime
+            .run_with_obs_labeled("slow command", || {
+                std::thread::sleep(Duration::from_millis(20))
+            })
+            .await
+            .unwrap();
+        let res = runtime
+            .run_with_obs_result_labeled("fast command", || 42)
+            .await
+            .unwrap();
+        assert_eq!(res, 42);
+
+        let metrics = context.metrics();
+        assert_eq!(metrics.queued_commands, 0);
+        assert!(metrics.max_queued_commands >= 1);
+        assert!(metrics.total.count >= 2);
+
+        let slow = &metrics.labels["slow command"];
+        assert_eq!(slow.count, 1);
+        assert_eq!(slow.timed_out, 0);
+        assert!(slow.execution.max >= Duration::from_millis(20));
+        assert!(slow.execution.quantile(0.5).unwrap() >= Duration::from_millis(20));
+        assert_eq!(metrics.labels["fast command"].count, 1);
+        assert_eq!(metrics.slowest(1)[0].0, "slow command");
+
+        // Commands without a label are attributed to the function that sent them
+        context.get_version().await.unwrap();
+        let metrics = context.metrics();
+        assert!(metrics
+            .labels
+            .keys()
+            .any(|label| label.ends_with("ObsContext::get_version")));
+
+        tokio::time::timeout(Duration::from_secs(5), receiver.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(receiver.borrow_and_update().total.count >= 2);
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::thread;
+    use std::time::Duration;
+
+    use libobs_wrapper::runtime::{ObsRuntimeEvent, ObsWatchdogOptions};
+    use libobs_wrapper::utils::ObsError;
+
+    use crate::common::fake_context_with;
+
+    #[tokio::test]
+    pub async fn watchdog_test() {
+        let options = ObsWatchdogOptions::new()
+            .set_stall_threshold(Duration::from_millis(100))
+            .set_poll_interval(Duration::from_millis(10));
+
+        let (_backend, context) =
+            fake_context_with(|startup_info| startup_info.set_watchdog_options(options)).await;
+
+        let runtime = context.runtime().clone();
+        let mut events = runtime.watchdog_events();
+
+        // A command that finishes in time returns its value
+        let res = runtime
+            .run_with_obs_result_timeout(Duration::from_secs(5), || 42)
+            .await
+            .unwrap();
+        assert_eq!(res, 42);
+
+        // A command that blocks the OBS thread for too long
+        let res = runtime
+            .run_with_obs_result_timeout(Duration::from_millis(50), || {
+                thread::sleep(Duration::from_millis(300));
+            })
+            .await;
+
+        let err = res.unwrap_err().downcast::<ObsError>().unwrap();
+        assert!(matches!(err, ObsError::CommandTimeout(_, t) if t == Duration::from_millis(50)));
+
+        // The OBS thread is usable again after the blocking command finished
+        runtime.run_with_obs(|| {}).await.unwrap();
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+
+        assert!(matches!(received[0], ObsRuntimeEvent::TimedOut { .. }));
+        assert!(received
+            .iter()
+            .any(|e| matches!(e, ObsRuntimeEvent::Stalled { queued_commands, .. } if *queued_commands >= 1)));
+        assert!(matches!(
+            received.last(),
+            Some(ObsRuntimeEvent::Recovered { .. })
+        ));
+    }
+}
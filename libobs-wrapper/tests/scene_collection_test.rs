@@ -0,0 +1,162 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::backend::fake::FakeObsBackend;
+    use libobs_wrapper::scenes::ObsSceneItemCrop;
+    use libobs_wrapper::utils::{FilterInfo, SourceInfo};
+    use libobs_wrapper::Vec2;
+    use serde_json::{json, Value};
+
+    use crate::common::{assert_clean_shutdown, restart_fake_context};
+
+    fn item_names(backend: &FakeObsBackend, scene: &str) -> Vec<String> {
+        backend
+            .scene(scene)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.source)
+            .collect()
+    }
+
+    #[tokio::test]
+    pub async fn scene_collection_test() {
+        let backend = FakeObsBackend::new();
+        let mut context = restart_fake_context(&backend).await;
+
+        let json = {
+            let mut overlay = context.scene("overlay").await.unwrap();
+            overlay
+                .add_source(SourceInfo::new("text_ft2_source_v2", "title", None, None))
+                .await
+                .unwrap();
+
+            let mut main = context.scene("main").await.unwrap();
+            let mut settings = context.data().await.unwrap();
+            settings.set_int("color", 0xFF00FF00).await.unwrap();
+            let camera = main
+                .add_source(SourceInfo::new(
+                    "color_source",
+                    "camera",
+                    Some(settings),
+                    None,
+                ))
+                .await
+                .unwrap();
+
+            let item = main.scene_item(&camera).await.unwrap();
+            item.set_position(Vec2::new(100.0, 50.0)).await.unwrap();
+            item.set_scale(Vec2::new(0.5, 0.5)).await.unwrap();
+            item.set_crop(ObsSceneItemCrop::new(1, 2, 3, 4))
+                .await
+                .unwrap();
+            item.set_locked(true).await.unwrap();
+
+            main.add_scene(&overlay).await.unwrap();
+            let logo = main
+                .add_source(SourceInfo::new("image_source", "logo", None, None))
+                .await
+                .unwrap();
+            let group = main.add_group("branding").await.unwrap();
+            group
+                .add_item(&main.scene_item(&logo).await.unwrap())
+                .await
+                .unwrap();
+
+            let filter = context
+                .obs_filter(FilterInfo::new("color_filter", "grade", None, None))
+                .await
+                .unwrap();
+            main.add_source_filter(&camera, &filter).await.unwrap();
+
+            main.set_to_channel(0).await.unwrap();
+            context.save_scene_collection("Show").await.unwrap()
+        };
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.sources().is_empty());
+        assert!(backend.scenes().is_empty());
+
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["name"], "Show");
+        assert_eq!(document["current_scene"], "main");
+        assert_eq!(
+            document["scene_order"],
+            json!([{ "name": "overlay" }, { "name": "main" }])
+        );
+        assert_eq!(document["groups"][0]["name"], "branding");
+
+        let sources = document["sources"].as_array().unwrap();
+        let names = sources.iter().map(|s| &s["name"]).collect::<Vec<_>>();
+        assert_eq!(names, ["overlay", "main", "title", "camera", "logo"]);
+        assert_eq!(sources[3]["filters"][0]["name"], "grade");
+
+        // Loading into a fresh context restores everything
+        let backend = FakeObsBackend::new();
+        let mut context = restart_fake_context(&backend).await;
+        {
+            let scenes = context.load_scene_collection(&json).await.unwrap();
+            let names = scenes
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["overlay", "main"]);
+            assert_eq!(backend.channel_source(0).as_deref(), Some("main"));
+
+            assert_eq!(
+                item_names(&backend, "main"),
+                ["camera", "overlay", "branding"]
+            );
+            assert_eq!(item_names(&backend, "overlay"), ["title"]);
+            assert_eq!(item_names(&backend, "branding"), ["logo"]);
+            assert!(backend.scene("branding").unwrap().group);
+
+            let camera = backend.source("camera").unwrap();
+            assert_eq!(camera.settings["color"], 0xFF00FF00u32 as i64);
+            assert_eq!(camera.filters, ["grade"]);
+            assert_eq!(backend.source("grade").unwrap().id, "color_filter");
+
+            let fake_item = &backend.scene("main").unwrap().items[0];
+            assert_eq!(fake_item.position, Vec2::new(100.0, 50.0));
+            assert_eq!(fake_item.scale, Vec2::new(0.5, 0.5));
+            assert_eq!(fake_item.crop, ObsSceneItemCrop::new(1, 2, 3, 4));
+            assert!(fake_item.locked);
+
+            // The loaded objects are registered like ones created through the wrapper
+            let mut main = context.get_scene("main").await.unwrap();
+            let camera = main.get_source_mut("camera").await.unwrap();
+            assert_eq!(
+                main.get_source_position(&camera).await.unwrap(),
+                Vec2::new(100.0, 50.0)
+            );
+            assert!(main.get_source_locked(&camera).await.unwrap());
+            assert!(context.get_filter("grade").await.is_some());
+
+            let groups = main.groups().await;
+            assert_eq!(groups.len(), 1);
+            let logo = main.get_source_mut("logo").await.unwrap();
+            assert!(groups[0]
+                .contains(&main.scene_item(&logo).await.unwrap())
+                .await
+                .unwrap());
+
+            let overlay = context.get_scene("overlay").await.unwrap();
+            main.remove_scene(&overlay).await.unwrap();
+            assert_eq!(item_names(&backend, "main"), ["camera", "branding"]);
+
+            // Saving again gives the same collection, minus the removed scene
+            let json = context.save_scene_collection("Show").await.unwrap();
+            let document: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(document["current_scene"], "main");
+            assert_eq!(document["sources"].as_array().unwrap().len(), 5);
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.sources().is_empty());
+        assert!(backend.scenes().is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::backend::fake::FakeObsBackend;
+    use libobs_wrapper::enums::ObsOrderMovement;
+    use libobs_wrapper::scenes::ObsSceneEvent;
+    use libobs_wrapper::utils::SourceInfo;
+
+    use crate::common::fake_context;
+
+    fn item_names(backend: &FakeObsBackend) -> Vec<String> {
+        backend
+            .scene("main")
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.source)
+            .collect()
+    }
+
+    #[tokio::test]
+    pub async fn scene_item_order_test() {
+        let (backend, mut context) = fake_context().await;
+
+        let mut scene = context.scene("main").await.unwrap();
+        let mut sources = Vec::new();
+        for name in ["a", "b", "c"] {
+            let source = scene
+                .add_source(SourceInfo::new("color_source", name, None, None))
+                .await
+                .unwrap();
+            sources.push(source);
+        }
+        let [a, b, c] = [&sources[0], &sources[1], &sources[2]];
+
+        let signals = scene.signal_manager();
+        let mut reorder_rx = signals.on_reorder().await.unwrap();
+        let mut visible_rx = signals.on_item_visible().await.unwrap();
+        let mut locked_rx = signals.on_item_locked().await.unwrap();
+        let mut events = scene.events();
+
+        assert_eq!(item_names(&backend), ["a", "b", "c"]);
+        assert_eq!(scene.get_source_order_position(c).await.unwrap(), 2);
+
+        scene
+            .set_source_order(a, ObsOrderMovement::MoveTop)
+            .await
+            .unwrap();
+        assert_eq!(item_names(&backend), ["b", "c", "a"]);
+        reorder_rx.try_recv().unwrap();
+        let a_ptr = scene.scene_item(a).await.unwrap().as_ptr();
+        match events.try_recv().unwrap() {
+            ObsSceneEvent::Reordered { item } => assert_eq!(item.0, a_ptr.0),
+            e => panic!("Unexpected event {:?}", e),
+        }
+
+        scene
+            .set_source_order(a, ObsOrderMovement::MoveDown)
+            .await
+            .unwrap();
+        assert_eq!(item_names(&backend), ["b", "a", "c"]);
+
+        let item = scene.scene_item(c).await.unwrap();
+        item.set_order(ObsOrderMovement::MoveBottom).await.unwrap();
+        assert_eq!(item_names(&backend), ["c", "b", "a"]);
+        assert_eq!(item.order_position().await.unwrap(), 0);
+
+        item.set_order(ObsOrderMovement::MoveUp).await.unwrap();
+        assert_eq!(item_names(&backend), ["b", "c", "a"]);
+
+        scene.set_source_order_position(b, 2).await.unwrap();
+        assert_eq!(item_names(&backend), ["c", "a", "b"]);
+        item.set_order_position(10).await.unwrap();
+        assert_eq!(item_names(&backend), ["a", "b", "c"]);
+
+        // Moving to the current position doesn't change anything
+        while reorder_rx.try_recv().is_ok() {}
+        while events.try_recv().is_ok() {}
+        scene.set_source_order_position(a, 0).await.unwrap();
+        assert!(reorder_rx.try_recv().is_err());
+        assert!(events.try_recv().is_err());
+
+        // Visibility
+        assert!(scene.get_source_visible(b).await.unwrap());
+        scene.set_source_visible(b, false).await.unwrap();
+        assert!(!scene.get_source_visible(b).await.unwrap());
+        assert!(!backend.scene("main").unwrap().items[1].visible);
+
+        let signal = visible_rx.try_recv().unwrap();
+        assert!(!signal.visible);
+        assert_eq!(signal.item.0, scene.scene_item(b).await.unwrap().as_ptr().0);
+
+        match events.try_recv().unwrap() {
+            ObsSceneEvent::VisibilityChanged { item, visible } => {
+                assert_eq!(item.0, signal.item.0);
+                assert!(!visible);
+            }
+            e => panic!("Unexpected event {:?}", e),
+        }
+
+        scene.set_source_visible(b, false).await.unwrap();
+        assert!(visible_rx.try_recv().is_err());
+        assert!(events.try_recv().is_err());
+
+        item.set_visible(false).await.unwrap();
+        assert!(!item.visible().await.unwrap());
+        assert!(!visible_rx.try_recv().unwrap().visible);
+
+        // Locking
+        assert!(!item.locked().await.unwrap());
+        item.set_locked(true).await.unwrap();
+        assert!(scene.get_source_locked(c).await.unwrap());
+        assert!(backend.scene("main").unwrap().items[2].locked);
+
+        let signal = locked_rx.try_recv().unwrap();
+        assert!(signal.locked);
+        assert_eq!(signal.item.0, item.as_ptr().0);
+
+        events.try_recv().unwrap();
+        match events.try_recv().unwrap() {
+            ObsSceneEvent::LockChanged {
+                item: locked_item,
+                locked,
+            } => {
+                assert_eq!(locked_item.0, item.as_ptr().0);
+                assert!(locked);
+            }
+            e => panic!("Unexpected event {:?}", e),
+        }
+
+        scene.set_source_locked(c, false).await.unwrap();
+        assert!(!locked_rx.try_recv().unwrap().locked);
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            ObsSceneEvent::LockChanged { locked: false, .. }
+        ));
+        assert!(!item.locked().await.unwrap());
+    }
+}
@@ -0,0 +1,168 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::enums::{
+        ObsAlignment, ObsBlendingMethod, ObsBlendingType, ObsBoundsType, ObsScaleType,
+    };
+    use libobs_wrapper::scenes::{ObsSceneItemCrop, ObsTransformInfo};
+    use libobs_wrapper::utils::{ObsError, SourceInfo};
+    use libobs_wrapper::Vec2;
+
+    use crate::common::fake_context;
+
+    #[tokio::test]
+    pub async fn scene_item_test() {
+        let (backend, mut context) = fake_context().await;
+
+        let mut main = context.scene("main").await.unwrap();
+        let camera = main
+            .add_source(SourceInfo::new("color_source", "camera", None, None))
+            .await
+            .unwrap();
+
+        let item = main.scene_item(&camera).await.unwrap();
+        assert_eq!(item.source().name(), "camera");
+        assert_eq!(item.alignment().await.unwrap(), ObsAlignment::TOP_LEFT);
+
+        item.set_rotation(90.0).await.unwrap();
+        item.set_alignment(ObsAlignment::BOTTOM | ObsAlignment::RIGHT)
+            .await
+            .unwrap();
+        item.set_bounds_type(ObsBoundsType::ScaleInner)
+            .await
+            .unwrap();
+        item.set_bounds_alignment(ObsAlignment::TOP).await.unwrap();
+        item.set_bounds(Vec2::new(1280.0, 720.0)).await.unwrap();
+        item.set_crop(ObsSceneItemCrop::new(1, 2, 3, 4))
+            .await
+            .unwrap();
+        item.set_scale_filter(ObsScaleType::Lanczos).await.unwrap();
+        item.set_blending_mode(ObsBlendingType::Multiply)
+            .await
+            .unwrap();
+        item.set_blending_method(ObsBlendingMethod::SrgbOff)
+            .await
+            .unwrap();
+
+        assert_eq!(item.rotation().await.unwrap(), 90.0);
+        assert_eq!(item.alignment().await.unwrap(), ObsAlignment::BOTTOM_RIGHT);
+        assert_eq!(item.bounds_type().await.unwrap(), ObsBoundsType::ScaleInner);
+        assert_eq!(item.bounds_alignment().await.unwrap(), ObsAlignment::TOP);
+        assert_eq!(item.bounds().await.unwrap(), Vec2::new(1280.0, 720.0));
+        assert_eq!(
+            item.crop().await.unwrap(),
+            ObsSceneItemCrop::new(1, 2, 3, 4)
+        );
+        assert_eq!(item.scale_filter().await.unwrap(), ObsScaleType::Lanczos);
+        assert_eq!(
+            item.blending_mode().await.unwrap(),
+            ObsBlendingType::Multiply
+        );
+        assert_eq!(
+            item.blending_method().await.unwrap(),
+            ObsBlendingMethod::SrgbOff
+        );
+
+        let fake_item = backend.scene("main").unwrap().items[0].clone();
+        assert_eq!(fake_item.rotation, 90.0);
+        assert_eq!(fake_item.crop, ObsSceneItemCrop::new(1, 2, 3, 4));
+        assert_eq!(fake_item.blending_mode, ObsBlendingType::Multiply);
+
+        // The whole transform at once
+        let info = ObsTransformInfo {
+            position: Vec2::new(10.0, 20.0),
+            rotation: 45.0,
+            scale: Vec2::new(0.5, 0.5),
+            alignment: ObsAlignment::CENTER,
+            bounds_type: ObsBoundsType::None,
+            bounds_alignment: ObsAlignment::CENTER,
+            bounds: Vec2::new(0.0, 0.0),
+            crop_to_bounds: true,
+        };
+        item.set_transform_info(info).await.unwrap();
+        assert_eq!(item.transform_info().await.unwrap(), info);
+        assert_eq!(item.position().await.unwrap(), Vec2::new(10.0, 20.0));
+        assert_eq!(
+            main.get_source_scale(&camera).await.unwrap(),
+            Vec2::new(0.5, 0.5)
+        );
+
+        // The same source in another scene has its own transform
+        let mut layout = context.scene("layout").await.unwrap();
+        let layout_item = layout.add_existing_source(&camera).await.unwrap();
+        layout_item
+            .set_position(Vec2::new(100.0, 200.0))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            layout.get_source_position(&camera).await.unwrap(),
+            Vec2::new(100.0, 200.0)
+        );
+        assert_eq!(
+            main.get_source_position(&camera).await.unwrap(),
+            Vec2::new(10.0, 20.0)
+        );
+        assert_eq!(backend.scene("layout").unwrap().items[0].source, "camera");
+        assert_eq!(
+            backend
+                .sources()
+                .iter()
+                .filter(|s| s.name == "camera")
+                .count(),
+            1
+        );
+
+        assert_eq!(main.items().await.unwrap().len(), 1);
+        assert_eq!(layout.items().await.unwrap().len(), 1);
+
+        // Removing it from one scene keeps the other one
+        layout.remove_source(&camera).await.unwrap();
+        assert!(backend.scene("layout").unwrap().items.is_empty());
+        assert_eq!(backend.scene("main").unwrap().items.len(), 1);
+        assert!(layout.scene_item(&camera).await.is_err());
+        assert!(main.scene_item(&camera).await.is_ok());
+    }
+
+    #[tokio::test]
+    pub async fn duplicate_scene_item_test() {
+        let (backend, mut context) = fake_context().await;
+
+        let mut main = context.scene("main").await.unwrap();
+        let camera = main
+            .add_source(SourceInfo::new("color_source", "camera", None, None))
+            .await
+            .unwrap();
+        main.add_existing_source(&camera).await.unwrap();
+
+        // Both placements share the source, so it is unclear which one is meant
+        assert_eq!(
+            main.scene_item(&camera).await.unwrap_err(),
+            ObsError::AmbiguousSceneItem("camera".to_string())
+        );
+        assert_eq!(
+            main.set_source_visible(&camera, false).await.unwrap_err(),
+            ObsError::AmbiguousSceneItem("camera".to_string())
+        );
+        assert!(main
+            .set_source_position(&camera, Vec2::new(1.0, 2.0))
+            .await
+            .is_err());
+        assert!(backend
+            .scene("main")
+            .unwrap()
+            .items
+            .iter()
+            .all(|i| i.visible));
+
+        let items = main.items().await.unwrap();
+        assert_eq!(items.len(), 2);
+        items[1].set_visible(false).await.unwrap();
+        assert!(items[0].visible().await.unwrap());
+        assert!(!backend.scene("main").unwrap().items[1].visible);
+    }
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::backend::fake::FakeObsBackend;
+    use libobs_wrapper::utils::{ObsError, SourceInfo};
+    use libobs_wrapper::Vec2;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    fn item_names(backend: &FakeObsBackend, scene: &str) -> Vec<String> {
+        backend
+            .scene(scene)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.source)
+            .collect()
+    }
+
+    #[tokio::test]
+    pub async fn scene_nesting_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let mut rig = context.scene("rig").await.unwrap();
+            rig.add_source(SourceInfo::new("color_source", "camera", None, None))
+                .await
+                .unwrap();
+
+            let mut layout1 = context.scene("layout1").await.unwrap();
+            let mut layout2 = context.scene("layout2").await.unwrap();
+
+            let rig_item = layout1.add_scene(&rig).await.unwrap();
+            rig_item.set_position(Vec2::new(10.0, 10.0)).await.unwrap();
+            layout2.add_scene(&rig).await.unwrap();
+            layout2.add_scene(&layout1).await.unwrap();
+
+            assert_eq!(rig_item.source().name(), "rig");
+            assert_eq!(item_names(&backend, "layout1"), ["rig"]);
+            assert_eq!(item_names(&backend, "layout2"), ["rig", "layout1"]);
+            assert_eq!(layout2.items().await.unwrap().len(), 2);
+
+            // Scenes can't contain themselves, not even through other scenes
+            let res = rig.add_scene(&rig.clone()).await;
+            assert!(matches!(res, Err(ObsError::SceneCycle(_))));
+            let res = rig.add_scene(&layout1).await;
+            assert!(matches!(res, Err(ObsError::SceneCycle(name)) if name == "layout1"));
+            let res = layout1.add_scene(&layout2).await;
+            assert!(matches!(res, Err(ObsError::SceneCycle(_))));
+            assert_eq!(item_names(&backend, "rig"), ["camera"]);
+
+            layout2.remove_scene(&rig).await.unwrap();
+            assert_eq!(item_names(&backend, "layout2"), ["layout1"]);
+            assert!(layout1.remove_scene(&layout2).await.is_err());
+
+            // Groups
+            let mut main = context.scene("main").await.unwrap();
+            let a = main
+                .add_source(SourceInfo::new("color_source", "a", None, None))
+                .await
+                .unwrap();
+            let b = main
+                .add_source(SourceInfo::new("color_source", "b", None, None))
+                .await
+                .unwrap();
+
+            let group = main.add_group("group").await.unwrap();
+            assert_eq!(group.name(), "group");
+            assert_eq!(main.groups().await.len(), 1);
+            assert!(backend.scene("group").unwrap().group);
+            assert!(backend.source("group").is_none());
+
+            let item_a = main.scene_item(&a).await.unwrap();
+            group.add_item(&item_a).await.unwrap();
+            assert!(group.contains(&item_a).await.unwrap());
+            assert_eq!(item_names(&backend, "main"), ["b", "group"]);
+            assert_eq!(item_names(&backend, "group"), ["a"]);
+
+            // The group is moved like any other item
+            group
+                .item()
+                .set_position(Vec2::new(50.0, 50.0))
+                .await
+                .unwrap();
+            assert_eq!(
+                main.get_source_position(group.source()).await.unwrap(),
+                Vec2::new(50.0, 50.0)
+            );
+
+            // Cycles through groups are detected as well
+            let nested_rig = main.add_scene(&rig).await.unwrap();
+            group.add_item(&nested_rig).await.unwrap();
+            assert_eq!(group.item_count().await.unwrap(), 2);
+            let res = rig.add_scene(&main).await;
+            assert!(matches!(res, Err(ObsError::SceneCycle(_))));
+
+            group.remove_item(&item_a).await.unwrap();
+            assert!(!group.contains(&item_a).await.unwrap());
+            assert_eq!(item_names(&backend, "main"), ["b", "group", "a"]);
+
+            main.ungroup(&group).await.unwrap();
+            assert_eq!(item_names(&backend, "main"), ["b", "rig", "a"]);
+            assert!(backend.scene("group").is_none());
+            assert!(main.groups().await.is_empty());
+
+            // The items are recreated in the scene and tracked by their new scene items
+            main.set_source_position(nested_rig.source(), Vec2::new(5.0, 5.0))
+                .await
+                .unwrap();
+            assert_eq!(
+                backend.scene("main").unwrap().items[1].position,
+                Vec2::new(5.0, 5.0)
+            );
+            assert_eq!(main.items().await.unwrap().len(), 3);
+
+            // Removing a group removes its items as well
+            let group = main.add_group("other").await.unwrap();
+            group
+                .add_item(&main.scene_item(&b).await.unwrap())
+                .await
+                .unwrap();
+            main.remove_group(&group).await.unwrap();
+            assert_eq!(item_names(&backend, "main"), ["rig", "a"]);
+            assert!(backend.scene("other").is_none());
+            assert!(main.groups().await.is_empty());
+            assert_eq!(main.items().await.unwrap().len(), 2);
+            assert!(main.scene_item(&b).await.is_err());
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.scenes().is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::data::video::ObsVideoInfoBuilder;
+    use libobs_wrapper::media::ObsScreenshotOptions;
+    use libobs_wrapper::utils::{ObsError, SourceInfo};
+
+    use crate::common::fake_context_with;
+
+    #[tokio::test]
+    pub async fn screenshot_test() {
+        let video_info = ObsVideoInfoBuilder::new()
+            .base_width(16)
+            .base_height(8)
+            .output_width(16)
+            .output_height(8)
+            .build();
+        let (backend, mut context) =
+            fake_context_with(|startup_info| startup_info.set_video_info(video_info)).await;
+
+        let mut scene = context.scene("main").await.unwrap();
+        let source = scene
+            .add_source(SourceInfo::new("color_source", "red", None, None))
+            .await
+            .unwrap();
+
+        // Sources without a size can't be captured
+        let res = source.screenshot(ObsScreenshotOptions::new()).await;
+        assert!(matches!(res, Err(ObsError::ScreenshotFailure(_))));
+
+        assert!(backend.set_source_video("red", 4, 2, [255, 0, 0, 255]));
+        let screenshot = source
+            .screenshot(ObsScreenshotOptions::new())
+            .await
+            .unwrap();
+        assert_eq!((screenshot.width, screenshot.height), (4, 2));
+        assert_eq!(screenshot.data.len(), 4 * 2 * 4);
+        assert_eq!(screenshot.pixel(3, 1), Some([255, 0, 0, 255]));
+        assert_eq!(screenshot.pixel(4, 0), None);
+
+        // Scaled to the requested resolution
+        let screenshot = source
+            .screenshot(ObsScreenshotOptions::new().set_resolution(10, 5))
+            .await
+            .unwrap();
+        assert_eq!((screenshot.width, screenshot.height), (10, 5));
+        assert_eq!(screenshot.data.len(), 10 * 5 * 4);
+
+        // Scenes are captured at the base resolution
+        let screenshot = scene.screenshot(ObsScreenshotOptions::new()).await.unwrap();
+        assert_eq!((screenshot.width, screenshot.height), (16, 8));
+        assert_eq!(screenshot.pixel(0, 0), Some([255, 0, 0, 255]));
+
+        // Nothing is shown on the program output yet
+        let screenshot = context
+            .screenshot(ObsScreenshotOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(screenshot.pixel(0, 0), Some([0, 0, 0, 0]));
+
+        scene.set_to_channel(0).await.unwrap();
+        let screenshot = context
+            .screenshot(ObsScreenshotOptions::new().set_resolution(8, 4))
+            .await
+            .unwrap();
+        assert_eq!((screenshot.width, screenshot.height), (8, 4));
+        assert!(screenshot
+            .data
+            .chunks_exact(4)
+            .all(|p| p == [255, 0, 0, 255]));
+
+        // All graphics objects are freed again
+        assert_eq!(backend.graphics_objects(), 0);
+    }
+}
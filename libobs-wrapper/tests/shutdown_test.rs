@@ -0,0 +1,65 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::utils::{OutputInfo, SourceInfo};
+
+    use crate::common::fake_context;
+
+    #[tokio::test]
+    pub async fn shutdown_test() {
+        let (backend, mut context) = fake_context().await;
+
+        let mut scene = context.scene("main").await.unwrap();
+        scene
+            .add_source(SourceInfo::new("color_source", "released", None, None))
+            .await
+            .unwrap();
+
+        // This one is kept alive until after the shutdown
+        let kept = scene
+            .add_source(SourceInfo::new("color_source", "kept", None, None))
+            .await
+            .unwrap();
+        let mut volume_rx = kept.signal_manager().on_volume().await.unwrap();
+        assert!(!backend.connected_signals("kept").is_empty());
+        scene.set_to_channel(0).await.unwrap();
+        drop(scene);
+
+        let output = context
+            .output(OutputInfo::new("ffmpeg_muxer", "output", None, None))
+            .await
+            .unwrap();
+        output.start().await.unwrap();
+        drop(output);
+
+        let report = context.shutdown().await;
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.leaked_objects, ["Source kept in scene main"]);
+        assert!(!report.is_clean());
+        assert_eq!(report.memory_leaks, Some(0));
+
+        assert!(!backend.is_started());
+        assert!(backend.source("released").is_none());
+        assert!(backend.channel_source(0).is_none());
+
+        // Signals are disconnected even though the source is still referenced
+        assert!(backend.connected_signals("kept").is_empty());
+        assert!(volume_rx.recv().await.is_err());
+
+        let signals = backend
+            .emitted_signals()
+            .into_iter()
+            .filter(|s| s.object == "output")
+            .map(|s| s.signal)
+            .collect::<Vec<_>>();
+        assert!(signals.ends_with(&["stopping".into(), "deactivate".into(), "stop".into()]));
+
+        // Dropping objects after the shutdown must not call into libobs anymore
+        drop(kept);
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use libobs_wrapper::enums::{ObsAudioFormat, ObsSpeakerLayout};
+    use libobs_wrapper::media::ObsSourceAudioOptions;
+    use libobs_wrapper::sources::ObsSourceRef;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    fn f32_bytes(samples: &[f32]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_ne_bytes()).collect()
+    }
+
+    #[tokio::test]
+    pub async fn source_audio_test() {
+        let (backend, context) = fake_context().await;
+
+        let source = ObsSourceRef::new(
+            "wasapi_input_capture",
+            "mic",
+            None,
+            None,
+            context.runtime().clone(),
+        )
+        .await
+        .unwrap();
+
+        let mut stream = source
+            .audio_stream(ObsSourceAudioOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(backend.audio_capture_callbacks("mic"), 1);
+
+        let left = f32_bytes(&[0.1, 0.2]);
+        let right = f32_bytes(&[0.3, 0.4]);
+        assert!(backend.emit_source_audio(
+            "mic",
+            &[left.as_slice(), right.as_slice()],
+            2,
+            500,
+            false
+        ));
+        assert!(backend.emit_source_audio(
+            "mic",
+            &[left.as_slice(), right.as_slice()],
+            2,
+            600,
+            true
+        ));
+
+        let frame = stream.next().await.unwrap();
+        assert!(!frame.muted);
+        assert_eq!(frame.audio.format, ObsAudioFormat::FloatPlanar);
+        assert_eq!(frame.audio.speakers, ObsSpeakerLayout::Stereo);
+        assert_eq!(frame.audio.timestamp, 500);
+        assert_eq!(frame.audio.samples_f32(1).unwrap(), vec![0.3, 0.4]);
+
+        let frame = stream.next().await.unwrap();
+        assert!(frame.muted);
+        assert_eq!(frame.audio.timestamp, 600);
+
+        // The stream keeps the source alive
+        drop(source);
+        assert!(backend.source("mic").is_some());
+
+        drop(stream);
+        assert!(backend.source("mic").is_none());
+        assert_eq!(backend.audio_capture_callbacks("mic"), 0);
+
+        // The stream ends once OBS is shut down
+        let source = ObsSourceRef::new(
+            "wasapi_input_capture",
+            "speakers",
+            None,
+            None,
+            context.runtime().clone(),
+        )
+        .await
+        .unwrap();
+        let mut stream = source
+            .audio_stream(ObsSourceAudioOptions::new())
+            .await
+            .unwrap();
+        drop(source);
+
+        assert_clean_shutdown(context.shutdown().await);
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next());
+        assert!(next.await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use libobs_wrapper::backend::fake::{FakeOutputStats, FakeRenderStats};
+    use libobs_wrapper::utils::OutputInfo;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn stats_test() {
+        let (backend, mut context) = fake_context().await;
+
+        backend.set_render_stats(FakeRenderStats {
+            active_fps: 59.9,
+            average_frame_time_ns: 2_500_000,
+            total_frames: 1000,
+            lagged_frames: 50,
+        });
+
+        let stats = context.stats().await.unwrap();
+        assert_eq!(stats.active_fps, 59.9);
+        assert_eq!(stats.average_frame_time, Duration::from_micros(2500));
+        assert_eq!(stats.total_frames, 1000);
+        assert_eq!(stats.lagged_frames, 50);
+        assert_eq!(stats.lagged_percentage(), 5.0);
+        assert!(stats.outputs.is_empty());
+
+        let mut stream = context.stats_stream(Duration::from_millis(10));
+        let first = stream.next().await.unwrap();
+        assert!(first.outputs.is_empty());
+
+        // Outputs that are added later show up in the stream
+        let output = context
+            .output(OutputInfo::new("ffmpeg_muxer", "output", None, None))
+            .await
+            .unwrap();
+        assert!(backend.set_output_stats(
+            "output",
+            FakeOutputStats {
+                total_bytes: 4096,
+                dropped_frames: 3,
+                total_frames: 300,
+                congestion: 0.25,
+            },
+        ));
+
+        let stats = stream.next().await.unwrap();
+        let output_stats = stats.output("output").unwrap();
+        assert!(!output_stats.active);
+        assert_eq!(output_stats.dropped_frames, 3);
+        assert_eq!(output_stats.total_frames, 300);
+        assert_eq!(output_stats.total_bytes, 4096);
+        assert_eq!(output_stats.congestion, 0.25);
+        assert_eq!(output_stats.dropped_percentage(), 1.0);
+
+        assert_eq!(&output.stats().await.unwrap(), output_stats);
+
+        // Streams end once OBS is shut down, without waiting for the next tick
+        let mut slow_stream = context.stats_stream(Duration::from_secs(3600));
+        assert!(slow_stream.next().await.is_some());
+
+        drop(output);
+        assert_clean_shutdown(context.shutdown().await);
+        let next = tokio::time::timeout(Duration::from_secs(5), slow_stream.next());
+        assert!(next.await.unwrap().is_none());
+        assert!(stream.next().await.is_none());
+    }
+}
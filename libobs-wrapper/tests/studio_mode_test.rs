@@ -0,0 +1,82 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use libobs_wrapper::studio_mode::ObsStudioModeEvent;
+    use libobs_wrapper::transitions::ObsTransitionType;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn studio_mode_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let live = context.scene("live").await.unwrap();
+            let next = context.scene("next").await.unwrap();
+            let other = context.scene("other").await.unwrap();
+            let fade = context
+                .transition("fade", ObsTransitionType::Fade)
+                .await
+                .unwrap();
+
+            let studio = context
+                .studio_mode(&next, &live, Some(&fade))
+                .await
+                .unwrap();
+            let mut events = studio.events();
+            assert_eq!(backend.channel_source(0).as_deref(), Some("fade"));
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("live"));
+
+            studio
+                .transition_to_program(Duration::from_millis(30))
+                .await
+                .unwrap();
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("next"));
+            assert!(!backend.is_transitioning("fade"));
+            assert_eq!(studio.program_scene().await.name().to_string(), "next");
+            assert_eq!(studio.preview_scene().await.name().to_string(), "live");
+            assert_eq!(
+                events.recv().await.unwrap(),
+                ObsStudioModeEvent::ProgramSceneChanged("next".to_string())
+            );
+            assert_eq!(
+                events.recv().await.unwrap(),
+                ObsStudioModeEvent::PreviewSceneChanged("live".to_string())
+            );
+
+            // Changing the preview doesn't touch the program
+            studio.set_preview_scene(&other).await.unwrap();
+            assert_eq!(
+                events.recv().await.unwrap(),
+                ObsStudioModeEvent::PreviewSceneChanged("other".to_string())
+            );
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("next"));
+
+            studio
+                .transition_to_program(Duration::from_millis(30))
+                .await
+                .unwrap();
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("other"));
+            assert_eq!(studio.preview_scene().await.name().to_string(), "next");
+
+            // Without a transition the scenes are cut on the channel directly
+            let studio = context.studio_mode(&live, &next, None).await.unwrap();
+            assert_eq!(backend.channel_source(0).as_deref(), Some("next"));
+            studio
+                .transition_to_program(Duration::from_secs(60))
+                .await
+                .unwrap();
+            assert_eq!(backend.channel_source(0).as_deref(), Some("live"));
+            assert_eq!(studio.preview_scene().await.name().to_string(), "next");
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.sources().is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use libobs_wrapper::data::{ObsData, ObsDataUpdater, ObsObjectBuilder};
+    use libobs_wrapper::runtime::ObsRuntime;
+    use libobs_wrapper::sources::ObsSourceBuilder;
+    use libobs_wrapper::utils::{ObjectInfo, ObsError, ObsString, SourceInfo};
+
+    use crate::common::fake_context;
+
+    struct ColorSourceBuilder {
+        name: ObsString,
+        settings: ObsData,
+        settings_updater: ObsDataUpdater,
+        hotkeys: ObsData,
+        hotkeys_updater: ObsDataUpdater,
+    }
+
+    #[async_trait::async_trait]
+    impl ObsObjectBuilder for ColorSourceBuilder {
+        async fn new<T: Into<ObsString> + Send + Sync>(
+            name: T,
+            runtime: ObsRuntime,
+        ) -> Result<Self, ObsError> {
+            let mut settings = ObsData::new(runtime.clone()).await?;
+            let mut hotkeys = ObsData::new(runtime).await?;
+
+            Ok(Self {
+                name: name.into(),
+                settings_updater: settings.bulk_update(),
+                settings,
+                hotkeys_updater: hotkeys.bulk_update(),
+                hotkeys,
+            })
+        }
+
+        fn get_name(&self) -> ObsString {
+            self.name.clone()
+        }
+
+        async fn build(self) -> Result<ObjectInfo, ObsError> {
+            self.settings_updater.update().await?;
+            self.hotkeys_updater.update().await?;
+
+            Ok(ObjectInfo::new(
+                Self::get_id(),
+                self.name,
+                Some(self.settings),
+                Some(self.hotkeys),
+            ))
+        }
+
+        fn get_settings(&self) -> &ObsData {
+            &self.settings
+        }
+
+        fn get_settings_updater(&mut self) -> &mut ObsDataUpdater {
+            &mut self.settings_updater
+        }
+
+        fn get_hotkeys(&self) -> &ObsData {
+            &self.hotkeys
+        }
+
+        fn get_hotkeys_updater(&mut self) -> &mut ObsDataUpdater {
+            &mut self.hotkeys_updater
+        }
+
+        fn get_id() -> ObsString {
+            "color_source".into()
+        }
+    }
+
+    impl ObsSourceBuilder for ColorSourceBuilder {}
+
+    #[tokio::test]
+    pub async fn transaction_test() {
+        let (backend, mut context) = fake_context().await;
+
+        let runtime = context.runtime().clone();
+
+        // Creating a scene together with its sources
+        let mut transaction = runtime.transaction();
+        let scene = context.scene_in_transaction(&mut transaction, "main");
+        let mut sources = Vec::new();
+        for i in 0..10 {
+            let mut builder = context
+                .source_builder::<ColorSourceBuilder, _>(format!("color {}", i))
+                .await
+                .unwrap();
+            builder.get_settings_updater().set_int_ref("color", i);
+
+            sources.push(builder.add_to_scene_in_transaction(&mut transaction, scene));
+        }
+
+        let scene_name = transaction.add(move |values| {
+            let scene = values.get(scene).ok_or(ObsError::SceneNotFound)?;
+            Ok(scene.name().to_string())
+        });
+        assert_eq!(transaction.len(), 12);
+
+        let mut values = transaction.commit().await.unwrap();
+        assert_eq!(values.take(scene_name).as_deref(), Some("main"));
+
+        let mut scene = values.take(scene).unwrap();
+        assert!(values.take(scene).is_none());
+        assert_eq!(scene.sources().read().await.len(), 10);
+        assert_eq!(backend.scene("main").unwrap().items.len(), 10);
+
+        let source = values.take(sources[3]).unwrap();
+        assert_eq!(source.name(), "color 3");
+        assert_eq!(backend.source("color 3").unwrap().settings["color"], 3);
+
+        // Everything of a failed transaction is rolled back
+        let mut transaction = runtime.transaction();
+        let builder = context
+            .source_builder::<ColorSourceBuilder, _>("rolled back")
+            .await
+            .unwrap();
+        builder.add_to_scene_in_transaction(&mut transaction, &scene);
+        scene.add_source_in_transaction(
+            &mut transaction,
+            SourceInfo::new("color_source", "rolled back raw", None, None),
+        );
+        context.scene_in_transaction(&mut transaction, "rolled back scene");
+        transaction.add(|_| Err::<(), _>(ObsError::NullPointer));
+
+        let res = transaction.commit().await;
+        assert_eq!(res.unwrap_err(), ObsError::NullPointer);
+
+        assert!(backend.source("rolled back").is_none());
+        assert!(backend.source("rolled back raw").is_none());
+        assert!(backend.scene("rolled back scene").is_none());
+        assert!(context.get_scene("rolled back scene").await.is_none());
+        assert_eq!(backend.scene("main").unwrap().items.len(), 10);
+        assert_eq!(scene.sources().read().await.len(), 10);
+
+        // The scene still works outside of transactions
+        scene
+            .add_source(SourceInfo::new("color_source", "after", None, None))
+            .await
+            .unwrap();
+        assert_eq!(backend.scene("main").unwrap().items.len(), 11);
+    }
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use libobs_wrapper::backend::fake::FakeObsBackend;
+    use libobs_wrapper::transitions::{ObsTransitionDirection, ObsTransitionType};
+    use libobs_wrapper::utils::ObsError;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    fn count_signals(backend: &FakeObsBackend, object: &str, signal: &str) -> usize {
+        backend
+            .emitted_signals()
+            .iter()
+            .filter(|s| s.object == object && s.signal == signal)
+            .count()
+    }
+
+    #[tokio::test]
+    pub async fn transition_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let intro = context.scene("intro").await.unwrap();
+            let main = context.scene("main").await.unwrap();
+
+            let fade = context
+                .transition("fade", ObsTransitionType::Fade)
+                .await
+                .unwrap();
+            fade.set_scene(&intro).await.unwrap();
+            fade.set_to_channel(0).await.unwrap();
+            assert_eq!(backend.channel_source(0).as_deref(), Some("fade"));
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("intro"));
+            assert_eq!(backend.source("fade").unwrap().id, "fade_transition");
+
+            fade.transition_to(&main, Duration::from_millis(30))
+                .await
+                .unwrap();
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("main"));
+            assert!(!backend.is_transitioning("fade"));
+            assert_eq!(count_signals(&backend, "fade", "transition_start"), 1);
+            assert_eq!(count_signals(&backend, "fade", "transition_stop"), 1);
+
+            // Long transitions can be finished early
+            fade.start(&intro, Duration::from_secs(60)).await.unwrap();
+            assert!(backend.is_transitioning("fade"));
+            assert!(fade.is_running().await.unwrap());
+
+            // Waiting for another transition would end with the stop of the running one
+            let res = fade.transition_to(&main, Duration::from_millis(30)).await;
+            assert!(matches!(res, Err(ObsError::TransitionFailure(_))));
+
+            fade.force_stop().await.unwrap();
+            assert!(!fade.is_running().await.unwrap());
+            assert!(!backend.is_transitioning("fade"));
+            assert_eq!(backend.transition_source("fade").as_deref(), Some("intro"));
+
+            // Cuts finish right away, regardless of the duration
+            let cut = context
+                .transition("cut", ObsTransitionType::Cut)
+                .await
+                .unwrap();
+            cut.set_scene(&intro).await.unwrap();
+            cut.start(&main, Duration::from_secs(60)).await.unwrap();
+            assert!(!backend.is_transitioning("cut"));
+            assert_eq!(backend.transition_source("cut").as_deref(), Some("main"));
+
+            // Transitions which aren't rendered never finish, so waiting for them fails
+            let res = cut.transition_to(&intro, Duration::from_millis(30)).await;
+            assert!(matches!(res, Err(ObsError::TransitionFailure(_))));
+            assert_eq!(backend.transition_source("cut").as_deref(), Some("main"));
+
+            // Typed settings
+            context
+                .transition(
+                    "swipe",
+                    ObsTransitionType::Swipe {
+                        direction: ObsTransitionDirection::Up,
+                        swipe_in: true,
+                    },
+                )
+                .await
+                .unwrap();
+            let swipe = backend.source("swipe").unwrap();
+            assert_eq!(swipe.id, "swipe_transition");
+            assert_eq!(swipe.settings["direction"], "up");
+            assert_eq!(swipe.settings["swipe_in"], true);
+
+            context
+                .transition(
+                    "stinger",
+                    ObsTransitionType::Stinger {
+                        path: "stinger.webm".to_string(),
+                        transition_point: Duration::from_millis(800),
+                    },
+                )
+                .await
+                .unwrap();
+            let stinger = backend.source("stinger").unwrap();
+            assert_eq!(stinger.settings["path"], "stinger.webm");
+            assert_eq!(stinger.settings["transition_point"], 800);
+
+            context
+                .transition(
+                    "to_red",
+                    ObsTransitionType::FadeToColor {
+                        color: [255, 0, 0, 255],
+                        switch_point: 50,
+                    },
+                )
+                .await
+                .unwrap();
+            let to_red = backend.source("to_red").unwrap();
+            assert_eq!(to_red.settings["color"], 0xFF0000FFu32 as i64);
+            assert_eq!(to_red.settings["switch_point"], 50);
+
+            assert_eq!(
+                context
+                    .get_transition("swipe")
+                    .await
+                    .unwrap()
+                    .transition_type(),
+                &ObsTransitionType::Swipe {
+                    direction: ObsTransitionDirection::Up,
+                    swipe_in: true,
+                }
+            );
+        }
+
+        assert_clean_shutdown(context.shutdown().await);
+        assert!(backend.sources().is_empty());
+    }
+}
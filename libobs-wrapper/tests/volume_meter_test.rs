@@ -0,0 +1,128 @@
+#![cfg(feature = "fake-backend")]
+
+#[cfg(not(feature = "blocking"))]
+mod common;
+
+#[cfg(not(feature = "blocking"))]
+mod require_non_blocking {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use libobs_wrapper::enums::{ObsFaderType, ObsPeakMeterType};
+    use libobs_wrapper::media::{ObsVolumeLevels, ObsVolumeMeterOptions};
+    use libobs_wrapper::utils::SourceInfo;
+
+    use crate::common::{assert_clean_shutdown, fake_context};
+
+    #[tokio::test]
+    pub async fn volume_meter_test() {
+        let (backend, mut context) = fake_context().await;
+
+        {
+            let mut scene = context.scene("main").await.unwrap();
+            let mic = scene
+                .add_source(SourceInfo::new("wasapi_input_capture", "mic", None, None))
+                .await
+                .unwrap();
+
+            let mut meter = mic
+                .volume_meter(
+                    ObsVolumeMeterOptions::new()
+                        .set_peak_meter_type(ObsPeakMeterType::TruePeak)
+                        .set_interval(Duration::ZERO),
+                )
+                .await
+                .unwrap();
+            assert_eq!(backend.volume_meters("mic"), 1);
+            assert_eq!(
+                backend.volume_meter_peak_types("mic"),
+                [ObsPeakMeterType::TruePeak]
+            );
+            assert_eq!(meter.channels().await.unwrap(), 2);
+
+            assert!(backend.emit_volume_levels("mic", &[-20.0, -30.0], &[-6.0, -12.0], &[-3.0]));
+            let levels = meter.next().await.unwrap();
+            assert_eq!(
+                levels,
+                ObsVolumeLevels {
+                    magnitude: vec![-20.0, -30.0],
+                    peak: vec![-6.0, -12.0],
+                    input_peak: vec![-3.0, f32::NEG_INFINITY],
+                }
+            );
+            assert!(meter.try_next_levels().is_none());
+
+            // The meter keeps the source alive and detaches once it is dropped
+            scene.remove_source(&mic).await.unwrap();
+            drop(mic);
+            assert_eq!(backend.volume_meters("mic"), 1);
+            drop(meter);
+            assert_eq!(backend.volume_meters("mic"), 0);
+        }
+
+        let mut meter = {
+            let mut scene = context.scene("music").await.unwrap();
+            let music = scene
+                .add_source(SourceInfo::new("ffmpeg_source", "music", None, None))
+                .await
+                .unwrap();
+
+            // Updates within the interval are merged into the next one
+            let meter = music
+                .volume_meter(ObsVolumeMeterOptions::new().set_interval(Duration::from_secs(3600)))
+                .await
+                .unwrap();
+            backend.emit_volume_levels("music", &[-10.0, -10.0], &[-1.0, -1.0], &[-1.0, -1.0]);
+            backend.emit_volume_levels("music", &[-5.0, -15.0], &[-2.0, -0.5], &[-1.0, -1.0]);
+            assert_eq!(meter.try_next_levels().unwrap().magnitude, [-10.0, -10.0]);
+            assert!(meter.try_next_levels().is_none());
+            assert_eq!(meter.buffered_levels(), 0);
+            assert_eq!(meter.dropped_levels(), 0);
+
+            let fader = music.fader(ObsFaderType::Cubic).await.unwrap();
+            assert_eq!(fader.fader_type(), ObsFaderType::Cubic);
+            assert_eq!(backend.faders("music"), 1);
+            assert_eq!(fader.db().await.unwrap(), 0.0);
+            assert_eq!(fader.deflection().await.unwrap(), 1.0);
+
+            let mut volume_rx = music.signal_manager().on_volume().await.unwrap();
+            assert!(fader.set_deflection(0.5).await.unwrap());
+            assert!((music.volume().await.unwrap() - 0.125).abs() < 1e-6);
+            assert!((volume_rx.try_recv().unwrap().volume - 0.125).abs() < 1e-6);
+            assert!((fader.mul().await.unwrap() - 0.125).abs() < 1e-6);
+
+            // The fader follows the volume of the source
+            music.set_volume(0.1).await.unwrap();
+            assert!((fader.db().await.unwrap() + 20.0).abs() < 1e-4);
+
+            assert!(!fader.set_db(6.0).await.unwrap());
+            assert_eq!(fader.db().await.unwrap(), 0.0);
+            assert!(fader.set_mul(0.0).await.unwrap());
+            assert_eq!(music.volume().await.unwrap(), 0.0);
+
+            let iec = music.fader(ObsFaderType::Iec).await.unwrap();
+            assert!(iec.set_db(-9.0).await.unwrap());
+            assert!((iec.deflection().await.unwrap() - 0.75).abs() < 1e-5);
+            assert!(iec.set_deflection(0.5).await.unwrap());
+            assert!((iec.db().await.unwrap() + 20.0).abs() < 1e-4);
+
+            // Log faders mute below their range
+            let log = music.fader(ObsFaderType::Log).await.unwrap();
+            assert!(!log.set_db(-120.0).await.unwrap());
+            assert_eq!(log.db().await.unwrap(), f32::NEG_INFINITY);
+            assert!(log.set_deflection(1.0).await.unwrap());
+            assert_eq!(log.db().await.unwrap(), 0.0);
+            assert_eq!(backend.faders("music"), 3);
+
+            drop((fader, iec, log));
+            assert_eq!(backend.faders("music"), 0);
+            scene.remove_source(&music).await.unwrap();
+            meter
+        };
+
+        // The meter ends once OBS is shut down
+        assert_clean_shutdown(context.shutdown().await);
+        let next = tokio::time::timeout(Duration::from_secs(5), meter.next());
+        assert!(next.await.unwrap().is_none());
+    }
+}